@@ -0,0 +1,253 @@
+#![allow(clippy::too_many_arguments)]
+#![no_std]
+
+use soroban_sdk::{contractclient, contracterror, contracttype, Address, Env, String, Symbol, Vec};
+
+// Structure defining an asset available for lease
+#[contracttype]
+#[derive(Clone)] 
+pub struct Asset {
+    pub asset_id: u64,          // Unique asset identifier
+    pub owner: Address,         // Address of the asset owner
+    pub title: String,          // Title/name of the asset
+    pub description: String,    // Description of the asset
+    pub price_per_period: i128, // Price per period, in base units of the payment token
+    pub period_duration: u64,   // Duration of a payment period in seconds
+    pub min_lease_duration: u64, // Minimum lease duration in seconds
+    pub max_lease_duration: u64, // Maximum lease duration in seconds
+    pub deposit_required: i128,  // Required security deposit, in base units of the payment token
+    pub is_available: bool,      // Whether the asset is available for lease
+    pub current_lease_id: u64,   // ID of current active lease (0 if none)
+    pub deposit_mode: DepositMode, // How the security deposit is secured
+    pub payment_policy: PaymentPolicy, // Billing model applied to new leases
+    pub penalty_policy: PenaltyPolicy, // Late-payment behavior for leases of this asset
+    pub holdover_bps: u32,       // Rent multiplier in basis points after end_time (0 disables holdover)
+    pub min_lead_time: u64,      // Minimum seconds between booking and lease start
+    pub max_advance: u64,        // Maximum seconds ahead a lease may start (0 for no limit)
+    pub listing_fee_held: i128,  // Refundable listing fee, in stroops, held until the first completed lease
+    pub listing_expires_at: u64, // When the listing stops being offered (0 for never)
+    pub grace_period: u64,       // Seconds after a due date before the lease turns overdue and the penalty policy charges a late fee
+    pub payment_token: Address,  // Token contract rent and deposits are paid in
+    pub currency_symbol: String, // Display symbol of the payment token, e.g. "XLM"
+    pub currency_decimals: u32,  // Decimal places of the payment token, e.g. 6 for USDC, so a price of 120_500_000 reads 120.50
+    pub escalation_bps: u32,     // Rent increase in basis points applied to new leases every escalation_interval periods
+    pub escalation_interval: u32, // Periods between rent increases (0 for no escalation)
+    pub notice_period: u64,      // Seconds of notice needed to end a lease before its term is over
+    pub early_termination_fee: i128, // Fee taken from escrow when the lessee ends a lease without notice
+    pub category: Symbol,        // Marketplace category the asset is listed under, e.g. "apartment"
+    pub tags: Vec<Symbol>,       // Free-form discovery tags, at most MAX_TAGS
+}
+
+// Structure to track the status of a lease
+#[contracttype]
+#[derive(Clone)] 
+pub struct LeaseStatus {
+    pub lease_id: u64,          // Unique lease identifier
+    pub asset_id: u64,          // Associated asset identifier
+    pub lessee: Address,        // Address of the person leasing the asset
+    pub start_time: u64,        // Lease start timestamp
+    pub end_time: u64,          // Lease end timestamp
+    pub period_payment: i128,   // Amount due per payment period, in base units of the payment token
+    pub payment_frequency: u64, // How often payments occur (in seconds)
+    pub last_payment_time: u64, // When the last payment was made
+    pub next_payment_time: u64, // When the next payment is due
+    pub is_active: bool,        // Whether the lease is currently active
+    pub is_overdue: bool,       // Whether payments are overdue
+    pub total_paid: i128,       // Total paid so far, in base units of the payment token
+    pub security_deposit: i128, // Security deposit amount, in base units of the payment token
+    pub payment_policy: PaymentPolicy, // Billing model used to compute each payment
+    pub payments_made: u64,     // Number of payments made after the initial one
+    pub metered_units: u64,     // Usage recorded since the last payment (metered leases)
+    pub shortfall: i128,        // Amount owed but unrecoverable, e.g. after an issuer clawback
+    pub in_holdover: bool,      // Whether the tenant stayed past end_time and the lease rolls per period
+    pub holdover_end: u64,      // When a noticed holdover terminates (0 while rolling)
+    pub term_length: u64,       // Length of one lease term in seconds, reused on renewal
+    pub auto_renew: bool,       // Whether the lessee opted in to automatic renewal
+    pub non_renewal_filed: bool, // Whether either party gave notice not to renew
+    pub trial_end: u64,         // End of the trial period either party may exit during (0 if none)
+    pub late_fees_paid: i128,   // Total late fees paid on this lease, in base units of the payment token
+    pub outstanding_balance: i128, // Still owed on the current period after partial payments (0 when none made)
+    pub escalation_bps: u32,    // Rent increase in basis points applied every escalation_interval periods
+    pub escalation_interval: u32, // Periods between rent increases (0 for no escalation)
+    pub co_tenants: Vec<CoTenant>, // Tenants sharing the lease and their rent shares, lessee included (empty for a sole lessee)
+}
+
+// A tenant sharing a lease and the part of each period's rent they pay
+#[contracttype]
+#[derive(Clone)] 
+pub struct CoTenant {
+    pub tenant: Address,        // Address of the co-tenant
+    pub share_bps: u32,         // Share of each payment in basis points; a lease's shares total 10000
+}
+
+// How a lease's security deposit is secured
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DepositMode {
+    Standard,      // Deposit is held in contract escrow and refunded when the lease ends
+    AllowanceLock, // Deposit stays in the lessee's wallet behind a token allowance to the contract
+}
+
+// One token component of a split security deposit
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DepositPosition {
+    pub token: Address, // Token contract of this component
+    pub amount: i128,   // Amount in the token's smallest unit
+}
+
+// Billing model applied to a lease; every payment computation dispatches through here
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PaymentPolicy {
+    FixedPeriod,    // Full period_payment once per payment period
+    Streaming,      // Pro-rata period_payment for the time elapsed since the last payment
+    Metered(i128),  // Base units of the payment token per unit of recorded usage since the last payment
+    RentToOwn(u64), // Fixed periods; ownership passes to the lessee after this many payments
+}
+
+// Late-payment behavior of an asset; all overdue handling is evaluated here
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PenaltyPolicy {
+    None,                      // Late payments carry no penalty
+    Flat(i128),                // Fixed fee on any late payment, in base units of the payment token
+    Interest(InterestPenalty), // Fee accrues per period late, up to a cap
+    Strict,                    // Once marked overdue, payments are refused and the owner must end the lease
+}
+
+// Interest-style penalty parameters
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct InterestPenalty {
+    pub rate_bps: u32, // Charge per started period late, in basis points of the amount due
+    pub cap: i128,     // Maximum penalty for a single late payment, in base units of the payment token
+}
+
+// Error codes surfaced to clients; values are stable and must never be reused. The contract
+// spec allows at most 50 cases per enum, so codes from 51 up continue in the rent payment
+// contract's RentErrorExt.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum RentError {
+    AssetNotFound = 1,        // No asset registered under the given ID
+    AssetUnavailable = 2,     // Asset exists but is already leased, delisted, or only leases through applications
+    InvalidLeaseDuration = 3, // Requested duration outside the asset's min/max range
+    LeaseNotActive = 4,       // Lease does not exist or has already ended
+    NotLessee = 5,            // Caller must be the lessee
+    NotLesseeOrOwner = 6,     // Caller must be the lessee or the asset owner
+    NotAssetOwner = 7,        // Caller must be the asset owner
+    PaymentNotDue = 8,        // Next payment time has not been reached yet
+    AlreadyInitialized = 9,   // initialize has already been called, records are imported into a non-empty contract, or the escrow contract is already set
+    NotAdmin = 10,            // Caller must be the contract admin (or the arbiter, for dispute rulings)
+    LandlordNotVerified = 11, // Owner is not verified by the configured landlord registry
+    NativeTokenNotSet = 12,   // Wrapped-XLM token contract has not been configured
+    AllowanceTooLow = 13,     // Token allowance granted to the contract does not cover the amount
+    NoDepositLock = 14,       // Lease has no allowance-locked deposit
+    InvalidClaim = 15,        // Deposit claim amount is zero, exceeds the lock, or is not pending, the lease is already disputed, or it has no guarantor to claim from
+    NotMetered = 16,          // Usage can only be recorded on metered leases
+    LatePaymentRefused = 17,  // Asset's penalty policy refuses payment on an overdue lease
+    AmountExceedsMax = 18,    // Amount due is higher than the payer authorized
+    LeaseExpired = 19,        // Lease is past its term and not in an open holdover
+    NotInHoldover = 20,       // Action requires the lease to be in holdover
+    NoticeDeadlinePassed = 21, // Non-renewal notice must be filed at least one period before end_time
+    InvalidWindow = 22,       // Time window (or migration batch) is empty, malformed, out of order, or index is out of range
+    BlackoutConflict = 23,    // Lease period overlaps an owner blackout window
+    BookingWindowViolation = 24, // Lease start is too soon or too far ahead for the asset
+    InvalidAmount = 25,       // Amount or basis-point share is zero, negative, or out of range
+    PlatformNotSet = 26,      // Platform account has not been configured
+    ListingExpired = 27,      // Asset listing has expired and must be refreshed by the owner
+    MutualReleaseRequired = 28, // Deposit can only be released with sign-off from both parties
+    NoPendingRelease = 29,    // Lease has no deposit release open for this action
+    DeadlineNotReached = 30,  // Action is only allowed once its deadline has passed
+    NotInDefault = 31,        // Lease has not escalated to a default warning
+    ExternalIdTaken = 32,     // Owner already uses this external ID for another record
+    InsufficientBalance = 33, // Payer's token balance (or escrowed deposit, for an early-termination fee) does not cover the amount
+    UnsupportedDecimals = 34, // Native token does not use 7 decimals, or a payment token reports more than 18
+    ConsolidatedBillingOff = 35, // Lessee has not opted in to consolidated billing with this owner
+    NotInTrial = 36,          // Lease has no trial period or it has ended
+    AccessCodeMismatch = 37,  // Revealed access code does not match its committed hash, or none was committed
+    AccessCodeRevealed = 38,  // Access code has already been revealed
+    PeriodClosed = 39,        // Reporting period has already been closed
+    LeaseRaceLost = 40,       // Asset was leased by a competing creation earlier in the same ledger
+    NoTrustline = 41,         // Recipient has no authorized trustline for the token
+    NetworkMismatch = 42,     // Ledger is not on the network named at initialize
+    InvalidApplication = 43,  // Application does not exist, can no longer be decided, or is not approved for promotion
+    RenewalRefused = 44,      // Notice not to renew was filed on the lease
+    MixedPaymentTokens = 45,  // Leases billed together are priced in different tokens
+    ContractPaused = 46,      // Admin has paused lease creation and payments
+    TermLimitExceeded = 47,   // Asset has the most custom terms or tags (lease the most occupants, lessee the most concurrent leases) allowed, or a term value is too long
+    AccountOverdrawn = 48,    // Payout exceeds what the internal sub-account (or the owner's withdrawable rent) it comes from holds
+    ExtensionRefused = 49,    // Extension asked for on or after the due date, or none are left this year
+    NoPendingExtension = 50,  // Lease has no extension request pending for its current due date
+}
+
+// Asset status structure to track leasing metrics
+#[contracttype]
+#[derive(Clone)]
+pub struct AssetStats {
+    pub active_leases: u64,     // Count of currently active leases
+    pub completed_leases: u64,  // Count of completed leases
+    pub overdue_leases: u64,    // Count of leases with overdue payments
+    pub total_leases: u64,      // Total count of all leases created
+    pub total_xlm_processed: i128, // Total stroops processed for XLM-priced assets (see TokenStats for other tokens)
+    pub total_late_fees: i128,   // Late fees in stroops paid across XLM-priced assets
+}
+
+// Core lease lifecycle of the rent payment contract: listing, leasing, paying and ending a
+// lease, and the views integrators need around them. The contract implements it, so
+// integrators can call it cross-contract through RentPaymentInterfaceClient (or swap in the
+// mock implementation during tests) depending on this crate alone. Every other entrypoint is
+// reached through the contract's own client.
+#[contractclient(name = "RentPaymentInterfaceClient")]
+pub trait RentPaymentInterface {
+    // Register a new asset for leasing
+    fn register_asset(
+        env: Env,
+        owner: Address,
+        title: String,
+        description: String,
+        price_per_period: i128,
+        period_duration: u64,
+        min_lease_duration: u64,
+        max_lease_duration: u64,
+        deposit_required: i128
+    ) -> Result<u64, RentError>;
+    
+    // Create a new lease for an asset
+    fn create_lease(env: Env, asset_id: u64, lessee: Address, lease_duration: u64) -> Result<u64, RentError>;
+    
+    // Book a lease that starts at a future time
+    fn create_scheduled_lease(env: Env, asset_id: u64, lessee: Address, start_time: u64, lease_duration: u64) -> Result<u64, RentError>;
+    
+    // Process a payment for a lease
+    fn process_payment(env: Env, lease_id: u64, caller: Address) -> Result<(), RentError>;
+    
+    // Process a payment submitted by a relayer with only the lessee's signed auth entry
+    fn process_sponsored_payment(env: Env, lease_id: u64, lessee: Address, max_amount: i128) -> Result<i128, RentError>;
+    
+    // End a lease
+    fn end_lease(env: Env, lease_id: u64, caller: Address) -> Result<(), RentError>;
+    
+    // Mark a lease as overdue
+    fn mark_lease_overdue(env: Env, lease_id: u64, caller: Address) -> Result<(), RentError>;
+    
+    // View asset stats
+    fn view_asset_stats(env: Env) -> Result<AssetStats, RentError>;
+    
+    // View asset details
+    fn view_asset(env: Env, asset_id: u64) -> Result<Option<Asset>, RentError>;
+    
+    // View lease details
+    fn view_lease(env: Env, lease_id: u64) -> Result<Option<LeaseStatus>, RentError>;
+    
+    // Get all assets owned by a specific address
+    fn get_owner_assets(env: Env, owner: Address) -> Result<Vec<u64>, RentError>;
+    
+    // Get all active leases for a specific lessee
+    fn get_lessee_leases(env: Env, lessee: Address) -> Result<Vec<u64>, RentError>;
+    
+    // List every error code with its stable symbolic name
+    fn list_error_codes(env: Env) -> Result<Vec<(u32, Symbol)>, RentError>;
+}
@@ -0,0 +1,112 @@
+#![allow(clippy::needless_return)]
+#![no_std]
+
+use common::DepositPosition;
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, log, panic_with_error, token, Address, Env};
+
+mod test;
+
+// Error codes surfaced to clients; values are stable and must never be reused
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum EscrowError {
+    AlreadyInitialized = 1,  // initialize has already been called
+    NotController = 2,       // Caller must be the controlling contract
+    InvalidAmount = 3,       // Amount is zero or negative
+    InsufficientBalance = 4, // Lease holds less of the token than requested
+}
+
+// Storage keys of the escrow contract
+#[contracttype]
+pub enum DataKey {
+    Controller,              // Contract (or account) allowed to release funds
+    Balance(u64, Address),   // (lease_id, token) -> amount held (persistent)
+}
+
+// Ledgers a held balance is kept alive for, refreshed whenever it changes
+const BALANCE_TTL: u32 = 518_400;
+
+// Token custody for lease deposits, kept apart from the rent-payment contract so funds
+// sit in a minimal contract whose only job is holding and releasing them
+#[contract]
+pub struct EscrowContract;
+
+#[contractimpl]
+impl EscrowContract {
+    // Set the controller that decides where held funds go (can only be called once)
+    pub fn initialize(env: Env, controller: Address) {
+        if env.storage().instance().has(&DataKey::Controller) {
+            log!(&env, "Escrow already initialized");
+            panic_with_error!(&env, EscrowError::AlreadyInitialized);
+        }
+        
+        env.storage().instance().set(&DataKey::Controller, &controller);
+        env.storage().instance().extend_ttl(10000, 10000);
+        
+        log!(&env, "Escrow initialized");
+    }
+    
+    // View the controller, if set
+    pub fn view_controller(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::Controller)
+    }
+    
+    // Move a deposit position from `from` into custody for a lease
+    pub fn deposit(env: Env, lease_id: u64, from: Address, position: DepositPosition) {
+        from.require_auth();
+        
+        if position.amount <= 0 {
+            log!(&env, "Deposit must be positive");
+            panic_with_error!(&env, EscrowError::InvalidAmount);
+        }
+        
+        token::Client::new(&env, &position.token).transfer(&from, &env.current_contract_address(), &position.amount);
+        
+        let key = DataKey::Balance(lease_id, position.token.clone());
+        let held: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        env.storage().persistent().set(&key, &(held + position.amount));
+        env.storage().persistent().extend_ttl(&key, BALANCE_TTL, BALANCE_TTL);
+        env.storage().instance().extend_ttl(10000, 10000);
+        
+        log!(&env, "Deposit of {} held for lease ID: {}", position.amount, lease_id);
+    }
+    
+    // Controller pays part of a lease's held funds out to `to`
+    pub fn release(env: Env, lease_id: u64, to: Address, position: DepositPosition) {
+        let controller = Self::view_controller(env.clone());
+        let Some(controller) = controller else {
+            log!(&env, "Escrow has no controller");
+            panic_with_error!(&env, EscrowError::NotController)
+        };
+        controller.require_auth();
+        
+        if position.amount <= 0 {
+            log!(&env, "Release must be positive");
+            panic_with_error!(&env, EscrowError::InvalidAmount);
+        }
+        
+        let key = DataKey::Balance(lease_id, position.token.clone());
+        let held: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        if position.amount > held {
+            log!(&env, "Release exceeds held balance");
+            panic_with_error!(&env, EscrowError::InsufficientBalance);
+        }
+        
+        if held == position.amount {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &(held - position.amount));
+            env.storage().persistent().extend_ttl(&key, BALANCE_TTL, BALANCE_TTL);
+        }
+        
+        token::Client::new(&env, &position.token).transfer(&env.current_contract_address(), &to, &position.amount);
+        
+        log!(&env, "Released {} for lease ID: {}", position.amount, lease_id);
+    }
+    
+    // View how much of a token is held for a lease
+    pub fn view_balance(env: Env, lease_id: u64, token: Address) -> i128 {
+        env.storage().persistent().get(&DataKey::Balance(lease_id, token)).unwrap_or(0)
+    }
+}
@@ -0,0 +1,33 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::token::{StellarAssetClient, TokenClient};
+
+#[test]
+fn test_deposit_and_controlled_release() {
+    let env = Env::default();
+    env.mock_all_auths();
+    
+    let contract_id = env.register(EscrowContract, ());
+    let client = EscrowContractClient::new(&env, &contract_id);
+    let controller = Address::generate(&env);
+    client.initialize(&controller);
+    
+    let issuer = Address::generate(&env);
+    let usdc = env.register_stellar_asset_contract_v2(issuer).address();
+    let lessee = Address::generate(&env);
+    let owner = Address::generate(&env);
+    StellarAssetClient::new(&env, &usdc).mint(&lessee, &1_000);
+    
+    client.deposit(&7, &lessee, &DepositPosition { token: usdc.clone(), amount: 1_000 });
+    assert_eq!(client.view_balance(&7, &usdc), 1_000);
+    
+    client.release(&7, &owner, &DepositPosition { token: usdc.clone(), amount: 400 });
+    assert!(client.try_release(&7, &lessee, &DepositPosition { token: usdc.clone(), amount: 601 }).is_err());
+    client.release(&7, &lessee, &DepositPosition { token: usdc.clone(), amount: 600 });
+    
+    assert_eq!(client.view_balance(&7, &usdc), 0);
+    assert_eq!(TokenClient::new(&env, &usdc).balance(&owner), 400);
+    assert_eq!(TokenClient::new(&env, &usdc).balance(&lessee), 600);
+}
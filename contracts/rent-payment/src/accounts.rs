@@ -0,0 +1,92 @@
+use soroban_sdk::{contracttype, log, Address, Env, Map, Vec};
+
+use crate::storage_keys::AccountBook;
+use crate::RentError;
+
+// Internal sub-account owning a slice of the funds the contract holds. Every inflow is
+// credited to exactly one sub-account and every outflow debited from one, so for each token
+// the sub-accounts always sum to the contract's balance of it.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SubAccount {
+    TenantDeposits, // Split and holding deposits, trial and prepaid rent held for lessees (standard deposits sit with the escrow contract)
+    OwnerEarnings,  // Funds owed to asset owners: rent awaiting withdrawal and refundable listing fees
+    PlatformFees,   // Platform fees awaiting withdrawal by the admin
+    PenaltyPool,    // Penalties collected and not yet paid out
+    RewardsPool,    // Funds set aside to reward keepers
+}
+
+const ALL: [SubAccount; 5] = [
+    SubAccount::TenantDeposits,
+    SubAccount::OwnerEarnings,
+    SubAccount::PlatformFees,
+    SubAccount::PenaltyPool,
+    SubAccount::RewardsPool,
+];
+
+// Base units of `token` held in one sub-account
+pub fn view_sub_account(env: Env, account: SubAccount, token: Address) -> i128 {
+    env.storage().instance().get(&AccountBook::SubBalance(account, token)).unwrap_or(0)
+}
+
+// Base units of `token` held in each sub-account
+pub fn view_sub_accounts(env: Env, token: Address) -> Map<SubAccount, i128> {
+    let mut balances = Map::new(&env);
+    for account in ALL {
+        balances.set(account, view_sub_account(env.clone(), account, token.clone()));
+    }
+    
+    return balances;
+}
+
+// Every token the contract has ever taken into custody
+pub fn view_held_tokens(env: Env) -> Vec<Address> {
+    env.storage().instance().get(&AccountBook::HeldTokens).unwrap_or(Vec::new(&env))
+}
+
+// Book `units` of `token` received by the contract into `account`
+pub(crate) fn credit(env: &Env, account: SubAccount, token: &Address, units: i128) {
+    if units == 0 {
+        return;
+    }
+    
+    let mut tokens = view_held_tokens(env.clone());
+    if !tokens.contains(token) {
+        tokens.push_back(token.clone());
+        env.storage().instance().set(&AccountBook::HeldTokens, &tokens);
+    }
+    
+    let balance = view_sub_account(env.clone(), account, token.clone());
+    env.storage().instance().set(&AccountBook::SubBalance(account, token.clone()), &(balance + units));
+}
+
+// Take over the sub-account balances an exporting contract booked for `token`, replacing any
+// booked here; the tokens themselves are transferred in separately
+pub(crate) fn restore(env: &Env, token: &Address, balances: &Map<SubAccount, i128>) {
+    let mut tokens = view_held_tokens(env.clone());
+    if !tokens.contains(token) {
+        tokens.push_back(token.clone());
+        env.storage().instance().set(&AccountBook::HeldTokens, &tokens);
+    }
+    
+    for (account, units) in balances.iter() {
+        env.storage().instance().set(&AccountBook::SubBalance(account, token.clone()), &units);
+    }
+}
+
+// Book `units` of `token` leaving the contract out of `account`. A sub-account never pays
+// out more than was booked into it, so funds owed to one party can't leave through another.
+pub(crate) fn debit(env: &Env, account: SubAccount, token: &Address, units: i128) -> Result<(), RentError> {
+    if units == 0 {
+        return Ok(());
+    }
+    
+    let balance = view_sub_account(env.clone(), account, token.clone());
+    if units > balance {
+        log!(env, "Sub-account holds less than the amount paid out of it");
+        return Err(RentError::AccountOverdrawn);
+    }
+    env.storage().instance().set(&AccountBook::SubBalance(account, token.clone()), &(balance - units));
+    
+    Ok(())
+}
@@ -0,0 +1,443 @@
+use soroban_sdk::{contracttype, log, symbol_short, Address, Env, IntoVal, Map, Symbol, Val, Vec};
+
+use crate::storage_keys::{DataKey, AssetBook, LeaseStatusBook};
+use crate::accounts::{self, SubAccount};
+use crate::network::{self, Network};
+use crate::{events, raise, xlm, RentError, RentErrorExt, ERROR_CODES, ERROR_CODES_EXT};
+
+// TTL policy: whenever the remaining TTL drops below `threshold` ledgers, it is extended to
+// `extend_to` ledgers. Covers the contract code and instance entries (config, counters and
+// stats), and each persistent asset or lease record as it is written.
+#[contracttype]
+#[derive(Clone)]
+pub struct TtlConfig {
+    pub threshold: u32, // Remaining ledgers below which the instance is bumped
+    pub extend_to: u32, // Ledgers of TTL the instance is bumped to
+}
+
+// Record type addressed by a batch TTL bump
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EntryKind {
+    Asset, // Asset records, by asset_id
+    Lease, // Lease records, by lease_id
+}
+
+// Anti-spam fee charged when an asset is registered
+#[contracttype]
+#[derive(Clone)]
+pub struct ListingFee {
+    pub amount: i128,      // Fee in stroops (0 disables the fee)
+    pub refundable: bool, // Refunded on the asset's first completed lease if true, burned otherwise
+}
+
+// Mark a UTC day number (timestamp / 86400) as a holiday on which no payment falls due
+pub fn add_holiday(env: Env, admin: Address, day: u64) -> Result<(), RentError> {
+    require_admin(&env, &admin)?;
+    
+    let mut holidays = view_holidays(env.clone());
+    if !holidays.contains(day) {
+        holidays.push_back(day);
+        env.storage().instance().set(&DataKey::Holidays, &holidays);
+    }
+    
+    log!(&env, "Holiday added for day {}", day);
+    
+    Ok(())
+}
+
+// Remove a day from the holiday calendar
+pub fn remove_holiday(env: Env, admin: Address, day: u64) -> Result<(), RentError> {
+    require_admin(&env, &admin)?;
+    
+    let mut holidays = view_holidays(env.clone());
+    if let Some(index) = holidays.first_index_of(day) {
+        holidays.remove(index);
+        env.storage().instance().set(&DataKey::Holidays, &holidays);
+    }
+    
+    log!(&env, "Holiday removed for day {}", day);
+    
+    Ok(())
+}
+
+// View the holiday calendar as UTC day numbers
+pub fn view_holidays(env: Env) -> Vec<u64> {
+    env.storage().instance().get(&DataKey::Holidays).unwrap_or(Vec::new(&env))
+}
+
+// Choose whether due dates on Saturdays and Sundays roll to the following Monday
+pub fn set_weekend_rollover(env: Env, admin: Address, enabled: bool) -> Result<(), RentError> {
+    require_admin(&env, &admin)?;
+    
+    env.storage().instance().set(&DataKey::WeekendRollover, &enabled);
+    
+    log!(&env, "Weekend rollover set to {}", enabled);
+    
+    Ok(())
+}
+
+// View whether weekend due dates roll to the next business day
+pub fn view_weekend_rollover(env: Env) -> bool {
+    env.storage().instance().get(&DataKey::WeekendRollover).unwrap_or(false)
+}
+
+// Set the contract admin (can only be called once)
+pub fn initialize(env: Env, admin: Address) -> Result<(), RentError> {
+    if env.storage().instance().has(&DataKey::Admin) {
+        log!(&env, "Contract already initialized");
+        return Err(RentError::AlreadyInitialized);
+    }
+    
+    admin.require_auth();
+    env.storage().instance().set(&DataKey::Admin, &admin);
+    bump_instance(&env);
+    
+    log!(&env, "Contract initialized");
+    
+    Ok(())
+}
+
+// Initialize with the native token and fee defaults of a known network, so deployments don't
+// hand-edit addresses. The ledger must be on that network.
+pub fn initialize_for_network(env: Env, admin: Address, network: Network) -> Result<(), RentError> {
+    let profile = network::profile(network);
+    if env.ledger().network_id() != network::network_id(&env, &profile) {
+        log!(&env, "Ledger is not on the requested network");
+        return Err(RentError::NetworkMismatch);
+    }
+    
+    initialize(env.clone(), admin)?;
+    env.storage().instance().set(&DataKey::Network, &network);
+    env.storage().instance().set(&DataKey::NativeToken, &Address::from_str(&env, profile.native_token));
+    env.storage().instance().set(&DataKey::ListingFee, &ListingFee { amount: profile.listing_fee, refundable: true });
+    env.storage().instance().set(&DataKey::BoostFee, &profile.boost_fee);
+    
+    log!(&env, "Network profile applied");
+    
+    Ok(())
+}
+
+// View the network named at initialize, if the contract was initialized for one
+pub fn view_network(env: Env) -> Option<Network> {
+    env.storage().instance().get(&DataKey::Network)
+}
+
+// Set or clear the landlord-verification contract used to curate asset owners
+pub fn set_landlord_registry(env: Env, admin: Address, registry: Option<Address>) -> Result<(), RentError> {
+    require_admin(&env, &admin)?;
+    
+    match registry {
+        Some(registry) => env.storage().instance().set(&DataKey::LandlordRegistry, &registry),
+        None => env.storage().instance().remove(&DataKey::LandlordRegistry),
+    }
+    
+    log!(&env, "Landlord registry updated");
+    
+    Ok(())
+}
+
+// View the configured landlord-verification contract, if any
+pub fn view_landlord_registry(env: Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::LandlordRegistry)
+}
+
+// Set the escrow contract that holds standard deposits, which must name this contract as its
+// controller. It can only be set once, since deposits already in custody stay with it.
+pub fn set_escrow_contract(env: Env, admin: Address, escrow: Address) -> Result<(), RentError> {
+    require_admin(&env, &admin)?;
+    
+    if view_escrow_contract(env.clone()).is_some() {
+        log!(&env, "Escrow contract is already set");
+        return Err(RentError::AlreadyInitialized);
+    }
+    env.storage().instance().set(&DataKey::EscrowContract, &escrow);
+    
+    log!(&env, "Escrow contract set");
+    
+    Ok(())
+}
+
+// View the escrow contract holding standard deposits, if set
+pub fn view_escrow_contract(env: Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::EscrowContract)
+}
+
+// Set the wrapped-XLM Stellar Asset Contract address for this network
+pub fn set_native_token(env: Env, admin: Address, token: Address) -> Result<(), RentError> {
+    require_admin(&env, &admin)?;
+    
+    env.storage().instance().set(&DataKey::NativeToken, &token);
+    xlm::check_decimals(&env)?;
+    
+    log!(&env, "Native XLM token set");
+    
+    Ok(())
+}
+
+// View the configured wrapped-XLM token contract, if any
+pub fn view_native_token(env: Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::NativeToken)
+}
+
+// Set the anti-spam listing fee charged by register_asset
+pub fn set_listing_fee(env: Env, admin: Address, amount: i128, refundable: bool) -> Result<(), RentError> {
+    require_admin(&env, &admin)?;
+    xlm::require_amount(&env, amount)?;
+    
+    env.storage().instance().set(&DataKey::ListingFee, &ListingFee { amount, refundable });
+    
+    log!(&env, "Listing fee set to {}", amount);
+    
+    Ok(())
+}
+
+// View the current listing fee
+pub fn view_listing_fee(env: Env) -> ListingFee {
+    env.storage().instance().get(&DataKey::ListingFee).unwrap_or(ListingFee { amount: 0, refundable: false })
+}
+
+// Set the platform account that receives marketplace fees
+pub fn set_platform_account(env: Env, admin: Address, account: Address) -> Result<(), RentError> {
+    require_admin(&env, &admin)?;
+    
+    env.storage().instance().set(&DataKey::Platform, &account);
+    
+    log!(&env, "Platform account updated");
+    
+    Ok(())
+}
+
+// View the platform account, if configured
+pub fn view_platform_account(env: Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::Platform)
+}
+
+// Set the stroops charged per day of featured-listing boost
+pub fn set_boost_fee(env: Env, admin: Address, fee_per_day: i128) -> Result<(), RentError> {
+    require_admin(&env, &admin)?;
+    xlm::require_amount(&env, fee_per_day)?;
+    
+    env.storage().instance().set(&DataKey::BoostFee, &fee_per_day);
+    
+    log!(&env, "Boost fee set to {} per day", fee_per_day);
+    
+    Ok(())
+}
+
+// View the stroops charged per day of featured-listing boost
+pub fn view_boost_fee(env: Env) -> i128 {
+    env.storage().instance().get(&DataKey::BoostFee).unwrap_or(0)
+}
+
+// Set the share of every rent payment, in basis points, kept by the contract as a platform fee
+pub fn set_platform_fee_bps(env: Env, admin: Address, fee_bps: u32) -> Result<(), RentError> {
+    require_admin(&env, &admin)?;
+    
+    if fee_bps > 10_000 {
+        log!(&env, "Platform fee exceeds 100%");
+        return Err(RentError::InvalidAmount);
+    }
+    env.storage().instance().set(&DataKey::PlatformFeeBps, &fee_bps);
+    
+    log!(&env, "Platform fee set to {} bps", fee_bps);
+    
+    Ok(())
+}
+
+// View the platform fee taken from rent payments, in basis points
+pub fn view_platform_fee_bps(env: Env) -> u32 {
+    env.storage().instance().get(&DataKey::PlatformFeeBps).unwrap_or(0)
+}
+
+// View the platform fees held by the contract, in base units per token
+pub fn view_platform_fees(env: Env) -> Map<Address, i128> {
+    let mut fees = Map::new(&env);
+    for token in accounts::view_held_tokens(env.clone()).iter() {
+        let units = accounts::view_sub_account(env.clone(), SubAccount::PlatformFees, token.clone());
+        if units > 0 {
+            fees.set(token.clone(), units);
+        }
+    }
+    
+    return fees;
+}
+
+// Send every held platform fee to `to`; returns what was withdrawn per token
+pub fn withdraw_platform_fees(env: Env, admin: Address, to: Address) -> Result<Map<Address, i128>, RentError> {
+    require_admin(&env, &admin)?;
+    
+    let fees = view_platform_fees(env.clone());
+    events::publish(&env, (symbol_short!("fees"), symbol_short!("withdrawn"), to.clone()), fees.clone());
+    
+    for (address, amount) in fees.iter() {
+        let token = xlm::token(&address);
+        accounts::debit(&env, SubAccount::PlatformFees, &address, amount)?;
+        xlm::transfer(&env, &token, &env.current_contract_address(), &to, amount)?;
+    }
+    
+    return Ok(fees);
+}
+
+// Set the minimum TTL policy applied to the contract instance and its config
+pub fn set_min_ttl(env: Env, admin: Address, threshold: u32, extend_to: u32) -> Result<(), RentError> {
+    require_admin(&env, &admin)?;
+    
+    if threshold > extend_to || extend_to > env.storage().max_ttl() {
+        log!(&env, "Invalid TTL policy");
+        return Err(RentError::InvalidAmount);
+    }
+    
+    env.storage().instance().set(&DataKey::TtlConfig, &TtlConfig { threshold, extend_to });
+    bump_instance(&env);
+    
+    log!(&env, "Minimum TTL set to {} ledgers", extend_to);
+    
+    Ok(())
+}
+
+// View the instance TTL policy
+pub fn view_min_ttl(env: Env) -> TtlConfig {
+    env.storage().instance().get(&DataKey::TtlConfig).unwrap_or(TtlConfig { threshold: 10000, extend_to: 10000 })
+}
+
+// Maintenance entrypoint anyone can call to keep the contract from being archived
+pub fn bump_contract_ttl(env: Env) {
+    bump_instance(&env);
+}
+
+// Maintenance entrypoint anyone can call to extend the TTL of many persistent asset or lease
+// records to `extend_to` ledgers in one transaction, e.g. ahead of a long quiet period. The
+// instance is bumped as well. Returns how many records were extended.
+pub fn bump_entries(env: Env, kind: EntryKind, ids: Vec<u64>, extend_to: u32) -> Result<u32, RentError> {
+    if extend_to > env.storage().max_ttl() {
+        log!(&env, "TTL exceeds the network maximum");
+        return Err(RentError::InvalidAmount);
+    }
+    
+    let mut bumped = 0;
+    for id in ids.iter() {
+        let extended = match kind {
+            EntryKind::Asset => extend_entry(&env, &AssetBook::Asset(id), extend_to),
+            EntryKind::Lease => extend_entry(&env, &LeaseStatusBook::LeaseStatus(id), extend_to),
+        };
+        if extended {
+            bumped += 1;
+        }
+    }
+    bump_instance(&env);
+    
+    return Ok(bumped);
+}
+
+// Pause or resume lease creation and payments; views and every other action keep working
+pub fn set_paused(env: Env, admin: Address, paused: bool) -> Result<(), RentError> {
+    require_admin(&env, &admin)?;
+    
+    env.storage().instance().set(&DataKey::Paused, &paused);
+    
+    log!(&env, "Paused set to {}", paused);
+    
+    Ok(())
+}
+
+// View whether the admin has paused lease creation and payments
+pub fn is_paused(env: Env) -> bool {
+    env.storage().instance().get(&DataKey::Paused).unwrap_or(false)
+}
+
+// Stop or resume new leases and listings only, e.g. while winding down or migrating;
+// payments, settlements, refunds and withdrawals carry on
+pub fn set_creation_frozen(env: Env, admin: Address, frozen: bool) -> Result<(), RentError> {
+    require_admin(&env, &admin)?;
+    
+    env.storage().instance().set(&DataKey::CreationFrozen, &frozen);
+    
+    log!(&env, "Creation frozen set to {}", frozen);
+    
+    Ok(())
+}
+
+// View whether the admin has frozen new leases and listings
+pub fn is_creation_frozen(env: Env) -> bool {
+    env.storage().instance().get(&DataKey::CreationFrozen).unwrap_or(false)
+}
+
+// List every error code with its stable symbolic name so clients can render failures
+pub fn list_error_codes(env: Env) -> Vec<(u32, Symbol)> {
+    let mut codes = Vec::new(&env);
+    
+    for (error, name) in ERROR_CODES.iter() {
+        codes.push_back((*error as u32, Symbol::new(&env, name)));
+    }
+    for (error, name) in ERROR_CODES_EXT.iter() {
+        codes.push_back((*error as u32, Symbol::new(&env, name)));
+    }
+    
+    return codes;
+}
+
+// Verify the caller is the stored admin and has signed the invocation
+pub(crate) fn require_admin(env: &Env, admin: &Address) -> Result<(), RentError> {
+    let stored: Option<Address> = env.storage().instance().get(&DataKey::Admin);
+    if stored.as_ref() != Some(admin) {
+        log!(env, "Only the admin can perform this action");
+        return Err(RentError::NotAdmin);
+    }
+    admin.require_auth();
+    
+    // Config changes always refresh the TTL of the entries they touch
+    bump_instance(env);
+    
+    Ok(())
+}
+
+// Book the platform fee on a rent payment of `amount` base units of `token`; returns the fee,
+// which the payer sends to the contract instead of the owner
+pub(crate) fn charge_platform_fee(env: &Env, token: &xlm::Token, amount: i128) -> i128 {
+    let fee = amount * view_platform_fee_bps(env.clone()) as i128 / 10_000;
+    accounts::credit(env, SubAccount::PlatformFees, &token.address, fee);
+    
+    return fee;
+}
+
+// Refuse the action while the contract is paused
+pub(crate) fn require_not_paused(env: &Env) -> Result<(), RentError> {
+    if is_paused(env.clone()) {
+        log!(env, "Contract is paused");
+        return Err(RentError::ContractPaused);
+    }
+    
+    Ok(())
+}
+
+// Refuse a new lease or listing while creation is frozen
+pub(crate) fn require_creation_open(env: &Env) -> Result<(), RentError> {
+    if is_creation_frozen(env.clone()) {
+        log!(env, "New leases and listings are frozen");
+        return Err(raise(env, RentErrorExt::CreationFrozen));
+    }
+    
+    Ok(())
+}
+
+// Extend a persistent entry's TTL to `extend_to` ledgers; false if no such persistent entry exists
+fn extend_entry<K: IntoVal<Env, Val>>(env: &Env, key: &K, extend_to: u32) -> bool {
+    if !env.storage().persistent().has(key) {
+        return false;
+    }
+    env.storage().persistent().extend_ttl(key, extend_to, extend_to);
+    return true;
+}
+
+// Keep a persistent record above the configured minimum TTL
+pub(crate) fn bump_entry<K: IntoVal<Env, Val>>(env: &Env, key: &K) {
+    let ttl = view_min_ttl(env.clone());
+    env.storage().persistent().extend_ttl(key, ttl.threshold, ttl.extend_to);
+}
+
+// Keep the contract instance (code, config, counters and stats) above the configured minimum TTL
+pub(crate) fn bump_instance(env: &Env) {
+    let ttl = view_min_ttl(env.clone());
+    env.storage().instance().extend_ttl(ttl.threshold, ttl.extend_to);
+}
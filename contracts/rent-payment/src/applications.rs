@@ -0,0 +1,278 @@
+use soroban_sdk::{contracttype, log, symbol_short, Address, Env};
+
+use crate::storage_keys::{DataKey, ApplicationBook};
+use crate::accounts::{self, SubAccount};
+use crate::{events, admin, assets, leases, stats, xlm, Asset, RentError};
+
+// Where an application to lease an asset stands
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ApplicationStatus {
+    Pending,  // Awaiting the owner's decision
+    Approved, // Owner approved; the applicant may turn it into a lease
+    Rejected, // Owner turned it down
+    Leased,   // Promoted into a lease
+    Expired,  // Left unanswered past the owner's response deadline
+}
+
+// How an asset's owner handles applications: how long they have to answer one, and the
+// holding deposit an applicant escrows until they do
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ApplicationTerms {
+    pub response_window: u64, // Seconds the owner has to decide an application (0 for no deadline)
+    pub holding_deposit: i128, // Held from the applicant in the asset's payment token while pending (0 for none)
+}
+
+// A prospective lessee's application to lease an asset, screened by its owner
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LeaseApplication {
+    pub application_id: u64,       // Unique application identifier
+    pub asset_id: u64,             // Asset applied for
+    pub applicant: Address,        // Prospective lessee
+    pub lease_duration: u64,       // Requested lease duration in seconds
+    pub applied_at: u64,           // When the application was submitted
+    pub status: ApplicationStatus, // Where the application stands
+    pub lease_id: u64,             // Lease it was promoted into (0 until leased)
+    pub respond_by: u64,           // Deadline for the owner's decision (0 for none)
+    pub holding_deposit: i128,      // Held from the applicant until the owner decides or the deadline passes
+}
+
+// Owner requires (or stops requiring) an approved application before an asset can be leased;
+// while set, create_lease and create_scheduled_lease refuse the asset
+pub fn set_screening(env: Env, asset_id: u64, owner: Address, required: bool) -> Result<(), RentError> {
+    assets::owned_asset(&env, asset_id, &owner)?;
+    
+    if required {
+        let key = ApplicationBook::ScreeningRequired(asset_id);
+        env.storage().persistent().set(&key, &true);
+        admin::bump_entry(&env, &key);
+    } else {
+        env.storage().persistent().remove(&ApplicationBook::ScreeningRequired(asset_id));
+    }
+    
+    log!(&env, "Screening for asset ID {} set to {}", asset_id, required);
+    
+    Ok(())
+}
+
+// Whether an asset only leases through approved applications
+pub fn view_screening(env: Env, asset_id: u64) -> bool {
+    env.storage().persistent().has(&ApplicationBook::ScreeningRequired(asset_id))
+}
+
+// Owner sets how long they have to decide each new application and the holding deposit an
+// applicant escrows meanwhile; applications already submitted keep the terms they were made under
+pub fn set_application_terms(env: Env, asset_id: u64, owner: Address, terms: ApplicationTerms) -> Result<(), RentError> {
+    assets::owned_asset(&env, asset_id, &owner)?;
+    xlm::require_amount(&env, terms.holding_deposit)?;
+    
+    let key = ApplicationBook::Terms(asset_id);
+    if terms == ApplicationTerms::default() {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &terms);
+        admin::bump_entry(&env, &key);
+    }
+    
+    log!(&env, "Application terms for asset ID {} updated", asset_id);
+    
+    Ok(())
+}
+
+// Response deadline and holding deposit new applications for an asset are made under
+pub fn view_application_terms(env: Env, asset_id: u64) -> ApplicationTerms {
+    env.storage().persistent().get(&ApplicationBook::Terms(asset_id)).unwrap_or_default()
+}
+
+// Prospective lessee applies to lease an asset for `lease_duration` seconds; returns the
+// application ID
+pub fn apply_for_lease(env: Env, asset_id: u64, applicant: Address, lease_duration: u64) -> Result<u64, RentError> {
+    applicant.require_auth();
+    admin::require_creation_open(&env)?;
+    
+    let asset = assets::load_asset(&env, asset_id)?;
+    if lease_duration < asset.min_lease_duration || lease_duration > asset.max_lease_duration {
+        log!(&env, "Invalid lease duration");
+        return Err(RentError::InvalidLeaseDuration);
+    }
+    
+    let now = env.ledger().timestamp();
+    let terms = view_application_terms(env.clone(), asset_id);
+    let application_id = get_application_count(env.clone()) + 1;
+    let application = LeaseApplication {
+        application_id: application_id,
+        asset_id: asset_id,
+        applicant: applicant.clone(),
+        lease_duration: lease_duration,
+        applied_at: now,
+        status: ApplicationStatus::Pending,
+        lease_id: 0,
+        respond_by: if terms.response_window > 0 { now + terms.response_window } else { 0 },
+        holding_deposit: terms.holding_deposit,
+    };
+    save_application(&env, &application);
+    env.storage().instance().set(&DataKey::ApplicationCount, &application_id);
+    admin::bump_instance(&env);
+    
+    events::publish(&env, (symbol_short!("apply"), symbol_short!("submitted"), applicant.clone()), (application_id, asset_id, lease_duration));
+    log!(&env, "Application submitted with ID: {}", application_id);
+    
+    if terms.holding_deposit > 0 {
+        let payment_token = xlm::payment_token(&asset);
+        accounts::credit(&env, SubAccount::TenantDeposits, &payment_token.address, terms.holding_deposit);
+        xlm::transfer(&env, &payment_token, &applicant, &env.current_contract_address(), terms.holding_deposit)?;
+    }
+    
+    return Ok(application_id);
+}
+
+// Owner approves a pending application, letting the applicant turn it into a lease
+pub fn approve_application(env: Env, application_id: u64, owner: Address) -> Result<(), RentError> {
+    decide(&env, application_id, &owner, ApplicationStatus::Approved)?;
+    
+    Ok(())
+}
+
+// Owner turns down a pending application
+pub fn reject_application(env: Env, application_id: u64, owner: Address) -> Result<(), RentError> {
+    decide(&env, application_id, &owner, ApplicationStatus::Rejected)?;
+    
+    Ok(())
+}
+
+// Applicant turns an approved application into a lease starting now, on the terms the asset
+// offers at this point; returns the lease ID
+pub fn accept_application(env: Env, application_id: u64, applicant: Address) -> Result<u64, RentError> {
+    let mut application = load_application(&env, application_id)?;
+    
+    if applicant != application.applicant {
+        log!(&env, "Only the applicant can accept an application");
+        return Err(RentError::NotLessee);
+    }
+    if application.status != ApplicationStatus::Approved {
+        log!(&env, "Application is not approved");
+        return Err(RentError::InvalidApplication);
+    }
+    
+    // Closed before the lease is opened, so the approval can't be used twice
+    application.status = ApplicationStatus::Leased;
+    save_application(&env, &application);
+    
+    let now = env.ledger().timestamp();
+    application.lease_id = leases::open_lease(&env, application.asset_id, applicant, now, application.lease_duration)?;
+    save_application(&env, &application);
+    
+    return Ok(application.lease_id);
+}
+
+// Close a pending application the owner left unanswered past its response deadline, refunding
+// the applicant's holding deposit and counting the miss against the owner. Anyone may call it.
+pub fn expire_application(env: Env, application_id: u64) -> Result<(), RentError> {
+    let mut application = load_application(&env, application_id)?;
+    
+    if application.status != ApplicationStatus::Pending {
+        log!(&env, "Application has already been decided");
+        return Err(RentError::InvalidApplication);
+    }
+    if application.respond_by == 0 || env.ledger().timestamp() <= application.respond_by {
+        log!(&env, "Owner's response deadline has not passed");
+        return Err(RentError::DeadlineNotReached);
+    }
+    
+    application.status = ApplicationStatus::Expired;
+    save_application(&env, &application);
+    
+    let asset = assets::load_asset(&env, application.asset_id)?;
+    stats::update_reputation(&env, &asset.owner, |r| r.applications_expired += 1);
+    refund_holding_deposit(&env, &asset, &application)?;
+    
+    events::publish(&env, (symbol_short!("apply"), symbol_short!("expired"), application.applicant.clone()), (application_id, application.asset_id));
+    log!(&env, "Application ID {} expired", application_id);
+    
+    Ok(())
+}
+
+// View an application, or None if none was submitted under `application_id`
+pub fn view_application(env: Env, application_id: u64) -> Option<LeaseApplication> {
+    env.storage().persistent().get(&ApplicationBook::Application(application_id))
+}
+
+// Get the number of applications submitted so far
+pub fn get_application_count(env: Env) -> u64 {
+    env.storage().instance().get(&DataKey::ApplicationCount).unwrap_or(0)
+}
+
+// Refuse direct lease creation on an asset that only leases through approved applications
+pub(crate) fn require_unscreened(env: &Env, asset_id: u64) -> Result<(), RentError> {
+    if view_screening(env.clone(), asset_id) {
+        log!(env, "Asset only leases through approved applications");
+        return Err(RentError::AssetUnavailable);
+    }
+    
+    Ok(())
+}
+
+// Record the owner's decision on a pending application
+fn decide(env: &Env, application_id: u64, owner: &Address, status: ApplicationStatus) -> Result<(), RentError> {
+    let mut application = load_application(env, application_id)?;
+    let asset = assets::owned_asset(env, application.asset_id, owner)?;
+    
+    if application.status != ApplicationStatus::Pending {
+        log!(env, "Application has already been decided");
+        return Err(RentError::InvalidApplication);
+    }
+    let now = env.ledger().timestamp();
+    if application.respond_by != 0 && now > application.respond_by {
+        log!(env, "Response deadline has passed; the application can only expire");
+        return Err(RentError::InvalidApplication);
+    }
+    
+    application.status = status;
+    save_application(env, &application);
+    
+    // Either answer releases the holding deposit and counts toward the owner's responsiveness
+    stats::update_reputation(env, &asset.owner, |r| {
+        r.applications_answered += 1;
+        r.total_response_time += now - application.applied_at;
+    });
+    refund_holding_deposit(env, &asset, &application)?;
+    
+    let action = match status {
+        ApplicationStatus::Approved => symbol_short!("approved"),
+        _ => symbol_short!("rejected"),
+    };
+    events::publish(env, (symbol_short!("apply"), action, application.applicant.clone()), (application_id, application.asset_id));
+    log!(env, "Application ID {} decided", application_id);
+    
+    Ok(())
+}
+
+// Return the holding deposit escrowed with an application to its applicant
+fn refund_holding_deposit(env: &Env, asset: &Asset, application: &LeaseApplication) -> Result<(), RentError> {
+    if application.holding_deposit == 0 {
+        return Ok(());
+    }
+    
+    let payment_token = xlm::payment_token(asset);
+    accounts::debit(env, SubAccount::TenantDeposits, &payment_token.address, application.holding_deposit)?;
+    xlm::transfer(env, &payment_token, &env.current_contract_address(), &application.applicant, application.holding_deposit)?;
+    
+    Ok(())
+}
+
+// Load an application that must exist
+fn load_application(env: &Env, application_id: u64) -> Result<LeaseApplication, RentError> {
+    view_application(env.clone(), application_id).ok_or_else(|| {
+        log!(env, "Application not found");
+        RentError::InvalidApplication
+    })
+}
+
+// Write an application to persistent storage and keep it above the minimum TTL
+fn save_application(env: &Env, application: &LeaseApplication) {
+    let key = ApplicationBook::Application(application.application_id);
+    env.storage().persistent().set(&key, application);
+    admin::bump_entry(env, &key);
+}
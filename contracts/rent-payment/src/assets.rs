@@ -0,0 +1,1152 @@
+use core::ops::RangeInclusive;
+
+use soroban_sdk::{contracttype, log, symbol_short, Address, BytesN, Env, Map, String, Symbol, Vec};
+
+use crate::accounts::{self, SubAccount};
+use crate::calendar::{AssetCalendar, BlackoutWindow};
+use crate::penalty::PenaltyPolicy;
+use crate::policy::PaymentPolicy;
+use crate::interface::LandlordRegistryClient;
+use crate::xlm::Amount;
+use crate::storage_keys::{DataKey, AssetBook, RelistBook, BlackoutBook, JurisdictionBook, ExternalIdBook, ReminderBook, TrialBook, CureBook, VacationBook, IndexBook, TermsBook};
+use crate::{calendar, disputes, escrow, events, xlm, admin, leases, stats, raise, RentError, RentErrorExt, Asset, DepositMode};
+
+// Price an asset is relisted at when its current lease ends
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RelistPlan {
+    Price(i128),      // Relist at this price per period
+    IncreaseBps(u32), // Relist at the current price raised by this many basis points
+}
+
+// When reminders of an upcoming payment start and how often they repeat while it stays unpaid
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ReminderPolicy {
+    pub lead_time: u64, // Seconds before next_payment_time that reminders start
+    pub cadence: u64,   // Seconds between repeat reminders
+}
+
+// Longest window an owner may hand their permissions to an emergency contact in one go
+pub const MAX_VACATION: u64 = 90 * calendar::DAY;
+
+// Most records a listing view returns per call
+pub const MAX_PAGE: u32 = 100;
+
+// Most custom terms an asset may carry, and the longest value in bytes
+pub const MAX_TERMS: u32 = 16;
+pub const MAX_TERM_LEN: u32 = 64;
+
+// Category new listings are filed under until their owner picks one, and the most tags a
+// listing may carry. The default category has no index: every listing starts in it, so one
+// would be rewritten on every registration and grow without bound.
+pub const DEFAULT_CATEGORY: Symbol = symbol_short!("general");
+pub const MAX_TAGS: u32 = 8;
+
+// Emergency contact acting for an owner while they are away
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VacationDelegate {
+    pub delegate: Address, // Who may act for the owner
+    pub until: u64,        // When the delegation lapses on its own
+}
+
+// Register a new asset with price and deposit given as tagged amounts, normalized to stroops
+pub fn register_asset_with_amounts(
+    env: Env,
+    owner: Address,
+    title: String,
+    description: String,
+    price_per_period: Amount,
+    period_duration: u64,
+    min_lease_duration: u64,
+    max_lease_duration: u64,
+    deposit_required: Amount
+) -> Result<u64, RentError> {
+    let price = xlm::normalize(&env, price_per_period, xlm::XLM_DECIMALS)?;
+    let deposit = xlm::normalize(&env, deposit_required, xlm::XLM_DECIMALS)?;
+    register_asset(env, owner, title, description, price, period_duration, min_lease_duration, max_lease_duration, deposit)
+}
+
+// Register a new asset for leasing
+pub fn register_asset(
+    env: Env, 
+    owner: Address,  // Pass the owner address as a parameter instead of using invoker
+    title: String, 
+    description: String,
+    price_per_period: i128,
+    period_duration: u64,
+    min_lease_duration: u64,
+    max_lease_duration: u64,
+    deposit_required: i128
+) -> Result<u64, RentError> {
+    // Only the owner can list their own asset
+    owner.require_auth();
+    admin::require_creation_open(&env)?;
+    
+    xlm::require_amount(&env, price_per_period)?;
+    xlm::require_amount(&env, deposit_required)?;
+    
+    // New assets are priced in stroops until the owner picks another payment token, which
+    // only holds if the native token really has 7 decimals
+    xlm::check_decimals(&env)?;
+    let payment_token = xlm::native_token(&env)?;
+    let (currency_symbol, currency_decimals) = xlm::currency(&env, &payment_token)?;
+    
+    // When a landlord registry is configured, only verified owners may list
+    if let Some(registry) = admin::view_landlord_registry(env.clone()) {
+        if !LandlordRegistryClient::new(&env, &registry).is_verified(&owner) {
+            log!(&env, "Owner is not a verified landlord");
+            return Err(RentError::LandlordNotVerified);
+        }
+    }
+    
+    let listing_fee = admin::view_listing_fee(env.clone());
+    
+    // Create a new unique asset ID
+    let mut count_assets: u64 = env.storage().instance().get(&DataKey::AssetCount).unwrap_or(0);
+    count_assets += 1;
+    
+    // Create a new asset
+    let mut asset = Asset {
+        asset_id: count_assets,
+        owner: owner,
+        title: title,
+        description: description,
+        price_per_period: price_per_period,
+        period_duration: period_duration,
+        min_lease_duration: min_lease_duration,
+        max_lease_duration: max_lease_duration,
+        deposit_required: deposit_required,
+        is_available: true,
+        current_lease_id: 0,
+        deposit_mode: DepositMode::Standard,
+        payment_policy: PaymentPolicy::FixedPeriod,
+        penalty_policy: PenaltyPolicy::None,
+        holdover_bps: 0,
+        min_lead_time: 0,
+        max_advance: 0,
+        listing_fee_held: 0,
+        listing_expires_at: 0,
+        grace_period: 0,
+        payment_token: payment_token,
+        currency_symbol: currency_symbol,
+        currency_decimals: currency_decimals,
+        escalation_bps: 0,
+        escalation_interval: 0,
+        notice_period: 0,
+        early_termination_fee: 0,
+        category: DEFAULT_CATEGORY,
+        tags: Vec::new(&env),
+    };
+    
+    if listing_fee.refundable {
+        asset.listing_fee_held = listing_fee.amount;
+    } else if listing_fee.amount > 0 {
+        stats::update_financials(&env, count_assets, |f| f.platform_fees += listing_fee.amount);
+    }
+    
+    // Update the asset count
+    env.storage().instance().set(&DataKey::AssetCount, &count_assets);
+    
+    // Store the asset data
+    save_asset(&env, &asset);
+    index_listing(&env, &asset);
+    
+    // Update global stats
+    let stats = stats::view_asset_stats(env.clone());
+    env.storage().instance().set(&DataKey::Stats, &stats);
+    
+    admin::bump_instance(&env);
+    
+    events::publish(
+        &env,
+        (symbol_short!("asset"), symbol_short!("register"), asset.owner.clone()),
+        (count_assets, asset.price_per_period, asset.period_duration, asset.deposit_required),
+    );
+    
+    // Charge the listing fee once the asset is recorded: held for refund, or burned outright
+    if listing_fee.amount > 0 {
+        if listing_fee.refundable {
+            let native = xlm::native(&env)?;
+            accounts::credit(&env, SubAccount::OwnerEarnings, &native.address, listing_fee.amount);
+            xlm::transfer(&env, &native, &asset.owner, &env.current_contract_address(), listing_fee.amount)?;
+        } else {
+            xlm::burn(&env, &asset.owner, listing_fee.amount)?;
+        }
+    }
+    log!(&env, "Asset registered with ID: {}", count_assets);
+    
+    return Ok(count_assets);
+}
+
+// Change an asset's listing terms. While a lease runs only the price and description may
+// change, and the lease keeps its own rent until it renews at the current price; deposit and
+// duration changes must wait until the asset is free.
+pub fn update_asset(
+    env: Env,
+    asset_id: u64,
+    owner: Address,
+    price_per_period: i128,
+    deposit_required: i128,
+    min_lease_duration: u64,
+    max_lease_duration: u64,
+    description: String
+) -> Result<(), RentError> {
+    let mut asset = owned_asset(&env, asset_id, &owner)?;
+    xlm::require_amount(&env, price_per_period)?;
+    xlm::require_amount(&env, deposit_required)?;
+    
+    if min_lease_duration > max_lease_duration {
+        log!(&env, "Minimum lease duration exceeds the maximum");
+        return Err(RentError::InvalidLeaseDuration);
+    }
+    
+    let terms_changed = deposit_required != asset.deposit_required
+        || min_lease_duration != asset.min_lease_duration
+        || max_lease_duration != asset.max_lease_duration;
+    if asset.current_lease_id != 0 && terms_changed {
+        log!(&env, "Deposit and durations cannot change while the asset is leased");
+        return Err(RentError::AssetUnavailable);
+    }
+    
+    asset.price_per_period = price_per_period;
+    asset.deposit_required = deposit_required;
+    asset.min_lease_duration = min_lease_duration;
+    asset.max_lease_duration = max_lease_duration;
+    asset.description = description;
+    save_asset(&env, &asset);
+    
+    events::publish(&env, (symbol_short!("asset"), symbol_short!("updated"), asset.owner.clone()), (asset_id, price_per_period, deposit_required));
+    log!(&env, "Asset updated for asset ID: {}", asset_id);
+    
+    Ok(())
+}
+
+// Sell or hand an asset to `new_owner`, with both parties signing. Any running lease carries on
+// unchanged and its future rent is held for the new owner; rent already paid stays withdrawable
+// by the seller. A held listing fee goes with the asset, and the seller's external ID is cleared.
+// The new owner takes over claims on an allowance-locked deposit and the lease's external ID.
+pub fn transfer_asset(env: Env, asset_id: u64, current_owner: Address, new_owner: Address) -> Result<(), RentError> {
+    let mut asset = load_asset(&env, asset_id)?;
+    if asset.owner != current_owner {
+        log!(&env, "Only the asset owner can transfer it");
+        return Err(RentError::NotAssetOwner);
+    }
+    current_owner.require_auth();
+    new_owner.require_auth();
+    
+    // The buyer must be able to list the asset themselves
+    if let Some(registry) = admin::view_landlord_registry(env.clone()) {
+        if !LandlordRegistryClient::new(&env, &registry).is_verified(&new_owner) {
+            log!(&env, "New owner is not a verified landlord");
+            return Err(RentError::LandlordNotVerified);
+        }
+    }
+    // A ruling or release pays whoever owns the asset when it lands, so none may be pending
+    let disputed = asset.current_lease_id != 0 && disputes::is_disputed(&env, asset.current_lease_id);
+    if disputed || !escrow::view_unsettled(&env, asset_id).is_empty() {
+        log!(&env, "Asset has deposits awaiting a release or ruling");
+        return Err(raise(&env, RentErrorExt::DepositUnsettled));
+    }
+    
+    if let Some(external_id) = view_asset_external_id(env.clone(), asset_id) {
+        env.storage().persistent().remove(&ExternalIdBook::AssetLookup(current_owner.clone(), external_id));
+        env.storage().persistent().remove(&ExternalIdBook::OfAsset(asset_id));
+        stats::count_storage(&env, |m| m.external_ids -= 1);
+    }
+    if asset.current_lease_id != 0 {
+        leases::hand_over_lease(&env, asset.current_lease_id, &current_owner, &new_owner)?;
+    }
+    unindex_owner_asset(&env, &current_owner, asset_id);
+    index_owner_asset(&env, &new_owner, asset_id);
+    asset.owner = new_owner.clone();
+    // The seller paid the held listing fee, so it goes back to them rather than with the asset
+    let listing_fee = release_listing_fee(&mut asset);
+    save_asset(&env, &asset);
+    
+    events::publish(&env, (symbol_short!("asset"), symbol_short!("transfer"), new_owner), (asset_id, current_owner.clone()));
+    log!(&env, "Ownership transferred for asset ID: {}", asset_id);
+    
+    refund_listing_fee(&env, asset_id, &current_owner, listing_fee)?;
+    
+    Ok(())
+}
+
+// Take an asset off the market for good; only allowed while it has no lease
+pub fn delist_asset(env: Env, asset_id: u64, owner: Address) -> Result<(), RentError> {
+    let mut asset = owned_asset(&env, asset_id, &owner)?;
+    
+    if asset.current_lease_id != 0 {
+        log!(&env, "Asset cannot be delisted while leased");
+        return Err(RentError::AssetUnavailable);
+    }
+    
+    asset.is_available = false;
+    save_asset(&env, &asset);
+    
+    unindex_listing(&env, &asset);
+    
+    events::publish(&env, (symbol_short!("asset"), symbol_short!("delisted"), asset.owner.clone()), asset_id);
+    log!(&env, "Asset delisted for asset ID: {}", asset_id);
+    
+    Ok(())
+}
+
+// Choose the billing model applied to future leases of an asset
+pub fn set_payment_policy(env: Env, asset_id: u64, owner: Address, payment_policy: PaymentPolicy) -> Result<(), RentError> {
+    let mut asset = owned_asset(&env, asset_id, &owner)?;
+    if let PaymentPolicy::Metered(rate) = payment_policy {
+        xlm::require_amount(&env, rate)?;
+    }
+    asset.payment_policy = payment_policy;
+    save_asset(&env, &asset);
+    
+    log!(&env, "Payment policy updated for asset ID: {}", asset_id);
+    
+    Ok(())
+}
+
+// Choose how late payments are penalized for leases of an asset
+pub fn set_penalty_policy(env: Env, asset_id: u64, owner: Address, penalty_policy: PenaltyPolicy) -> Result<(), RentError> {
+    let mut asset = owned_asset(&env, asset_id, &owner)?;
+    match &penalty_policy {
+        PenaltyPolicy::Flat(fee) => xlm::require_amount(&env, *fee)?,
+        PenaltyPolicy::Interest(interest) => xlm::require_amount(&env, interest.cap)?,
+        _ => {}
+    }
+    asset.penalty_policy = penalty_policy;
+    save_asset(&env, &asset);
+    
+    log!(&env, "Penalty policy updated for asset ID: {}", asset_id);
+    
+    Ok(())
+}
+
+// Set how many seconds past a due date a lessee may pay before the lease can be marked overdue
+// and the penalty policy starts charging late fees; fees then accrue from the due date itself
+pub fn set_grace_period(env: Env, asset_id: u64, owner: Address, grace_period: u64) -> Result<(), RentError> {
+    let mut asset = owned_asset(&env, asset_id, &owner)?;
+    asset.grace_period = grace_period;
+    save_asset(&env, &asset);
+    
+    log!(&env, "Grace period for asset ID {} set to {} seconds", asset_id, grace_period);
+    
+    Ok(())
+}
+
+// Price an asset in any Soroban token (a SAC such as USDC, or a custom token contract)
+// instead of XLM. Existing price and deposit figures are kept as base units of the new token,
+// so this is only allowed while the asset has no lease and the owner should reprice after it.
+// Tokens reporting more than xlm::MAX_DECIMALS places are refused with UnsupportedDecimals.
+pub fn set_payment_token(env: Env, asset_id: u64, owner: Address, payment_token: Address) -> Result<(), RentError> {
+    let mut asset = owned_asset(&env, asset_id, &owner)?;
+    
+    if asset.current_lease_id != 0 {
+        log!(&env, "Payment token cannot change while the asset is leased");
+        return Err(RentError::AssetUnavailable);
+    }
+    
+    let (currency_symbol, currency_decimals) = xlm::currency(&env, &payment_token)?;
+    asset.payment_token = payment_token;
+    asset.currency_symbol = currency_symbol;
+    asset.currency_decimals = currency_decimals;
+    save_asset(&env, &asset);
+    
+    log!(&env, "Payment token updated for asset ID: {}", asset_id);
+    
+    Ok(())
+}
+
+// Pay to feature an asset for `duration` seconds; boosts on an already featured asset
+// extend its window. The fee goes to the platform account. Returns the new expiry.
+pub fn boost_listing(env: Env, asset_id: u64, owner: Address, duration: u64) -> Result<u64, RentError> {
+    owned_asset(&env, asset_id, &owner)?;
+    
+    if duration == 0 {
+        log!(&env, "Boost duration must be positive");
+        return Err(RentError::InvalidAmount);
+    }
+    
+    let platform = admin::view_platform_account(env.clone()).ok_or_else(|| {
+        log!(&env, "Platform account is not configured");
+        RentError::PlatformNotSet
+    })?;
+    let fee = admin::view_boost_fee(env.clone()).checked_mul(duration as i128).and_then(|f| f.checked_add(86_399)).ok_or_else(|| {
+        log!(&env, "Boost duration is too long");
+        RentError::InvalidAmount
+    })? / 86_400;
+    stats::update_financials(&env, asset_id, |f| f.platform_fees += fee);
+    
+    // Drop expired entries while rewriting the list
+    let now = env.ledger().timestamp();
+    let featured: Vec<(u64, u64)> = env.storage().instance().get(&DataKey::Featured).unwrap_or(Vec::new(&env));
+    let mut updated = Vec::new(&env);
+    let mut expires_at = now.saturating_add(duration);
+    for (id, expiry) in featured.iter() {
+        if id == asset_id {
+            expires_at = expiry.max(now).saturating_add(duration);
+        } else if expiry > now {
+            updated.push_back((id, expiry));
+        }
+    }
+    updated.push_back((asset_id, expires_at));
+    env.storage().instance().set(&DataKey::Featured, &updated);
+    
+    xlm::transfer(&env, &xlm::native(&env)?, &owner, &platform, fee)?;
+    log!(&env, "Asset ID {} featured until {}", asset_id, expires_at);
+    
+    return Ok(expires_at);
+}
+
+// List the currently featured assets; boosts expire automatically
+pub fn get_featured_assets(env: Env) -> Result<Vec<u64>, RentError> {
+    let now = env.ledger().timestamp();
+    let featured: Vec<(u64, u64)> = env.storage().instance().get(&DataKey::Featured).unwrap_or(Vec::new(&env));
+    let mut assets = Vec::new(&env);
+    
+    for (id, expiry) in featured.iter() {
+        if expiry > now && !listing_expired(&load_asset(&env, id)?, now) {
+            assets.push_back(id);
+        }
+    }
+    
+    return Ok(assets);
+}
+
+// Preset the price an asset is relisted at when its current lease ends (None to cancel)
+pub fn set_relist_plan(env: Env, asset_id: u64, owner: Address, plan: Option<RelistPlan>) -> Result<(), RentError> {
+    owned_asset(&env, asset_id, &owner)?;
+    if let Some(RelistPlan::Price(price)) = plan {
+        xlm::require_amount(&env, price)?;
+    }
+    
+    match plan {
+        Some(plan) => {
+            let key = RelistBook::Plan(asset_id);
+            env.storage().persistent().set(&key, &plan);
+            admin::bump_entry(&env, &key);
+        }
+        None => env.storage().persistent().remove(&RelistBook::Plan(asset_id)),
+    }
+    
+    log!(&env, "Relist plan updated for asset ID: {}", asset_id);
+    
+    Ok(())
+}
+
+// View an asset's pending relist plan, if any
+pub fn view_relist_plan(env: Env, asset_id: u64) -> Option<RelistPlan> {
+    env.storage().persistent().get(&RelistBook::Plan(asset_id))
+}
+
+// Set how far ahead of each due date reminders fire and how often they repeat
+pub fn set_reminder_policy(env: Env, asset_id: u64, owner: Address, policy: ReminderPolicy) -> Result<(), RentError> {
+    owned_asset(&env, asset_id, &owner)?;
+    
+    if policy.cadence == 0 {
+        log!(&env, "Reminder cadence must be positive");
+        return Err(RentError::InvalidWindow);
+    }
+    let key = ReminderBook::Cadence(asset_id);
+    env.storage().persistent().set(&key, &policy);
+    admin::bump_entry(&env, &key);
+    
+    log!(&env, "Reminder policy updated for asset ID: {}", asset_id);
+    
+    Ok(())
+}
+
+// View an asset's reminder policy (default: start 3 days ahead, repeat daily)
+pub fn view_reminder_policy(env: Env, asset_id: u64) -> ReminderPolicy {
+    env.storage().persistent().get(&ReminderBook::Cadence(asset_id)).unwrap_or(ReminderPolicy {
+        lead_time: 3 * calendar::DAY,
+        cadence: calendar::DAY,
+    })
+}
+
+// Offer the first `days` of each new lease as a trial either party may exit (0 offers none)
+pub fn set_trial_period(env: Env, asset_id: u64, owner: Address, days: u32) -> Result<(), RentError> {
+    owned_asset(&env, asset_id, &owner)?;
+    
+    let key = TrialBook::Trial(asset_id);
+    env.storage().persistent().set(&key, &days);
+    admin::bump_entry(&env, &key);
+    
+    log!(&env, "Trial period for asset ID {} set to {} days", asset_id, days);
+    
+    Ok(())
+}
+
+// View the trial period offered on new leases of an asset, in days
+pub fn view_trial_period(env: Env, asset_id: u64) -> u32 {
+    env.storage().persistent().get(&TrialBook::Trial(asset_id)).unwrap_or(0)
+}
+
+// Set how much notice ends a lease of the asset before its term is over, and the fee a lessee
+// leaving without it pays out of the escrowed deposit. Leases already signed keep the terms
+// they were signed under.
+pub fn set_termination_terms(env: Env, asset_id: u64, owner: Address, notice_period: u64, early_termination_fee: i128) -> Result<(), RentError> {
+    let mut asset = owned_asset(&env, asset_id, &owner)?;
+    xlm::require_amount(&env, early_termination_fee)?;
+    asset.notice_period = notice_period;
+    asset.early_termination_fee = early_termination_fee;
+    save_asset(&env, &asset);
+    
+    log!(&env, "Termination terms updated for asset ID: {}", asset_id);
+    
+    Ok(())
+}
+
+// Give lessees `cure_period` seconds after a lease is marked overdue to pay everything owed
+// without a late fee or any escalation of the late notice (0 offers no cure period)
+pub fn set_cure_period(env: Env, asset_id: u64, owner: Address, cure_period: u64) -> Result<(), RentError> {
+    owned_asset(&env, asset_id, &owner)?;
+    
+    let key = CureBook::Period(asset_id);
+    env.storage().persistent().set(&key, &cure_period);
+    admin::bump_entry(&env, &key);
+    
+    log!(&env, "Cure period for asset ID {} set to {} seconds", asset_id, cure_period);
+    
+    Ok(())
+}
+
+// File an asset under a marketplace category and replace its discovery tags, keeping the
+// category and tag indexes in step. Repeated tags are kept once.
+pub fn set_asset_category(env: Env, asset_id: u64, owner: Address, category: Symbol, tags: Vec<Symbol>) -> Result<(), RentError> {
+    let mut asset = owned_asset(&env, asset_id, &owner)?;
+    if delisted(&asset) {
+        log!(&env, "Delisted assets cannot be refiled");
+        return Err(RentError::AssetUnavailable);
+    }
+    
+    let mut unique = Vec::new(&env);
+    for tag in tags.iter() {
+        if !unique.contains(&tag) {
+            unique.push_back(tag);
+        }
+    }
+    if unique.len() > MAX_TAGS {
+        log!(&env, "An asset may carry at most {} tags", MAX_TAGS);
+        return Err(RentError::TermLimitExceeded);
+    }
+    
+    if asset.category != category {
+        if asset.category != DEFAULT_CATEGORY {
+            unindex_asset(&env, IndexBook::CategoryIndex(asset.category.clone()), asset_id);
+        }
+        if category != DEFAULT_CATEGORY {
+            index_asset(&env, IndexBook::CategoryIndex(category.clone()), asset_id);
+        }
+    }
+    for tag in asset.tags.iter() {
+        if !unique.contains(&tag) {
+            unindex_asset(&env, IndexBook::TagIndex(tag), asset_id);
+        }
+    }
+    for tag in unique.iter() {
+        if !asset.tags.contains(&tag) {
+            index_asset(&env, IndexBook::TagIndex(tag), asset_id);
+        }
+    }
+    
+    asset.category = category;
+    asset.tags = unique;
+    save_asset(&env, &asset);
+    
+    log!(&env, "Category and tags updated for asset ID: {}", asset_id);
+    
+    Ok(())
+}
+
+// View the cure period offered on overdue leases of an asset, in seconds
+pub fn view_cure_period(env: Env, asset_id: u64) -> u64 {
+    env.storage().persistent().get(&CureBook::Period(asset_id)).unwrap_or(0)
+}
+
+// Set or clear one custom term on an asset, e.g. "pets" -> "no". Terms are copied onto each
+// new lease at creation, so changes never reach leases already signed.
+pub fn set_asset_term(env: Env, asset_id: u64, owner: Address, key: Symbol, value: Option<String>) -> Result<(), RentError> {
+    owned_asset(&env, asset_id, &owner)?;
+    
+    let mut terms = view_asset_terms(env.clone(), asset_id);
+    match value {
+        Some(value) => {
+            if value.len() > MAX_TERM_LEN || (!terms.contains_key(key.clone()) && terms.len() >= MAX_TERMS) {
+                log!(&env, "Custom term limit exceeded");
+                return Err(RentError::TermLimitExceeded);
+            }
+            terms.set(key.clone(), value);
+        }
+        None => {
+            terms.remove(key.clone());
+        }
+    }
+    
+    let storage_key = TermsBook::AssetTerms(asset_id);
+    env.storage().persistent().set(&storage_key, &terms);
+    admin::bump_entry(&env, &storage_key);
+    
+    log!(&env, "Custom term {} updated for asset ID: {}", key, asset_id);
+    
+    Ok(())
+}
+
+// View the custom terms an asset offers on new leases
+pub fn view_asset_terms(env: Env, asset_id: u64) -> Map<Symbol, String> {
+    env.storage().persistent().get(&TermsBook::AssetTerms(asset_id)).unwrap_or(Map::new(&env))
+}
+
+// Set or refresh when an asset's listing expires (0 keeps it listed indefinitely)
+pub fn refresh_listing(env: Env, asset_id: u64, owner: Address, listing_expires_at: u64) -> Result<(), RentError> {
+    let mut asset = owned_asset(&env, asset_id, &owner)?;
+    
+    if listing_expires_at != 0 && listing_expires_at <= env.ledger().timestamp() {
+        log!(&env, "Listing expiry must be in the future");
+        return Err(RentError::InvalidWindow);
+    }
+    
+    asset.listing_expires_at = listing_expires_at;
+    save_asset(&env, &asset);
+    
+    log!(&env, "Listing for asset ID {} now expires at {}", asset_id, listing_expires_at);
+    
+    Ok(())
+}
+
+// Set how soon and how far ahead leases of an asset may start (max_advance 0 for no limit)
+pub fn set_booking_window(env: Env, asset_id: u64, owner: Address, min_lead_time: u64, max_advance: u64) -> Result<(), RentError> {
+    let mut asset = owned_asset(&env, asset_id, &owner)?;
+    
+    if max_advance != 0 && max_advance < min_lead_time {
+        log!(&env, "Invalid booking window");
+        return Err(RentError::InvalidWindow);
+    }
+    
+    asset.min_lead_time = min_lead_time;
+    asset.max_advance = max_advance;
+    save_asset(&env, &asset);
+    
+    log!(&env, "Booking window updated for asset ID: {}", asset_id);
+    
+    Ok(())
+}
+
+// Add a one-off or recurring blackout window to an asset's calendar; returns its index
+pub fn add_blackout(env: Env, asset_id: u64, owner: Address, window: BlackoutWindow) -> Result<u32, RentError> {
+    let asset = owned_asset(&env, asset_id, &owner)?;
+    
+    if !calendar::is_valid(&window) {
+        log!(&env, "Invalid blackout window");
+        return Err(RentError::InvalidWindow);
+    }
+    
+    // An existing lease keeps its booked period
+    if asset.current_lease_id != 0 {
+        let lease = leases::load_lease(&env, asset.current_lease_id)?;
+        if calendar::overlaps(&window, lease.start_time, lease.end_time) {
+            log!(&env, "Blackout overlaps the current lease");
+            return Err(RentError::BlackoutConflict);
+        }
+    }
+    
+    let mut blackouts = view_blackouts(env.clone(), asset_id);
+    blackouts.push_back(window);
+    let key = BlackoutBook::Blackouts(asset_id);
+    env.storage().persistent().set(&key, &blackouts);
+    admin::bump_entry(&env, &key);
+    stats::count_storage(&env, |m| m.blackout_windows += 1);
+    
+    log!(&env, "Blackout added for asset ID: {}", asset_id);
+    
+    return Ok(blackouts.len() - 1);
+}
+
+// Remove a blackout window by index
+pub fn remove_blackout(env: Env, asset_id: u64, owner: Address, index: u32) -> Result<(), RentError> {
+    owned_asset(&env, asset_id, &owner)?;
+    
+    let mut blackouts = view_blackouts(env.clone(), asset_id);
+    if index >= blackouts.len() {
+        log!(&env, "Blackout index out of range");
+        return Err(RentError::InvalidWindow);
+    }
+    blackouts.remove(index);
+    let key = BlackoutBook::Blackouts(asset_id);
+    env.storage().persistent().set(&key, &blackouts);
+    admin::bump_entry(&env, &key);
+    stats::count_storage(&env, |m| m.blackout_windows -= 1);
+    
+    log!(&env, "Blackout removed for asset ID: {}", asset_id);
+    
+    Ok(())
+}
+
+// View an asset's blackout windows
+pub fn view_blackouts(env: Env, asset_id: u64) -> Vec<BlackoutWindow> {
+    env.storage().persistent().get(&BlackoutBook::Blackouts(asset_id)).unwrap_or(Vec::new(&env))
+}
+
+// View an asset's calendar: blackout windows and the currently booked lease period
+pub fn view_asset_calendar(env: Env, asset_id: u64) -> Result<AssetCalendar, RentError> {
+    let asset = load_asset(&env, asset_id)?;
+    let mut calendar = AssetCalendar {
+        asset_id: asset_id,
+        blackouts: view_blackouts(env.clone(), asset_id),
+        booked_from: 0,
+        booked_until: 0,
+    };
+    
+    if asset.current_lease_id != 0 {
+        let lease = leases::load_lease(&env, asset.current_lease_id)?;
+        calendar.booked_from = lease.start_time;
+        calendar.booked_until = lease.end_time;
+    }
+    
+    return Ok(calendar);
+}
+
+// Place an asset under a jurisdiction's tenant-protection rules (None to clear)
+pub fn set_asset_jurisdiction(env: Env, asset_id: u64, owner: Address, code: Option<Symbol>) -> Result<(), RentError> {
+    owned_asset(&env, asset_id, &owner)?;
+    
+    match code {
+        Some(code) => {
+            let key = JurisdictionBook::Jurisdiction(asset_id);
+            env.storage().persistent().set(&key, &code);
+            admin::bump_entry(&env, &key);
+        }
+        None => env.storage().persistent().remove(&JurisdictionBook::Jurisdiction(asset_id)),
+    }
+    
+    log!(&env, "Jurisdiction updated for asset ID: {}", asset_id);
+    
+    Ok(())
+}
+
+// View the jurisdiction an asset falls under, if any
+pub fn view_asset_jurisdiction(env: Env, asset_id: u64) -> Option<Symbol> {
+    env.storage().persistent().get(&JurisdictionBook::Jurisdiction(asset_id))
+}
+
+// Tag an asset with the owner's own reference (e.g. a hash of an internal property code) so
+// ERP systems can find it by their identifier regardless of renames; None clears it
+pub fn set_asset_external_id(env: Env, asset_id: u64, owner: Address, external_id: Option<BytesN<32>>) -> Result<(), RentError> {
+    let owner = owned_asset(&env, asset_id, &owner)?.owner;
+    
+    if let Some(id) = &external_id {
+        let existing = find_asset_by_external_id(env.clone(), owner.clone(), id.clone());
+        if existing.is_some_and(|other| other != asset_id) {
+            log!(&env, "External ID already used by another asset");
+            return Err(RentError::ExternalIdTaken);
+        }
+    }
+    
+    let previous = view_asset_external_id(env.clone(), asset_id);
+    if let Some(previous) = &previous {
+        env.storage().persistent().remove(&ExternalIdBook::AssetLookup(owner.clone(), previous.clone()));
+    }
+    match (&previous, &external_id) {
+        (None, Some(_)) => stats::count_storage(&env, |m| m.external_ids += 1),
+        (Some(_), None) => stats::count_storage(&env, |m| m.external_ids -= 1),
+        _ => {}
+    }
+    match external_id {
+        Some(id) => {
+            let key = ExternalIdBook::OfAsset(asset_id);
+            env.storage().persistent().set(&key, &id);
+            admin::bump_entry(&env, &key);
+            let key = ExternalIdBook::AssetLookup(owner, id);
+            env.storage().persistent().set(&key, &asset_id);
+            admin::bump_entry(&env, &key);
+        }
+        None => env.storage().persistent().remove(&ExternalIdBook::OfAsset(asset_id)),
+    }
+    
+    log!(&env, "External ID updated for asset ID: {}", asset_id);
+    
+    Ok(())
+}
+
+// View the external ID of an asset, if any
+pub fn view_asset_external_id(env: Env, asset_id: u64) -> Option<BytesN<32>> {
+    env.storage().persistent().get(&ExternalIdBook::OfAsset(asset_id))
+}
+
+// Find an owner's asset by the external ID they assigned
+pub fn find_asset_by_external_id(env: Env, owner: Address, external_id: BytesN<32>) -> Option<u64> {
+    env.storage().persistent().get(&ExternalIdBook::AssetLookup(owner, external_id))
+}
+
+// Set the holdover rent multiplier (basis points, e.g. 12500 for 125%) for an asset;
+// 0 disables holdover so leases stop taking payments at end_time
+pub fn set_holdover_rate(env: Env, asset_id: u64, owner: Address, holdover_bps: u32) -> Result<(), RentError> {
+    let mut asset = owned_asset(&env, asset_id, &owner)?;
+    asset.holdover_bps = holdover_bps;
+    save_asset(&env, &asset);
+    
+    log!(&env, "Holdover rate updated for asset ID: {}", asset_id);
+    
+    Ok(())
+}
+
+// Set the rent escalation clause for new leases of an asset: period_payment rises by
+// `escalation_bps` (at most 100%) every `escalation_interval` periods. Either being 0
+// disables escalation; leases already signed keep the clause they were signed under.
+pub fn set_rent_escalation(env: Env, asset_id: u64, owner: Address, escalation_bps: u32, escalation_interval: u32) -> Result<(), RentError> {
+    let mut asset = owned_asset(&env, asset_id, &owner)?;
+    if escalation_bps > 10_000 {
+        log!(&env, "Escalation cannot exceed 100% per step");
+        return Err(RentError::InvalidAmount);
+    }
+    asset.escalation_bps = escalation_bps;
+    asset.escalation_interval = escalation_interval;
+    save_asset(&env, &asset);
+    
+    log!(&env, "Rent escalation updated for asset ID: {}", asset_id);
+    
+    Ok(())
+}
+
+// Whether an asset is registered under `asset_id`, without loading the record
+pub fn asset_exists(env: Env, asset_id: u64) -> bool {
+    env.storage().persistent().has(&AssetBook::Asset(asset_id))
+}
+
+// Number of assets registered so far (also the highest asset ID issued)
+pub fn get_asset_count(env: Env) -> u64 {
+    env.storage().instance().get(&DataKey::AssetCount).unwrap_or(0)
+}
+
+// View asset details, or None if no asset is registered under `asset_id`
+pub fn view_asset(env: Env, asset_id: u64) -> Option<Asset> {
+    env.storage().persistent().get(&AssetBook::Asset(asset_id))
+}
+
+// Write an asset record to persistent storage and keep it above the minimum TTL
+pub(crate) fn save_asset(env: &Env, asset: &Asset) {
+    let key = AssetBook::Asset(asset.asset_id);
+    env.storage().persistent().set(&key, asset);
+    admin::bump_entry(env, &key);
+}
+
+// Load an asset that must exist
+pub(crate) fn load_asset(env: &Env, asset_id: u64) -> Result<Asset, RentError> {
+    match view_asset(env.clone(), asset_id) {
+        Some(asset) => Ok(asset),
+        None => {
+            log!(env, "Asset not found");
+            return Err(RentError::AssetNotFound);
+        }
+    }
+}
+
+// Get all assets owned by a specific address
+pub fn get_owner_assets(env: Env, owner: Address) -> Vec<u64> {
+    env.storage().persistent().get(&IndexBook::OwnerAssets(owner)).unwrap_or(Vec::new(&env))
+}
+
+// Record that `owner` now owns an asset
+pub(crate) fn index_owner_asset(env: &Env, owner: &Address, asset_id: u64) {
+    let key = IndexBook::OwnerAssets(owner.clone());
+    let mut owned = get_owner_assets(env.clone(), owner.clone());
+    owned.push_back(asset_id);
+    env.storage().persistent().set(&key, &owned);
+    admin::bump_entry(env, &key);
+    stats::count_storage(env, |m| m.owner_index_entries += 1);
+}
+
+// Record that `owner` no longer owns an asset
+pub(crate) fn unindex_owner_asset(env: &Env, owner: &Address, asset_id: u64) {
+    let key = IndexBook::OwnerAssets(owner.clone());
+    let mut owned = get_owner_assets(env.clone(), owner.clone());
+    if let Some(position) = owned.first_index_of(asset_id) {
+        owned.remove(position);
+        stats::count_storage(env, |m| m.owner_index_entries -= 1);
+    }
+    env.storage().persistent().set(&key, &owned);
+    admin::bump_entry(env, &key);
+}
+
+// Page through the assets filed under `category`: up to `limit` (capped at MAX_PAGE) records
+// starting at position `start` of the category index. The unindexed default category is
+// searched one page of asset IDs at a time instead (as list_assets), so its pages can come
+// back short or empty; the next page starts at `start + limit`.
+pub fn search_assets_by_category(env: Env, category: Symbol, start: u32, limit: u32) -> Result<Vec<Asset>, RentError> {
+    if category != DEFAULT_CATEGORY {
+        return search_index(&env, IndexBook::CategoryIndex(category), start, limit);
+    }
+    
+    let count_assets: u64 = env.storage().instance().get(&DataKey::AssetCount).unwrap_or(0);
+    let mut page = Vec::new(&env);
+    for asset_id in page_range(count_assets, start as u64, limit) {
+        let asset = load_asset(&env, asset_id)?;
+        if asset.category == DEFAULT_CATEGORY && !delisted(&asset) {
+            page.push_back(asset);
+        }
+    }
+    
+    return Ok(page);
+}
+
+// Page through the assets carrying `tag`, as `search_assets_by_category` does
+pub fn search_assets_by_tag(env: Env, tag: Symbol, start: u32, limit: u32) -> Result<Vec<Asset>, RentError> {
+    return search_index(&env, IndexBook::TagIndex(tag), start, limit);
+}
+
+fn search_index(env: &Env, key: IndexBook, start: u32, limit: u32) -> Result<Vec<Asset>, RentError> {
+    let ids: Vec<u64> = env.storage().persistent().get(&key).unwrap_or(Vec::new(env));
+    let mut page = Vec::new(env);
+    
+    let end = ids.len().min(start.saturating_add(limit.min(MAX_PAGE)));
+    for position in start..end {
+        page.push_back(load_asset(env, ids.get_unchecked(position))?);
+    }
+    
+    return Ok(page);
+}
+
+// Add a newly recorded asset to its owner's assets and to its category and tag indexes
+pub(crate) fn index_listing(env: &Env, asset: &Asset) {
+    index_owner_asset(env, &asset.owner, asset.asset_id);
+    if asset.category != DEFAULT_CATEGORY {
+        index_asset(env, IndexBook::CategoryIndex(asset.category.clone()), asset.asset_id);
+    }
+    for tag in asset.tags.iter() {
+        index_asset(env, IndexBook::TagIndex(tag), asset.asset_id);
+    }
+}
+
+// Take an asset that will never be offered again out of its category and tag indexes and the
+// featured list; it stays among its owner's assets
+pub(crate) fn unindex_listing(env: &Env, asset: &Asset) {
+    if asset.category != DEFAULT_CATEGORY {
+        unindex_asset(env, IndexBook::CategoryIndex(asset.category.clone()), asset.asset_id);
+    }
+    for tag in asset.tags.iter() {
+        unindex_asset(env, IndexBook::TagIndex(tag), asset.asset_id);
+    }
+    
+    let mut featured: Vec<(u64, u64)> = env.storage().instance().get(&DataKey::Featured).unwrap_or(Vec::new(env));
+    if let Some(position) = featured.iter().position(|(id, _)| id == asset.asset_id) {
+        featured.remove(position as u32);
+        env.storage().instance().set(&DataKey::Featured, &featured);
+    }
+}
+
+// Add an asset to a discovery index
+fn index_asset(env: &Env, key: IndexBook, asset_id: u64) {
+    let mut ids: Vec<u64> = env.storage().persistent().get(&key).unwrap_or(Vec::new(env));
+    ids.push_back(asset_id);
+    env.storage().persistent().set(&key, &ids);
+    admin::bump_entry(env, &key);
+    count_index_entry(env, &key, true);
+}
+
+// Drop an asset from a discovery index
+fn unindex_asset(env: &Env, key: IndexBook, asset_id: u64) {
+    let mut ids: Vec<u64> = env.storage().persistent().get(&key).unwrap_or(Vec::new(env));
+    if let Some(position) = ids.first_index_of(asset_id) {
+        ids.remove(position);
+        count_index_entry(env, &key, false);
+    }
+    env.storage().persistent().set(&key, &ids);
+    admin::bump_entry(env, &key);
+}
+
+// Count an entry added to (or dropped from) a category or tag index
+fn count_index_entry(env: &Env, key: &IndexBook, added: bool) {
+    let step = |count: &mut u64| if added { *count += 1 } else { *count -= 1 };
+    match key {
+        IndexBook::CategoryIndex(_) => stats::count_storage(env, |m| step(&mut m.category_index_entries)),
+        IndexBook::TagIndex(_) => stats::count_storage(env, |m| step(&mut m.tag_index_entries)),
+        _ => {}
+    }
+}
+
+// Page through every asset in ID order: up to `limit` (capped at MAX_PAGE) records starting
+// at asset ID `start`
+pub fn list_assets(env: Env, start: u64, limit: u32) -> Result<Vec<Asset>, RentError> {
+    let count_assets: u64 = env.storage().instance().get(&DataKey::AssetCount).unwrap_or(0);
+    let mut page = Vec::new(&env);
+    
+    for asset_id in page_range(count_assets, start, limit) {
+        page.push_back(load_asset(&env, asset_id)?);
+    }
+    
+    return Ok(page);
+}
+
+// IDs a page of up to `limit` records (capped at MAX_PAGE) covers, starting at ID `start`,
+// out of `count` records numbered from 1
+pub(crate) fn page_range(count: u64, start: u64, limit: u32) -> RangeInclusive<u64> {
+    let first = start.max(1);
+    let last = count.min(first.saturating_add(limit.min(MAX_PAGE) as u64).saturating_sub(1));
+    return first..=last;
+}
+
+// Search one page of asset IDs (as list_assets) for listings open for lease whose custom
+// terms match every required key/value pair. Pages can come back short or empty; the next
+// page starts at `start + limit`.
+pub fn find_available_assets(env: Env, start: u64, limit: u32, required: Vec<(Symbol, String)>) -> Result<Vec<u64>, RentError> {
+    if required.len() > MAX_TERMS {
+        log!(&env, "Too many required terms");
+        return Err(RentError::TermLimitExceeded);
+    }
+    
+    let count_assets: u64 = env.storage().instance().get(&DataKey::AssetCount).unwrap_or(0);
+    let now = env.ledger().timestamp();
+    let mut matches = Vec::new(&env);
+    
+    for asset_id in page_range(count_assets, start, limit) {
+        let asset = load_asset(&env, asset_id)?;
+        if !asset.is_available || listing_expired(&asset, now) {
+            continue;
+        }
+        let terms = view_asset_terms(env.clone(), asset_id);
+        if required.iter().all(|(key, value)| terms.get(key) == Some(value)) {
+            matches.push_back(asset_id);
+        }
+    }
+    
+    return Ok(matches);
+}
+
+// Get all assets currently open for lease; expired listings are left out
+pub fn get_available_assets(env: Env) -> Result<Vec<u64>, RentError> {
+    let count_assets: u64 = env.storage().instance().get(&DataKey::AssetCount).unwrap_or(0);
+    let now = env.ledger().timestamp();
+    let mut available = Vec::new(&env);
+    
+    for i in 1..=count_assets {
+        let asset = load_asset(&env, i)?;
+        if asset.is_available && !listing_expired(&asset, now) {
+            available.push_back(asset.asset_id);
+        }
+    }
+    
+    return Ok(available);
+}
+
+// Apply and consume the owner's relist plan as a lease ends, so the asset goes straight
+// back on the market at the preset price even if its listing had lapsed
+pub(crate) fn apply_relist_plan(env: &Env, asset: &mut Asset) -> Result<(), RentError> {
+    let plan: Option<RelistPlan> = env.storage().persistent().get(&RelistBook::Plan(asset.asset_id));
+    let Some(plan) = plan else {
+        return Ok(());
+    };
+    
+    asset.price_per_period = match plan {
+        RelistPlan::Price(price) => price,
+        RelistPlan::IncreaseBps(bps) => asset.price_per_period.checked_mul(bps as i128)
+            .and_then(|raise| asset.price_per_period.checked_add(raise / 10_000))
+            .ok_or_else(|| {
+                log!(env, "Relist price is too large");
+                RentError::InvalidAmount
+            })?,
+    };
+    if listing_expired(asset, env.ledger().timestamp()) {
+        asset.listing_expires_at = 0;
+    }
+    env.storage().persistent().remove(&RelistBook::Plan(asset.asset_id));
+    
+    log!(env, "Asset ID {} relisted at {}", asset.asset_id, asset.price_per_period);
+    
+    Ok(())
+}
+
+// Whether the owner has taken an asset off the market for good
+fn delisted(asset: &Asset) -> bool {
+    !asset.is_available && asset.current_lease_id == 0
+}
+
+// Whether an asset's listing has lapsed
+pub(crate) fn listing_expired(asset: &Asset, now: u64) -> bool {
+    asset.listing_expires_at != 0 && now >= asset.listing_expires_at
+}
+
+// Release a held listing fee once a lease completes, returning the amount owed to the owner.
+// The caller saves the asset and only then pays it out with refund_listing_fee.
+pub(crate) fn release_listing_fee(asset: &mut Asset) -> i128 {
+    let held = asset.listing_fee_held;
+    asset.listing_fee_held = 0;
+    return held;
+}
+
+// Pay a released listing fee back to the owner it was charged to
+pub(crate) fn refund_listing_fee(env: &Env, asset_id: u64, owner: &Address, amount: i128) -> Result<(), RentError> {
+    if amount == 0 {
+        return Ok(());
+    }
+    
+    let native = xlm::native(env)?;
+    accounts::debit(env, SubAccount::OwnerEarnings, &native.address, amount)?;
+    xlm::transfer(env, &native, &env.current_contract_address(), owner, amount)?;
+    log!(env, "Listing fee of {} refunded for asset ID: {}", amount, asset_id);
+    
+    Ok(())
+}
+
+// Owner hands every owner-only action on their assets to an emergency contact until `until`,
+// at most MAX_VACATION ahead; a new call replaces the current delegation. Payouts still go to
+// the owner.
+pub fn set_vacation_delegate(env: Env, owner: Address, delegate: Address, until: u64) -> Result<(), RentError> {
+    owner.require_auth();
+    
+    let now = env.ledger().timestamp();
+    if until <= now || until - now > MAX_VACATION {
+        log!(&env, "Delegation must end within the maximum vacation window");
+        return Err(RentError::InvalidWindow);
+    }
+    if delegate == owner {
+        log!(&env, "Owner cannot delegate to themselves");
+        return Err(RentError::InvalidAmount);
+    }
+    
+    let vacation = VacationDelegate { delegate: delegate.clone(), until: until };
+    let key = VacationBook::Delegate(owner.clone());
+    env.storage().persistent().set(&key, &vacation);
+    admin::bump_entry(&env, &key);
+    
+    events::publish(&env, (symbol_short!("owner"), symbol_short!("delegated"), owner), (delegate, until));
+    
+    Ok(())
+}
+
+// Owner ends a delegation early
+pub fn revoke_vacation_delegate(env: Env, owner: Address) {
+    owner.require_auth();
+    
+    env.storage().persistent().remove(&VacationBook::Delegate(owner.clone()));
+    
+    events::publish(&env, (symbol_short!("owner"), symbol_short!("revoked"), owner), ());
+}
+
+// View who may currently act for an owner (None once the delegation has lapsed)
+pub fn view_vacation_delegate(env: Env, owner: Address) -> Option<VacationDelegate> {
+    let vacation: Option<VacationDelegate> = env.storage().persistent().get(&VacationBook::Delegate(owner));
+    vacation.filter(|vacation| env.ledger().timestamp() < vacation.until)
+}
+
+// Whether `caller` may act for `owner`: the owner themselves or their current vacation delegate
+pub(crate) fn acts_for_owner(env: &Env, owner: &Address, caller: &Address) -> bool {
+    if caller == owner {
+        return true;
+    }
+    view_vacation_delegate(env.clone(), owner.clone()).is_some_and(|vacation| vacation.delegate == *caller)
+}
+
+// Load an asset and verify `owner` owns it, or stands in for its owner, and has signed the
+// invocation
+pub(crate) fn owned_asset(env: &Env, asset_id: u64, owner: &Address) -> Result<Asset, RentError> {
+    let asset = load_asset(env, asset_id)?;
+    
+    if !acts_for_owner(env, &asset.owner, owner) {
+        log!(env, "Only the asset owner can perform this action");
+        return Err(RentError::NotAssetOwner);
+    }
+    owner.require_auth();
+    
+    return Ok(asset);
+}
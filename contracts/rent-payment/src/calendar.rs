@@ -0,0 +1,133 @@
+use soroban_sdk::{contracttype, Vec};
+
+use crate::Asset;
+
+// Seconds in a calendar day; holidays are identified by UTC day number (timestamp / DAY)
+pub const DAY: u64 = 24 * 60 * 60;
+
+// How reporting periods are bounded for the stats aggregation buckets
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ReportingPeriod {
+    CalendarMonth,      // UTC calendar months
+    FiscalQuarter(u32), // Quarters of a fiscal year starting on the 1st of this month (1-12)
+}
+
+// Period during which an asset cannot be leased (owner use, maintenance, ...)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BlackoutWindow {
+    pub start: u64,        // First occurrence start timestamp
+    pub end: u64,          // First occurrence end timestamp (exclusive)
+    pub repeat_every: u64, // Recurrence interval in seconds (0 for a one-off window)
+}
+
+// Bookings and blackouts of an asset, for calendar displays
+#[contracttype]
+#[derive(Clone)]
+pub struct AssetCalendar {
+    pub asset_id: u64,                    // Asset identifier
+    pub blackouts: Vec<BlackoutWindow>,   // Owner-registered blackout windows
+    pub booked_from: u64,                 // Start of the current lease (0 if none)
+    pub booked_until: u64,                // End of the current lease (0 if none)
+}
+
+// A window is well-formed if it is non-empty and never overlaps its own next occurrence
+pub fn is_valid(window: &BlackoutWindow) -> bool {
+    window.end > window.start && (window.repeat_every == 0 || window.repeat_every >= window.end - window.start)
+}
+
+// Whether any occurrence of the window overlaps the half-open interval [from, until)
+pub fn overlaps(window: &BlackoutWindow, from: u64, until: u64) -> bool {
+    if until <= window.start {
+        return false;
+    }
+    if window.repeat_every == 0 {
+        return from < window.end;
+    }
+    
+    // First occurrence that ends after `from`
+    let skip = if from < window.end {
+        0
+    } else {
+        (from - window.end) / window.repeat_every + 1
+    };
+    window.start + skip * window.repeat_every < until
+}
+
+// Whether any of the windows overlaps [from, until)
+pub fn any_overlaps(windows: &Vec<BlackoutWindow>, from: u64, until: u64) -> bool {
+    windows.iter().any(|window| overlaps(&window, from, until))
+}
+
+// Whether a lease starting at `start` honors the asset's lead-time and advance-booking limits
+pub fn within_booking_window(asset: &Asset, now: u64, start: u64) -> bool {
+    if start < now + asset.min_lead_time {
+        return false;
+    }
+    asset.max_advance == 0 || start <= now + asset.max_advance
+}
+
+// Whether the UTC day `day` (days since 1970-01-01, a Thursday) falls on a Saturday or Sunday
+pub fn is_weekend(day: u64) -> bool {
+    (day + 3) % 7 >= 5
+}
+
+// Roll a due date forward one day at a time, keeping its time of day, until it lands on a
+// business day; bounded so a misconfigured calendar cannot loop for more than a year
+pub fn next_business_day(due: u64, holidays: &Vec<u64>, skip_weekends: bool) -> u64 {
+    let mut due = due;
+    for _ in 0..366 {
+        let day = due / DAY;
+        let closed = holidays.contains(day) || (skip_weekends && is_weekend(day));
+        if !closed {
+            break;
+        }
+        due += DAY;
+    }
+    return due;
+}
+
+// Convert a UTC day number into its (year, month 1-12, day of month) civil date
+pub fn civil_from_days(day: u64) -> (i64, u32, u32) {
+    let z = day as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month as u32, (doy - (153 * mp + 2) / 5 + 1) as u32)
+}
+
+// Convert a civil date into its UTC day number
+pub fn days_from_civil(year: i64, month: u32, day: u32) -> u64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let mp = (month as i64 + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    (era * 146_097 + doe - 719_468) as u64
+}
+
+// Bounds [start, end) of the reporting period containing `now`
+pub fn period_bounds(period: ReportingPeriod, now: u64) -> (u64, u64) {
+    let (year, month, _) = civil_from_days(now / DAY);
+    let (length, first_month) = match period {
+        ReportingPeriod::CalendarMonth => (1, 1),
+        ReportingPeriod::FiscalQuarter(first_month) => (3, first_month),
+    };
+    
+    // Count months from year 0 so periods can straddle a year boundary
+    let offset = first_month as i64 - 1;
+    let index = year * 12 + month as i64 - 1 - offset;
+    let start = index - index.rem_euclid(length) + offset;
+    (month_start(start), month_start(start + length))
+}
+
+// Timestamp of the first of a month counted from January of year 0
+fn month_start(index: i64) -> u64 {
+    days_from_civil(index.div_euclid(12), index.rem_euclid(12) as u32 + 1, 1) * DAY
+}
@@ -0,0 +1,235 @@
+use soroban_sdk::{contracttype, log, symbol_short, Address, Env, Symbol, Vec};
+
+use crate::storage_keys::{DataKey, DisputeBook, EscrowBook, ReleaseBook, SplitDepositBook};
+use crate::{admin, assets, escrow, events, leases, stats, raise, RentError, RentErrorExt};
+
+// Where a dispute stands
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DisputeStatus {
+    Open,     // Deposits on the lease are frozen until a ruling
+    Resolved, // The arbiter has split the held deposits
+}
+
+// A disagreement over a lease raised by either party and ruled on by the arbiter
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Dispute {
+    pub dispute_id: u64,
+    pub lease_id: u64,
+    pub opener: Address,         // Party who raised it
+    pub reason: Symbol,          // Reason code given by the opener
+    pub opened_at: u64,
+    pub status: DisputeStatus,
+    pub deposit_split_bps: u32,  // Owner's share of the held deposits per the ruling
+    pub resolved_at: u64,        // When the ruling was made (0 while open)
+}
+
+// Either party escalates a release they cannot agree on to the admin
+pub fn escalate_deposit_release(env: Env, lease_id: u64, caller: Address) -> Result<(), RentError> {
+    let mut release = escrow::open_release(&env, lease_id, &caller)?;
+    
+    release.disputed = true;
+    let key = ReleaseBook::Pending(lease_id);
+    env.storage().persistent().set(&key, &release);
+    admin::bump_entry(&env, &key);
+    
+    log!(&env, "Deposit release escalated for lease ID: {}", lease_id);
+    
+    Ok(())
+}
+
+// Admin settles an escalated release by fixing the owner's share
+pub fn resolve_deposit_release(env: Env, admin: Address, lease_id: u64, owner_bps: u32) -> Result<(), RentError> {
+    admin::require_admin(&env, &admin)?;
+    
+    let release = escrow::view_deposit_release(env.clone(), lease_id).filter(|r| r.disputed).ok_or_else(|| {
+        log!(&env, "No escalated deposit release");
+        RentError::NoPendingRelease
+    })?;
+    if owner_bps > 10_000 {
+        log!(&env, "Owner share out of range");
+        return Err(RentError::InvalidAmount);
+    }
+    require_undisputed(&env, lease_id)?;
+    
+    escrow::execute_release(&env, &release, owner_bps)?;
+    
+    Ok(())
+}
+
+// Appoint the account that rules on disputes alongside the admin (None to leave it to the admin)
+pub fn set_arbiter(env: Env, admin: Address, arbiter: Option<Address>) -> Result<(), RentError> {
+    admin::require_admin(&env, &admin)?;
+    
+    match arbiter {
+        Some(arbiter) => env.storage().instance().set(&DataKey::Arbiter, &arbiter),
+        None => env.storage().instance().remove(&DataKey::Arbiter),
+    }
+    
+    log!(&env, "Arbiter updated");
+    
+    Ok(())
+}
+
+// View the appointed arbiter, if any
+pub fn view_arbiter(env: Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::Arbiter)
+}
+
+// Lessee or owner disputes a lease. Its deposits stay frozen until the arbiter rules: the
+// owner cannot claim them, a mutual release cannot execute, and closing the lease refunds nothing.
+pub fn open_dispute(env: Env, lease_id: u64, opener: Address, reason: Symbol) -> Result<u64, RentError> {
+    let lease = leases::load_lease(&env, lease_id)?;
+    let asset = assets::load_asset(&env, lease.asset_id)?;
+    if opener != lease.lessee && !assets::acts_for_owner(&env, &asset.owner, &opener) {
+        log!(&env, "Only the lessee or asset owner can open a dispute");
+        return Err(RentError::NotLesseeOrOwner);
+    }
+    opener.require_auth();
+    
+    // An ended lease can only be disputed while its deposit is still held
+    let deposit_held = escrow::view_escrow_balance(env.clone(), lease_id) > 0 || !escrow::view_split_deposit(env.clone(), lease_id).is_empty();
+    if !lease.is_active && !deposit_held {
+        log!(&env, "Lease has ended and holds no deposit");
+        return Err(RentError::LeaseNotActive);
+    }
+    if is_disputed(&env, lease_id) {
+        log!(&env, "Lease already has an open dispute");
+        return Err(RentError::InvalidClaim);
+    }
+    
+    let dispute_id: u64 = env.storage().instance().get(&DataKey::DisputeCount).unwrap_or(0) + 1;
+    let dispute = Dispute {
+        dispute_id: dispute_id,
+        lease_id: lease_id,
+        opener: opener.clone(),
+        reason: reason.clone(),
+        opened_at: env.ledger().timestamp(),
+        status: DisputeStatus::Open,
+        deposit_split_bps: 0,
+        resolved_at: 0,
+    };
+    env.storage().instance().set(&DataKey::DisputeCount, &dispute_id);
+    let key = DisputeBook::OpenDispute(lease_id);
+    env.storage().persistent().set(&key, &dispute_id);
+    admin::bump_entry(&env, &key);
+    save_dispute(&env, &dispute);
+    admin::bump_instance(&env);
+    
+    events::publish(&env, (symbol_short!("dispute"), symbol_short!("opened"), opener), (dispute_id, lease_id, reason));
+    log!(&env, "Dispute {} opened on lease ID: {}", dispute_id, lease_id);
+    
+    return Ok(dispute_id);
+}
+
+// Admin or arbiter rules on an open dispute: `deposit_split_bps` of every held deposit goes to
+// the owner. On an ended lease the rest is refunded to the lessee; on a running one it stays
+// in escrow to be refunded when the lease ends.
+pub fn resolve_dispute(env: Env, arbiter: Address, dispute_id: u64, deposit_split_bps: u32) -> Result<(), RentError> {
+    require_arbiter(&env, &arbiter)?;
+    
+    let mut dispute = view_dispute(env.clone(), dispute_id).filter(|d| d.status == DisputeStatus::Open).ok_or_else(|| {
+        log!(&env, "No open dispute");
+        raise(&env, RentErrorExt::NoOpenDispute)
+    })?;
+    if deposit_split_bps > 10_000 {
+        log!(&env, "Owner share out of range");
+        return Err(RentError::InvalidAmount);
+    }
+    
+    dispute.status = DisputeStatus::Resolved;
+    dispute.deposit_split_bps = deposit_split_bps;
+    dispute.resolved_at = env.ledger().timestamp();
+    save_dispute(&env, &dispute);
+    env.storage().persistent().remove(&DisputeBook::OpenDispute(dispute.lease_id));
+    
+    let lease = leases::load_lease(&env, dispute.lease_id)?;
+    let asset = assets::load_asset(&env, lease.asset_id)?;
+    
+    // Escrowed deposit: the owner's share now, the rest back to the lessee once the lease is over
+    let held = escrow::view_escrow_balance(env.clone(), lease.lease_id);
+    let owner_share = held * deposit_split_bps as i128 / 10_000;
+    let refund = if lease.is_active { 0 } else { held - owner_share };
+    if held - owner_share - refund > 0 {
+        let key = EscrowBook::Escrowed(lease.lease_id);
+        env.storage().persistent().set(&key, &(held - owner_share - refund));
+        admin::bump_entry(&env, &key);
+    } else {
+        env.storage().persistent().remove(&EscrowBook::Escrowed(lease.lease_id));
+    }
+    stats::update_financials(&env, lease.asset_id, |f| f.deposits_retained += owner_share);
+    
+    // Split deposit components are divided the same way; a ruling replaces any pending release
+    let mut owner_positions = Vec::new(&env);
+    if deposit_split_bps > 0 {
+        owner_positions = escrow::take_split_share(&env, lease.lease_id, lease.asset_id, deposit_split_bps)?;
+    }
+    let mut rest = Vec::new(&env);
+    if !lease.is_active {
+        rest = escrow::view_split_deposit(env.clone(), lease.lease_id);
+        env.storage().persistent().remove(&SplitDepositBook::Held(lease.lease_id));
+        env.storage().persistent().remove(&ReleaseBook::Pending(lease.lease_id));
+        escrow::untrack_unsettled(&env, lease.asset_id, lease.lease_id);
+    }
+    admin::bump_instance(&env);
+    
+    events::publish(&env, (symbol_short!("dispute"), symbol_short!("resolved"), arbiter), (dispute_id, lease.lease_id, deposit_split_bps));
+    
+    escrow::release_to_owner(&env, lease.lease_id, &asset, owner_share)?;
+    escrow::refund_escrow(&env, &lease, refund)?;
+    escrow::pay_split_deposit(&env, &owner_positions, &asset.owner)?;
+    escrow::pay_split_deposit(&env, &rest, &lease.lessee)?;
+    
+    log!(&env, "Dispute {} resolved with {} bps to owner", dispute_id, deposit_split_bps);
+    
+    Ok(())
+}
+
+// View a dispute by ID
+pub fn view_dispute(env: Env, dispute_id: u64) -> Option<Dispute> {
+    env.storage().persistent().get(&DisputeBook::Dispute(dispute_id))
+}
+
+// ID of the dispute open on a lease, if any
+pub fn view_open_dispute(env: Env, lease_id: u64) -> Option<u64> {
+    env.storage().persistent().get(&DisputeBook::OpenDispute(lease_id))
+}
+
+// Number of disputes opened so far (also the highest dispute ID issued)
+pub fn get_dispute_count(env: Env) -> u64 {
+    env.storage().instance().get(&DataKey::DisputeCount).unwrap_or(0)
+}
+
+// Whether a lease's deposits are frozen by an open dispute
+pub(crate) fn is_disputed(env: &Env, lease_id: u64) -> bool {
+    env.storage().persistent().has(&DisputeBook::OpenDispute(lease_id))
+}
+
+// Refuse to move a disputed lease's deposit before the arbiter rules
+pub(crate) fn require_undisputed(env: &Env, lease_id: u64) -> Result<(), RentError> {
+    if is_disputed(env, lease_id) {
+        log!(env, "Deposit is frozen by an open dispute");
+        return Err(raise(env, RentErrorExt::DepositFrozen));
+    }
+    
+    Ok(())
+}
+
+// Verify `caller` is the admin or the appointed arbiter and signed the call
+fn require_arbiter(env: &Env, caller: &Address) -> Result<(), RentError> {
+    if view_arbiter(env.clone()).as_ref() != Some(caller) {
+        admin::require_admin(env, caller)?;
+        return Ok(());
+    }
+    caller.require_auth();
+    
+    Ok(())
+}
+
+// Store a dispute record and extend its TTL
+pub(crate) fn save_dispute(env: &Env, dispute: &Dispute) {
+    let key = DisputeBook::Dispute(dispute.dispute_id);
+    env.storage().persistent().set(&key, dispute);
+    admin::bump_entry(env, &key);
+}
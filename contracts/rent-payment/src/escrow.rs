@@ -0,0 +1,686 @@
+use soroban_sdk::{contracttype, log, symbol_short, token, Address, Bytes, BytesN, Env, Symbol, Vec};
+
+use crate::storage_keys::{DataKey, DepositLockBook, EscrowBook, SplitDepositBook, ReleaseBook, JurisdictionBook, HandoverBook};
+use crate::accounts::{self, SubAccount};
+use crate::interface::DepositCustodianClient;
+use crate::{disputes, events, xlm, admin, assets, leases, payments, stats, raise, RentError, RentErrorExt, Asset, DepositMode, DepositPosition, LeaseStatus};
+
+// Seconds in the 365-day year deposit interest accrues over
+const YEAR: u64 = 365 * 24 * 60 * 60;
+
+// How a deposit on a tenant's statement is held
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DepositKind {
+    Collected,     // Deposit held by the escrow contract
+    AllowanceLock, // Deposit left in the lessee's wallet behind an allowance
+    Split,         // Token position held in contract custody
+}
+
+// One deposit line on a tenant's statement
+#[contracttype]
+#[derive(Clone)]
+pub struct DepositStatementEntry {
+    pub lease_id: u64,            // Lease the deposit secures
+    pub kind: DepositKind,        // How the deposit is held
+    pub token: Address,           // Token of the deposit
+    pub amount: i128,             // Amount still held, in the token's smallest unit
+    pub accrued_interest: i128,   // Interest owed to the lessee at the jurisdiction's statutory rate
+    pub eligible_at: u64,         // Earliest time the deposit can be released (0 if open-ended)
+}
+
+// Deposit held as a token allowance from the lessee, pulled only for approved claims
+#[contracttype]
+#[derive(Clone)]
+pub struct DepositLock {
+    pub lease_id: u64,       // Lease the deposit secures
+    pub lessee: Address,     // Wallet holding the funds
+    pub owner: Address,      // Asset owner entitled to claim
+    pub amount: i128,         // Deposit amount covered by the allowance, in base units of the payment token
+    pub claimed: i128,        // Amount already pulled for approved claims
+    pub pending_claim: i128,  // Amount requested by the owner awaiting lessee approval (0 if none)
+}
+
+// Split deposit release that needs both parties' signatures (or an admin ruling) to execute
+#[contracttype]
+#[derive(Clone)]
+pub struct DepositRelease {
+    pub lease_id: u64,       // Lease whose held deposit is being released
+    pub lessee: Address,     // Receives whatever the owner does not keep
+    pub owner: Address,      // Asset owner at the time the lease ended
+    pub owner_bps: u32,      // Proposed share of every held position paid to the owner
+    pub owner_signed: bool,  // Owner has signed the current proposal
+    pub lessee_signed: bool, // Lessee has signed the current proposal
+    pub disputed: bool,      // Escalated to the admin; deadline no longer applies
+    pub deadline: u64,       // After this, an unsigned and undisputed release refunds the lessee in full
+}
+
+// Access credential (door code, key-box PIN, vehicle unlock) committed by the owner as a hash
+// and revealed on-chain once the lessee's first payment has cleared
+#[contracttype]
+#[derive(Clone)]
+pub struct AccessHandover {
+    pub lease_id: u64,         // Lease the credential grants access for
+    pub code_hash: BytesN<32>, // SHA-256 of the credential
+    pub committed_at: u64,     // When the owner committed the hash
+    pub code: Option<Bytes>,   // Revealed credential (None until revealed)
+    pub revealed_at: u64,      // When the credential was revealed (0 until revealed)
+}
+
+// Choose how deposits are secured for future leases of an asset
+pub fn set_deposit_mode(env: Env, asset_id: u64, owner: Address, mode: DepositMode) -> Result<(), RentError> {
+    let mut asset = assets::owned_asset(&env, asset_id, &owner)?;
+    asset.deposit_mode = mode;
+    assets::save_asset(&env, &asset);
+    
+    log!(&env, "Deposit mode updated for asset ID: {}", asset_id);
+    
+    Ok(())
+}
+
+// Require future lessees to post the deposit as several token positions (empty to clear)
+pub fn set_split_deposit(env: Env, asset_id: u64, owner: Address, positions: Vec<DepositPosition>) -> Result<(), RentError> {
+    assets::owned_asset(&env, asset_id, &owner)?;
+    
+    for position in positions.iter() {
+        if position.amount <= 0 {
+            log!(&env, "Deposit positions must be positive");
+            return Err(RentError::InvalidAmount);
+        }
+    }
+    
+    if positions.is_empty() {
+        env.storage().persistent().remove(&SplitDepositBook::Required(asset_id));
+    } else {
+        let key = SplitDepositBook::Required(asset_id);
+        env.storage().persistent().set(&key, &positions);
+        admin::bump_entry(&env, &key);
+    }
+    
+    log!(&env, "Split deposit updated for asset ID: {}", asset_id);
+    
+    Ok(())
+}
+
+// Owner claims a share (basis points) of the held split deposit, taken proportionally
+// from every token component; returns the amounts paid out per component
+pub fn claim_split_deposit(env: Env, lease_id: u64, owner: Address, share_bps: u32) -> Result<Vec<DepositPosition>, RentError> {
+    let lease = leases::load_lease(&env, lease_id)?;
+    let asset = assets::load_asset(&env, lease.asset_id)?;
+    
+    if !assets::acts_for_owner(&env, &asset.owner, &owner) {
+        log!(&env, "Only the asset owner can claim the deposit");
+        return Err(RentError::NotAssetOwner);
+    }
+    owner.require_auth();
+    
+    if env.storage().persistent().has(&ReleaseBook::Lease(lease_id)) {
+        log!(&env, "Deposit release requires sign-off from both parties");
+        return Err(RentError::MutualReleaseRequired);
+    }
+    disputes::require_undisputed(&env, lease_id)?;
+    
+    if share_bps == 0 || share_bps > 10_000 {
+        log!(&env, "Claim share out of range");
+        return Err(RentError::InvalidAmount);
+    }
+    
+    if view_split_deposit(env.clone(), lease_id).is_empty() {
+        log!(&env, "Lease has no split deposit");
+        return Err(RentError::InvalidClaim);
+    }
+    
+    let paid = take_split_share(&env, lease_id, lease.asset_id, share_bps)?;
+    pay_split_deposit(&env, &paid, &asset.owner)?;
+    
+    log!(&env, "Claimed {} bps of split deposit for lease ID: {}", share_bps, lease_id);
+    
+    return Ok(paid);
+}
+
+// View the split deposit composition an asset requires
+pub fn view_split_deposit_terms(env: Env, asset_id: u64) -> Vec<DepositPosition> {
+    env.storage().persistent().get(&SplitDepositBook::Required(asset_id)).unwrap_or(Vec::new(&env))
+}
+
+// View the split deposit positions held for a lease
+pub fn view_split_deposit(env: Env, lease_id: u64) -> Vec<DepositPosition> {
+    env.storage().persistent().get(&SplitDepositBook::Held(lease_id)).unwrap_or(Vec::new(&env))
+}
+
+// Require both parties to sign off on how the split deposit of future leases is released
+// once they end, within `response_window` seconds (0 to restore unilateral release)
+pub fn set_mutual_release(env: Env, asset_id: u64, owner: Address, response_window: u64) -> Result<(), RentError> {
+    assets::owned_asset(&env, asset_id, &owner)?;
+    
+    if response_window == 0 {
+        env.storage().persistent().remove(&ReleaseBook::Window(asset_id));
+    } else {
+        let key = ReleaseBook::Window(asset_id);
+        env.storage().persistent().set(&key, &response_window);
+        admin::bump_entry(&env, &key);
+    }
+    
+    log!(&env, "Mutual release window set to {} for asset ID: {}", response_window, asset_id);
+    
+    Ok(())
+}
+
+// View the mutual sign-off window an asset requires (0 if release is unilateral)
+pub fn view_mutual_release(env: Env, asset_id: u64) -> u64 {
+    env.storage().persistent().get(&ReleaseBook::Window(asset_id)).unwrap_or(0)
+}
+
+// Either party proposes the owner's share of the held deposit; this signs it for the
+// proposer and clears any signature the other party gave an earlier proposal
+pub fn propose_deposit_release(env: Env, lease_id: u64, caller: Address, owner_bps: u32) -> Result<(), RentError> {
+    let mut release = open_release(&env, lease_id, &caller)?;
+    
+    if owner_bps > 10_000 {
+        log!(&env, "Owner share out of range");
+        return Err(RentError::InvalidAmount);
+    }
+    
+    release.owner_bps = owner_bps;
+    release.owner_signed = caller == release.owner;
+    release.lessee_signed = caller == release.lessee;
+    let key = ReleaseBook::Pending(lease_id);
+    env.storage().persistent().set(&key, &release);
+    admin::bump_entry(&env, &key);
+    
+    log!(&env, "Deposit release of {} bps to owner proposed for lease ID: {}", owner_bps, lease_id);
+    
+    Ok(())
+}
+
+// Counter-sign the current proposal; the release executes once both parties have signed
+pub fn sign_deposit_release(env: Env, lease_id: u64, caller: Address) -> Result<(), RentError> {
+    let mut release = open_release(&env, lease_id, &caller)?;
+    
+    if caller == release.owner {
+        release.owner_signed = true;
+    }
+    if caller == release.lessee {
+        release.lessee_signed = true;
+    }
+    
+    if release.owner_signed && release.lessee_signed {
+        disputes::require_undisputed(&env, lease_id)?;
+        execute_release(&env, &release, release.owner_bps)?;
+    } else {
+        let key = ReleaseBook::Pending(lease_id);
+        env.storage().persistent().set(&key, &release);
+        admin::bump_entry(&env, &key);
+        log!(&env, "Deposit release signed for lease ID: {}", lease_id);
+    }
+    
+    Ok(())
+}
+
+// Anyone may refund the full deposit to the lessee once an undisputed release passes its
+// deadline without both signatures, so an unresponsive owner cannot hold it hostage
+pub fn finalize_deposit_release(env: Env, lease_id: u64) -> Result<(), RentError> {
+    let release = view_deposit_release(env.clone(), lease_id).filter(|r| !r.disputed).ok_or_else(|| {
+        log!(&env, "No undisputed deposit release");
+        RentError::NoPendingRelease
+    })?;
+    if env.ledger().timestamp() <= release.deadline {
+        log!(&env, "Release deadline has not passed");
+        return Err(RentError::DeadlineNotReached);
+    }
+    disputes::require_undisputed(&env, lease_id)?;
+    
+    execute_release(&env, &release, 0)?;
+    
+    Ok(())
+}
+
+// Note an ended lease of `asset_id` whose deposit stays held for a release or ruling
+pub(crate) fn track_unsettled(env: &Env, asset_id: u64, lease_id: u64) {
+    let key = ReleaseBook::Unsettled(asset_id);
+    let mut unsettled = view_unsettled(env, asset_id);
+    unsettled.push_back(lease_id);
+    env.storage().persistent().set(&key, &unsettled);
+    admin::bump_entry(env, &key);
+}
+
+// An ended lease's held deposit has been paid out
+pub(crate) fn untrack_unsettled(env: &Env, asset_id: u64, lease_id: u64) {
+    let key = ReleaseBook::Unsettled(asset_id);
+    let mut unsettled = view_unsettled(env, asset_id);
+    if let Some(position) = unsettled.first_index_of(lease_id) {
+        unsettled.remove(position);
+    }
+    if unsettled.is_empty() {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &unsettled);
+        admin::bump_entry(env, &key);
+    }
+}
+
+// Ended leases of an asset whose deposit still awaits a release or ruling
+pub(crate) fn view_unsettled(env: &Env, asset_id: u64) -> Vec<u64> {
+    env.storage().persistent().get(&ReleaseBook::Unsettled(asset_id)).unwrap_or(Vec::new(env))
+}
+
+// View the deposit release awaiting sign-off for a lease, if any
+pub fn view_deposit_release(env: Env, lease_id: u64) -> Option<DepositRelease> {
+    env.storage().persistent().get(&ReleaseBook::Pending(lease_id))
+}
+
+// Set the yearly interest (basis points) a jurisdiction requires on deposits held for tenants
+pub fn set_deposit_interest(env: Env, admin: Address, code: Symbol, rate_bps: u32) -> Result<(), RentError> {
+    admin::require_admin(&env, &admin)?;
+    
+    env.storage().instance().set(&JurisdictionBook::DepositInterest(code.clone()), &rate_bps);
+    
+    log!(&env, "Deposit interest for {} set to {} bps", code, rate_bps);
+    
+    Ok(())
+}
+
+// View the yearly deposit interest of a jurisdiction (0 if it requires none)
+pub fn view_deposit_interest(env: Env, code: Symbol) -> u32 {
+    env.storage().instance().get(&JurisdictionBook::DepositInterest(code)).unwrap_or(0)
+}
+
+// Every deposit a tenant currently has tied up, across active leases and ended leases whose
+// deposit is still awaiting a mutual release
+pub fn get_deposit_statement(env: Env, lessee: Address) -> Result<Vec<DepositStatementEntry>, RentError> {
+    let now = env.ledger().timestamp();
+    let mut statement = Vec::new(&env);
+    
+    for lease_id in leases::view_lessee_history(&env, &lessee).iter() {
+        let lease = leases::load_lease(&env, lease_id)?;
+        if lease.lessee != lessee {
+            continue;
+        }
+        let release = view_deposit_release(env.clone(), lease_id);
+        if !lease.is_active && release.is_none() {
+            continue;
+        }
+        
+        // Active deposits free up at the end of the term (open-ended while rolling in holdover);
+        // pending releases default to a refund at their deadline
+        let eligible_at = match &release {
+            Some(release) => release.deadline,
+            None if lease.in_holdover => lease.holdover_end,
+            None => lease.end_time,
+        };
+        let rate_bps = assets::view_asset_jurisdiction(env.clone(), lease.asset_id)
+            .map(|code| view_deposit_interest(env.clone(), code))
+            .unwrap_or(0);
+        let interest = |amount: i128| amount * rate_bps as i128 * now.saturating_sub(lease.start_time) as i128 / (10_000 * YEAR as i128);
+        
+        if lease.is_active {
+            let token = xlm::payment_token(&assets::load_asset(&env, lease.asset_id)?);
+            let escrowed = view_escrow_balance(env.clone(), lease_id);
+            match view_deposit_lock(env.clone(), lease_id) {
+                Some(lock) if lock.amount > lock.claimed => statement.push_back(DepositStatementEntry {
+                    lease_id: lease_id,
+                    kind: DepositKind::AllowanceLock,
+                    token: token.address.clone(),
+                    amount: lock.amount - lock.claimed,
+                    accrued_interest: 0,
+                    eligible_at: eligible_at,
+                }),
+                None if escrowed > 0 => {
+                    let amount = escrowed;
+                    statement.push_back(DepositStatementEntry {
+                        lease_id: lease_id,
+                        kind: DepositKind::Collected,
+                        token: token.address.clone(),
+                        amount: amount,
+                        accrued_interest: interest(amount),
+                        eligible_at: eligible_at,
+                    });
+                }
+                _ => {}
+            }
+        }
+        
+        for position in view_split_deposit(env.clone(), lease_id).iter() {
+            if position.amount > 0 {
+                statement.push_back(DepositStatementEntry {
+                    lease_id: lease_id,
+                    kind: DepositKind::Split,
+                    token: position.token,
+                    amount: position.amount,
+                    accrued_interest: interest(position.amount),
+                    eligible_at: eligible_at,
+                });
+            }
+        }
+    }
+    
+    return Ok(statement);
+}
+
+// Owner requests part of an allowance-locked deposit; nothing moves until the lessee approves
+pub fn claim_locked_deposit(env: Env, lease_id: u64, owner: Address, amount: i128) -> Result<(), RentError> {
+    let mut lock = get_deposit_lock(&env, lease_id)?;
+    
+    if !assets::acts_for_owner(&env, &lock.owner, &owner) {
+        log!(&env, "Only the asset owner can claim the deposit");
+        return Err(RentError::NotAssetOwner);
+    }
+    owner.require_auth();
+    
+    if amount <= 0 || amount > lock.amount - lock.claimed {
+        log!(&env, "Claim exceeds remaining deposit");
+        return Err(RentError::InvalidClaim);
+    }
+    
+    lock.pending_claim = amount;
+    let key = DepositLockBook::Lock(lease_id);
+    env.storage().persistent().set(&key, &lock);
+    admin::bump_entry(&env, &key);
+    
+    log!(&env, "Deposit claim of {} requested for lease ID: {}", amount, lease_id);
+    
+    Ok(())
+}
+
+// Lessee approves the pending claim, letting the contract pull it from their allowance
+pub fn approve_deposit_claim(env: Env, lease_id: u64, lessee: Address) -> Result<(), RentError> {
+    let mut lock = get_deposit_lock(&env, lease_id)?;
+    
+    if lessee != lock.lessee {
+        log!(&env, "Only the lessee can approve a deposit claim");
+        return Err(RentError::NotLessee);
+    }
+    lessee.require_auth();
+    
+    if lock.pending_claim == 0 {
+        log!(&env, "No pending deposit claim");
+        return Err(RentError::InvalidClaim);
+    }
+    
+    // Funds left in the lessee's wallet may have been clawed back by the issuer since the
+    // lock was created; pull what is still there and record the rest as a shortfall
+    let amount = lock.pending_claim;
+    let lease = leases::load_lease(&env, lease_id)?;
+    let payment_token = xlm::payment_token(&assets::load_asset(&env, lease.asset_id)?);
+    let available = xlm::balance(&env, &payment_token, &lock.lessee).min(amount);
+    lock.claimed += available;
+    lock.pending_claim = 0;
+    let key = DepositLockBook::Lock(lease_id);
+    env.storage().persistent().set(&key, &lock);
+    admin::bump_entry(&env, &key);
+    
+    if available < amount {
+        payments::record_shortfall(&env, lease_id, amount - available)?;
+    }
+    
+    let mut stats = stats::view_asset_stats(env.clone());
+    stats::record_token_flow(&env, &mut stats, &payment_token.address, available, 0);
+    env.storage().instance().set(&DataKey::Stats, &stats);
+    
+    stats::update_financials(&env, lease.asset_id, |f| f.deposits_retained += available);
+    
+    xlm::transfer_from(&env, &payment_token, &lock.lessee, &lock.owner, available)?;
+    
+    log!(&env, "Deposit claim of {} paid for lease ID: {}", available, lease_id);
+    
+    Ok(())
+}
+
+// Owner deducts part of an escrowed deposit (damage, unpaid charges) while the lease runs;
+// whatever is left goes back to the lessee when it ends. Returns the deposit still held.
+pub fn claim_deposit_deduction(env: Env, lease_id: u64, owner: Address, amount: i128) -> Result<i128, RentError> {
+    let lease = leases::active_lease(&env, lease_id)?;
+    let asset = assets::owned_asset(&env, lease.asset_id, &owner)?;
+    
+    disputes::require_undisputed(&env, lease_id)?;
+    let held = view_escrow_balance(env.clone(), lease_id);
+    if amount <= 0 || amount > held {
+        log!(&env, "Deduction exceeds escrowed deposit");
+        return Err(RentError::InvalidClaim);
+    }
+    
+    let remaining = held - amount;
+    let key = EscrowBook::Escrowed(lease_id);
+    env.storage().persistent().set(&key, &remaining);
+    admin::bump_entry(&env, &key);
+    stats::update_financials(&env, lease.asset_id, |f| f.deposits_retained += amount);
+    release_to_owner(&env, lease_id, &asset, amount)?;
+    
+    log!(&env, "Deposit deduction of {} credited for lease ID: {}", amount, lease_id);
+    
+    return Ok(remaining);
+}
+
+// Deposit, in base units of the asset's payment token, the escrow contract still holds for a
+// lease (0 once refunded)
+pub fn view_escrow_balance(env: Env, lease_id: u64) -> i128 {
+    env.storage().persistent().get(&EscrowBook::Escrowed(lease_id)).unwrap_or(0)
+}
+
+// Owner commits the hash of a lease's access credential; may be replaced until revealed
+pub fn commit_access_code(env: Env, lease_id: u64, owner: Address, code_hash: BytesN<32>) -> Result<(), RentError> {
+    let lease = leases::active_lease(&env, lease_id)?;
+    assets::owned_asset(&env, lease.asset_id, &owner)?;
+    
+    if let Some(handover) = view_access_handover(env.clone(), lease_id) {
+        if handover.code.is_some() {
+            log!(&env, "Access code has already been revealed");
+            return Err(RentError::AccessCodeRevealed);
+        }
+    }
+    
+    let handover = AccessHandover {
+        lease_id: lease_id,
+        code_hash: code_hash,
+        committed_at: env.ledger().timestamp(),
+        code: None,
+        revealed_at: 0,
+    };
+    let key = HandoverBook::Handover(lease_id);
+    env.storage().persistent().set(&key, &handover);
+    admin::bump_entry(&env, &key);
+    
+    log!(&env, "Access code committed for lease ID: {}", lease_id);
+    
+    Ok(())
+}
+
+// Owner reveals the committed credential once the lessee has made their first payment;
+// the credential must hash to the committed value
+pub fn reveal_access_code(env: Env, lease_id: u64, owner: Address, code: Bytes) -> Result<(), RentError> {
+    let lease = leases::active_lease(&env, lease_id)?;
+    assets::owned_asset(&env, lease.asset_id, &owner)?;
+    
+    let mut handover = match view_access_handover(env.clone(), lease_id) {
+        Some(handover) if handover.code.is_none() => handover,
+        Some(_) => {
+            log!(&env, "Access code has already been revealed");
+            return Err(RentError::AccessCodeRevealed)
+        }
+        None => {
+            log!(&env, "No access code committed");
+            return Err(RentError::AccessCodeMismatch)
+        }
+    };
+    if lease.payments_made == 0 {
+        log!(&env, "First payment has not cleared");
+        return Err(RentError::PaymentNotDue);
+    }
+    if BytesN::from(env.crypto().sha256(&code)) != handover.code_hash {
+        log!(&env, "Access code does not match the committed hash");
+        return Err(RentError::AccessCodeMismatch);
+    }
+    
+    handover.code = Some(code);
+    handover.revealed_at = env.ledger().timestamp();
+    let key = HandoverBook::Handover(lease_id);
+    env.storage().persistent().set(&key, &handover);
+    admin::bump_entry(&env, &key);
+    
+    events::publish(&env, (symbol_short!("handover"), symbol_short!("reveal")), lease_id);
+    log!(&env, "Access code revealed for lease ID: {}", lease_id);
+    
+    Ok(())
+}
+
+// View a lease's access-code handover, if one was committed
+pub fn view_access_handover(env: Env, lease_id: u64) -> Option<AccessHandover> {
+    env.storage().persistent().get(&HandoverBook::Handover(lease_id))
+}
+
+// View the allowance-locked deposit of a lease, if any
+pub fn view_deposit_lock(env: Env, lease_id: u64) -> Option<DepositLock> {
+    env.storage().persistent().get(&DepositLockBook::Lock(lease_id))
+}
+
+// Take `share_bps` of every held split deposit component out of custody, keeping the rest
+// held; returns the amounts taken per component, to be sent with pay_split_deposit
+pub(crate) fn take_split_share(env: &Env, lease_id: u64, asset_id: u64, share_bps: u32) -> Result<Vec<DepositPosition>, RentError> {
+    let held = view_split_deposit(env.clone(), lease_id);
+    
+    let mut remaining = Vec::new(env);
+    let mut paid = Vec::new(env);
+    for position in held.iter() {
+        let take = position.amount * share_bps as i128 / 10_000;
+        remaining.push_back(DepositPosition { token: position.token.clone(), amount: position.amount - take });
+        paid.push_back(DepositPosition { token: position.token, amount: take });
+    }
+    let key = SplitDepositBook::Held(lease_id);
+    env.storage().persistent().set(&key, &remaining);
+    admin::bump_entry(env, &key);
+    
+    // Only the component in the asset's payment token is counted in its financials
+    let payment_token = xlm::payment_token(&assets::load_asset(env, asset_id)?);
+    for position in paid.iter() {
+        if position.token == payment_token.address {
+            stats::update_financials(env, asset_id, |f| f.deposits_retained += position.amount);
+        }
+    }
+    
+    return Ok(paid);
+}
+
+// Send split deposit components already taken out of custody to `recipient`
+pub(crate) fn pay_split_deposit(env: &Env, positions: &Vec<DepositPosition>, recipient: &Address) -> Result<(), RentError> {
+    for position in positions.iter() {
+        if position.amount > 0 {
+            accounts::debit(env, SubAccount::TenantDeposits, &position.token, position.amount)?;
+            token::Client::new(env, &position.token).transfer(&env.current_contract_address(), recipient, &position.amount);
+        }
+    }
+    
+    Ok(())
+}
+
+// Take whatever remains of a lease's escrowed deposit out of escrow for refund to the lessee,
+// returning the amount to send with refund_escrow
+pub(crate) fn take_escrow(env: &Env, lease: &LeaseStatus) -> i128 {
+    let held = view_escrow_balance(env.clone(), lease.lease_id);
+    env.storage().persistent().remove(&EscrowBook::Escrowed(lease.lease_id));
+    
+    if held > 0 {
+        events::publish(env, (symbol_short!("deposit"), symbol_short!("refunded"), lease.lessee.clone()), (lease.lease_id, held));
+    }
+    
+    return held;
+}
+
+// Send a deposit taken out of escrow back to the lessee
+pub(crate) fn refund_escrow(env: &Env, lease: &LeaseStatus, held: i128) -> Result<(), RentError> {
+    let asset = assets::load_asset(env, lease.asset_id)?;
+    release_escrow(env, lease.lease_id, &asset, &lease.lessee, held)?;
+    
+    Ok(())
+}
+
+// Move a lease's standard deposit from the lessee into the escrow contract
+pub(crate) fn hold_escrow(env: &Env, lease: &LeaseStatus, asset: &Asset) -> Result<(), RentError> {
+    let payment_token = xlm::payment_token(asset);
+    xlm::check_balance(env, &payment_token, &lease.lessee, asset.deposit_required)?;
+    let position = DepositPosition { token: payment_token.address, amount: asset.deposit_required };
+    custodian(env)?.deposit(&lease.lease_id, &lease.lessee, &position);
+    
+    Ok(())
+}
+
+// Release deposit the owner keeps (a deduction, a dispute share or an early-termination fee)
+// from the escrow contract into this one, to be withdrawn with the owner's rent
+pub(crate) fn release_to_owner(env: &Env, lease_id: u64, asset: &Asset, amount: i128) -> Result<(), RentError> {
+    release_escrow(env, lease_id, asset, &env.current_contract_address(), amount)?;
+    payments::credit_owner(env, asset, amount)?;
+    
+    Ok(())
+}
+
+// Have the escrow contract pay `amount` of a lease's deposit to `to`
+fn release_escrow(env: &Env, lease_id: u64, asset: &Asset, to: &Address, amount: i128) -> Result<(), RentError> {
+    if amount == 0 {
+        return Ok(());
+    }
+    
+    let payment_token = xlm::payment_token(asset);
+    xlm::check_receivable(env, &payment_token, to)?;
+    let position = DepositPosition { token: payment_token.address, amount: amount };
+    custodian(env)?.release(&lease_id, to, &position);
+    
+    Ok(())
+}
+
+// Client for the configured escrow contract, or EscrowNotSet
+fn custodian(env: &Env) -> Result<DepositCustodianClient<'_>, RentError> {
+    let escrow = admin::view_escrow_contract(env.clone()).ok_or_else(|| {
+        log!(env, "Escrow contract is not configured");
+        raise(env, RentErrorExt::EscrowNotSet)
+    })?;
+    
+    return Ok(DepositCustodianClient::new(env, &escrow));
+}
+
+// Load a release still open for sign-off and verify `caller` is a party who signed the call
+pub(crate) fn open_release(env: &Env, lease_id: u64, caller: &Address) -> Result<DepositRelease, RentError> {
+    let release = view_deposit_release(env.clone(), lease_id).ok_or_else(|| {
+        log!(env, "No deposit release pending");
+        RentError::NoPendingRelease
+    })?;
+    if release.disputed || env.ledger().timestamp() > release.deadline {
+        log!(env, "Deposit release is no longer open for sign-off");
+        return Err(RentError::NoPendingRelease);
+    }
+    
+    if *caller != release.owner && *caller != release.lessee {
+        log!(env, "Only the lessee or asset owner can sign a deposit release");
+        return Err(RentError::NotLesseeOrOwner);
+    }
+    caller.require_auth();
+    
+    return Ok(release);
+}
+
+// Pay the owner's share of a released deposit and refund the rest to the lessee
+pub(crate) fn execute_release(env: &Env, release: &DepositRelease, owner_bps: u32) -> Result<(), RentError> {
+    let lease = leases::load_lease(env, release.lease_id)?;
+    
+    env.storage().persistent().remove(&ReleaseBook::Pending(release.lease_id));
+    untrack_unsettled(env, lease.asset_id, release.lease_id);
+    let mut owner_share = Vec::new(env);
+    if owner_bps > 0 {
+        owner_share = take_split_share(env, release.lease_id, lease.asset_id, owner_bps)?;
+    }
+    let rest = view_split_deposit(env.clone(), release.lease_id);
+    env.storage().persistent().remove(&SplitDepositBook::Held(release.lease_id));
+    
+    pay_split_deposit(env, &owner_share, &release.owner)?;
+    pay_split_deposit(env, &rest, &lease.lessee)?;
+    
+    log!(env, "Deposit released with {} bps to owner for lease ID: {}", owner_bps, release.lease_id);
+    
+    Ok(())
+}
+
+// Load a lease's deposit lock or fail with NoDepositLock
+pub(crate) fn get_deposit_lock(env: &Env, lease_id: u64) -> Result<DepositLock, RentError> {
+    env.storage().persistent().get(&DepositLockBook::Lock(lease_id)).ok_or_else(|| {
+        log!(env, "Lease has no locked deposit");
+        RentError::NoDepositLock
+    })
+}
@@ -0,0 +1,18 @@
+use soroban_sdk::{Env, IntoVal, Topics, Val};
+
+use crate::storage_keys::DataKey;
+
+// Publish an event whose data is `(sequence, data)`. The sequence starts at 1 and grows by one
+// with every event this contract emits, so consumers can detect gaps and replay in order.
+pub(crate) fn publish<T: Topics, D: IntoVal<Env, Val>>(env: &Env, topics: T, data: D) {
+    let sequence = get_event_sequence(env.clone()) + 1;
+    env.storage().instance().set(&DataKey::EventSequence, &sequence);
+    
+    let data: Val = data.into_val(env);
+    env.events().publish(topics, (sequence, data));
+}
+
+// Sequence number of the latest event emitted (0 before the first)
+pub fn get_event_sequence(env: Env) -> u64 {
+    env.storage().instance().get(&DataKey::EventSequence).unwrap_or(0)
+}
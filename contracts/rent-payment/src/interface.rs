@@ -0,0 +1,25 @@
+use soroban_sdk::{contractclient, Address, Env};
+
+use crate::DepositPosition;
+
+pub use common::{RentPaymentInterface, RentPaymentInterfaceClient};
+
+// Landlord-verification registry consulted by register_asset when configured
+#[contractclient(name = "LandlordRegistryClient")]
+pub trait LandlordRegistry {
+    // Whether the given address is a verified landlord
+    fn is_verified(env: Env, landlord: Address) -> bool;
+}
+
+// Escrow contract holding standard lease deposits, with this contract as its controller
+#[contractclient(name = "DepositCustodianClient")]
+pub trait DepositCustodian {
+    // Move a deposit position from `from` into custody for a lease
+    fn deposit(env: Env, lease_id: u64, from: Address, position: DepositPosition);
+    
+    // Pay part of a lease's held funds out to `to`
+    fn release(env: Env, lease_id: u64, to: Address, position: DepositPosition);
+    
+    // How much of a token is held for a lease
+    fn view_balance(env: Env, lease_id: u64, token: Address) -> i128;
+}
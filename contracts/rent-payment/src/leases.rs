@@ -0,0 +1,1579 @@
+use soroban_sdk::{contracttype, log, symbol_short, token, xdr::ToXdr, Address, BytesN, Env, Map, String, Symbol, Vec};
+
+use crate::accounts::{self, SubAccount};
+use crate::penalty::{EscalationLevel, EscalationSchedule, LateNotice, PenaltyPolicy};
+use crate::policy::PaymentPolicy;
+use crate::storage_keys::{DataKey, LeaseStatusBook, AssetBook, DepositLockBook, EscrowBook, SplitDepositBook, ReleaseBook, EscalationBook, JurisdictionBook, ExternalIdBook, ReminderBook, IndexBook, TermsBook, LeaseLimitBook, CureBook, TerminationBook, AutoPayBook, AssignmentBook, PartialBook, GuarantorBook};
+use crate::{applications, calendar, disputes, events, penalty, xlm, admin, assets, escrow, payments, rewards, stats, RentError, Asset, CoTenant, DepositMode, DepositLock, DepositRelease, LeaseStatus};
+
+// Every term a lease is bound by, normalized into one struct so its hash pins them down
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CanonicalTerms {
+    pub lease_id: u64,
+    pub asset_id: u64,
+    pub owner: Address,
+    pub lessee: Address,
+    pub payment_token: Address,
+    pub currency_decimals: u32,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub period_payment: i128,                // Rent per period, in base units of the payment token
+    pub payment_frequency: u64,             // Seconds per period
+    pub security_deposit: i128,
+    pub deposit_mode: DepositMode,
+    pub payment_policy: PaymentPolicy,
+    pub penalty_policy: PenaltyPolicy,
+    pub grace_period: u64,
+    pub holdover_bps: u32,
+    pub escalation_bps: u32,                // Rent increase every escalation_interval periods
+    pub escalation_interval: u32,
+    pub notice_period: u64,                 // Notice needed to end the lease early, in seconds
+    pub early_termination_fee: i128,         // Taken from escrow when the lessee leaves without notice
+    pub custom_terms: Map<Symbol, String>,  // Owner-defined terms frozen at signing
+    pub occupants: Vec<Address>,            // Additional occupants named by amendment
+    pub bound_at: u64,                      // When these terms took effect
+    pub previous_hash: BytesN<32>,          // Hash of the terms these amend (all zeros as signed)
+}
+
+// Notice one party gave to end a lease
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TerminationNotice {
+    pub given_by: Address,  // Lessee or owner who gave it
+    pub given_at: u64,      // When it was given
+    pub effective_at: u64,  // When the lease may be ended under it
+}
+
+// Most additional occupants a lease may name
+pub const MAX_OCCUPANTS: u32 = 8;
+
+// Most tenants a lease may be shared between
+pub const MAX_CO_TENANTS: u32 = 8;
+
+// A change to a live lease that both parties have agreed to
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AmendmentChange {
+    Rent(i128),           // New rent per period, in base units of the payment token
+    EndDate(u64),         // New end of the lease term
+    AddOccupant(Address), // Additional occupant named on the lease
+}
+
+// One numbered entry in a lease's amendment history
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LeaseAmendment {
+    pub number: u32,               // 1 for the lease's first amendment, counting up
+    pub change: AmendmentChange,   // What was changed
+    pub previous_hash: BytesN<32>, // Hash of the terms amended
+    pub terms_hash: BytesN<32>,    // Hash of the terms binding the lease from then on
+    pub amended_at: u64,           // When the amendment took effect
+}
+
+// Create a new lease for an asset, starting now
+pub fn create_lease(
+    env: Env,
+    asset_id: u64,
+    lessee: Address,  // Pass the lessee address as a parameter
+    lease_duration: u64  // Duration in seconds
+) -> Result<u64, RentError> {
+    applications::require_unscreened(&env, asset_id)?;
+    let now = env.ledger().timestamp();
+    open_lease(&env, asset_id, lessee, now, lease_duration)
+}
+
+// Book a lease that starts at a future time, subject to the asset's lead-time rules
+pub fn create_scheduled_lease(
+    env: Env,
+    asset_id: u64,
+    lessee: Address,
+    start_time: u64,     // Lease start timestamp
+    lease_duration: u64  // Duration in seconds
+) -> Result<u64, RentError> {
+    applications::require_unscreened(&env, asset_id)?;
+    open_lease(&env, asset_id, lessee, start_time, lease_duration)
+}
+
+// Create a lease starting now, backed by a guarantor who signs alongside the lessee. Once rent
+// is past due by the asset's claim threshold, claim_from_guarantor collects it from the
+// guarantor's token allowance to the contract.
+pub fn create_guaranteed_lease(
+    env: Env,
+    asset_id: u64,
+    lessee: Address,
+    lease_duration: u64,  // Duration in seconds
+    guarantor: Address
+) -> Result<u64, RentError> {
+    guarantor.require_auth();
+    let lease_id = create_lease(env.clone(), asset_id, lessee, lease_duration)?;
+    let key = GuarantorBook::Guarantor(lease_id);
+    env.storage().persistent().set(&key, &guarantor);
+    admin::bump_entry(&env, &key);
+    
+    events::publish(&env, (symbol_short!("lease"), symbol_short!("guarantor"), guarantor), lease_id);
+    
+    return Ok(lease_id);
+}
+
+// View the guarantor backing a lease, if any
+pub fn view_guarantor(env: Env, lease_id: u64) -> Option<Address> {
+    env.storage().persistent().get(&GuarantorBook::Guarantor(lease_id))
+}
+
+// Owner sets how many seconds rent on the asset must be past due before its guarantor can be
+// claimed from (0 allows a claim as soon as a payment is missed)
+pub fn set_guarantor_threshold(env: Env, asset_id: u64, owner: Address, threshold: u64) -> Result<(), RentError> {
+    assets::owned_asset(&env, asset_id, &owner)?;
+    let key = GuarantorBook::ClaimThreshold(asset_id);
+    env.storage().persistent().set(&key, &threshold);
+    admin::bump_entry(&env, &key);
+    
+    log!(&env, "Guarantor claim threshold for asset ID {} set to {}", asset_id, threshold);
+    
+    Ok(())
+}
+
+// View how long rent on an asset must be past due before a guarantor can be claimed from
+pub fn view_guarantor_threshold(env: Env, asset_id: u64) -> u64 {
+    env.storage().persistent().get(&GuarantorBook::ClaimThreshold(asset_id)).unwrap_or(0)
+}
+
+// End a lease (can be called by lessee or automatically when lease expires)
+pub fn end_lease(env: Env, lease_id: u64, caller: Address) -> Result<(), RentError> {
+    // Get the lease
+    let lease = load_lease(&env, lease_id)?;
+    
+    // Check if lease exists and is active
+    if !lease.is_active {
+        log!(&env, "Lease is not active");
+        return Err(RentError::LeaseNotActive);
+    }
+    
+    // Get the asset
+    let asset = assets::load_asset(&env, lease.asset_id)?;
+    
+    // Verify caller is either a tenant or the asset owner
+    if !is_tenant(&lease, &caller) && !assets::acts_for_owner(&env, &asset.owner, &caller) {
+        log!(&env, "Only the lessee or asset owner can end the lease");
+        return Err(RentError::NotLesseeOrOwner);
+    }
+    caller.require_auth();
+    
+    // Before the term is over, the lease only ends once the notice given on it has run; a
+    // lessee may instead leave at once by paying the early-termination fee out of escrow, with
+    // every co-tenant signing. Without a fee there is no early exit.
+    let now = env.ledger().timestamp();
+    let (notice_period, early_termination_fee) = termination_terms(&env, lease_id, &asset);
+    let notice_served = match view_termination_notice(env.clone(), lease_id) {
+        Some(notice) => now >= notice.effective_at,
+        None => notice_period == 0,
+    };
+    let mut fee = 0;
+    if now < lease.end_time && !notice_served {
+        if !is_tenant(&lease, &caller) || early_termination_fee == 0 {
+            log!(&env, "Notice period has not elapsed");
+            return Err(RentError::DeadlineNotReached);
+        }
+        for co_tenant in lease.co_tenants.iter() {
+            if co_tenant.tenant != caller {
+                co_tenant.tenant.require_auth();
+            }
+        }
+        
+        // The fee comes out of the deposit, which an open dispute freezes
+        disputes::require_undisputed(&env, lease_id)?;
+        fee = early_termination_fee;
+        let held = escrow::view_escrow_balance(env.clone(), lease_id);
+        if fee > held {
+            log!(&env, "Escrowed deposit does not cover the early-termination fee");
+            return Err(RentError::InsufficientBalance);
+        }
+        let key = EscrowBook::Escrowed(lease_id);
+        env.storage().persistent().set(&key, &(held - fee));
+        admin::bump_entry(&env, &key);
+        stats::update_financials(&env, lease.asset_id, |f| f.deposits_retained += fee);
+        events::publish(&env, (symbol_short!("lease"), symbol_short!("earlyend"), lease.lessee.clone()), (lease_id, fee));
+    }
+    
+    escrow::release_to_owner(&env, lease_id, &asset, fee)?;
+    close_lease(&env, lease, asset)?;
+    
+    log!(&env, "Lease ended for lease ID: {}", lease_id);
+    
+    Ok(())
+}
+
+// Either party gives notice to end a lease; it may be ended once the asset's notice period has
+// run from now. Notice already given stands. Returns when the notice takes effect.
+pub fn give_termination_notice(env: Env, lease_id: u64, caller: Address) -> Result<u64, RentError> {
+    let lease = active_lease(&env, lease_id)?;
+    let asset = assets::load_asset(&env, lease.asset_id)?;
+    
+    if caller != lease.lessee && !assets::acts_for_owner(&env, &asset.owner, &caller) {
+        log!(&env, "Only the lessee or asset owner can give notice");
+        return Err(RentError::NotLesseeOrOwner);
+    }
+    caller.require_auth();
+    
+    if let Some(notice) = view_termination_notice(env.clone(), lease_id) {
+        return Ok(notice.effective_at);
+    }
+    
+    let now = env.ledger().timestamp();
+    let notice = TerminationNotice {
+        given_by: caller.clone(),
+        given_at: now,
+        effective_at: now + termination_terms(&env, lease_id, &asset).0,
+    };
+    let key = TerminationBook::Termination(lease_id);
+    env.storage().persistent().set(&key, &notice);
+    admin::bump_entry(&env, &key);
+    
+    events::publish(&env, (symbol_short!("lease"), symbol_short!("notice"), caller), (lease_id, notice.effective_at));
+    log!(&env, "Termination notice for lease ID {} takes effect at {}", lease_id, notice.effective_at);
+    
+    return Ok(notice.effective_at);
+}
+
+// View the termination notice given on a lease, if any
+pub fn view_termination_notice(env: Env, lease_id: u64) -> Option<TerminationNotice> {
+    env.storage().persistent().get(&TerminationBook::Termination(lease_id))
+}
+
+// The (notice period, early-termination fee) a lease was signed under, frozen in its canonical
+// terms; a lease created without them follows the asset's current terms
+fn termination_terms(env: &Env, lease_id: u64, asset: &Asset) -> (u64, i128) {
+    match get_canonical_terms(env.clone(), lease_id) {
+        Some((terms, _)) => (terms.notice_period, terms.early_termination_fee),
+        None => (asset.notice_period, asset.early_termination_fee),
+    }
+}
+
+// Require the owner to co-sign any transfer of a lease on the asset (or stop requiring it)
+pub fn set_assignment_approval(env: Env, asset_id: u64, owner: Address, required: bool) -> Result<(), RentError> {
+    assets::owned_asset(&env, asset_id, &owner)?;
+    
+    let key = AssignmentBook::ApprovalRequired(asset_id);
+    env.storage().persistent().set(&key, &required);
+    admin::bump_entry(&env, &key);
+    
+    log!(&env, "Assignment approval for asset ID {} set to {}", asset_id, required);
+    
+    Ok(())
+}
+
+// View whether transfers of leases on an asset need the owner's signature
+pub fn view_assignment_approval(env: Env, asset_id: u64) -> bool {
+    env.storage().persistent().get(&AssignmentBook::ApprovalRequired(asset_id)).unwrap_or(false)
+}
+
+// Assign a running lease to `new_lessee`, signed by both lessees (and the owner, if the asset
+// requires it). The terms carry on unchanged; deposits and prepaid rent held on the lease now
+// belong to the new lessee, and an allowance-locked deposit moves to their allowance, which
+// must already cover what is left of it, and they take over the outgoing lessee's co-tenant
+// share. Auto-pay set up by the outgoing lessee is cancelled, and a guarantor who backed them
+// is released.
+pub fn transfer_lease(env: Env, lease_id: u64, current_lessee: Address, new_lessee: Address) -> Result<(), RentError> {
+    let mut lease = active_lease(&env, lease_id)?;
+    if lease.lessee != current_lessee {
+        log!(&env, "Only the lessee can transfer the lease");
+        return Err(RentError::NotLessee);
+    }
+    current_lessee.require_auth();
+    new_lessee.require_auth();
+    
+    let asset = assets::load_asset(&env, lease.asset_id)?;
+    if view_assignment_approval(env.clone(), asset.asset_id) {
+        asset.owner.require_auth();
+    }
+    disputes::require_undisputed(&env, lease_id)?;
+    let already_tenant = is_tenant(&lease, &new_lessee);
+    if !already_tenant {
+        check_lease_limits(&env, &asset, &new_lessee)?;
+    }
+    
+    if let Some(mut lock) = escrow::view_deposit_lock(env.clone(), lease_id) {
+        xlm::check_allowance(&env, &xlm::payment_token(&asset), &new_lessee, lock.amount - lock.claimed)?;
+        lock.lessee = new_lessee.clone();
+        let key = DepositLockBook::Lock(lease_id);
+        env.storage().persistent().set(&key, &lock);
+        admin::bump_entry(&env, &key);
+    }
+    env.storage().persistent().remove(&AutoPayBook::AutoPay(lease_id));
+    env.storage().persistent().remove(&GuarantorBook::Guarantor(lease_id));
+    
+    // A co-tenant taking over the lease keeps their own share on top of the outgoing lessee's
+    let mut co_tenants = Vec::new(&env);
+    let mut share_bps = 0;
+    for co_tenant in lease.co_tenants.iter() {
+        if co_tenant.tenant == current_lessee || co_tenant.tenant == new_lessee {
+            share_bps += co_tenant.share_bps;
+        } else {
+            co_tenants.push_back(co_tenant);
+        }
+    }
+    if share_bps > 0 {
+        co_tenants.push_back(CoTenant { tenant: new_lessee.clone(), share_bps: share_bps });
+    }
+    
+    unindex_lessee_lease(&env, &current_lessee, lease_id);
+    count_tenancy(&env, &current_lessee, &asset.owner, false);
+    if !already_tenant {
+        index_lessee_lease(&env, &new_lessee, lease_id);
+        count_tenancy(&env, &new_lessee, &asset.owner, true);
+    }
+    lease.lessee = new_lessee.clone();
+    lease.co_tenants = co_tenants;
+    save_lease(&env, &lease);
+    
+    events::publish(&env, (symbol_short!("lease"), symbol_short!("transfer"), new_lessee), (lease_id, current_lessee));
+    log!(&env, "Lease ID {} transferred to a new lessee", lease_id);
+    
+    Ok(())
+}
+
+// Share a lease between co-tenants, each paying `share_bps` of every period's rent through
+// process_payment. The shares must total 10000 and include the lessee, who stays the primary
+// tenant; an empty list leaves the lease to the lessee alone. The lessee, the owner and every
+// tenant joining or leaving the lease sign. Shares already paid this period are forgotten, but
+// what they paid stays credited to the period.
+pub fn set_co_tenants(env: Env, lease_id: u64, lessee: Address, co_tenants: Vec<CoTenant>) -> Result<(), RentError> {
+    let mut lease = active_lease(&env, lease_id)?;
+    if lease.lessee != lessee {
+        log!(&env, "Only the lessee can name co-tenants");
+        return Err(RentError::NotLessee);
+    }
+    if co_tenants.len() > MAX_CO_TENANTS {
+        log!(&env, "Lease may be shared by at most {} tenants", MAX_CO_TENANTS);
+        return Err(RentError::TermLimitExceeded);
+    }
+    
+    let mut tenants: Vec<Address> = Vec::new(&env);
+    let mut total_bps = 0;
+    for co_tenant in co_tenants.iter() {
+        if co_tenant.share_bps == 0 || tenants.contains(&co_tenant.tenant) {
+            log!(&env, "Each co-tenant must be named once with a positive share");
+            return Err(RentError::InvalidAmount);
+        }
+        total_bps += co_tenant.share_bps;
+        tenants.push_back(co_tenant.tenant);
+    }
+    if !co_tenants.is_empty() && (total_bps != 10_000 || !tenants.contains(&lessee)) {
+        log!(&env, "Co-tenant shares must include the lessee and total 10000 basis points");
+        return Err(RentError::InvalidAmount);
+    }
+    
+    let asset = assets::load_asset(&env, lease.asset_id)?;
+    lessee.require_auth();
+    asset.owner.require_auth();
+    for co_tenant in lease.co_tenants.iter() {
+        if !tenants.contains(&co_tenant.tenant) {
+            co_tenant.tenant.require_auth();
+            unindex_lessee_lease(&env, &co_tenant.tenant, lease_id);
+            count_tenancy(&env, &co_tenant.tenant, &asset.owner, false);
+        }
+    }
+    for tenant in tenants.iter() {
+        if !is_tenant(&lease, &tenant) {
+            tenant.require_auth();
+            check_lease_limits(&env, &asset, &tenant)?;
+            index_lessee_lease(&env, &tenant, lease_id);
+            count_tenancy(&env, &tenant, &asset.owner, true);
+        }
+    }
+    
+    lease.co_tenants = co_tenants;
+    save_lease(&env, &lease);
+    env.storage().persistent().remove(&PartialBook::SharesPaid(lease_id));
+    
+    events::publish(&env, (symbol_short!("lease"), symbol_short!("cotenant"), lessee), (lease_id, tenants));
+    log!(&env, "Co-tenants set for lease ID {}", lease_id);
+    
+    Ok(())
+}
+
+// Whether `address` is the lessee or one of the co-tenants of a lease
+pub(crate) fn is_tenant(lease: &LeaseStatus, address: &Address) -> bool {
+    *address == lease.lessee || lease.co_tenants.iter().any(|co_tenant| co_tenant.tenant == *address)
+}
+
+// Close a lease whose term is over: frees the asset, refunds the deposit and updates stats.
+// Anyone may call it, so a keeper can clear expired leases without either party. A lease that
+// auto-renews or rolls into holdover has not expired; a noticed holdover expires at holdover_end.
+pub fn settle_expired_lease(env: Env, lease_id: u64, keeper: Address) -> Result<(), RentError> {
+    let mut lease = active_lease(&env, lease_id)?;
+    let asset = assets::load_asset(&env, lease.asset_id)?;
+    
+    if roll_term(&env, &asset, &mut lease, env.ledger().timestamp())? {
+        log!(&env, "Lease term has not ended");
+        return Err(RentError::DeadlineNotReached);
+    }
+    
+    let payment_token = xlm::payment_token(&asset);
+    close_lease(&env, lease, asset)?;
+    rewards::reward_keeper(&env, &keeper, lease_id, &payment_token, 0)?;
+    
+    log!(&env, "Expired lease settled for lease ID: {}", lease_id);
+    
+    Ok(())
+}
+
+// Either party exits a lease during its trial period. Settlement is simplified: the lessee owes
+// rent only for the time used, any rent paid beyond that is credited back, and the whole
+// deposit is returned without claims or sign-off. Returns the rent credited back.
+pub fn exit_trial(env: Env, lease_id: u64, caller: Address) -> Result<i128, RentError> {
+    let mut lease = active_lease(&env, lease_id)?;
+    let asset = assets::load_asset(&env, lease.asset_id)?;
+    
+    if caller != lease.lessee && !assets::acts_for_owner(&env, &asset.owner, &caller) {
+        log!(&env, "Only the lessee or asset owner can exit a trial");
+        return Err(RentError::NotLesseeOrOwner);
+    }
+    caller.require_auth();
+    
+    let now = env.ledger().timestamp();
+    if now >= lease.trial_end {
+        log!(&env, "Lease is not in a trial period");
+        return Err(RentError::NotInTrial);
+    }
+    
+    // Prorate rent to the seconds used (nothing on a lease without periods); a standard deposit
+    // is part of total_paid
+    let deposit_held = if escrow::view_deposit_lock(env.clone(), lease_id).is_some() {
+        0
+    } else {
+        lease.security_deposit
+    };
+    let rent_paid = lease.total_paid - deposit_held;
+    let rent_owed = match lease.payment_frequency {
+        0 => 0,
+        period => lease.period_payment * now.saturating_sub(lease.start_time) as i128 / period as i128,
+    };
+    let credit = (rent_paid - rent_owed).max(0);
+    lease.total_paid -= credit;
+    stats::update_financials(&env, lease.asset_id, |f| f.rent_collected = (f.rent_collected - credit).max(0));
+    payments::settle_trial_rent(&env, &lease, &asset, credit)?;
+    
+    // The full deposit goes back: no allowance claims and no mutual sign-off on split deposits
+    env.storage().persistent().remove(&DepositLockBook::Lock(lease_id));
+    env.storage().persistent().remove(&ReleaseBook::Lease(lease_id));
+    close_lease(&env, lease, asset)?;
+    
+    events::publish(&env, (symbol_short!("trial"), symbol_short!("exit")), (lease_id, rent_owed.min(rent_paid), credit));
+    log!(&env, "Trial exited for lease ID: {}", lease_id);
+    
+    return Ok(credit);
+}
+
+// Mark a lease as overdue (called by a scheduled job or manually by owner)
+pub fn mark_lease_overdue(env: Env, lease_id: u64, caller: Address) -> Result<(), RentError> {
+    // Get the lease
+    let mut lease = load_lease(&env, lease_id)?;
+    
+    // Check if lease exists and is active
+    if !lease.is_active {
+        log!(&env, "Lease is not active");
+        return Err(RentError::LeaseNotActive);
+    }
+    
+    // Get the asset
+    let asset = assets::load_asset(&env, lease.asset_id)?;
+    
+    // Verify caller is the asset owner
+    if !assets::acts_for_owner(&env, &asset.owner, &caller) {
+        log!(&env, "Only the asset owner can mark a lease as overdue");
+        return Err(RentError::NotAssetOwner);
+    }
+    caller.require_auth();
+    
+    // Get current time
+    let now = env.ledger().timestamp();
+    
+    // Check if payment is actually overdue, past the asset's grace period
+    if now < payments::overdue_at(&env, &asset, &lease) {
+        log!(&env, "Payment is not yet overdue");
+        return Err(RentError::PaymentNotDue);
+    }
+    
+    // A lease is only counted overdue (and against the lessee's record) once per missed payment
+    if lease.is_overdue {
+        log!(&env, "Lease ID {} is already marked overdue", lease_id);
+        return Ok(());
+    }
+    
+    flag_overdue(&env, &mut lease, now);
+    
+    admin::bump_instance(&env);
+    
+    Ok(())
+}
+
+// Flag a lease overdue once its payment is past due by the asset's grace period, so overdue
+// status doesn't wait on the owner. Anyone may call it; returns whether the lease was flagged
+// now, and false if it is not overdue yet or already flagged. Flagging earns `keeper` the
+// keeper reward.
+pub fn check_and_mark_overdue(env: Env, lease_id: u64, keeper: Address) -> Result<bool, RentError> {
+    let mut lease = active_lease(&env, lease_id)?;
+    let asset = assets::load_asset(&env, lease.asset_id)?;
+    let now = env.ledger().timestamp();
+    if lease.is_overdue || now < payments::overdue_at(&env, &asset, &lease) {
+        return Ok(false);
+    }
+    
+    flag_overdue(&env, &mut lease, now);
+    rewards::reward_keeper(&env, &keeper, lease_id, &xlm::payment_token(&asset), 0)?;
+    admin::bump_instance(&env);
+    
+    return Ok(true);
+}
+
+// Flag a lease whose payment is due as overdue and open its late-notice escalation
+pub(crate) fn flag_overdue(env: &Env, lease: &mut LeaseStatus, now: u64) {
+    lease.is_overdue = true;
+    save_lease(env, lease);
+    if assets::view_cure_period(env.clone(), lease.asset_id) > 0 {
+        let key = CureBook::MarkedAt(lease.lease_id);
+        env.storage().persistent().set(&key, &now);
+        admin::bump_entry(env, &key);
+    }
+    escalate(env, lease, now);
+    stats::update_reputation(env, &lease.lessee, |r| r.late_payments += 1);
+    events::publish(env, (symbol_short!("lease"), symbol_short!("overdue"), lease.lessee.clone()), (lease.lease_id, lease.asset_id, lease.next_payment_time));
+    
+    // Update global stats
+    let mut stats = stats::view_asset_stats(env.clone());
+    stats.overdue_leases += 1;
+    env.storage().instance().set(&DataKey::Stats, &stats);
+    
+    log!(env, "Lease marked as overdue for lease ID: {}", lease.lease_id);
+}
+
+// Lessee opts in to (or out of) automatic renewal for another term at current pricing
+pub fn set_auto_renew(env: Env, lease_id: u64, lessee: Address, enabled: bool) -> Result<(), RentError> {
+    let mut lease = active_lease(&env, lease_id)?;
+    
+    if lessee != lease.lessee {
+        log!(&env, "Only the lessee can change auto-renewal");
+        return Err(RentError::NotLessee);
+    }
+    lessee.require_auth();
+    
+    lease.auto_renew = enabled;
+    save_lease(&env, &lease);
+    
+    log!(&env, "Auto-renewal set to {} for lease ID: {}", enabled, lease_id);
+    
+    Ok(())
+}
+
+// Either party files notice not to renew; must be at least one payment period before end_time
+pub fn file_non_renewal(env: Env, lease_id: u64, caller: Address) -> Result<(), RentError> {
+    let mut lease = active_lease(&env, lease_id)?;
+    let asset = assets::load_asset(&env, lease.asset_id)?;
+    
+    if caller != lease.lessee && !assets::acts_for_owner(&env, &asset.owner, &caller) {
+        log!(&env, "Only the lessee or asset owner can file non-renewal");
+        return Err(RentError::NotLesseeOrOwner);
+    }
+    caller.require_auth();
+    
+    let now = env.ledger().timestamp();
+    roll_term(&env, &asset, &mut lease, now)?;
+    if now + lease.payment_frequency > lease.end_time {
+        log!(&env, "Non-renewal notice deadline has passed");
+        return Err(RentError::NoticeDeadlinePassed);
+    }
+    
+    lease.non_renewal_filed = true;
+    save_lease(&env, &lease);
+    
+    log!(&env, "Non-renewal filed for lease ID: {}", lease_id);
+    
+    Ok(())
+}
+
+// Lessee extends a lease in its current term by `additional_duration` seconds, optionally
+// moving it to the asset's current price. The whole lease must still fit the asset's maximum
+// duration and stay clear of blackouts. Returns the new end_time.
+pub fn renew_lease(env: Env, lease_id: u64, lessee: Address, additional_duration: u64, reprice: bool) -> Result<u64, RentError> {
+    let mut lease = active_lease(&env, lease_id)?;
+    
+    if lessee != lease.lessee {
+        log!(&env, "Only the lessee can renew");
+        return Err(RentError::NotLessee);
+    }
+    lessee.require_auth();
+    
+    let asset = assets::load_asset(&env, lease.asset_id)?;
+    let now = env.ledger().timestamp();
+    if !roll_term(&env, &asset, &mut lease, now)? || lease.in_holdover {
+        log!(&env, "Lease term has ended");
+        return Err(RentError::LeaseExpired);
+    }
+    if lease.non_renewal_filed {
+        log!(&env, "Notice not to renew has been filed");
+        return Err(RentError::RenewalRefused);
+    }
+    
+    let end_time = lease.end_time + additional_duration;
+    if additional_duration == 0 || end_time - lease.start_time > asset.max_lease_duration {
+        log!(&env, "Lease duration outside allowed range");
+        return Err(RentError::InvalidLeaseDuration);
+    }
+    if calendar::any_overlaps(&assets::view_blackouts(env.clone(), lease.asset_id), lease.end_time, end_time) {
+        log!(&env, "Renewal overlaps a blackout window");
+        return Err(RentError::BlackoutConflict);
+    }
+    
+    lease.end_time = end_time;
+    if reprice {
+        lease.period_payment = asset.price_per_period;
+    }
+    save_lease(&env, &lease);
+    
+    events::publish(&env, (symbol_short!("lease"), symbol_short!("renewed"), lessee), (lease_id, end_time, lease.period_payment));
+    log!(&env, "Lease ID {} renewed until {}", lease_id, end_time);
+    
+    return Ok(end_time);
+}
+
+// Apply any renewal or holdover transition that time has triggered, and book the rent held
+// through a finished trial for the owner (callable by anyone)
+pub fn poke_lease(env: Env, lease_id: u64) -> Result<(), RentError> {
+    let mut lease = active_lease(&env, lease_id)?;
+    let asset = assets::load_asset(&env, lease.asset_id)?;
+    
+    roll_term(&env, &asset, &mut lease, env.ledger().timestamp())?;
+    save_lease(&env, &lease);
+    payments::release_trial_rent(&env, &lease, &asset)?;
+    
+    escalate(&env, &lease, env.ledger().timestamp());
+    
+    Ok(())
+}
+
+// Set the cooling-off period a jurisdiction requires between a default warning and default
+pub fn set_jurisdiction(env: Env, admin: Address, code: Symbol, cooling_off: u64) -> Result<(), RentError> {
+    admin::require_admin(&env, &admin)?;
+    
+    env.storage().instance().set(&JurisdictionBook::CoolingOff(code.clone()), &cooling_off);
+    
+    log!(&env, "Cooling-off period for {} set to {}", code, cooling_off);
+    
+    Ok(())
+}
+
+// View the cooling-off period of a jurisdiction (0 if it sets none)
+pub fn view_jurisdiction(env: Env, code: Symbol) -> u64 {
+    env.storage().instance().get(&JurisdictionBook::CoolingOff(code)).unwrap_or(0)
+}
+
+// Owner terminates a lease in default once its jurisdiction's cooling-off period after the
+// default warning has run out; paying all arrears before then withdraws the warning
+pub fn default_lease(env: Env, lease_id: u64, owner: Address) -> Result<(), RentError> {
+    let lease = active_lease(&env, lease_id)?;
+    let asset = assets::owned_asset(&env, lease.asset_id, &owner)?;
+    
+    let notice = view_late_notice(env.clone(), lease_id);
+    if notice.level != EscalationLevel::DefaultWarning {
+        log!(&env, "Lease has not received a default warning");
+        return Err(RentError::NotInDefault);
+    }
+    
+    let cooling_off = assets::view_asset_jurisdiction(env.clone(), asset.asset_id)
+        .map(|code| view_jurisdiction(env.clone(), code))
+        .unwrap_or(0);
+    if env.ledger().timestamp() < notice.warning_at + cooling_off {
+        log!(&env, "Cooling-off period has not ended");
+        return Err(RentError::DeadlineNotReached);
+    }
+    
+    env.storage().persistent().remove(&EscalationBook::Notice(lease_id));
+    stats::update_reputation(&env, &lease.lessee, |r| r.defaults += 1);
+    close_lease(&env, lease, asset)?;
+    
+    events::publish(&env, (symbol_short!("lease"), symbol_short!("default")), lease_id);
+    log!(&env, "Lease defaulted for lease ID: {}", lease_id);
+    
+    Ok(())
+}
+
+// Set how many seconds past due a lease escalates to each notification level
+pub fn set_escalation_schedule(env: Env, admin: Address, schedule: EscalationSchedule) -> Result<(), RentError> {
+    admin::require_admin(&env, &admin)?;
+    
+    if !penalty::is_valid_schedule(&schedule) {
+        log!(&env, "Escalation thresholds must not decrease");
+        return Err(RentError::InvalidWindow);
+    }
+    
+    env.storage().instance().set(&DataKey::EscalationSchedule, &schedule);
+    
+    log!(&env, "Escalation schedule updated");
+    
+    Ok(())
+}
+
+// View the escalation thresholds (default: reminder at once, notice after 3 days, warning after 7)
+pub fn view_escalation_schedule(env: Env) -> EscalationSchedule {
+    env.storage().instance().get(&DataKey::EscalationSchedule).unwrap_or(EscalationSchedule {
+        reminder_after: 0,
+        notice_after: 3 * 24 * 60 * 60,
+        warning_after: 7 * 24 * 60 * 60,
+    })
+}
+
+// View how far the current late payment of a lease has escalated
+pub fn view_late_notice(env: Env, lease_id: u64) -> LateNotice {
+    env.storage().persistent().get(&EscalationBook::Notice(lease_id)).unwrap_or(LateNotice {
+        level: EscalationLevel::None,
+        reminder_at: 0,
+        notice_at: 0,
+        warning_at: 0,
+    })
+}
+
+// Either party gives notice to terminate a holdover tenancy; it ends after one more
+// full period, or when already-paid time runs out if that is later
+pub fn give_holdover_notice(env: Env, lease_id: u64, caller: Address) -> Result<u64, RentError> {
+    let mut lease = active_lease(&env, lease_id)?;
+    let asset = assets::load_asset(&env, lease.asset_id)?;
+    
+    if caller != lease.lessee && !assets::acts_for_owner(&env, &asset.owner, &caller) {
+        log!(&env, "Only the lessee or asset owner can give notice");
+        return Err(RentError::NotLesseeOrOwner);
+    }
+    caller.require_auth();
+    
+    let now = env.ledger().timestamp();
+    roll_term(&env, &asset, &mut lease, now)?;
+    if !lease.in_holdover || lease.holdover_end != 0 {
+        log!(&env, "Lease is not in an open holdover");
+        return Err(RentError::NotInHoldover);
+    }
+    
+    lease.holdover_end = lease.next_payment_time.max(now + lease.payment_frequency);
+    lease.end_time = lease.holdover_end;
+    save_lease(&env, &lease);
+    
+    log!(&env, "Holdover for lease ID {} ends at {}", lease_id, lease.holdover_end);
+    
+    return Ok(lease.holdover_end);
+}
+
+// Whether a lease exists under `lease_id`, without loading the record
+pub fn lease_exists(env: Env, lease_id: u64) -> bool {
+    env.storage().persistent().has(&LeaseStatusBook::LeaseStatus(lease_id))
+}
+
+// Number of leases created so far (also the highest lease ID issued)
+pub fn get_lease_count(env: Env) -> u64 {
+    env.storage().instance().get(&DataKey::LeaseCount).unwrap_or(0)
+}
+
+// View lease details, or None if no lease was created under `lease_id`
+pub fn view_lease(env: Env, lease_id: u64) -> Option<LeaseStatus> {
+    env.storage().persistent().get(&LeaseStatusBook::LeaseStatus(lease_id))
+}
+
+// View the canonical terms currently binding a lease and their SHA-256 hash over the XDR
+// encoding, so either party can rebuild the struct off-chain and check it against the hash.
+// None for a lease created without them.
+pub fn get_canonical_terms(env: Env, lease_id: u64) -> Option<(CanonicalTerms, BytesN<32>)> {
+    env.storage().persistent().get(&TermsBook::Canonical(lease_id))
+}
+
+// Owner and lessee jointly amend a live lease: change the rent, move the end date or name
+// another occupant. The change is applied to the lease and its canonical terms in the same
+// call, and recorded against the hash of the terms it replaces. Returns the amendment number.
+pub fn amend_lease(env: Env, lease_id: u64, owner: Address, lessee: Address, change: AmendmentChange) -> Result<u32, RentError> {
+    let mut lease = active_lease(&env, lease_id)?;
+    let asset = assets::owned_asset(&env, lease.asset_id, &owner)?;
+    
+    if lessee != lease.lessee {
+        log!(&env, "Only the lessee can agree to an amendment");
+        return Err(RentError::NotLessee);
+    }
+    lessee.require_auth();
+    
+    let now = env.ledger().timestamp();
+    let (mut terms, previous_hash) = get_canonical_terms(env.clone(), lease_id).unwrap_or_else(|| {
+        let terms = signing_terms(&env, &lease, &asset);
+        let hash = terms_hash(&env, &terms);
+        (terms, hash)
+    });
+    match change.clone() {
+        AmendmentChange::Rent(rent) => {
+            if rent <= 0 {
+                log!(&env, "Rent must be positive");
+                return Err(RentError::InvalidAmount);
+            }
+            lease.period_payment = rent;
+            terms.period_payment = rent;
+        }
+        AmendmentChange::EndDate(end_time) => {
+            if end_time <= now || end_time <= lease.start_time {
+                log!(&env, "New end date must be in the future");
+                return Err(RentError::InvalidLeaseDuration);
+            }
+            lease.end_time = end_time;
+            terms.end_time = end_time;
+        }
+        AmendmentChange::AddOccupant(occupant) => {
+            if occupant == lease.lessee || terms.occupants.contains(&occupant) {
+                log!(&env, "Occupant is already named on the lease");
+                return Err(RentError::InvalidAmount);
+            }
+            if terms.occupants.len() >= MAX_OCCUPANTS {
+                log!(&env, "Lease names the most occupants allowed");
+                return Err(RentError::TermLimitExceeded);
+            }
+            terms.occupants.push_back(occupant);
+        }
+    }
+    save_lease(&env, &lease);
+    
+    terms.bound_at = now;
+    terms.previous_hash = previous_hash.clone();
+    let terms_hash = bind_terms(&env, terms);
+    
+    let mut amendments = view_amendments(env.clone(), lease_id);
+    let amendment = LeaseAmendment {
+        number: amendments.len() + 1,
+        change: change,
+        previous_hash: previous_hash,
+        terms_hash: terms_hash.clone(),
+        amended_at: now,
+    };
+    amendments.push_back(amendment.clone());
+    let key = TermsBook::Amendments(lease_id);
+    env.storage().persistent().set(&key, &amendments);
+    admin::bump_entry(&env, &key);
+    
+    events::publish(&env, (symbol_short!("lease"), symbol_short!("amended"), lessee), (lease_id, amendment.number, terms_hash));
+    log!(&env, "Amendment {} recorded for lease ID: {}", amendment.number, lease_id);
+    
+    return Ok(amendment.number);
+}
+
+// View every amendment made to a lease, oldest first
+pub fn view_amendments(env: Env, lease_id: u64) -> Vec<LeaseAmendment> {
+    env.storage().persistent().get(&TermsBook::Amendments(lease_id)).unwrap_or(Vec::new(&env))
+}
+
+// The terms a lease is signed under: its schedule and the asset's policies as they stand,
+// with the custom terms frozen for it
+fn signing_terms(env: &Env, lease: &LeaseStatus, asset: &Asset) -> CanonicalTerms {
+    CanonicalTerms {
+        lease_id: lease.lease_id,
+        asset_id: asset.asset_id,
+        owner: asset.owner.clone(),
+        lessee: lease.lessee.clone(),
+        payment_token: asset.payment_token.clone(),
+        currency_decimals: asset.currency_decimals,
+        start_time: lease.start_time,
+        end_time: lease.end_time,
+        period_payment: lease.period_payment,
+        payment_frequency: lease.payment_frequency,
+        security_deposit: lease.security_deposit,
+        deposit_mode: asset.deposit_mode,
+        payment_policy: lease.payment_policy,
+        penalty_policy: asset.penalty_policy,
+        grace_period: asset.grace_period,
+        holdover_bps: asset.holdover_bps,
+        escalation_bps: lease.escalation_bps,
+        escalation_interval: lease.escalation_interval,
+        notice_period: asset.notice_period,
+        early_termination_fee: asset.early_termination_fee,
+        custom_terms: view_lease_terms(env.clone(), lease.lease_id),
+        occupants: Vec::new(env),
+        bound_at: env.ledger().timestamp(),
+        previous_hash: BytesN::from_array(env, &[0; 32]),
+    }
+}
+
+// SHA-256 over the XDR encoding of a set of terms
+fn terms_hash(env: &Env, terms: &CanonicalTerms) -> BytesN<32> {
+    env.crypto().sha256(&terms.clone().to_xdr(env)).to_bytes()
+}
+
+// Hash and store the terms binding a lease, replacing any it was bound by before; returns
+// the hash
+pub(crate) fn bind_terms(env: &Env, terms: CanonicalTerms) -> BytesN<32> {
+    let hash = terms_hash(env, &terms);
+    
+    let key = TermsBook::Canonical(terms.lease_id);
+    env.storage().persistent().set(&key, &(terms, hash.clone()));
+    admin::bump_entry(env, &key);
+    
+    return hash;
+}
+
+// View the custom terms a lease was signed under
+pub fn view_lease_terms(env: Env, lease_id: u64) -> Map<Symbol, String> {
+    env.storage().persistent().get(&TermsBook::LeaseTerms(lease_id)).unwrap_or(Map::new(&env))
+}
+
+// Write a lease record to persistent storage and keep it above the minimum TTL
+pub(crate) fn save_lease(env: &Env, lease: &LeaseStatus) {
+    let key = LeaseStatusBook::LeaseStatus(lease.lease_id);
+    env.storage().persistent().set(&key, lease);
+    admin::bump_entry(env, &key);
+}
+
+// Load a lease that must exist, active or not
+pub(crate) fn load_lease(env: &Env, lease_id: u64) -> Result<LeaseStatus, RentError> {
+    match view_lease(env.clone(), lease_id) {
+        Some(lease) => Ok(lease),
+        None => {
+            log!(env, "Lease not found");
+            return Err(RentError::LeaseNotActive);
+        }
+    }
+}
+
+// Get all active leases for a specific lessee
+pub fn get_lessee_leases(env: Env, lessee: Address) -> Result<Vec<u64>, RentError> {
+    let mut lessee_leases = Vec::new(&env);
+    
+    for lease_id in view_lessee_history(&env, &lessee).iter() {
+        if load_lease(&env, lease_id)?.is_active {
+            lessee_leases.push_back(lease_id);
+        }
+    }
+    
+    return Ok(lessee_leases);
+}
+
+// Cap how many active leases one lessee may hold at once across the platform, so scarce
+// assets can't be hoarded; 0 removes the cap
+pub fn set_max_concurrent_leases(env: Env, admin: Address, limit: u32) -> Result<(), RentError> {
+    admin::require_admin(&env, &admin)?;
+    env.storage().instance().set(&DataKey::MaxConcurrentLeases, &limit);
+    
+    log!(&env, "Max concurrent leases per lessee set to {}", limit);
+    
+    Ok(())
+}
+
+// View the platform cap on a lessee's active leases (0 for no limit)
+pub fn view_max_concurrent_leases(env: Env) -> u32 {
+    env.storage().instance().get(&DataKey::MaxConcurrentLeases).unwrap_or(0)
+}
+
+// Owner caps how many of their assets one lessee may lease at once; 0 removes the cap
+pub fn set_owner_lease_limit(env: Env, owner: Address, limit: u32) {
+    owner.require_auth();
+    let key = LeaseLimitBook::OwnerLimit(owner);
+    env.storage().persistent().set(&key, &limit);
+    admin::bump_entry(&env, &key);
+    
+    log!(&env, "Owner lease limit set to {}", limit);
+}
+
+// View an owner's cap on a lessee's active leases of their assets (0 for no limit)
+pub fn view_owner_lease_limit(env: Env, owner: Address) -> u32 {
+    env.storage().persistent().get(&LeaseLimitBook::OwnerLimit(owner)).unwrap_or(0)
+}
+
+// Refuse a booking that would take the lessee past the platform's or the owner's cap on
+// concurrent leases; scheduled leases count from booking
+fn check_lease_limits(env: &Env, asset: &Asset, lessee: &Address) -> Result<(), RentError> {
+    let platform_limit = view_max_concurrent_leases(env.clone());
+    let owner_limit = view_owner_lease_limit(env.clone(), asset.owner.clone());
+    if platform_limit == 0 && owner_limit == 0 {
+        return Ok(());
+    }
+    
+    let held: u32 = env.storage().persistent().get(&LeaseLimitBook::Held(lessee.clone())).unwrap_or(0);
+    let held_with_owner: u32 = env.storage().persistent()
+        .get(&LeaseLimitBook::HeldWith(lessee.clone(), asset.owner.clone()))
+        .unwrap_or(0);
+    if (platform_limit > 0 && held >= platform_limit) || (owner_limit > 0 && held_with_owner >= owner_limit) {
+        log!(env, "Lessee already holds the most concurrent leases allowed");
+        return Err(RentError::TermLimitExceeded);
+    }
+    
+    Ok(())
+}
+
+// Count `lease` for or against every tenant on it, as it opens or closes under `owner`
+pub(crate) fn count_tenancies(env: &Env, lease: &LeaseStatus, owner: &Address, opened: bool) {
+    count_tenancy(env, &lease.lessee, owner, opened);
+    for co_tenant in lease.co_tenants.iter() {
+        if co_tenant.tenant != lease.lessee {
+            count_tenancy(env, &co_tenant.tenant, owner, opened);
+        }
+    }
+}
+
+// Keep the active lease counts check_lease_limits reads for `tenant`, overall and with `owner`
+fn count_tenancy(env: &Env, tenant: &Address, owner: &Address, opened: bool) {
+    for key in [LeaseLimitBook::Held(tenant.clone()), LeaseLimitBook::HeldWith(tenant.clone(), owner.clone())] {
+        let held: u32 = env.storage().persistent().get(&key).unwrap_or(0);
+        let held = if opened { held + 1 } else { held.saturating_sub(1) };
+        if held == 0 {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &held);
+            admin::bump_entry(env, &key);
+        }
+    }
+}
+
+// Every lease a lessee has held, active or not, oldest first
+pub(crate) fn view_lessee_history(env: &Env, lessee: &Address) -> Vec<u64> {
+    env.storage().persistent().get(&IndexBook::LesseeLeases(lessee.clone())).unwrap_or(Vec::new(env))
+}
+
+// Record that `lessee` has held a lease
+pub(crate) fn index_lessee_lease(env: &Env, lessee: &Address, lease_id: u64) {
+    let key = IndexBook::LesseeLeases(lessee.clone());
+    let mut history = view_lessee_history(env, lessee);
+    history.push_back(lease_id);
+    env.storage().persistent().set(&key, &history);
+    admin::bump_entry(env, &key);
+    stats::count_storage(env, |m| m.lessee_index_entries += 1);
+}
+
+// Record that `lessee` no longer holds a lease they handed to someone else
+fn unindex_lessee_lease(env: &Env, lessee: &Address, lease_id: u64) {
+    let key = IndexBook::LesseeLeases(lessee.clone());
+    let mut history = view_lessee_history(env, lessee);
+    if let Some(position) = history.first_index_of(lease_id) {
+        history.remove(position);
+        stats::count_storage(env, |m| m.lessee_index_entries -= 1);
+    }
+    env.storage().persistent().set(&key, &history);
+    admin::bump_entry(env, &key);
+}
+
+// Page through every lease in ID order: up to `limit` (capped at MAX_PAGE) records starting
+// at lease ID `start`
+pub fn list_leases(env: Env, start: u64, limit: u32) -> Result<Vec<LeaseStatus>, RentError> {
+    let count_leases: u64 = env.storage().instance().get(&DataKey::LeaseCount).unwrap_or(0);
+    let mut page = Vec::new(&env);
+    
+    for lease_id in assets::page_range(count_leases, start, limit) {
+        page.push_back(load_lease(&env, lease_id)?);
+    }
+    
+    return Ok(page);
+}
+
+// Simulate create_lease: run every check and return the lease it would create, without
+// writing anything or moving funds
+pub fn dry_run_create_lease(env: Env, asset_id: u64, lessee: Address, lease_duration: u64) -> Result<LeaseStatus, RentError> {
+    let start_time = env.ledger().timestamp();
+    let (_, lease, _) = plan_lease(&env, asset_id, lessee, start_time, lease_duration)?;
+    return Ok(lease);
+}
+
+// Tag a lease with the asset owner's own reference so ERP systems can find it by their
+// identifier; None clears it
+pub fn set_lease_external_id(env: Env, lease_id: u64, owner: Address, external_id: Option<BytesN<32>>) -> Result<(), RentError> {
+    let lease = load_lease(&env, lease_id)?;
+    let owner = assets::owned_asset(&env, lease.asset_id, &owner)?.owner;
+    
+    if let Some(id) = &external_id {
+        let existing = find_lease_by_external_id(env.clone(), owner.clone(), id.clone());
+        if existing.is_some_and(|other| other != lease_id) {
+            log!(&env, "External ID already used by another lease");
+            return Err(RentError::ExternalIdTaken);
+        }
+    }
+    
+    let previous = view_lease_external_id(env.clone(), lease_id);
+    if let Some(previous) = &previous {
+        env.storage().persistent().remove(&ExternalIdBook::LeaseLookup(owner.clone(), previous.clone()));
+    }
+    match (&previous, &external_id) {
+        (None, Some(_)) => stats::count_storage(&env, |m| m.external_ids += 1),
+        (Some(_), None) => stats::count_storage(&env, |m| m.external_ids -= 1),
+        _ => {}
+    }
+    match external_id {
+        Some(id) => {
+            let key = ExternalIdBook::OfLease(lease_id);
+            env.storage().persistent().set(&key, &id);
+            admin::bump_entry(&env, &key);
+            let key = ExternalIdBook::LeaseLookup(owner, id);
+            env.storage().persistent().set(&key, &lease_id);
+            admin::bump_entry(&env, &key);
+        }
+        None => env.storage().persistent().remove(&ExternalIdBook::OfLease(lease_id)),
+    }
+    
+    log!(&env, "External ID updated for lease ID: {}", lease_id);
+    
+    Ok(())
+}
+
+// View the external ID of a lease, if any
+pub fn view_lease_external_id(env: Env, lease_id: u64) -> Option<BytesN<32>> {
+    env.storage().persistent().get(&ExternalIdBook::OfLease(lease_id))
+}
+
+// Find a lease on one of the owner's assets by the external ID they assigned
+pub fn find_lease_by_external_id(env: Env, owner: Address, external_id: BytesN<32>) -> Option<u64> {
+    env.storage().persistent().get(&ExternalIdBook::LeaseLookup(owner, external_id))
+}
+
+// Move what a running lease keeps for its owner to the buyer of its asset: claims on an
+// allowance-locked deposit, its tenants' counts towards the owner's lease cap, and the seller's
+// external ID for the lease unless the buyer already uses it for another lease
+pub(crate) fn hand_over_lease(env: &Env, lease_id: u64, seller: &Address, buyer: &Address) -> Result<(), RentError> {
+    let lease = load_lease(env, lease_id)?;
+    if lease.is_active {
+        count_tenancies(env, &lease, seller, false);
+        count_tenancies(env, &lease, buyer, true);
+    }
+    if let Some(mut lock) = escrow::view_deposit_lock(env.clone(), lease_id) {
+        lock.owner = buyer.clone();
+        let key = DepositLockBook::Lock(lease_id);
+        env.storage().persistent().set(&key, &lock);
+        admin::bump_entry(env, &key);
+    }
+    if let Some(id) = view_lease_external_id(env.clone(), lease_id) {
+        env.storage().persistent().remove(&ExternalIdBook::LeaseLookup(seller.clone(), id.clone()));
+        match find_lease_by_external_id(env.clone(), buyer.clone(), id.clone()) {
+            Some(_) => {
+                env.storage().persistent().remove(&ExternalIdBook::OfLease(lease_id));
+                stats::count_storage(env, |m| m.external_ids -= 1);
+            }
+            None => {
+                let key = ExternalIdBook::LeaseLookup(buyer.clone(), id);
+                env.storage().persistent().set(&key, &lease_id);
+                admin::bump_entry(env, &key);
+            }
+        }
+    }
+    
+    Ok(())
+}
+
+// Shared lease creation path: validates the asset, timing and deposit, then books the lease
+pub(crate) fn open_lease(env: &Env, asset_id: u64, lessee: Address, start_time: u64, lease_duration: u64) -> Result<u64, RentError> {
+    admin::require_not_paused(env)?;
+    admin::require_creation_open(env)?;
+    
+    // The lessee signs for the lease and the upfront payment it collects
+    lessee.require_auth();
+    
+    let (mut asset, lease_status, locks_deposit) = plan_lease(env, asset_id, lessee, start_time, lease_duration)?;
+    let count_leases = lease_status.lease_id;
+    let collected = lease_status.total_paid;
+    
+    // A split deposit is posted into contract custody, one transfer per token component
+    let split_deposit = escrow::view_split_deposit_terms(env.clone(), asset_id);
+    if !split_deposit.is_empty() {
+        let key = SplitDepositBook::Held(count_leases);
+        env.storage().persistent().set(&key, &split_deposit);
+        admin::bump_entry(env, &key);
+        
+        // Sign-off terms are fixed for the life of the lease
+        let window = escrow::view_mutual_release(env.clone(), asset_id);
+        if window > 0 {
+            let key = ReleaseBook::Lease(count_leases);
+            env.storage().persistent().set(&key, &window);
+            admin::bump_entry(env, &key);
+        }
+    }
+    
+    // A standard deposit is held by the escrow contract, in the asset's payment token, until
+    // the lease ends
+    let escrowed = !locks_deposit && asset.deposit_required > 0;
+    if escrowed {
+        let key = EscrowBook::Escrowed(count_leases);
+        env.storage().persistent().set(&key, &asset.deposit_required);
+        admin::bump_entry(env, &key);
+    }
+    
+    if locks_deposit {
+        let lock = DepositLock {
+            lease_id: count_leases,
+            lessee: lease_status.lessee.clone(),
+            owner: asset.owner.clone(),
+            amount: asset.deposit_required,
+            claimed: 0,
+            pending_claim: 0,
+        };
+        let key = DepositLockBook::Lock(count_leases);
+        env.storage().persistent().set(&key, &lock);
+        admin::bump_entry(env, &key);
+    }
+    
+    // Update the lease count
+    env.storage().instance().set(&DataKey::LeaseCount, &count_leases);
+    
+    // Store the lease data, with the asset's custom terms as they stand at signing
+    save_lease(env, &lease_status);
+    let terms = assets::view_asset_terms(env.clone(), asset_id);
+    if !terms.is_empty() {
+        let key = TermsBook::LeaseTerms(count_leases);
+        env.storage().persistent().set(&key, &terms);
+        admin::bump_entry(env, &key);
+    }
+    bind_terms(env, signing_terms(env, &lease_status, &asset));
+    index_lessee_lease(env, &lease_status.lessee, count_leases);
+    count_tenancy(env, &lease_status.lessee, &asset.owner, true);
+    
+    // Update asset availability
+    asset.is_available = false;
+    asset.current_lease_id = count_leases;
+    assets::save_asset(env, &asset);
+    // Only consulted within the same ledger, so a temporary entry is enough
+    env.storage().temporary().set(&AssetBook::LeasedInLedger(asset_id), &env.ledger().sequence());
+    
+    // Update global stats
+    let mut stats = stats::view_asset_stats(env.clone());
+    stats.active_leases += 1;
+    stats.total_leases += 1;
+    stats::record_token_flow(env, &mut stats, &asset.payment_token, collected, 0);
+    env.storage().instance().set(&DataKey::Stats, &stats);
+    
+    stats::update_financials(env, asset_id, |f| f.rent_collected += asset.price_per_period);
+    
+    admin::bump_instance(env);
+    
+    events::publish(
+        env,
+        (symbol_short!("lease"), symbol_short!("created"), lease_status.lessee.clone()),
+        (count_leases, asset_id, asset.owner.clone(), lease_status.start_time, lease_status.end_time, collected),
+    );
+    
+    // The first rent and deposits are pulled in only once the lease is fully recorded; rent
+    // paid into a trial is held until the trial is over
+    if lease_status.trial_end > lease_status.start_time {
+        payments::hold_trial_rent(env, &asset, &lease_status, asset.price_per_period)?;
+    } else {
+        payments::pay_rent(env, &asset, &lease_status.lessee, asset.price_per_period)?;
+    }
+    for position in split_deposit.iter() {
+        accounts::credit(env, SubAccount::TenantDeposits, &position.token, position.amount);
+        token::Client::new(env, &position.token).transfer(&lease_status.lessee, &env.current_contract_address(), &position.amount);
+    }
+    if escrowed {
+        escrow::hold_escrow(env, &lease_status, &asset)?;
+    }
+    log!(env, "Lease created with ID: {}", count_leases);
+    
+    return Ok(count_leases);
+}
+
+// Validate a booking and build the lease it would create, without writing anything; shared
+// by open_lease and the dry-run view. Returns (asset, lease, whether the deposit is allowance-locked).
+pub(crate) fn plan_lease(env: &Env, asset_id: u64, lessee: Address, start_time: u64, lease_duration: u64) -> Result<(Asset, LeaseStatus, bool), RentError> {
+    // Get the asset
+    let asset = assets::load_asset(env, asset_id)?;
+    
+    // Check if asset is available
+    // Competing creations in one ledger: the first transaction applied wins, and the others
+    // learn they lost the race rather than just seeing the asset unavailable
+    let leased_in: Option<u32> = env.storage().temporary().get(&AssetBook::LeasedInLedger(asset_id));
+    if !asset.is_available && asset.current_lease_id != 0 && leased_in == Some(env.ledger().sequence()) {
+        log!(env, "A lease was already created on this asset in this ledger");
+        return Err(RentError::LeaseRaceLost);
+    }
+    if !asset.is_available {
+        log!(env, "Asset is not available for lease");
+        return Err(RentError::AssetUnavailable);
+    }
+    if assets::listing_expired(&asset, env.ledger().timestamp()) {
+        log!(env, "Asset listing has expired");
+        return Err(RentError::ListingExpired);
+    }
+    
+    // Validate lease duration
+    if lease_duration < asset.min_lease_duration || lease_duration > asset.max_lease_duration {
+        log!(env, "Lease duration outside allowed range");
+        return Err(RentError::InvalidLeaseDuration);
+    }
+    check_lease_limits(env, &asset, &lessee)?;
+    
+    // Next unique lease ID
+    let count_leases: u64 = env.storage().instance().get(&DataKey::LeaseCount).unwrap_or(0) + 1;
+    
+    // Get current time
+    let now = env.ledger().timestamp();
+    
+    // Bookings must respect the owner's lead-time rules
+    if !calendar::within_booking_window(&asset, now, start_time) {
+        log!(env, "Lease start outside the asset's booking window");
+        return Err(RentError::BookingWindowViolation);
+    }
+    
+    // The lease period must stay clear of the owner's blackout windows
+    if calendar::any_overlaps(&assets::view_blackouts(env.clone(), asset_id), start_time, start_time + lease_duration) {
+        log!(env, "Lease overlaps a blackout window");
+        return Err(RentError::BlackoutConflict);
+    }
+    
+    // The first period's rent is collected up front, with the deposit unless it is
+    // allowance-locked; the lessee must then have approved the contract to pull at least the
+    // deposit amount instead
+    let payment_token = xlm::payment_token(&asset);
+    let locks_deposit = asset.deposit_mode == DepositMode::AllowanceLock && asset.deposit_required > 0;
+    if locks_deposit {
+        xlm::check_allowance(env, &payment_token, &lessee, asset.deposit_required)?;
+    }
+    let collected = if locks_deposit {
+        asset.price_per_period
+    } else {
+        asset.deposit_required + asset.price_per_period
+    };
+    if collected > 0 {
+        xlm::check_balance(env, &payment_token, &lessee, collected)?;
+    }
+    
+    // The lessee must hold every split deposit component
+    for position in escrow::view_split_deposit_terms(env.clone(), asset_id).iter() {
+        if token::Client::new(env, &position.token).balance(&lessee) < position.amount {
+            log!(env, "Balance too low for split deposit");
+            return Err(RentError::InsufficientBalance);
+        }
+    }
+    
+    // Create new lease
+    let lease_status = LeaseStatus {
+        lease_id: count_leases,
+        asset_id: asset_id,
+        lessee: lessee,
+        start_time: start_time,
+        end_time: start_time + lease_duration,
+        period_payment: asset.price_per_period,
+        payment_frequency: asset.period_duration,
+        last_payment_time: now,  // Initial payment happens at lease creation
+        next_payment_time: start_time + asset.period_duration,
+        is_active: true,
+        is_overdue: false,
+        total_paid: collected,  // Initial payment (+ deposit unless allowance-locked)
+        security_deposit: asset.deposit_required,
+        payment_policy: asset.payment_policy,
+        payments_made: 0,
+        metered_units: 0,
+        shortfall: 0,
+        in_holdover: false,
+        holdover_end: 0,
+        term_length: lease_duration,
+        auto_renew: false,
+        non_renewal_filed: false,
+        trial_end: match assets::view_trial_period(env.clone(), asset_id) {
+            0 => 0,
+            days => start_time + days as u64 * calendar::DAY,
+        },
+        late_fees_paid: 0,
+        outstanding_balance: 0,
+        escalation_bps: asset.escalation_bps,
+        escalation_interval: asset.escalation_interval,
+        co_tenants: Vec::new(env),
+    };
+    
+    return Ok((asset, lease_status, locks_deposit));
+}
+
+// Shared close-out of an active lease: settle its held deposit, free the asset and update stats.
+// Every record is final before any refund leaves the contract.
+pub(crate) fn close_lease(env: &Env, mut lease: LeaseStatus, mut asset: Asset) -> Result<(), RentError> {
+    // Update lease status
+    lease.is_active = false;
+    save_lease(env, &lease);
+    count_tenancies(env, &lease, &asset.owner, false);
+    stats::count_storage(env, |m| m.archived_leases += 1);
+    env.storage().persistent().remove(&TerminationBook::Termination(lease.lease_id));
+    env.storage().persistent().remove(&AutoPayBook::AutoPay(lease.lease_id));
+    env.storage().persistent().remove(&GuarantorBook::Guarantor(lease.lease_id));
+    
+    // Only a lease closed with nothing overdue counts towards either party's track record
+    if !lease.is_overdue {
+        stats::update_reputation(env, &lease.lessee, |r| r.completed_leases += 1);
+        stats::update_reputation(env, &asset.owner, |r| r.completed_leases += 1);
+    }
+    
+    // Return whatever remains of a split deposit to the lessee, or open a
+    // mutual release for it when the lease was signed under mutual sign-off terms. A disputed
+    // lease keeps every deposit held for the arbiter's ruling.
+    let disputed = disputes::is_disputed(env, lease.lease_id);
+    let window: Option<u64> = env.storage().persistent().get(&ReleaseBook::Lease(lease.lease_id));
+    let held = escrow::view_split_deposit(env.clone(), lease.lease_id);
+    let refund_split = match window {
+        _ if disputed => false,
+        Some(window) if !held.is_empty() => {
+            let release = DepositRelease {
+                lease_id: lease.lease_id,
+                lessee: lease.lessee.clone(),
+                owner: asset.owner.clone(),
+                owner_bps: 0,
+                owner_signed: false,
+                lessee_signed: false,
+                disputed: false,
+                deadline: env.ledger().timestamp() + window,
+            };
+            let key = ReleaseBook::Pending(lease.lease_id);
+            env.storage().persistent().set(&key, &release);
+            admin::bump_entry(env, &key);
+            log!(env, "Deposit release awaiting sign-off for lease ID: {}", lease.lease_id);
+            false
+        }
+        _ => {
+            env.storage().persistent().remove(&SplitDepositBook::Held(lease.lease_id));
+            true
+        }
+    };
+    if !refund_split {
+        escrow::track_unsettled(env, asset.asset_id, lease.lease_id);
+    }
+    let escrow_balance = escrow::view_escrow_balance(env.clone(), lease.lease_id);
+    let (escrowed, frozen) = if disputed { (0, escrow_balance) } else { (escrow_balance, 0) };
+    payments::record_closing(env, &lease, &asset, escrowed, frozen);
+    if !disputed {
+        escrow::take_escrow(env, &lease);
+    }
+    let prepaid = payments::take_prepaid(env, lease.lease_id);
+    // A lease closed other than by a trial exit owes the owner whatever rent its trial held
+    payments::settle_trial_rent(env, &lease, &asset, 0)?;
+    
+    // Update asset availability
+    asset.is_available = true;
+    asset.current_lease_id = 0;
+    let listing_fee = assets::release_listing_fee(&mut asset);
+    assets::apply_relist_plan(env, &mut asset)?;
+    stats::update_financials(env, lease.asset_id, |f| f.vacancies += 1);
+    assets::save_asset(env, &asset);
+    
+    // Update global stats
+    let mut stats = stats::view_asset_stats(env.clone());
+    stats.active_leases -= 1;
+    stats.completed_leases += 1;
+    if lease.is_overdue {
+        stats.overdue_leases -= 1;
+    }
+    env.storage().instance().set(&DataKey::Stats, &stats);
+    
+    admin::bump_instance(env);
+    events::publish(env, (symbol_short!("lease"), symbol_short!("ended"), lease.lessee.clone()), (lease.lease_id, lease.asset_id, lease.total_paid));
+    
+    escrow::refund_escrow(env, &lease, escrowed)?;
+    payments::refund_prepaid(env, &lease, &asset, prepaid)?;
+    if refund_split {
+        escrow::pay_split_deposit(env, &held, &lease.lessee)?;
+    }
+    assets::refund_listing_fee(env, asset.asset_id, &asset.owner, listing_fee)?;
+    
+    Ok(())
+}
+
+// Move a lease that has run past end_time into its next state: another term at current
+// pricing if auto-renewing, otherwise holdover when the asset allows it.
+// Returns false once the lease is past its term and can no longer take payments.
+pub(crate) fn roll_term(env: &Env, asset: &Asset, lease: &mut LeaseStatus, now: u64) -> Result<bool, RentError> {
+    if lease.in_holdover {
+        return Ok(lease.holdover_end == 0 || now < lease.holdover_end);
+    }
+    if now < lease.end_time {
+        return Ok(true);
+    }
+    if lease.auto_renew && !lease.non_renewal_filed && lease.term_length > 0 {
+        while now >= lease.end_time {
+            lease.end_time += lease.term_length;
+        }
+        lease.period_payment = asset.price_per_period;
+        return Ok(true);
+    }
+    if asset.holdover_bps == 0 {
+        return Ok(false);
+    }
+    
+    lease.in_holdover = true;
+    lease.period_payment = lease.period_payment.checked_mul(asset.holdover_bps as i128).ok_or_else(|| {
+        log!(env, "Holdover rent is too large");
+        RentError::InvalidAmount
+    })? / 10_000;
+    return Ok(true);
+}
+
+// Advance a late lease through every escalation level it has reached, emitting one
+// event per level so notification services can tailor their messaging
+pub(crate) fn escalate(env: &Env, lease: &LeaseStatus, now: u64) {
+    // Nothing escalates while the lessee can still cure the lease
+    if in_cure_window(env, lease, now) {
+        return;
+    }
+    
+    let target = penalty::escalation_level(&view_escalation_schedule(env.clone()), payments::due_date(env, lease), now);
+    let mut notice = view_late_notice(env.clone(), lease.lease_id);
+    if target <= notice.level {
+        return;
+    }
+    
+    if notice.level < EscalationLevel::Reminder {
+        notice.reminder_at = now;
+        events::publish(env, (symbol_short!("late"), symbol_short!("reminder")), (lease.lease_id, now));
+    }
+    if notice.level < EscalationLevel::FormalNotice && target >= EscalationLevel::FormalNotice {
+        notice.notice_at = now;
+        events::publish(env, (symbol_short!("late"), symbol_short!("notice")), (lease.lease_id, now));
+    }
+    if target == EscalationLevel::DefaultWarning {
+        notice.warning_at = now;
+        events::publish(env, (symbol_short!("late"), symbol_short!("warning")), (lease.lease_id, now));
+    }
+    notice.level = target;
+    let key = EscalationBook::Notice(lease.lease_id);
+    env.storage().persistent().set(&key, &notice);
+    admin::bump_entry(env, &key);
+    
+    log!(env, "Late notice for lease ID {} escalated", lease.lease_id);
+}
+
+// Whether an overdue lease is still within its asset's cure period at `now`
+pub(crate) fn in_cure_window(env: &Env, lease: &LeaseStatus, now: u64) -> bool {
+    if !lease.is_overdue {
+        return false;
+    }
+    let marked_at: Option<u64> = env.storage().persistent().get(&CureBook::MarkedAt(lease.lease_id));
+    match marked_at {
+        Some(marked_at) => now <= marked_at + assets::view_cure_period(env.clone(), lease.asset_id),
+        None => false,
+    }
+}
+
+// Emit a reminder of the lease's next payment if its asset's reminder window has opened and the
+// cadence since the last reminder for this due date has passed (callable by anyone, e.g. a keeper).
+// Returns whether a reminder was emitted.
+pub fn send_payment_reminder(env: Env, lease_id: u64) -> Result<bool, RentError> {
+    let lease = active_lease(&env, lease_id)?;
+    let policy = assets::view_reminder_policy(env.clone(), lease.asset_id);
+    let now = env.ledger().timestamp();
+    let due = payments::due_date(&env, &lease);
+    
+    if now + policy.lead_time < due {
+        return Ok(false);
+    }
+    let last: Option<(u64, u64)> = env.storage().persistent().get(&ReminderBook::LastSent(lease_id));
+    if let Some((reminded_due, sent_at)) = last {
+        if reminded_due == due && now < sent_at + policy.cadence {
+            return Ok(false);
+        }
+    }
+    
+    let key = ReminderBook::LastSent(lease_id);
+    env.storage().persistent().set(&key, &(due, now));
+    admin::bump_entry(&env, &key);
+    events::publish(&env, (symbol_short!("pay"), symbol_short!("remind")), (lease_id, due));
+    
+    return Ok(true);
+}
+
+// Load a lease that exists and is still active
+pub(crate) fn active_lease(env: &Env, lease_id: u64) -> Result<LeaseStatus, RentError> {
+    let lease = load_lease(env, lease_id)?;
+    
+    if !lease.is_active {
+        log!(env, "Lease is not active");
+        return Err(RentError::LeaseNotActive);
+    }
+    
+    return Ok(lease);
+}
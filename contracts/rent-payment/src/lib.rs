@@ -0,0 +1,1132 @@
+#![allow(non_snake_case)]
+#![allow(clippy::too_many_arguments, clippy::needless_return, clippy::redundant_field_names)]
+#![no_std]
+
+mod accounts;
+mod admin;
+mod applications;
+mod assets;
+pub mod calendar;
+mod disputes;
+mod escrow;
+mod events;
+pub mod interface;
+mod leases;
+pub mod merkle;
+mod migration;
+#[cfg(any(test, feature = "testutils"))]
+pub mod mock;
+pub mod network;
+mod payments;
+pub mod penalty;
+pub mod policy;
+mod rewards;
+mod stats;
+pub mod storage_keys;
+pub mod xlm;
+mod test;
+
+use calendar::{AssetCalendar, BlackoutWindow, ReportingPeriod};
+use interface::RentPaymentInterface;
+use merkle::StateCommitment;
+use network::Network;
+use penalty::{EscalationSchedule, LateNotice, PenaltyPolicy};
+use policy::PaymentPolicy;
+use soroban_sdk::{contract, contracterror, contractimpl, panic_with_error, Address, Bytes, BytesN, Env, Map, String, Symbol, Vec};
+
+pub use accounts::SubAccount;
+pub use admin::{TtlConfig, ListingFee, EntryKind};
+pub use applications::{ApplicationStatus, ApplicationTerms, LeaseApplication};
+pub use leases::{CanonicalTerms, AmendmentChange, LeaseAmendment, TerminationNotice};
+pub use assets::{RelistPlan, ReminderPolicy, VacationDelegate};
+pub use common::{Asset, AssetStats, CoTenant, DepositMode, DepositPosition, LeaseStatus, RentError};
+pub use payments::{PaymentQuote, LeaseAllocation, ConsolidatedSettlement, LateFeeWaiver, SettlementReport, ClosingStatement, PaymentBlock, DueDateExtension, PrepaidRent, ScheduledPayment, BatchPaymentResult, AutoPay};
+pub use xlm::Amount;
+pub use escrow::{DepositKind, DepositLock, DepositRelease, DepositStatementEntry, AccessHandover};
+pub use disputes::{Dispute, DisputeStatus};
+pub use rewards::KeeperReward;
+pub use migration::{MigrationBatch, TokenRecord, AssetBooks, LeaseBooks};
+pub use stats::{Reputation, DelinquencyEntry, TokenStats, AssetFinancials, OwnerDashboard, LesseeLeaseSummary, LesseeDashboard, RenewalStatus, RenewalPipelineEntry, StorageMetrics, ReportingBucket};
+
+// Stable symbolic names for every RentError code, kept in code order
+const ERROR_CODES: [(RentError, &str); 50] = [
+    (RentError::AssetNotFound, "AssetNotFound"),
+    (RentError::AssetUnavailable, "AssetUnavailable"),
+    (RentError::InvalidLeaseDuration, "InvalidLeaseDuration"),
+    (RentError::LeaseNotActive, "LeaseNotActive"),
+    (RentError::NotLessee, "NotLessee"),
+    (RentError::NotLesseeOrOwner, "NotLesseeOrOwner"),
+    (RentError::NotAssetOwner, "NotAssetOwner"),
+    (RentError::PaymentNotDue, "PaymentNotDue"),
+    (RentError::AlreadyInitialized, "AlreadyInitialized"),
+    (RentError::NotAdmin, "NotAdmin"),
+    (RentError::LandlordNotVerified, "LandlordNotVerified"),
+    (RentError::NativeTokenNotSet, "NativeTokenNotSet"),
+    (RentError::AllowanceTooLow, "AllowanceTooLow"),
+    (RentError::NoDepositLock, "NoDepositLock"),
+    (RentError::InvalidClaim, "InvalidClaim"),
+    (RentError::NotMetered, "NotMetered"),
+    (RentError::LatePaymentRefused, "LatePaymentRefused"),
+    (RentError::AmountExceedsMax, "AmountExceedsMax"),
+    (RentError::LeaseExpired, "LeaseExpired"),
+    (RentError::NotInHoldover, "NotInHoldover"),
+    (RentError::NoticeDeadlinePassed, "NoticeDeadlinePassed"),
+    (RentError::InvalidWindow, "InvalidWindow"),
+    (RentError::BlackoutConflict, "BlackoutConflict"),
+    (RentError::BookingWindowViolation, "BookingWindowViolation"),
+    (RentError::InvalidAmount, "InvalidAmount"),
+    (RentError::PlatformNotSet, "PlatformNotSet"),
+    (RentError::ListingExpired, "ListingExpired"),
+    (RentError::MutualReleaseRequired, "MutualReleaseRequired"),
+    (RentError::NoPendingRelease, "NoPendingRelease"),
+    (RentError::DeadlineNotReached, "DeadlineNotReached"),
+    (RentError::NotInDefault, "NotInDefault"),
+    (RentError::ExternalIdTaken, "ExternalIdTaken"),
+    (RentError::InsufficientBalance, "InsufficientBalance"),
+    (RentError::UnsupportedDecimals, "UnsupportedDecimals"),
+    (RentError::ConsolidatedBillingOff, "ConsolidatedBillingOff"),
+    (RentError::NotInTrial, "NotInTrial"),
+    (RentError::AccessCodeMismatch, "AccessCodeMismatch"),
+    (RentError::AccessCodeRevealed, "AccessCodeRevealed"),
+    (RentError::PeriodClosed, "PeriodClosed"),
+    (RentError::LeaseRaceLost, "LeaseRaceLost"),
+    (RentError::NoTrustline, "NoTrustline"),
+    (RentError::NetworkMismatch, "NetworkMismatch"),
+    (RentError::InvalidApplication, "InvalidApplication"),
+    (RentError::RenewalRefused, "RenewalRefused"),
+    (RentError::MixedPaymentTokens, "MixedPaymentTokens"),
+    (RentError::ContractPaused, "ContractPaused"),
+    (RentError::TermLimitExceeded, "TermLimitExceeded"),
+    (RentError::AccountOverdrawn, "AccountOverdrawn"),
+    (RentError::ExtensionRefused, "ExtensionRefused"),
+    (RentError::NoPendingExtension, "NoPendingExtension"),
+];
+
+// Error codes from 51 up, continuing RentError past the spec's 50-case limit. Entrypoints still
+// declare RentError; these are raised through `raise`, which traps with the code, so clients
+// receive it as a contract error like any other and decode it with this enum.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum RentErrorExt {
+    NoOpenDispute = 51,       // Dispute does not exist or has already been ruled on
+    DepositFrozen = 52,       // Deposit cannot move while a dispute on the lease is open
+    CreationFrozen = 53,      // Admin has frozen new leases and listings
+    PayerFrozen = 54,         // Batch payer is not authorized to move the asset's payment token
+    MigrationNotPaused = 55,  // Records can only be exported or imported while the contract is paused
+    LeaseNotEnded = 56,       // Lease being exported is still active or still holds a deposit
+    EscrowNotSet = 57,        // Escrow contract holding standard deposits has not been configured
+    DepositUnsettled = 58,    // Asset has a disputed lease, or an ended lease whose deposit awaits a release or ruling
+}
+
+// Stable symbolic names for every RentErrorExt code, kept in code order
+const ERROR_CODES_EXT: [(RentErrorExt, &str); 8] = [
+    (RentErrorExt::NoOpenDispute, "NoOpenDispute"),
+    (RentErrorExt::DepositFrozen, "DepositFrozen"),
+    (RentErrorExt::CreationFrozen, "CreationFrozen"),
+    (RentErrorExt::PayerFrozen, "PayerFrozen"),
+    (RentErrorExt::MigrationNotPaused, "MigrationNotPaused"),
+    (RentErrorExt::LeaseNotEnded, "LeaseNotEnded"),
+    (RentErrorExt::EscrowNotSet, "EscrowNotSet"),
+    (RentErrorExt::DepositUnsettled, "DepositUnsettled"),
+];
+
+// Fail with a code from RentErrorExt; typed as RentError so call sites read `return Err(raise(..))`
+pub(crate) fn raise(env: &Env, error: RentErrorExt) -> RentError {
+    panic_with_error!(env, error)
+}
+
+#[contract]
+pub struct RentPaymentContract;
+
+// Public entrypoints; each delegates to the module that owns the behavior
+#[contractimpl]
+impl RentPaymentContract {
+    // Configuration (admin.rs)
+    
+    pub fn initialize(env: Env, admin: Address) -> Result<(), RentError> {
+        admin::initialize(env, admin)
+    }
+    
+    pub fn initialize_for_network(env: Env, admin: Address, network: Network) -> Result<(), RentError> {
+        admin::initialize_for_network(env, admin, network)
+    }
+    
+    pub fn view_network(env: Env) -> Result<Option<Network>, RentError> {
+        Ok(admin::view_network(env))
+    }
+    
+    pub fn set_landlord_registry(env: Env, admin: Address, registry: Option<Address>) -> Result<(), RentError> {
+        admin::set_landlord_registry(env, admin, registry)
+    }
+    
+    pub fn view_landlord_registry(env: Env) -> Result<Option<Address>, RentError> {
+        Ok(admin::view_landlord_registry(env))
+    }
+    
+    pub fn set_escrow_contract(env: Env, admin: Address, escrow: Address) -> Result<(), RentError> {
+        admin::set_escrow_contract(env, admin, escrow)
+    }
+    
+    pub fn view_escrow_contract(env: Env) -> Result<Option<Address>, RentError> {
+        Ok(admin::view_escrow_contract(env))
+    }
+    
+    pub fn set_native_token(env: Env, admin: Address, token: Address) -> Result<(), RentError> {
+        admin::set_native_token(env, admin, token)
+    }
+    
+    pub fn view_native_token(env: Env) -> Result<Option<Address>, RentError> {
+        Ok(admin::view_native_token(env))
+    }
+    
+    pub fn set_listing_fee(env: Env, admin: Address, amount: i128, refundable: bool) -> Result<(), RentError> {
+        admin::set_listing_fee(env, admin, amount, refundable)
+    }
+    
+    pub fn view_listing_fee(env: Env) -> Result<ListingFee, RentError> {
+        Ok(admin::view_listing_fee(env))
+    }
+    
+    pub fn set_platform_account(env: Env, admin: Address, account: Address) -> Result<(), RentError> {
+        admin::set_platform_account(env, admin, account)
+    }
+    
+    pub fn view_platform_account(env: Env) -> Result<Option<Address>, RentError> {
+        Ok(admin::view_platform_account(env))
+    }
+    
+    pub fn set_boost_fee(env: Env, admin: Address, fee_per_day: i128) -> Result<(), RentError> {
+        admin::set_boost_fee(env, admin, fee_per_day)
+    }
+    
+    pub fn view_boost_fee(env: Env) -> Result<i128, RentError> {
+        Ok(admin::view_boost_fee(env))
+    }
+    
+    pub fn set_platform_fee_bps(env: Env, admin: Address, fee_bps: u32) -> Result<(), RentError> {
+        admin::set_platform_fee_bps(env, admin, fee_bps)
+    }
+    
+    pub fn view_platform_fee_bps(env: Env) -> Result<u32, RentError> {
+        Ok(admin::view_platform_fee_bps(env))
+    }
+    
+    pub fn view_platform_fees(env: Env) -> Result<Map<Address, i128>, RentError> {
+        Ok(admin::view_platform_fees(env))
+    }
+    
+    pub fn withdraw_platform_fees(env: Env, admin: Address, to: Address) -> Result<Map<Address, i128>, RentError> {
+        admin::withdraw_platform_fees(env, admin, to)
+    }
+    
+    pub fn view_sub_account(env: Env, account: SubAccount, token: Address) -> Result<i128, RentError> {
+        Ok(accounts::view_sub_account(env, account, token))
+    }
+    
+    pub fn view_sub_accounts(env: Env, token: Address) -> Result<Map<SubAccount, i128>, RentError> {
+        Ok(accounts::view_sub_accounts(env, token))
+    }
+    
+    pub fn view_held_tokens(env: Env) -> Result<Vec<Address>, RentError> {
+        Ok(accounts::view_held_tokens(env))
+    }
+    
+    pub fn set_min_ttl(env: Env, admin: Address, threshold: u32, extend_to: u32) -> Result<(), RentError> {
+        admin::set_min_ttl(env, admin, threshold, extend_to)
+    }
+    
+    pub fn view_min_ttl(env: Env) -> Result<TtlConfig, RentError> {
+        Ok(admin::view_min_ttl(env))
+    }
+    
+    pub fn bump_contract_ttl(env: Env) -> Result<(), RentError> {
+        admin::bump_contract_ttl(env);
+        Ok(())
+    }
+    
+    pub fn bump_entries(env: Env, kind: EntryKind, ids: Vec<u64>, extend_to: u32) -> Result<u32, RentError> {
+        admin::bump_entries(env, kind, ids, extend_to)
+    }
+    
+    pub fn add_holiday(env: Env, admin: Address, day: u64) -> Result<(), RentError> {
+        admin::add_holiday(env, admin, day)
+    }
+    
+    pub fn remove_holiday(env: Env, admin: Address, day: u64) -> Result<(), RentError> {
+        admin::remove_holiday(env, admin, day)
+    }
+    
+    pub fn view_holidays(env: Env) -> Result<Vec<u64>, RentError> {
+        Ok(admin::view_holidays(env))
+    }
+    
+    pub fn set_weekend_rollover(env: Env, admin: Address, enabled: bool) -> Result<(), RentError> {
+        admin::set_weekend_rollover(env, admin, enabled)
+    }
+    
+    pub fn view_weekend_rollover(env: Env) -> Result<bool, RentError> {
+        Ok(admin::view_weekend_rollover(env))
+    }
+    
+    pub fn set_paused(env: Env, admin: Address, paused: bool) -> Result<(), RentError> {
+        admin::set_paused(env, admin, paused)
+    }
+    
+    pub fn is_paused(env: Env) -> Result<bool, RentError> {
+        Ok(admin::is_paused(env))
+    }
+    
+    pub fn set_creation_frozen(env: Env, admin: Address, frozen: bool) -> Result<(), RentError> {
+        admin::set_creation_frozen(env, admin, frozen)
+    }
+    
+    pub fn is_creation_frozen(env: Env) -> Result<bool, RentError> {
+        Ok(admin::is_creation_frozen(env))
+    }
+    
+    // Assets (assets.rs)
+    
+    pub fn register_asset_with_amounts(
+        env: Env,
+        owner: Address,
+        title: String,
+        description: String,
+        price_per_period: Amount,
+        period_duration: u64,
+        min_lease_duration: u64,
+        max_lease_duration: u64,
+        deposit_required: Amount
+    ) -> Result<u64, RentError> {
+        assets::register_asset_with_amounts(env, owner, title, description, price_per_period, period_duration, min_lease_duration, max_lease_duration, deposit_required)
+    }
+    
+    pub fn update_asset(
+        env: Env,
+        asset_id: u64,
+        owner: Address,
+        price_per_period: i128,
+        deposit_required: i128,
+        min_lease_duration: u64,
+        max_lease_duration: u64,
+        description: String
+    ) -> Result<(), RentError> {
+        assets::update_asset(env, asset_id, owner, price_per_period, deposit_required, min_lease_duration, max_lease_duration, description)
+    }
+    
+    pub fn transfer_asset(env: Env, asset_id: u64, current_owner: Address, new_owner: Address) -> Result<(), RentError> {
+        assets::transfer_asset(env, asset_id, current_owner, new_owner)
+    }
+    
+    pub fn delist_asset(env: Env, asset_id: u64, owner: Address) -> Result<(), RentError> {
+        assets::delist_asset(env, asset_id, owner)
+    }
+    
+    pub fn set_payment_policy(env: Env, asset_id: u64, owner: Address, payment_policy: PaymentPolicy) -> Result<(), RentError> {
+        assets::set_payment_policy(env, asset_id, owner, payment_policy)
+    }
+    
+    pub fn set_penalty_policy(env: Env, asset_id: u64, owner: Address, penalty_policy: PenaltyPolicy) -> Result<(), RentError> {
+        assets::set_penalty_policy(env, asset_id, owner, penalty_policy)
+    }
+    
+    pub fn set_grace_period(env: Env, asset_id: u64, owner: Address, grace_period: u64) -> Result<(), RentError> {
+        assets::set_grace_period(env, asset_id, owner, grace_period)
+    }
+    
+    pub fn set_payment_token(env: Env, asset_id: u64, owner: Address, payment_token: Address) -> Result<(), RentError> {
+        assets::set_payment_token(env, asset_id, owner, payment_token)
+    }
+    
+    pub fn set_vacation_delegate(env: Env, owner: Address, delegate: Address, until: u64) -> Result<(), RentError> {
+        assets::set_vacation_delegate(env, owner, delegate, until)
+    }
+    
+    pub fn revoke_vacation_delegate(env: Env, owner: Address) -> Result<(), RentError> {
+        assets::revoke_vacation_delegate(env, owner);
+        Ok(())
+    }
+    
+    pub fn view_vacation_delegate(env: Env, owner: Address) -> Result<Option<VacationDelegate>, RentError> {
+        Ok(assets::view_vacation_delegate(env, owner))
+    }
+    
+    pub fn boost_listing(env: Env, asset_id: u64, owner: Address, duration: u64) -> Result<u64, RentError> {
+        assets::boost_listing(env, asset_id, owner, duration)
+    }
+    
+    pub fn get_featured_assets(env: Env) -> Result<Vec<u64>, RentError> {
+        assets::get_featured_assets(env)
+    }
+    
+    pub fn set_relist_plan(env: Env, asset_id: u64, owner: Address, plan: Option<RelistPlan>) -> Result<(), RentError> {
+        assets::set_relist_plan(env, asset_id, owner, plan)
+    }
+    
+    pub fn view_relist_plan(env: Env, asset_id: u64) -> Result<Option<RelistPlan>, RentError> {
+        Ok(assets::view_relist_plan(env, asset_id))
+    }
+    
+    pub fn set_trial_period(env: Env, asset_id: u64, owner: Address, days: u32) -> Result<(), RentError> {
+        assets::set_trial_period(env, asset_id, owner, days)
+    }
+    
+    pub fn view_trial_period(env: Env, asset_id: u64) -> Result<u32, RentError> {
+        Ok(assets::view_trial_period(env, asset_id))
+    }
+    
+    pub fn set_asset_term(env: Env, asset_id: u64, owner: Address, key: Symbol, value: Option<String>) -> Result<(), RentError> {
+        assets::set_asset_term(env, asset_id, owner, key, value)
+    }
+    
+    pub fn view_asset_terms(env: Env, asset_id: u64) -> Result<Map<Symbol, String>, RentError> {
+        Ok(assets::view_asset_terms(env, asset_id))
+    }
+    
+    pub fn set_reminder_policy(env: Env, asset_id: u64, owner: Address, policy: ReminderPolicy) -> Result<(), RentError> {
+        assets::set_reminder_policy(env, asset_id, owner, policy)
+    }
+    
+    pub fn view_reminder_policy(env: Env, asset_id: u64) -> Result<ReminderPolicy, RentError> {
+        Ok(assets::view_reminder_policy(env, asset_id))
+    }
+    
+    pub fn refresh_listing(env: Env, asset_id: u64, owner: Address, listing_expires_at: u64) -> Result<(), RentError> {
+        assets::refresh_listing(env, asset_id, owner, listing_expires_at)
+    }
+    
+    pub fn set_booking_window(env: Env, asset_id: u64, owner: Address, min_lead_time: u64, max_advance: u64) -> Result<(), RentError> {
+        assets::set_booking_window(env, asset_id, owner, min_lead_time, max_advance)
+    }
+    
+    pub fn add_blackout(env: Env, asset_id: u64, owner: Address, window: BlackoutWindow) -> Result<u32, RentError> {
+        assets::add_blackout(env, asset_id, owner, window)
+    }
+    
+    pub fn remove_blackout(env: Env, asset_id: u64, owner: Address, index: u32) -> Result<(), RentError> {
+        assets::remove_blackout(env, asset_id, owner, index)
+    }
+    
+    pub fn view_blackouts(env: Env, asset_id: u64) -> Result<Vec<BlackoutWindow>, RentError> {
+        Ok(assets::view_blackouts(env, asset_id))
+    }
+    
+    pub fn view_asset_calendar(env: Env, asset_id: u64) -> Result<AssetCalendar, RentError> {
+        assets::view_asset_calendar(env, asset_id)
+    }
+    
+    pub fn set_asset_jurisdiction(env: Env, asset_id: u64, owner: Address, code: Option<Symbol>) -> Result<(), RentError> {
+        assets::set_asset_jurisdiction(env, asset_id, owner, code)
+    }
+    
+    pub fn view_asset_jurisdiction(env: Env, asset_id: u64) -> Result<Option<Symbol>, RentError> {
+        Ok(assets::view_asset_jurisdiction(env, asset_id))
+    }
+    
+    pub fn set_asset_external_id(env: Env, asset_id: u64, owner: Address, external_id: Option<BytesN<32>>) -> Result<(), RentError> {
+        assets::set_asset_external_id(env, asset_id, owner, external_id)
+    }
+    
+    pub fn view_asset_external_id(env: Env, asset_id: u64) -> Result<Option<BytesN<32>>, RentError> {
+        Ok(assets::view_asset_external_id(env, asset_id))
+    }
+    
+    pub fn find_asset_by_external_id(env: Env, owner: Address, external_id: BytesN<32>) -> Result<Option<u64>, RentError> {
+        Ok(assets::find_asset_by_external_id(env, owner, external_id))
+    }
+    
+    pub fn set_termination_terms(env: Env, asset_id: u64, owner: Address, notice_period: u64, early_termination_fee: i128) -> Result<(), RentError> {
+        assets::set_termination_terms(env, asset_id, owner, notice_period, early_termination_fee)
+    }
+    
+    pub fn set_asset_category(env: Env, asset_id: u64, owner: Address, category: Symbol, tags: Vec<Symbol>) -> Result<(), RentError> {
+        assets::set_asset_category(env, asset_id, owner, category, tags)
+    }
+    
+    pub fn set_cure_period(env: Env, asset_id: u64, owner: Address, cure_period: u64) -> Result<(), RentError> {
+        assets::set_cure_period(env, asset_id, owner, cure_period)
+    }
+    
+    pub fn view_cure_period(env: Env, asset_id: u64) -> Result<u64, RentError> {
+        Ok(assets::view_cure_period(env, asset_id))
+    }
+    
+    pub fn set_holdover_rate(env: Env, asset_id: u64, owner: Address, holdover_bps: u32) -> Result<(), RentError> {
+        assets::set_holdover_rate(env, asset_id, owner, holdover_bps)
+    }
+    
+    pub fn set_rent_escalation(env: Env, asset_id: u64, owner: Address, escalation_bps: u32, escalation_interval: u32) -> Result<(), RentError> {
+        assets::set_rent_escalation(env, asset_id, owner, escalation_bps, escalation_interval)
+    }
+    
+    pub fn asset_exists(env: Env, asset_id: u64) -> Result<bool, RentError> {
+        Ok(assets::asset_exists(env, asset_id))
+    }
+    
+    pub fn get_asset_count(env: Env) -> Result<u64, RentError> {
+        Ok(assets::get_asset_count(env))
+    }
+    
+    pub fn list_assets(env: Env, start: u64, limit: u32) -> Result<Vec<Asset>, RentError> {
+        assets::list_assets(env, start, limit)
+    }
+    
+    pub fn search_assets_by_category(env: Env, category: Symbol, start: u32, limit: u32) -> Result<Vec<Asset>, RentError> {
+        assets::search_assets_by_category(env, category, start, limit)
+    }
+    
+    pub fn search_assets_by_tag(env: Env, tag: Symbol, start: u32, limit: u32) -> Result<Vec<Asset>, RentError> {
+        assets::search_assets_by_tag(env, tag, start, limit)
+    }
+    
+    pub fn get_available_assets(env: Env) -> Result<Vec<u64>, RentError> {
+        assets::get_available_assets(env)
+    }
+    
+    pub fn find_available_assets(env: Env, start: u64, limit: u32, required: Vec<(Symbol, String)>) -> Result<Vec<u64>, RentError> {
+        assets::find_available_assets(env, start, limit, required)
+    }
+    
+    // Leases (leases.rs)
+    
+    pub fn create_guaranteed_lease(env: Env, asset_id: u64, lessee: Address, lease_duration: u64, guarantor: Address) -> Result<u64, RentError> {
+        leases::create_guaranteed_lease(env, asset_id, lessee, lease_duration, guarantor)
+    }
+    
+    pub fn view_guarantor(env: Env, lease_id: u64) -> Result<Option<Address>, RentError> {
+        Ok(leases::view_guarantor(env, lease_id))
+    }
+    
+    pub fn set_guarantor_threshold(env: Env, asset_id: u64, owner: Address, threshold: u64) -> Result<(), RentError> {
+        leases::set_guarantor_threshold(env, asset_id, owner, threshold)
+    }
+    
+    pub fn view_guarantor_threshold(env: Env, asset_id: u64) -> Result<u64, RentError> {
+        Ok(leases::view_guarantor_threshold(env, asset_id))
+    }
+    
+    pub fn set_screening(env: Env, asset_id: u64, owner: Address, required: bool) -> Result<(), RentError> {
+        applications::set_screening(env, asset_id, owner, required)
+    }
+    
+    pub fn view_screening(env: Env, asset_id: u64) -> Result<bool, RentError> {
+        Ok(applications::view_screening(env, asset_id))
+    }
+    
+    pub fn set_application_terms(env: Env, asset_id: u64, owner: Address, terms: ApplicationTerms) -> Result<(), RentError> {
+        applications::set_application_terms(env, asset_id, owner, terms)
+    }
+    
+    pub fn view_application_terms(env: Env, asset_id: u64) -> Result<ApplicationTerms, RentError> {
+        Ok(applications::view_application_terms(env, asset_id))
+    }
+    
+    pub fn apply_for_lease(env: Env, asset_id: u64, applicant: Address, lease_duration: u64) -> Result<u64, RentError> {
+        applications::apply_for_lease(env, asset_id, applicant, lease_duration)
+    }
+    
+    pub fn approve_application(env: Env, application_id: u64, owner: Address) -> Result<(), RentError> {
+        applications::approve_application(env, application_id, owner)
+    }
+    
+    pub fn reject_application(env: Env, application_id: u64, owner: Address) -> Result<(), RentError> {
+        applications::reject_application(env, application_id, owner)
+    }
+    
+    pub fn accept_application(env: Env, application_id: u64, applicant: Address) -> Result<u64, RentError> {
+        applications::accept_application(env, application_id, applicant)
+    }
+    
+    pub fn expire_application(env: Env, application_id: u64) -> Result<(), RentError> {
+        applications::expire_application(env, application_id)
+    }
+    
+    pub fn view_application(env: Env, application_id: u64) -> Result<Option<LeaseApplication>, RentError> {
+        Ok(applications::view_application(env, application_id))
+    }
+    
+    pub fn get_application_count(env: Env) -> Result<u64, RentError> {
+        Ok(applications::get_application_count(env))
+    }
+    
+    pub fn dry_run_create_lease(env: Env, asset_id: u64, lessee: Address, lease_duration: u64) -> Result<LeaseStatus, RentError> {
+        leases::dry_run_create_lease(env, asset_id, lessee, lease_duration)
+    }
+    
+    pub fn give_termination_notice(env: Env, lease_id: u64, caller: Address) -> Result<u64, RentError> {
+        leases::give_termination_notice(env, lease_id, caller)
+    }
+    
+    pub fn view_termination_notice(env: Env, lease_id: u64) -> Result<Option<TerminationNotice>, RentError> {
+        Ok(leases::view_termination_notice(env, lease_id))
+    }
+    
+    pub fn set_assignment_approval(env: Env, asset_id: u64, owner: Address, required: bool) -> Result<(), RentError> {
+        leases::set_assignment_approval(env, asset_id, owner, required)
+    }
+    
+    pub fn view_assignment_approval(env: Env, asset_id: u64) -> Result<bool, RentError> {
+        Ok(leases::view_assignment_approval(env, asset_id))
+    }
+    
+    pub fn transfer_lease(env: Env, lease_id: u64, current_lessee: Address, new_lessee: Address) -> Result<(), RentError> {
+        leases::transfer_lease(env, lease_id, current_lessee, new_lessee)
+    }
+    
+    pub fn set_co_tenants(env: Env, lease_id: u64, lessee: Address, co_tenants: Vec<CoTenant>) -> Result<(), RentError> {
+        leases::set_co_tenants(env, lease_id, lessee, co_tenants)
+    }
+    
+    pub fn settle_expired_lease(env: Env, lease_id: u64, keeper: Address) -> Result<(), RentError> {
+        leases::settle_expired_lease(env, lease_id, keeper)
+    }
+    
+    pub fn exit_trial(env: Env, lease_id: u64, caller: Address) -> Result<i128, RentError> {
+        leases::exit_trial(env, lease_id, caller)
+    }
+    
+    pub fn check_and_mark_overdue(env: Env, lease_id: u64, keeper: Address) -> Result<bool, RentError> {
+        leases::check_and_mark_overdue(env, lease_id, keeper)
+    }
+    
+    pub fn set_auto_renew(env: Env, lease_id: u64, lessee: Address, enabled: bool) -> Result<(), RentError> {
+        leases::set_auto_renew(env, lease_id, lessee, enabled)
+    }
+    
+    pub fn file_non_renewal(env: Env, lease_id: u64, caller: Address) -> Result<(), RentError> {
+        leases::file_non_renewal(env, lease_id, caller)
+    }
+    
+    pub fn renew_lease(env: Env, lease_id: u64, lessee: Address, additional_duration: u64, reprice: bool) -> Result<u64, RentError> {
+        leases::renew_lease(env, lease_id, lessee, additional_duration, reprice)
+    }
+    
+    pub fn poke_lease(env: Env, lease_id: u64) -> Result<(), RentError> {
+        leases::poke_lease(env, lease_id)
+    }
+    
+    pub fn set_jurisdiction(env: Env, admin: Address, code: Symbol, cooling_off: u64) -> Result<(), RentError> {
+        leases::set_jurisdiction(env, admin, code, cooling_off)
+    }
+    
+    pub fn view_jurisdiction(env: Env, code: Symbol) -> Result<u64, RentError> {
+        Ok(leases::view_jurisdiction(env, code))
+    }
+    
+    pub fn default_lease(env: Env, lease_id: u64, owner: Address) -> Result<(), RentError> {
+        leases::default_lease(env, lease_id, owner)
+    }
+    
+    pub fn set_escalation_schedule(env: Env, admin: Address, schedule: EscalationSchedule) -> Result<(), RentError> {
+        leases::set_escalation_schedule(env, admin, schedule)
+    }
+    
+    pub fn view_escalation_schedule(env: Env) -> Result<EscalationSchedule, RentError> {
+        Ok(leases::view_escalation_schedule(env))
+    }
+    
+    pub fn view_late_notice(env: Env, lease_id: u64) -> Result<LateNotice, RentError> {
+        Ok(leases::view_late_notice(env, lease_id))
+    }
+    
+    pub fn send_payment_reminder(env: Env, lease_id: u64) -> Result<bool, RentError> {
+        leases::send_payment_reminder(env, lease_id)
+    }
+    
+    pub fn give_holdover_notice(env: Env, lease_id: u64, caller: Address) -> Result<u64, RentError> {
+        leases::give_holdover_notice(env, lease_id, caller)
+    }
+    
+    pub fn set_lease_external_id(env: Env, lease_id: u64, owner: Address, external_id: Option<BytesN<32>>) -> Result<(), RentError> {
+        leases::set_lease_external_id(env, lease_id, owner, external_id)
+    }
+    
+    pub fn view_lease_external_id(env: Env, lease_id: u64) -> Result<Option<BytesN<32>>, RentError> {
+        Ok(leases::view_lease_external_id(env, lease_id))
+    }
+    
+    pub fn find_lease_by_external_id(env: Env, owner: Address, external_id: BytesN<32>) -> Result<Option<u64>, RentError> {
+        Ok(leases::find_lease_by_external_id(env, owner, external_id))
+    }
+    
+    pub fn view_lease_terms(env: Env, lease_id: u64) -> Result<Map<Symbol, String>, RentError> {
+        Ok(leases::view_lease_terms(env, lease_id))
+    }
+    
+    pub fn get_canonical_terms(env: Env, lease_id: u64) -> Result<Option<(CanonicalTerms, BytesN<32>)>, RentError> {
+        Ok(leases::get_canonical_terms(env, lease_id))
+    }
+    
+    pub fn amend_lease(env: Env, lease_id: u64, owner: Address, lessee: Address, change: AmendmentChange) -> Result<u32, RentError> {
+        leases::amend_lease(env, lease_id, owner, lessee, change)
+    }
+    
+    pub fn view_amendments(env: Env, lease_id: u64) -> Result<Vec<LeaseAmendment>, RentError> {
+        Ok(leases::view_amendments(env, lease_id))
+    }
+    
+    pub fn lease_exists(env: Env, lease_id: u64) -> Result<bool, RentError> {
+        Ok(leases::lease_exists(env, lease_id))
+    }
+    
+    pub fn get_lease_count(env: Env) -> Result<u64, RentError> {
+        Ok(leases::get_lease_count(env))
+    }
+    
+    pub fn set_max_concurrent_leases(env: Env, admin: Address, limit: u32) -> Result<(), RentError> {
+        leases::set_max_concurrent_leases(env, admin, limit)
+    }
+    
+    pub fn view_max_concurrent_leases(env: Env) -> Result<u32, RentError> {
+        Ok(leases::view_max_concurrent_leases(env))
+    }
+    
+    pub fn set_owner_lease_limit(env: Env, owner: Address, limit: u32) -> Result<(), RentError> {
+        leases::set_owner_lease_limit(env, owner, limit);
+        Ok(())
+    }
+    
+    pub fn view_owner_lease_limit(env: Env, owner: Address) -> Result<u32, RentError> {
+        Ok(leases::view_owner_lease_limit(env, owner))
+    }
+    
+    pub fn list_leases(env: Env, start: u64, limit: u32) -> Result<Vec<LeaseStatus>, RentError> {
+        leases::list_leases(env, start, limit)
+    }
+    
+    // Payments (payments.rs)
+    
+    pub fn quote_in_stroops(_env: Env, amount: u64) -> Result<i128, RentError> {
+        Ok(payments::quote_in_stroops(_env, amount))
+    }
+    
+    pub fn process_payments_batch(env: Env, items: Vec<(u64, Address)>) -> Result<Vec<BatchPaymentResult>, RentError> {
+        payments::process_payments_batch(env, items)
+    }
+    
+    pub fn withdraw_rent(env: Env, owner: Address, token: Address, amount: i128) -> Result<i128, RentError> {
+        payments::withdraw_rent(env, owner, token, amount)
+    }
+    
+    pub fn view_withdrawable(env: Env, owner: Address, token: Address) -> Result<i128, RentError> {
+        Ok(payments::view_withdrawable(env, owner, token))
+    }
+    
+    pub fn process_partial_payment(env: Env, lease_id: u64, payer: Address, amount: i128) -> Result<i128, RentError> {
+        payments::process_partial_payment(env, lease_id, payer, amount)
+    }
+    
+    pub fn prepay(env: Env, lease_id: u64, lessee: Address, num_periods: u32) -> Result<u32, RentError> {
+        payments::prepay(env, lease_id, lessee, num_periods)
+    }
+    
+    pub fn settle_due_payment(env: Env, lease_id: u64) -> Result<bool, RentError> {
+        payments::settle_due_payment(env, lease_id)
+    }
+    
+    pub fn view_prepaid_rent(env: Env, lease_id: u64) -> Result<Option<PrepaidRent>, RentError> {
+        Ok(payments::view_prepaid_rent(env, lease_id))
+    }
+    
+    pub fn authorize_auto_pay(env: Env, lease_id: u64, lessee: Address, max_periods: u32) -> Result<(), RentError> {
+        payments::authorize_auto_pay(env, lease_id, lessee, max_periods)
+    }
+    
+    pub fn revoke_auto_pay(env: Env, lease_id: u64, lessee: Address) -> Result<(), RentError> {
+        payments::revoke_auto_pay(env, lease_id, lessee)
+    }
+    
+    pub fn view_auto_pay(env: Env, lease_id: u64) -> Result<Option<AutoPay>, RentError> {
+        Ok(payments::view_auto_pay(env, lease_id))
+    }
+    
+    pub fn collect_due_payment(env: Env, lease_id: u64, keeper: Address) -> Result<bool, RentError> {
+        payments::collect_due_payment(env, lease_id, keeper)
+    }
+    
+    pub fn claim_from_guarantor(env: Env, lease_id: u64) -> Result<i128, RentError> {
+        payments::claim_from_guarantor(env, lease_id)
+    }
+    
+    pub fn dry_run_process_payment(env: Env, lease_id: u64, caller: Address) -> Result<PaymentQuote, RentError> {
+        payments::dry_run_process_payment(env, lease_id, caller)
+    }
+    
+    pub fn view_payment_schedule(env: Env, lease_id: u64) -> Result<Vec<ScheduledPayment>, RentError> {
+        payments::view_payment_schedule(env, lease_id)
+    }
+    
+    pub fn waive_late_fee(env: Env, lease_id: u64, owner: Address, amount: i128, reason: Symbol) -> Result<(), RentError> {
+        payments::waive_late_fee(env, lease_id, owner, amount, reason)
+    }
+    
+    pub fn view_late_fee_waivers(env: Env, lease_id: u64) -> Result<Vec<LateFeeWaiver>, RentError> {
+        Ok(payments::view_late_fee_waivers(env, lease_id))
+    }
+    
+    pub fn request_extension(env: Env, lease_id: u64, lessee: Address, extra_days: u32, reason_hash: BytesN<32>) -> Result<(), RentError> {
+        payments::request_extension(env, lease_id, lessee, extra_days, reason_hash)
+    }
+    
+    pub fn approve_extension(env: Env, lease_id: u64, owner: Address) -> Result<(), RentError> {
+        payments::approve_extension(env, lease_id, owner)
+    }
+    
+    pub fn view_extension(env: Env, lease_id: u64) -> Result<Option<DueDateExtension>, RentError> {
+        Ok(payments::view_extension(env, lease_id))
+    }
+    
+    pub fn view_extensions_left(env: Env, lease_id: u64) -> Result<u32, RentError> {
+        Ok(payments::view_extensions_left(env, lease_id))
+    }
+    
+    pub fn calculate_late_fee(env: Env, lease_id: u64) -> Result<i128, RentError> {
+        payments::calculate_late_fee(env, lease_id)
+    }
+    
+    pub fn set_consolidated_billing(env: Env, lessee: Address, owner: Address, enabled: bool) -> Result<(), RentError> {
+        payments::set_consolidated_billing(env, lessee, owner, enabled);
+        Ok(())
+    }
+    
+    pub fn view_consolidated_billing(env: Env, lessee: Address, owner: Address) -> Result<bool, RentError> {
+        Ok(payments::view_consolidated_billing(env, lessee, owner))
+    }
+    
+    pub fn get_consolidated_bill(env: Env, lessee: Address, owner: Address) -> Result<ConsolidatedSettlement, RentError> {
+        payments::get_consolidated_bill(env, lessee, owner)
+    }
+    
+    pub fn pay_consolidated_bill(env: Env, lessee: Address, owner: Address, max_amount: i128) -> Result<u64, RentError> {
+        payments::pay_consolidated_bill(env, lessee, owner, max_amount)
+    }
+    
+    pub fn view_consolidated_settlement(env: Env, settlement_id: u64) -> Result<Option<ConsolidatedSettlement>, RentError> {
+        Ok(payments::view_consolidated_settlement(env, settlement_id))
+    }
+    
+    pub fn view_closing_statement(env: Env, lease_id: u64) -> Result<Option<(ClosingStatement, BytesN<32>)>, RentError> {
+        Ok(payments::view_closing_statement(env, lease_id))
+    }
+    
+    pub fn view_payment_block(env: Env, lease_id: u64) -> Result<Option<PaymentBlock>, RentError> {
+        Ok(payments::view_payment_block(env, lease_id))
+    }
+    
+    pub fn settle_all(env: Env, owner: Address, cursor: u64, limit: u32) -> Result<SettlementReport, RentError> {
+        payments::settle_all(env, owner, cursor, limit)
+    }
+    
+    pub fn record_usage(env: Env, lease_id: u64, owner: Address, units: u64) -> Result<(), RentError> {
+        payments::record_usage(env, lease_id, owner, units)
+    }
+    
+    pub fn view_amount_due(env: Env, lease_id: u64) -> Result<i128, RentError> {
+        payments::view_amount_due(env, lease_id)
+    }
+    
+    // Deposits (escrow.rs)
+    
+    pub fn set_deposit_mode(env: Env, asset_id: u64, owner: Address, mode: DepositMode) -> Result<(), RentError> {
+        escrow::set_deposit_mode(env, asset_id, owner, mode)
+    }
+    
+    pub fn set_split_deposit(env: Env, asset_id: u64, owner: Address, positions: Vec<DepositPosition>) -> Result<(), RentError> {
+        escrow::set_split_deposit(env, asset_id, owner, positions)
+    }
+    
+    pub fn claim_split_deposit(env: Env, lease_id: u64, owner: Address, share_bps: u32) -> Result<Vec<DepositPosition>, RentError> {
+        escrow::claim_split_deposit(env, lease_id, owner, share_bps)
+    }
+    
+    pub fn view_split_deposit_terms(env: Env, asset_id: u64) -> Result<Vec<DepositPosition>, RentError> {
+        Ok(escrow::view_split_deposit_terms(env, asset_id))
+    }
+    
+    pub fn view_split_deposit(env: Env, lease_id: u64) -> Result<Vec<DepositPosition>, RentError> {
+        Ok(escrow::view_split_deposit(env, lease_id))
+    }
+    
+    pub fn set_mutual_release(env: Env, asset_id: u64, owner: Address, response_window: u64) -> Result<(), RentError> {
+        escrow::set_mutual_release(env, asset_id, owner, response_window)
+    }
+    
+    pub fn view_mutual_release(env: Env, asset_id: u64) -> Result<u64, RentError> {
+        Ok(escrow::view_mutual_release(env, asset_id))
+    }
+    
+    pub fn propose_deposit_release(env: Env, lease_id: u64, caller: Address, owner_bps: u32) -> Result<(), RentError> {
+        escrow::propose_deposit_release(env, lease_id, caller, owner_bps)
+    }
+    
+    pub fn sign_deposit_release(env: Env, lease_id: u64, caller: Address) -> Result<(), RentError> {
+        escrow::sign_deposit_release(env, lease_id, caller)
+    }
+    
+    pub fn finalize_deposit_release(env: Env, lease_id: u64) -> Result<(), RentError> {
+        escrow::finalize_deposit_release(env, lease_id)
+    }
+    
+    pub fn view_deposit_release(env: Env, lease_id: u64) -> Result<Option<DepositRelease>, RentError> {
+        Ok(escrow::view_deposit_release(env, lease_id))
+    }
+    
+    pub fn set_deposit_interest(env: Env, admin: Address, code: Symbol, rate_bps: u32) -> Result<(), RentError> {
+        escrow::set_deposit_interest(env, admin, code, rate_bps)
+    }
+    
+    pub fn view_deposit_interest(env: Env, code: Symbol) -> Result<u32, RentError> {
+        Ok(escrow::view_deposit_interest(env, code))
+    }
+    
+    pub fn get_deposit_statement(env: Env, lessee: Address) -> Result<Vec<DepositStatementEntry>, RentError> {
+        escrow::get_deposit_statement(env, lessee)
+    }
+    
+    pub fn claim_locked_deposit(env: Env, lease_id: u64, owner: Address, amount: i128) -> Result<(), RentError> {
+        escrow::claim_locked_deposit(env, lease_id, owner, amount)
+    }
+    
+    pub fn approve_deposit_claim(env: Env, lease_id: u64, lessee: Address) -> Result<(), RentError> {
+        escrow::approve_deposit_claim(env, lease_id, lessee)
+    }
+    
+    pub fn view_deposit_lock(env: Env, lease_id: u64) -> Result<Option<DepositLock>, RentError> {
+        Ok(escrow::view_deposit_lock(env, lease_id))
+    }
+    
+    pub fn claim_deposit_deduction(env: Env, lease_id: u64, owner: Address, amount: i128) -> Result<i128, RentError> {
+        escrow::claim_deposit_deduction(env, lease_id, owner, amount)
+    }
+    
+    pub fn view_escrow_balance(env: Env, lease_id: u64) -> Result<i128, RentError> {
+        Ok(escrow::view_escrow_balance(env, lease_id))
+    }
+    
+    pub fn commit_access_code(env: Env, lease_id: u64, owner: Address, code_hash: BytesN<32>) -> Result<(), RentError> {
+        escrow::commit_access_code(env, lease_id, owner, code_hash)
+    }
+    
+    pub fn reveal_access_code(env: Env, lease_id: u64, owner: Address, code: Bytes) -> Result<(), RentError> {
+        escrow::reveal_access_code(env, lease_id, owner, code)
+    }
+    
+    pub fn view_access_handover(env: Env, lease_id: u64) -> Result<Option<AccessHandover>, RentError> {
+        Ok(escrow::view_access_handover(env, lease_id))
+    }
+    
+    // Disputes (disputes.rs)
+    
+    pub fn escalate_deposit_release(env: Env, lease_id: u64, caller: Address) -> Result<(), RentError> {
+        disputes::escalate_deposit_release(env, lease_id, caller)
+    }
+    
+    pub fn resolve_deposit_release(env: Env, admin: Address, lease_id: u64, owner_bps: u32) -> Result<(), RentError> {
+        disputes::resolve_deposit_release(env, admin, lease_id, owner_bps)
+    }
+    
+    pub fn set_arbiter(env: Env, admin: Address, arbiter: Option<Address>) -> Result<(), RentError> {
+        disputes::set_arbiter(env, admin, arbiter)
+    }
+    
+    pub fn view_arbiter(env: Env) -> Result<Option<Address>, RentError> {
+        Ok(disputes::view_arbiter(env))
+    }
+    
+    pub fn open_dispute(env: Env, lease_id: u64, opener: Address, reason: Symbol) -> Result<u64, RentError> {
+        disputes::open_dispute(env, lease_id, opener, reason)
+    }
+    
+    pub fn resolve_dispute(env: Env, arbiter: Address, dispute_id: u64, deposit_split_bps: u32) -> Result<(), RentError> {
+        disputes::resolve_dispute(env, arbiter, dispute_id, deposit_split_bps)
+    }
+    
+    pub fn view_dispute(env: Env, dispute_id: u64) -> Result<Option<Dispute>, RentError> {
+        Ok(disputes::view_dispute(env, dispute_id))
+    }
+    
+    pub fn view_open_dispute(env: Env, lease_id: u64) -> Result<Option<u64>, RentError> {
+        Ok(disputes::view_open_dispute(env, lease_id))
+    }
+    
+    pub fn get_dispute_count(env: Env) -> Result<u64, RentError> {
+        Ok(disputes::get_dispute_count(env))
+    }
+    
+    // Migration (migration.rs)
+    
+    pub fn export_batch(env: Env, admin: Address, cursor: u64, limit: u32) -> Result<MigrationBatch, RentError> {
+        migration::export_batch(env, admin, cursor, limit)
+    }
+    
+    pub fn import_batch(env: Env, admin: Address, batch: MigrationBatch) -> Result<u64, RentError> {
+        migration::import_batch(env, admin, batch)
+    }
+    
+    pub fn view_import_cursor(env: Env) -> Result<u64, RentError> {
+        Ok(migration::view_import_cursor(env))
+    }
+    
+    // Keeper rewards (rewards.rs)
+    
+    pub fn set_keeper_reward(env: Env, admin: Address, flat: i128, bps: u32, cap: i128) -> Result<(), RentError> {
+        rewards::set_keeper_reward(env, admin, flat, bps, cap)
+    }
+    
+    pub fn view_keeper_reward(env: Env) -> Result<KeeperReward, RentError> {
+        Ok(rewards::view_keeper_reward(env))
+    }
+    
+    pub fn view_keeper_rewards(env: Env, keeper: Address, token: Address) -> Result<i128, RentError> {
+        Ok(rewards::view_keeper_rewards(env, keeper, token))
+    }
+    
+    pub fn claim_keeper_rewards(env: Env, keeper: Address, token: Address) -> Result<i128, RentError> {
+        rewards::claim_keeper_rewards(env, keeper, token)
+    }
+    
+    // Statistics and dashboards (stats.rs)
+    
+    pub fn view_token_stats(env: Env, token: Address) -> Result<TokenStats, RentError> {
+        Ok(stats::view_token_stats(env, token))
+    }
+    
+    pub fn view_asset_financials(env: Env, asset_id: u64) -> Result<AssetFinancials, RentError> {
+        Ok(stats::view_asset_financials(env, asset_id))
+    }
+    
+    pub fn view_reputation(env: Env, address: Address) -> Result<Reputation, RentError> {
+        Ok(stats::view_reputation(env, address))
+    }
+    
+    pub fn get_owner_dashboard(env: Env, owner: Address) -> Result<OwnerDashboard, RentError> {
+        stats::get_owner_dashboard(env, owner)
+    }
+    
+    pub fn set_reporting_period(env: Env, admin: Address, period: ReportingPeriod) -> Result<(), RentError> {
+        stats::set_reporting_period(env, admin, period)
+    }
+    
+    pub fn view_reporting_period(env: Env) -> Result<ReportingPeriod, RentError> {
+        Ok(stats::view_reporting_period(env))
+    }
+    
+    pub fn view_reporting_bucket(env: Env, period_start: u64) -> Result<Option<ReportingBucket>, RentError> {
+        Ok(stats::view_reporting_bucket(env, period_start))
+    }
+    
+    pub fn close_reporting_period(env: Env) -> Result<ReportingBucket, RentError> {
+        stats::close_reporting_period(env)
+    }
+    
+    pub fn get_storage_metrics(env: Env) -> Result<StorageMetrics, RentError> {
+        stats::get_storage_metrics(env)
+    }
+    
+    pub fn get_renewal_pipeline(env: Env, owner: Address, horizon: u64) -> Result<Vec<RenewalPipelineEntry>, RentError> {
+        stats::get_renewal_pipeline(env, owner, horizon)
+    }
+    
+    pub fn get_delinquency_report(env: Env, owner: Address) -> Result<Vec<DelinquencyEntry>, RentError> {
+        stats::get_delinquency_report(env, owner)
+    }
+    
+    pub fn get_lessee_dashboard(env: Env, lessee: Address) -> Result<LesseeDashboard, RentError> {
+        stats::get_lessee_dashboard(env, lessee)
+    }
+    
+    pub fn commit_state_root(env: Env, limit: u32) -> Result<u64, RentError> {
+        stats::commit_state_root(env, limit)
+    }
+    
+    pub fn view_state_root(env: Env) -> Result<Option<StateCommitment>, RentError> {
+        Ok(stats::view_state_root(env))
+    }
+    
+    pub fn verify_state_proof(env: Env, leaf: BytesN<32>, index: u64, proof: Vec<BytesN<32>>) -> Result<bool, RentError> {
+        Ok(stats::verify_state_proof(env, leaf, index, proof))
+    }
+    
+    // Events (events.rs)
+    
+    pub fn get_event_sequence(env: Env) -> Result<u64, RentError> {
+        Ok(events::get_event_sequence(env))
+    }
+}
+
+// The core lease lifecycle integrators reach through RentPaymentInterfaceClient
+#[contractimpl]
+impl RentPaymentInterface for RentPaymentContract {
+    fn register_asset(
+        env: Env, 
+        owner: Address,  // Pass the owner address as a parameter instead of using invoker
+        title: String, 
+        description: String,
+        price_per_period: i128,
+        period_duration: u64,
+        min_lease_duration: u64,
+        max_lease_duration: u64,
+        deposit_required: i128
+    ) -> Result<u64, RentError> {
+        assets::register_asset(env, owner, title, description, price_per_period, period_duration, min_lease_duration, max_lease_duration, deposit_required)
+    }
+    
+    fn create_lease(
+        env: Env,
+        asset_id: u64,
+        lessee: Address,  // Pass the lessee address as a parameter
+        lease_duration: u64  // Duration in seconds
+    ) -> Result<u64, RentError> {
+        leases::create_lease(env, asset_id, lessee, lease_duration)
+    }
+    
+    fn create_scheduled_lease(
+        env: Env,
+        asset_id: u64,
+        lessee: Address,
+        start_time: u64,     // Lease start timestamp
+        lease_duration: u64  // Duration in seconds
+    ) -> Result<u64, RentError> {
+        leases::create_scheduled_lease(env, asset_id, lessee, start_time, lease_duration)
+    }
+    
+    fn process_payment(env: Env, lease_id: u64, caller: Address) -> Result<(), RentError> {
+        payments::process_payment(env, lease_id, caller)
+    }
+    
+    fn process_sponsored_payment(env: Env, lease_id: u64, lessee: Address, max_amount: i128) -> Result<i128, RentError> {
+        payments::process_sponsored_payment(env, lease_id, lessee, max_amount)
+    }
+    
+    fn end_lease(env: Env, lease_id: u64, caller: Address) -> Result<(), RentError> {
+        leases::end_lease(env, lease_id, caller)
+    }
+    
+    fn mark_lease_overdue(env: Env, lease_id: u64, caller: Address) -> Result<(), RentError> {
+        leases::mark_lease_overdue(env, lease_id, caller)
+    }
+    
+    fn view_asset_stats(env: Env) -> Result<AssetStats, RentError> {
+        Ok(stats::view_asset_stats(env))
+    }
+    
+    fn view_asset(env: Env, asset_id: u64) -> Result<Option<Asset>, RentError> {
+        Ok(assets::view_asset(env, asset_id))
+    }
+    
+    fn view_lease(env: Env, lease_id: u64) -> Result<Option<LeaseStatus>, RentError> {
+        Ok(leases::view_lease(env, lease_id))
+    }
+    
+    fn get_owner_assets(env: Env, owner: Address) -> Result<Vec<u64>, RentError> {
+        Ok(assets::get_owner_assets(env, owner))
+    }
+    
+    fn get_lessee_leases(env: Env, lessee: Address) -> Result<Vec<u64>, RentError> {
+        leases::get_lessee_leases(env, lessee)
+    }
+    
+    fn list_error_codes(env: Env) -> Result<Vec<(u32, Symbol)>, RentError> {
+        Ok(admin::list_error_codes(env))
+    }
+}
@@ -0,0 +1,115 @@
+use soroban_sdk::{contracttype, xdr::ToXdr, Bytes, BytesN, Env, IntoVal, Val, Vec};
+
+// Committed Merkle root over every asset and lease record
+#[contracttype]
+#[derive(Clone)]
+pub struct StateCommitment {
+    pub root: BytesN<32>,  // Merkle root (all zeroes when there are no records)
+    pub asset_count: u64,  // Assets covered; leaves 0..asset_count are assets 1..=asset_count
+    pub lease_count: u64,  // Leases covered; they follow the asset leaves in ID order
+    pub timestamp: u64,    // Ledger timestamp of the commitment
+    pub ledger: u32,       // Ledger sequence of the commitment
+}
+
+// Merkle commitment being built a batch of leaves at a time
+#[contracttype]
+#[derive(Clone)]
+pub struct StateRootBuild {
+    pub asset_count: u64,                 // Assets to cover, fixed when the build starts
+    pub lease_count: u64,                 // Leases to cover, fixed when the build starts
+    pub next_leaf: u64,                   // Leaves folded in so far
+    pub frontier: Vec<(u32, BytesN<32>)>, // Complete subtrees so far as (height, root), left to right
+}
+
+// Leaf hash: sha256(0x00 || XDR of the record)
+pub fn leaf_hash<T: IntoVal<Env, Val>>(env: &Env, record: &T) -> BytesN<32> {
+    let mut bytes = Bytes::from_array(env, &[0u8]);
+    bytes.append(&record.into_val(env).to_xdr(env));
+    env.crypto().sha256(&bytes).to_bytes()
+}
+
+// Interior node hash: sha256(0x01 || left || right)
+pub fn node_hash(env: &Env, left: &BytesN<32>, right: &BytesN<32>) -> BytesN<32> {
+    let mut bytes = Bytes::from_array(env, &[1u8]);
+    bytes.append(&Bytes::from(left));
+    bytes.append(&Bytes::from(right));
+    env.crypto().sha256(&bytes).to_bytes()
+}
+
+// Fold leaves into a root; an unpaired node at the end of a level is carried up unchanged
+pub fn root(env: &Env, leaves: Vec<BytesN<32>>) -> BytesN<32> {
+    if leaves.is_empty() {
+        return BytesN::from_array(env, &[0u8; 32]);
+    }
+    
+    let mut level = leaves;
+    while level.len() > 1 {
+        let mut next = Vec::new(env);
+        let mut i = 0;
+        while i < level.len() {
+            if i + 1 < level.len() {
+                next.push_back(node_hash(env, &level.get_unchecked(i), &level.get_unchecked(i + 1)));
+            } else {
+                next.push_back(level.get_unchecked(i));
+            }
+            i += 2;
+        }
+        level = next;
+    }
+    level.get_unchecked(0)
+}
+
+// Fold one more leaf into a frontier, merging equal-height subtrees as they complete
+pub fn push_leaf(env: &Env, frontier: &mut Vec<(u32, BytesN<32>)>, leaf: BytesN<32>) {
+    let mut node = (0u32, leaf);
+    while let Some((height, left)) = frontier.last() {
+        if height != node.0 {
+            break;
+        }
+        frontier.pop_back();
+        node = (height + 1, node_hash(env, &left, &node.1));
+    }
+    frontier.push_back(node);
+}
+
+// Root of a finished frontier; joining the subtrees right to left gives the same root as
+// `root` over the same leaves, since carried-up nodes are exactly the incomplete subtrees
+pub fn frontier_root(env: &Env, frontier: &Vec<(u32, BytesN<32>)>) -> BytesN<32> {
+    let Some((_, mut hash)) = frontier.last() else {
+        return BytesN::from_array(env, &[0u8; 32]);
+    };
+    for i in (0..frontier.len() - 1).rev() {
+        hash = node_hash(env, &frontier.get_unchecked(i).1, &hash);
+    }
+    hash
+}
+
+// Check a leaf at `index` of a tree with `leaf_count` leaves against `root`, given the
+// sibling hashes from the bottom up (levels where the node was carried up have no sibling)
+pub fn verify(env: &Env, root: &BytesN<32>, leaf: &BytesN<32>, index: u64, leaf_count: u64, proof: &Vec<BytesN<32>>) -> bool {
+    if index >= leaf_count {
+        return false;
+    }
+    
+    let mut hash = leaf.clone();
+    let mut index = index;
+    let mut width = leaf_count;
+    let mut used = 0;
+    while width > 1 {
+        let sibling = index ^ 1;
+        if sibling < width {
+            let Some(other) = proof.get(used) else {
+                return false;
+            };
+            hash = if index & 1 == 0 {
+                node_hash(env, &hash, &other)
+            } else {
+                node_hash(env, &other, &hash)
+            };
+            used += 1;
+        }
+        index /= 2;
+        width = width.div_ceil(2);
+    }
+    used == proof.len() && hash == *root
+}
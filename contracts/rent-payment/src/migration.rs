@@ -0,0 +1,373 @@
+use soroban_sdk::{contracttype, log, symbol_short, xdr::ToXdr, Address, BytesN, Env, IntoVal, Map, String, Symbol, TryFromVal, Val, Vec};
+
+use crate::storage_keys::{DataKey, DisputeBook, EarningsBook, ExternalIdBook, FinancialsBook, ReputationBook, TermsBook, TokenStatsBook, ClosingBook};
+use crate::stats::{self, AssetFinancials, Reputation, TokenStats};
+use crate::accounts::{self, SubAccount};
+use crate::disputes::{self, Dispute, DisputeStatus};
+use crate::leases::{CanonicalTerms, LeaseAmendment};
+use crate::payments::{self, ClosingStatement};
+use crate::{admin, assets, escrow, events, leases, raise, RentError, RentErrorExt, Asset, AssetStats, LeaseStatus};
+
+// One page of records moved from a retiring deployment to its replacement. Records are
+// addressed by cursor as in the state root: positions 0..asset_count are assets
+// 1..=asset_count, the leases follow in ID order, then the disputes in ID order. Each asset
+// and lease brings the per-record books kept for it, and every batch carries the per-token
+// totals and sub-account balances. Active leases are out of scope: every lease must have
+// ended and released its deposit before it is exported, so the books that only exist while
+// a lease runs (auto-pay, partial credit, prepaid rent, guarantors, notices, cure periods,
+// escrow) are never carried. Rent held for an address that no longer owns an asset is not
+// carried either and must be withdrawn first. Tokens still held in custody must be
+// transferred to the new contract separately to back the imported balances.
+#[contracttype]
+#[derive(Clone)]
+pub struct MigrationBatch {
+    pub cursor: u64,              // Position of the first record in the batch
+    pub next_cursor: u64,         // Position of the batch after this one (equal to the total once done)
+    pub asset_count: u64,         // Assets in the exporting contract
+    pub lease_count: u64,         // Leases in the exporting contract
+    pub dispute_count: u64,       // Disputes in the exporting contract
+    pub stats: AssetStats,        // Global lease counters of the exporting contract
+    pub tokens: Vec<TokenRecord>, // Totals and balances of every token the exporting contract has held
+    pub assets: Vec<Asset>,       // Asset records in the batch, in ID order
+    pub asset_books: Vec<AssetBooks>, // Books of each asset in `assets`, in the same order
+    pub leases: Vec<LeaseStatus>, // Lease records in the batch, in ID order
+    pub lease_books: Vec<LeaseBooks>, // Books of each lease in `leases`, in the same order
+    pub disputes: Vec<Dispute>,   // Dispute records in the batch, in ID order
+    pub previous_hash: BytesN<32>, // Hash of the batch exported before this one (zero for the first)
+    pub hash: BytesN<32>,         // SHA-256 over the XDR of every other field
+}
+
+// What the exporting contract has recorded for one payment token
+#[contracttype]
+#[derive(Clone)]
+pub struct TokenRecord {
+    pub token: Address,
+    pub stats: TokenStats,                 // Money that has moved through the contract in it
+    pub balances: Map<SubAccount, i128>,   // Base units held in each sub-account
+}
+
+// Per-record books kept for one asset
+#[contracttype]
+#[derive(Clone)]
+pub struct AssetBooks {
+    pub financials: AssetFinancials,
+    pub terms: Map<Symbol, String>,        // Custom terms offered on new leases
+    pub external_id: Vec<BytesN<32>>,      // External ID, if any (optional fields travel as 0 or 1 entries)
+    pub owner_reputation: Reputation,
+    pub owner_earnings: Map<Address, i128>, // Rent held for the owner, by token (non-zero only)
+}
+
+// Per-record books kept for one ended lease
+#[contracttype]
+#[derive(Clone)]
+pub struct LeaseBooks {
+    pub terms: Map<Symbol, String>,        // Custom terms frozen at lease creation
+    pub canonical: Vec<(CanonicalTerms, BytesN<32>)>, // Terms binding the lease and their hash, if bound
+    pub amendments: Vec<LeaseAmendment>,
+    pub external_id: Vec<BytesN<32>>,      // External ID, if any
+    pub closing: Vec<(ClosingStatement, BytesN<32>)>, // Closing statement and its hash, if closed
+    pub lessee_reputation: Reputation,
+}
+
+// How far an export or import has got: the cursor of the next batch, the record counts
+// pinned by the first batch, and the hash of the latest batch
+#[contracttype]
+#[derive(Clone)]
+pub struct MigrationProgress {
+    pub cursor: u64,
+    pub asset_count: u64,
+    pub lease_count: u64,
+    pub dispute_count: u64,
+    pub hash: BytesN<32>,
+}
+
+// Export up to `limit` (capped at MAX_PAGE) records starting at `cursor`, hashed and chained
+// to the batch before it so the importing contract can tell the batches arrived as exported.
+// Only allowed while this contract is paused; an export starts at cursor 0 and each batch
+// must continue from the previous one, with no records added in between.
+pub fn export_batch(env: Env, admin: Address, cursor: u64, limit: u32) -> Result<MigrationBatch, RentError> {
+    admin::require_admin(&env, &admin)?;
+    
+    if !admin::is_paused(env.clone()) {
+        log!(&env, "Pause the contract before exporting records");
+        return Err(raise(&env, RentErrorExt::MigrationNotPaused));
+    }
+    let asset_count = assets::get_asset_count(env.clone());
+    let lease_count = leases::get_lease_count(env.clone());
+    let dispute_count = disputes::get_dispute_count(env.clone());
+    let previous_hash = match cursor {
+        0 => BytesN::from_array(&env, &[0; 32]),
+        _ => {
+            let progress: Option<MigrationProgress> = env.storage().instance().get(&DataKey::ExportCursor);
+            match progress {
+                Some(progress) if progress.cursor == cursor
+                    && progress.asset_count == asset_count
+                    && progress.lease_count == lease_count
+                    && progress.dispute_count == dispute_count => progress.hash,
+                _ => {
+                    log!(&env, "Batch does not continue the export at cursor {}", cursor);
+                    return Err(RentError::InvalidWindow);
+                }
+            }
+        }
+    };
+    let record_count = asset_count + lease_count + dispute_count;
+    let next_cursor = record_count.min(cursor.saturating_add(limit.min(assets::MAX_PAGE) as u64));
+    
+    let mut batch = MigrationBatch {
+        cursor: cursor,
+        next_cursor: next_cursor.max(cursor),
+        asset_count: asset_count,
+        lease_count: lease_count,
+        dispute_count: dispute_count,
+        stats: stats::view_asset_stats(env.clone()),
+        tokens: Vec::new(&env),
+        assets: Vec::new(&env),
+        asset_books: Vec::new(&env),
+        leases: Vec::new(&env),
+        lease_books: Vec::new(&env),
+        disputes: Vec::new(&env),
+        previous_hash: previous_hash,
+        hash: BytesN::from_array(&env, &[0; 32]),
+    };
+    let held_tokens = accounts::view_held_tokens(env.clone());
+    for token in held_tokens.iter() {
+        batch.tokens.push_back(TokenRecord {
+            token: token.clone(),
+            stats: stats::view_token_stats(env.clone(), token.clone()),
+            balances: accounts::view_sub_accounts(env.clone(), token),
+        });
+    }
+    for position in cursor..next_cursor {
+        if position < asset_count {
+            let asset = assets::load_asset(&env, position + 1)?;
+            batch.asset_books.push_back(export_asset_books(&env, &asset, &held_tokens));
+            batch.assets.push_back(asset);
+        } else if position < asset_count + lease_count {
+            let lease = leases::load_lease(&env, position - asset_count + 1)?;
+            if lease.is_active
+                || escrow::view_escrow_balance(env.clone(), lease.lease_id) > 0
+                || escrow::view_deposit_lock(env.clone(), lease.lease_id).is_some() {
+                log!(&env, "Lease {} has not ended or still holds a deposit", lease.lease_id);
+                return Err(raise(&env, RentErrorExt::LeaseNotEnded));
+            }
+            batch.lease_books.push_back(export_lease_books(&env, &lease));
+            batch.leases.push_back(lease);
+        } else {
+            let dispute_id = position - asset_count - lease_count + 1;
+            let dispute = disputes::view_dispute(env.clone(), dispute_id).ok_or(RentError::InvalidWindow)?;
+            batch.disputes.push_back(dispute);
+        }
+    }
+    batch.hash = batch_hash(&env, &batch);
+    env.storage().instance().set(&DataKey::ExportCursor, &MigrationProgress {
+        cursor: batch.next_cursor,
+        asset_count: asset_count,
+        lease_count: lease_count,
+        dispute_count: dispute_count,
+        hash: batch.hash.clone(),
+    });
+    
+    return Ok(batch);
+}
+
+// Import the next batch exported from the retiring contract. Only allowed while this
+// contract is paused, and the first batch only while it holds no records of its own;
+// batches must arrive in cursor order, each chained to the one before it and carrying the
+// record counts of the first. Returns the cursor of the batch expected next.
+pub fn import_batch(env: Env, admin: Address, batch: MigrationBatch) -> Result<u64, RentError> {
+    admin::require_admin(&env, &admin)?;
+    
+    if !admin::is_paused(env.clone()) {
+        log!(&env, "Pause the contract before importing records");
+        return Err(raise(&env, RentErrorExt::MigrationNotPaused));
+    }
+    let progress: Option<MigrationProgress> = env.storage().instance().get(&DataKey::ImportCursor);
+    if progress.is_none() && (assets::get_asset_count(env.clone()) > 0 || leases::get_lease_count(env.clone()) > 0) {
+        log!(&env, "Contract already holds records of its own");
+        return Err(RentError::AlreadyInitialized);
+    }
+    let progress = progress.unwrap_or(MigrationProgress {
+        cursor: 0,
+        asset_count: batch.asset_count,
+        lease_count: batch.lease_count,
+        dispute_count: batch.dispute_count,
+        hash: BytesN::from_array(&env, &[0; 32]),
+    });
+    if batch.cursor != progress.cursor
+        || batch.next_cursor != batch.cursor + (batch.assets.len() + batch.leases.len() + batch.disputes.len()) as u64
+        || batch.asset_books.len() != batch.assets.len()
+        || batch.lease_books.len() != batch.leases.len()
+        || batch.asset_count != progress.asset_count
+        || batch.lease_count != progress.lease_count
+        || batch.dispute_count != progress.dispute_count
+        || batch.previous_hash != progress.hash
+        || batch_hash(&env, &batch) != batch.hash {
+        log!(&env, "Batch does not continue the import at cursor {} as exported", progress.cursor);
+        return Err(RentError::InvalidWindow);
+    }
+    
+    let mut position = batch.cursor;
+    for (asset, books) in batch.assets.iter().zip(batch.asset_books.iter()) {
+        if position >= batch.asset_count || asset.asset_id != position + 1 {
+            log!(&env, "Asset record out of place in batch");
+            return Err(RentError::InvalidWindow);
+        }
+        assets::save_asset(&env, &asset);
+        assets::index_listing(&env, &asset);
+        import_asset_books(&env, &asset, books);
+        env.storage().instance().set(&DataKey::AssetCount, &asset.asset_id);
+        position += 1;
+    }
+    let dispute_start = batch.asset_count + batch.lease_count;
+    for (lease, books) in batch.leases.iter().zip(batch.lease_books.iter()) {
+        if position < batch.asset_count || position >= dispute_start
+            || lease.lease_id != position - batch.asset_count + 1 {
+            log!(&env, "Lease record out of place in batch");
+            return Err(RentError::InvalidWindow);
+        }
+        leases::save_lease(&env, &lease);
+        leases::index_lessee_lease(&env, &lease.lessee, lease.lease_id);
+        if lease.is_active {
+            leases::count_tenancies(&env, &lease, &assets::load_asset(&env, lease.asset_id)?.owner, true);
+        }
+        stats::count_storage(&env, |m| m.archived_leases += 1);
+        import_lease_books(&env, &lease, books)?;
+        env.storage().instance().set(&DataKey::LeaseCount, &lease.lease_id);
+        position += 1;
+    }
+    for dispute in batch.disputes.iter() {
+        if position < dispute_start || dispute.dispute_id != position - dispute_start + 1 {
+            log!(&env, "Dispute record out of place in batch");
+            return Err(RentError::InvalidWindow);
+        }
+        disputes::save_dispute(&env, &dispute);
+        if dispute.status == DisputeStatus::Open {
+            restore(&env, &DisputeBook::OpenDispute(dispute.lease_id), &dispute.dispute_id);
+        }
+        env.storage().instance().set(&DataKey::DisputeCount, &dispute.dispute_id);
+        position += 1;
+    }
+    for record in batch.tokens.iter() {
+        restore(&env, &TokenStatsBook::TokenStats(record.token.clone()), &record.stats);
+        accounts::restore(&env, &record.token, &record.balances);
+    }
+    env.storage().instance().set(&DataKey::Stats, &batch.stats);
+    env.storage().instance().set(&DataKey::ImportCursor, &MigrationProgress {
+        cursor: batch.next_cursor,
+        asset_count: batch.asset_count,
+        lease_count: batch.lease_count,
+        dispute_count: batch.dispute_count,
+        hash: batch.hash.clone(),
+    });
+    admin::bump_instance(&env);
+    
+    events::publish(&env, (symbol_short!("migrate"), symbol_short!("import")), (batch.cursor, batch.next_cursor));
+    log!(&env, "Imported records {} to {}", batch.cursor, batch.next_cursor);
+    
+    return Ok(batch.next_cursor);
+}
+
+// View the cursor of the next batch import_batch will accept
+pub fn view_import_cursor(env: Env) -> u64 {
+    let progress: Option<MigrationProgress> = env.storage().instance().get(&DataKey::ImportCursor);
+    progress.map_or(0, |progress| progress.cursor)
+}
+
+// Collect the books kept for an asset, with the rent held for its owner in each of `tokens`
+fn export_asset_books(env: &Env, asset: &Asset, tokens: &Vec<Address>) -> AssetBooks {
+    let mut owner_earnings = Map::new(env);
+    for token in tokens.iter() {
+        let held = payments::view_withdrawable(env.clone(), asset.owner.clone(), token.clone());
+        if held > 0 {
+            owner_earnings.set(token, held);
+        }
+    }
+    
+    AssetBooks {
+        financials: stats::view_asset_financials(env.clone(), asset.asset_id),
+        terms: assets::view_asset_terms(env.clone(), asset.asset_id),
+        external_id: optional(env, assets::view_asset_external_id(env.clone(), asset.asset_id)),
+        owner_reputation: stats::view_reputation(env.clone(), asset.owner.clone()),
+        owner_earnings: owner_earnings,
+    }
+}
+
+// Collect the books kept for an ended lease
+fn export_lease_books(env: &Env, lease: &LeaseStatus) -> LeaseBooks {
+    LeaseBooks {
+        terms: leases::view_lease_terms(env.clone(), lease.lease_id),
+        canonical: optional(env, leases::get_canonical_terms(env.clone(), lease.lease_id)),
+        amendments: leases::view_amendments(env.clone(), lease.lease_id),
+        external_id: optional(env, leases::view_lease_external_id(env.clone(), lease.lease_id)),
+        closing: optional(env, payments::view_closing_statement(env.clone(), lease.lease_id)),
+        lessee_reputation: stats::view_reputation(env.clone(), lease.lessee.clone()),
+    }
+}
+
+// Write back the books exported with an asset. The owner's reputation and earnings come with
+// each of their assets and are set, not added, so repeating them is harmless.
+fn import_asset_books(env: &Env, asset: &Asset, books: AssetBooks) {
+    restore(env, &FinancialsBook::Financials(asset.asset_id), &books.financials);
+    if !books.terms.is_empty() {
+        restore(env, &TermsBook::AssetTerms(asset.asset_id), &books.terms);
+    }
+    if let Some(id) = books.external_id.first() {
+        restore(env, &ExternalIdBook::OfAsset(asset.asset_id), &id);
+        restore(env, &ExternalIdBook::AssetLookup(asset.owner.clone(), id), &asset.asset_id);
+        stats::count_storage(env, |m| m.external_ids += 1);
+    }
+    restore(env, &ReputationBook::Reputation(asset.owner.clone()), &books.owner_reputation);
+    for (token, held) in books.owner_earnings.iter() {
+        restore(env, &EarningsBook::Withdrawable(asset.owner.clone(), token), &held);
+    }
+}
+
+// Write back the books exported with a lease; its asset has already been imported
+fn import_lease_books(env: &Env, lease: &LeaseStatus, books: LeaseBooks) -> Result<(), RentError> {
+    if !books.terms.is_empty() {
+        restore(env, &TermsBook::LeaseTerms(lease.lease_id), &books.terms);
+    }
+    if let Some(canonical) = books.canonical.first() {
+        restore(env, &TermsBook::Canonical(lease.lease_id), &canonical);
+    }
+    if !books.amendments.is_empty() {
+        restore(env, &TermsBook::Amendments(lease.lease_id), &books.amendments);
+    }
+    if let Some(id) = books.external_id.first() {
+        let owner = assets::load_asset(env, lease.asset_id)?.owner;
+        restore(env, &ExternalIdBook::OfLease(lease.lease_id), &id);
+        restore(env, &ExternalIdBook::LeaseLookup(owner, id), &lease.lease_id);
+        stats::count_storage(env, |m| m.external_ids += 1);
+    }
+    if let Some(closing) = books.closing.first() {
+        restore(env, &ClosingBook::Closing(lease.lease_id), &closing);
+    }
+    restore(env, &ReputationBook::Reputation(lease.lessee.clone()), &books.lessee_reputation);
+    
+    Ok(())
+}
+
+// An optional record as a Vec of zero or one entries, since a contract type field cannot
+// hold an Option of a tuple or fixed-size bytes
+fn optional<T: IntoVal<Env, Val> + TryFromVal<Env, Val>>(env: &Env, value: Option<T>) -> Vec<T> {
+    let mut entries = Vec::new(env);
+    if let Some(value) = value {
+        entries.push_back(value);
+    }
+    
+    return entries;
+}
+
+// Write one imported persistent entry and keep it above the minimum TTL
+fn restore<K: IntoVal<Env, Val>, V: IntoVal<Env, Val>>(env: &Env, key: &K, value: &V) {
+    env.storage().persistent().set(key, value);
+    admin::bump_entry(env, key);
+}
+
+// SHA-256 over the XDR of a batch with its hash field zeroed
+fn batch_hash(env: &Env, batch: &MigrationBatch) -> BytesN<32> {
+    let mut unhashed = batch.clone();
+    unhashed.hash = BytesN::from_array(env, &[0; 32]);
+    env.crypto().sha256(&unhashed.to_xdr(env)).to_bytes()
+}
@@ -0,0 +1,196 @@
+use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env, String, Symbol, Vec};
+
+use crate::interface::{LandlordRegistry, RentPaymentInterface};
+use crate::penalty::PenaltyPolicy;
+use crate::policy::PaymentPolicy;
+use crate::storage_keys::{AssetBook, DataKey, LeaseStatusBook};
+use crate::{xlm, Asset, DepositMode, AssetStats, LeaseStatus, RentError};
+
+// Lightweight stand-in for the rent payment contract. It stores records as given and skips
+// validation, timing and payment rules so dependent contracts can be tested in isolation.
+#[contract]
+pub struct MockRentPayment;
+
+impl MockRentPayment {
+    fn stored_asset(env: &Env, asset_id: u64) -> Result<Asset, RentError> {
+        Self::view_asset(env.clone(), asset_id)?.ok_or(RentError::AssetNotFound)
+    }
+    
+    fn stored_lease(env: &Env, lease_id: u64) -> Result<LeaseStatus, RentError> {
+        Self::view_lease(env.clone(), lease_id)?.ok_or(RentError::LeaseNotActive)
+    }
+}
+
+#[contractimpl]
+impl RentPaymentInterface for MockRentPayment {
+    fn register_asset(
+        env: Env,
+        owner: Address,
+        title: String,
+        description: String,
+        price_per_period: i128,
+        period_duration: u64,
+        min_lease_duration: u64,
+        max_lease_duration: u64,
+        deposit_required: i128
+    ) -> Result<u64, RentError> {
+        let asset_id: u64 = env.storage().instance().get(&DataKey::AssetCount).unwrap_or(0) + 1;
+        let asset = Asset {
+            asset_id,
+            owner,
+            title,
+            description,
+            price_per_period,
+            period_duration,
+            min_lease_duration,
+            max_lease_duration,
+            deposit_required,
+            is_available: true,
+            current_lease_id: 0,
+            deposit_mode: DepositMode::Standard,
+            payment_policy: PaymentPolicy::FixedPeriod,
+            penalty_policy: PenaltyPolicy::None,
+            holdover_bps: 0,
+            min_lead_time: 0,
+            max_advance: 0,
+            listing_fee_held: 0,
+            listing_expires_at: 0,
+            grace_period: 0,
+            payment_token: env.current_contract_address(), // Never transferred by the mock
+            currency_symbol: String::from_str(&env, "XLM"),
+            currency_decimals: xlm::XLM_DECIMALS,
+            escalation_bps: 0,
+            escalation_interval: 0,
+            notice_period: 0,
+            early_termination_fee: 0,
+            category: symbol_short!("general"),
+            tags: Vec::new(&env),
+        };
+        env.storage().instance().set(&DataKey::AssetCount, &asset_id);
+        env.storage().instance().set(&AssetBook::Asset(asset_id), &asset);
+        Ok(asset_id)
+    }
+    
+    fn create_lease(env: Env, asset_id: u64, lessee: Address, lease_duration: u64) -> Result<u64, RentError> {
+        let now = env.ledger().timestamp();
+        Self::create_scheduled_lease(env, asset_id, lessee, now, lease_duration)
+    }
+    
+    fn create_scheduled_lease(env: Env, asset_id: u64, lessee: Address, start_time: u64, lease_duration: u64) -> Result<u64, RentError> {
+        let mut asset = Self::stored_asset(&env, asset_id)?;
+        let lease_id: u64 = env.storage().instance().get(&DataKey::LeaseCount).unwrap_or(0) + 1;
+        let now = env.ledger().timestamp();
+        let lease = LeaseStatus {
+            lease_id,
+            asset_id,
+            lessee,
+            start_time,
+            end_time: start_time + lease_duration,
+            period_payment: asset.price_per_period,
+            payment_frequency: asset.period_duration,
+            last_payment_time: now,
+            next_payment_time: start_time + asset.period_duration,
+            is_active: true,
+            is_overdue: false,
+            total_paid: asset.deposit_required + asset.price_per_period,
+            security_deposit: asset.deposit_required,
+            payment_policy: asset.payment_policy,
+            payments_made: 0,
+            metered_units: 0,
+            shortfall: 0,
+            in_holdover: false,
+            holdover_end: 0,
+            term_length: lease_duration,
+            auto_renew: false,
+            non_renewal_filed: false,
+            trial_end: 0,
+            late_fees_paid: 0,
+            outstanding_balance: 0,
+            escalation_bps: 0,
+            escalation_interval: 0,
+            co_tenants: Vec::new(&env),
+        };
+        asset.is_available = false;
+        asset.current_lease_id = lease_id;
+        env.storage().instance().set(&DataKey::LeaseCount, &lease_id);
+        env.storage().instance().set(&LeaseStatusBook::LeaseStatus(lease_id), &lease);
+        env.storage().instance().set(&AssetBook::Asset(asset_id), &asset);
+        Ok(lease_id)
+    }
+    
+    fn process_payment(env: Env, lease_id: u64, _caller: Address) -> Result<(), RentError> {
+        let mut lease = Self::stored_lease(&env, lease_id)?;
+        lease.total_paid += lease.period_payment;
+        lease.is_overdue = false;
+        env.storage().instance().set(&LeaseStatusBook::LeaseStatus(lease_id), &lease);
+        Ok(())
+    }
+    
+    fn process_sponsored_payment(env: Env, lease_id: u64, lessee: Address, _max_amount: i128) -> Result<i128, RentError> {
+        Self::process_payment(env.clone(), lease_id, lessee)?;
+        Ok(Self::stored_lease(&env, lease_id)?.period_payment)
+    }
+    
+    fn end_lease(env: Env, lease_id: u64, _caller: Address) -> Result<(), RentError> {
+        let mut lease = Self::stored_lease(&env, lease_id)?;
+        lease.is_active = false;
+        env.storage().instance().set(&LeaseStatusBook::LeaseStatus(lease_id), &lease);
+        Ok(())
+    }
+    
+    fn mark_lease_overdue(env: Env, lease_id: u64, _caller: Address) -> Result<(), RentError> {
+        let mut lease = Self::stored_lease(&env, lease_id)?;
+        lease.is_overdue = true;
+        env.storage().instance().set(&LeaseStatusBook::LeaseStatus(lease_id), &lease);
+        Ok(())
+    }
+    
+    fn view_asset_stats(env: Env) -> Result<AssetStats, RentError> {
+        Ok(env.storage().instance().get(&DataKey::Stats).unwrap_or(AssetStats {
+            active_leases: 0,
+            completed_leases: 0,
+            overdue_leases: 0,
+            total_leases: 0,
+            total_xlm_processed: 0,
+            total_late_fees: 0,
+        }))
+    }
+    
+    fn view_asset(env: Env, asset_id: u64) -> Result<Option<Asset>, RentError> {
+        Ok(env.storage().instance().get(&AssetBook::Asset(asset_id)))
+    }
+    
+    fn view_lease(env: Env, lease_id: u64) -> Result<Option<LeaseStatus>, RentError> {
+        Ok(env.storage().instance().get(&LeaseStatusBook::LeaseStatus(lease_id)))
+    }
+    
+    fn get_owner_assets(env: Env, _owner: Address) -> Result<Vec<u64>, RentError> {
+        Ok(Vec::new(&env))
+    }
+    
+    fn get_lessee_leases(env: Env, _lessee: Address) -> Result<Vec<u64>, RentError> {
+        Ok(Vec::new(&env))
+    }
+    
+    fn list_error_codes(env: Env) -> Result<Vec<(u32, Symbol)>, RentError> {
+        Ok(Vec::new(&env))
+    }
+}
+
+// Stand-in landlord registry that verifies whichever addresses it is told to
+#[contract]
+pub struct MockLandlordRegistry;
+
+#[contractimpl]
+impl MockLandlordRegistry {
+    pub fn set_verified(env: Env, landlord: Address, verified: bool) {
+        env.storage().instance().set(&landlord, &verified);
+    }
+}
+
+#[contractimpl]
+impl LandlordRegistry for MockLandlordRegistry {
+    fn is_verified(env: Env, landlord: Address) -> bool {
+        env.storage().instance().get(&landlord).unwrap_or(false)
+    }
+}
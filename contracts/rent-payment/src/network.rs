@@ -0,0 +1,55 @@
+use soroban_sdk::{contracttype, Bytes, BytesN, Env};
+
+// Stellar network a deployment targets, chosen at initialize time
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Network {
+    Testnet,
+    Futurenet,
+    Mainnet,
+}
+
+// Addresses and fee defaults baked in for one network
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct NetworkProfile {
+    pub passphrase: &'static str,   // Network passphrase; its SHA-256 is the ledger's network ID
+    pub native_token: &'static str, // Native XLM Stellar Asset Contract on this network
+    pub listing_fee: i128,           // Default refundable listing fee in stroops
+    pub boost_fee: i128,             // Default featured-listing fee in stroops per day
+}
+
+// Test networks charge nothing, so integrations can run without funding fee payers
+pub const TESTNET: NetworkProfile = NetworkProfile {
+    passphrase: "Test SDF Network ; September 2015",
+    native_token: "CDLZFC3SYJYDZT7K67VZ75HPJVIEUVNIXF47ZG2FB2RMQQVU2HHGCYSC",
+    listing_fee: 0,
+    boost_fee: 0,
+};
+
+pub const FUTURENET: NetworkProfile = NetworkProfile {
+    passphrase: "Test SDF Future Network ; October 2022",
+    native_token: "CB64D3G7SM2RTH6JSGG34DDTFTQ5CFDKVDZJZSODMCX4NJ2HV2KN7OHT",
+    listing_fee: 0,
+    boost_fee: 0,
+};
+
+pub const MAINNET: NetworkProfile = NetworkProfile {
+    passphrase: "Public Global Stellar Network ; September 2015",
+    native_token: "CAS3J7GYLGXMF6TDJBBYYSE3HQ6BBSMLNUQ34T6TZMYMW2EVH34XOWMA",
+    listing_fee: 10,
+    boost_fee: 1,
+};
+
+// Profile of a network
+pub fn profile(network: Network) -> NetworkProfile {
+    match network {
+        Network::Testnet => TESTNET,
+        Network::Futurenet => FUTURENET,
+        Network::Mainnet => MAINNET,
+    }
+}
+
+// Network ID a ledger on this profile's network reports
+pub fn network_id(env: &Env, profile: &NetworkProfile) -> BytesN<32> {
+    env.crypto().sha256(&Bytes::from_slice(env, profile.passphrase.as_bytes())).to_bytes()
+}
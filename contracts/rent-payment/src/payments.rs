@@ -0,0 +1,1514 @@
+use soroban_sdk::{contracttype, log, symbol_short, xdr::ToXdr, Address, BytesN, Env, Symbol, Vec};
+
+use crate::policy::PaymentPolicy;
+use crate::storage_keys::{DataKey, EscalationBook, BillingBook, WaiverBook, ClosingBook, BlockBook, ExtensionBook, PartialBook, PrepaidBook, CureBook, EarningsBook, AutoPayBook, TrialBook};
+use crate::accounts::{self, SubAccount};
+use crate::{calendar, events, penalty, policy, rewards, xlm, admin, assets, escrow, leases, stats, RentError, RentErrorExt, Asset, LeaseStatus};
+
+// What a payment would charge, as returned by the dry-run view
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PaymentQuote {
+    pub rent: i128,                // Rent per the payment policy, in base units of the payment token
+    pub late_fee: i128,            // Late fee per the penalty policy
+    pub amount: i128,              // Total the payment would charge
+    pub next_payment_time: u64,    // Next due time once the payment is applied
+    pub transfers_ownership: bool, // Whether this payment completes a rent-to-own schedule
+}
+
+// Share of a consolidated payment applied to one lease
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LeaseAllocation {
+    pub lease_id: u64,
+    pub rent: i128,     // Rent settled on this lease, in base units of the payment token
+    pub late_fee: i128, // Late fee settled on this lease
+}
+
+// One net payment covering every due lease a lessee holds with an owner
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConsolidatedSettlement {
+    pub settlement_id: u64,                // 0 for a bill that has not been paid yet
+    pub lessee: Address,
+    pub owner: Address,
+    pub paid_at: u64,                      // Settlement time (quote time for an unpaid bill)
+    pub token: Option<Address>,            // Payment token of the billed leases (None if nothing is due)
+    pub total: i128,                       // Net amount moved in the single transfer, in base units
+    pub allocations: Vec<LeaseAllocation>, // Per-lease breakdown of the total
+}
+
+// One settle_all batch, also published as a ("settle", "report") event
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SettlementReport {
+    pub owner: Address,
+    pub first_asset: u64,    // Position in the owner's asset list of the first asset examined (from 1)
+    pub last_asset: u64,     // Position of the last asset examined (first_asset - 1 if none)
+    pub next_cursor: u64,    // Cursor for the next batch; 0 once every owned asset has been examined
+    pub leases_settled: u32, // Owner's active leases brought up to date
+    pub newly_overdue: u32,  // Leases this batch flagged overdue
+    pub overdue: u32,        // Owner's leases overdue after this batch
+    pub amount_due: i128,    // Due now across those leases, late fees included, in base units
+}
+
+// Final financial summary of a closed lease, in base units of its payment token. Its
+// hash is stored and published at closure so an off-chain statement can be checked against it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClosingStatement {
+    pub lease_id: u64,
+    pub asset_id: u64,
+    pub lessee: Address,
+    pub owner: Address,
+    pub payment_token: Address,
+    pub closed_at: u64,      // When the lease was closed
+    pub total_paid: i128,    // Everything the lease recorded as paid, upfront collection included
+    pub payments_made: u64,  // Payments made after the initial one
+    pub late_fees: i128,     // Late fees paid
+    pub waived_fees: i128,   // Late fees the owner waived
+    pub deposit: i128,       // Security deposit required
+    pub deductions: i128,    // Deposit kept by the owner through claims or deductions
+    pub refunded: i128,      // Deposit returned to the lessee at closure
+    pub held: i128,          // Deposit still held for the ruling on an open dispute
+    pub shortfall: i128,     // Amount owed but never recovered
+}
+
+// A payment the token refused because the payer is frozen for it; kept on the lease until a
+// payment goes through
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PaymentBlock {
+    pub blocked_at: u64,     // When the payment was attempted
+    pub payer: Address,
+    pub token: Address,
+    pub amount: i128,        // Rent and late fee the payment would have moved, in base units
+}
+
+// A late fee the owner forgave, with the reason they recorded
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LateFeeWaiver {
+    pub amount: i128,      // Late fee waived, in base units of the payment token
+    pub reason: Symbol,    // Owner's reason code, e.g. "goodwill" or "bankfail"
+    pub waived_at: u64,    // When the waiver was granted
+    pub paid_due: u64,     // Due date of the payment the waiver applied to (0 until paid)
+}
+
+// Due-date extensions a lease may be granted within any 365 days
+pub const MAX_EXTENSIONS_PER_YEAR: u32 = 2;
+
+// A lessee's request to push back one due date, and whether the owner granted it
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DueDateExtension {
+    pub due_at: u64,              // next_payment_time of the payment being pushed back
+    pub extra_days: u32,          // Days added to that due date
+    pub reason_hash: BytesN<32>,  // Hash of the lessee's reason, kept off-chain
+    pub requested_at: u64,        // When the lessee asked
+    pub approved_at: u64,         // When the owner granted it (0 while pending)
+}
+
+// One future payment projected by view_payment_schedule
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScheduledPayment {
+    pub due_at: u64, // When the payment falls due
+    pub amount: i128, // Rent due then, after any escalation
+}
+
+// Most payments view_payment_schedule projects
+pub const MAX_SCHEDULE_PERIODS: u32 = 120;
+
+// Rent a lessee paid ahead, held by the contract and drawn down one period per due date
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PrepaidRent {
+    pub prepaid_periods: u32, // Periods still covered
+    pub period_payment: i128, // Base units held for each period
+}
+
+// Outcome of one item of process_payments_batch
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchPaymentResult {
+    pub lease_id: u64,
+    pub payer: Address,
+    pub amount: i128,    // Rent and late fee paid, in base units (0 if the item failed)
+    pub error_code: u32, // RentError or RentErrorExt code the item failed with, as listed by list_error_codes (0 if paid)
+}
+
+// Standing authorization for keepers to collect a lease's rent from the lessee's token allowance
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AutoPay {
+    pub periods_left: u32,   // Due payments that may still be collected
+    pub authorized_at: u64,  // When the lessee signed it
+}
+
+// Quote a whole-XLM amount in stroops, the unit used by the token contract
+pub fn quote_in_stroops(_env: Env, amount: u64) -> i128 {
+    xlm::to_stroops(amount)
+}
+
+// Process a payment for a lease, collecting the rent (and any late fee) from the caller and holding it for the owner.
+// On a shared lease the caller pays only their co-tenant share.
+pub fn process_payment(env: Env, lease_id: u64, caller: Address) -> Result<(), RentError> {
+    let lease = leases::load_lease(&env, lease_id)?;
+    if lease.co_tenants.is_empty() {
+        settle_payment(&env, lease_id, &caller, i128::MAX)?;
+    } else {
+        pay_share(&env, &lease, &caller, i128::MAX)?;
+    }
+    
+    Ok(())
+}
+
+// A co-tenant pays their share of the period's rent and late fee, at most `max_amount`.
+// Returns what they were charged.
+fn pay_share(env: &Env, lease: &LeaseStatus, tenant: &Address, max_amount: i128) -> Result<i128, RentError> {
+    admin::require_not_paused(env)?;
+    tenant.require_auth();
+    
+    let (asset, _, _, _) = plan_payment(env, lease.clone(), tenant, i128::MAX)?;
+    let (amount, _) = share_due(env, lease, tenant)?;
+    if amount > max_amount {
+        log!(env, "Share due exceeds the authorized maximum");
+        return Err(RentError::AmountExceedsMax);
+    }
+    if payment_blocked(env, lease.lease_id, tenant, &asset, amount) {
+        return Ok(0);
+    }
+    
+    return charge_share(env, lease, &asset, tenant);
+}
+
+// What a co-tenant owes now and whether it settles the period. Shares go in as partial
+// payments; the last co-tenant to pay settles the period with whatever is left of it.
+fn share_due(env: &Env, lease: &LeaseStatus, tenant: &Address) -> Result<(i128, bool), RentError> {
+    let share_bps = match lease.co_tenants.iter().find(|co_tenant| co_tenant.tenant == *tenant) {
+        Some(co_tenant) => co_tenant.share_bps,
+        None => {
+            log!(env, "Only a co-tenant can pay a share of the rent");
+            return Err(RentError::NotLessee);
+        }
+    };
+    let paid = view_shares_paid(env, lease.lease_id);
+    if paid.contains(tenant) {
+        log!(env, "Co-tenant has already paid their share of this period");
+        return Err(RentError::PaymentNotDue);
+    }
+    
+    let (_, _, rent, late_fee) = plan_payment(env, lease.clone(), tenant, i128::MAX)?;
+    let owed = rent + late_fee;
+    let share = (owed + view_partial_credit(env, lease.lease_id)) * share_bps as i128 / 10_000;
+    if share >= owed || paid.len() + 1 == lease.co_tenants.len() {
+        return Ok((owed, true));
+    }
+    
+    return Ok((share, false));
+}
+
+// Charge a co-tenant who has signed for it their share, and return the amount
+fn charge_share(env: &Env, lease: &LeaseStatus, asset: &Asset, tenant: &Address) -> Result<i128, RentError> {
+    let (amount, settles) = share_due(env, lease, tenant)?;
+    check_payable(env, asset, tenant, amount)?;
+    if settles {
+        let amount = record_payment(env, lease.lease_id, tenant, i128::MAX)?;
+        pay_rent(env, asset, tenant, amount)?;
+        return Ok(amount);
+    }
+    
+    let (_, _, rent, late_fee) = plan_payment(env, lease.clone(), tenant, i128::MAX)?;
+    book_partial(env, lease.lease_id, asset, tenant, amount, rent + late_fee - amount)?;
+    let mut paid = view_shares_paid(env, lease.lease_id);
+    paid.push_back(tenant.clone());
+    let key = PartialBook::SharesPaid(lease.lease_id);
+    env.storage().persistent().set(&key, &paid);
+    admin::bump_entry(env, &key);
+    
+    return Ok(amount);
+}
+
+// Pay rent on several leases in one call, e.g. for a property manager collecting from many
+// tenants. Each (lease_id, payer) item is checked and charged in turn like process_payment,
+// so a co-tenant pays only their share, and every payer signs once; an item that would fail
+// is skipped and reported with its error code rather than failing the batch. At most
+// MAX_PAGE items.
+pub fn process_payments_batch(env: Env, items: Vec<(u64, Address)>) -> Result<Vec<BatchPaymentResult>, RentError> {
+    admin::require_not_paused(&env)?;
+    if items.is_empty() || items.len() > assets::MAX_PAGE {
+        log!(&env, "Batch must hold between 1 and {} payments", assets::MAX_PAGE);
+        return Err(RentError::InvalidAmount);
+    }
+    
+    let mut signed: Vec<Address> = Vec::new(&env);
+    let mut results = Vec::new(&env);
+    for (lease_id, payer) in items.iter() {
+        if !signed.contains(&payer) {
+            payer.require_auth();
+            signed.push_back(payer.clone());
+        }
+        
+        let mut result = BatchPaymentResult {
+            lease_id: lease_id,
+            payer: payer.clone(),
+            amount: 0,
+            error_code: 0,
+        };
+        match batch_payment_error(&env, lease_id, &payer)? {
+            Some(error) => result.error_code = error as u32,
+            None => {
+                let lease = leases::load_lease(&env, lease_id)?;
+                let (asset, _, rent, late_fee) = plan_payment(&env, lease.clone(), &payer, i128::MAX)?;
+                if payment_blocked(&env, lease_id, &payer, &asset, rent + late_fee) {
+                    result.error_code = RentErrorExt::PayerFrozen as u32;
+                } else if !lease.co_tenants.is_empty() {
+                    result.amount = charge_share(&env, &lease, &asset, &payer)?;
+                } else {
+                    result.amount = record_payment(&env, lease_id, &payer, i128::MAX)?;
+                    pay_rent(&env, &asset, &payer, result.amount)?;
+                }
+            }
+        }
+        results.push_back(result);
+    }
+    
+    return Ok(results);
+}
+
+// The error process_payment would raise for `payer` paying on a lease now, if any, found
+// without failing so a batch can skip the item
+fn batch_payment_error(env: &Env, lease_id: u64, payer: &Address) -> Result<Option<RentError>, RentError> {
+    let mut lease = match leases::view_lease(env.clone(), lease_id) {
+        Some(lease) if lease.is_active => lease,
+        _ => return Ok(Some(RentError::LeaseNotActive)),
+    };
+    if !leases::is_tenant(&lease, payer) {
+        return Ok(Some(RentError::NotLessee));
+    }
+    if view_shares_paid(env, lease_id).contains(payer) {
+        return Ok(Some(RentError::PaymentNotDue));
+    }
+    
+    let now = env.ledger().timestamp();
+    let asset = assets::load_asset(env, lease.asset_id)?;
+    if !leases::roll_term(env, &asset, &mut lease, now)? {
+        return Ok(Some(RentError::LeaseExpired));
+    }
+    if !penalty::accepts_payment(&asset.penalty_policy, &lease) && !leases::in_cure_window(env, &lease, now) {
+        return Ok(Some(RentError::LatePaymentRefused));
+    }
+    
+    let (rent, late_fee) = quote_payment(env, &asset, &lease, now);
+    let due = if lease.co_tenants.is_empty() {
+        rent + late_fee
+    } else {
+        share_due(env, &lease, payer)?.0
+    };
+    if xlm::balance(env, &xlm::payment_token(&asset), payer) < due {
+        return Ok(Some(RentError::InsufficientBalance));
+    }
+    
+    return Ok(None);
+}
+
+// Lessee pays part of what the current period is due. Partial payments accumulate toward it
+// are held for the owner as they are made; the one that covers the rest settles the period like
+// process_payment and advances next_payment_time. Returns what is still outstanding.
+pub fn process_partial_payment(env: Env, lease_id: u64, payer: Address, amount: i128) -> Result<i128, RentError> {
+    if amount <= 0 {
+        log!(&env, "Partial payment must be positive");
+        return Err(RentError::InvalidAmount);
+    }
+    
+    let (asset, _, rent, late_fee) = plan_payment(&env, leases::load_lease(&env, lease_id)?, &payer, i128::MAX)?;
+    let owed = rent + late_fee;
+    if amount >= owed {
+        settle_payment(&env, lease_id, &payer, owed)?;
+        return Ok(0);
+    }
+    
+    admin::require_not_paused(&env)?;
+    payer.require_auth();
+    if payment_blocked(&env, lease_id, &payer, &asset, amount) {
+        return Ok(owed);
+    }
+    check_payable(&env, &asset, &payer, amount)?;
+    book_partial(&env, lease_id, &asset, &payer, amount, owed - amount)?;
+    
+    return Ok(owed - amount);
+}
+
+// Take a partial payment the payer has signed for, leaving `outstanding` of the period due.
+// Booked as rent when received; the payment that completes the period is charged net of it.
+fn book_partial(env: &Env, lease_id: u64, asset: &Asset, payer: &Address, amount: i128, outstanding: i128) -> Result<(), RentError> {
+    let mut lease = leases::load_lease(env, lease_id)?;
+    lease.total_paid += amount;
+    lease.outstanding_balance = outstanding;
+    leases::save_lease(env, &lease);
+    let key = PartialBook::PartialCredit(lease_id);
+    env.storage().persistent().set(&key, &(view_partial_credit(env, lease_id) + amount));
+    admin::bump_entry(env, &key);
+    
+    stats::update_financials(env, lease.asset_id, |f| f.rent_collected += amount);
+    let mut stats = stats::view_asset_stats(env.clone());
+    stats::record_token_flow(env, &mut stats, &asset.payment_token, amount, 0);
+    env.storage().instance().set(&DataKey::Stats, &stats);
+    admin::bump_instance(env);
+    
+    events::publish(env, (symbol_short!("pay"), symbol_short!("partial"), payer.clone()), (lease_id, amount, outstanding));
+    pay_rent(env, asset, payer, amount)?;
+    
+    Ok(())
+}
+
+// Lessee pays `num_periods` periods ahead at the current rent. The funds are held by the
+// contract and settle_due_payment draws one period down at each due date, so no further
+// payment transaction is needed; whatever is left is refunded when the lease closes.
+pub fn prepay(env: Env, lease_id: u64, lessee: Address, num_periods: u32) -> Result<u32, RentError> {
+    admin::require_not_paused(&env)?;
+    let lease = leases::active_lease(&env, lease_id)?;
+    if lessee != lease.lessee {
+        log!(&env, "Only the lessee can prepay rent");
+        return Err(RentError::NotLessee);
+    }
+    lessee.require_auth();
+    
+    // Held periods are all priced alike; after a rent change the old credit runs out first
+    let mut prepaid = view_prepaid_rent(env.clone(), lease_id).unwrap_or(PrepaidRent { prepaid_periods: 0, period_payment: lease.period_payment });
+    if num_periods == 0 || (prepaid.prepaid_periods > 0 && prepaid.period_payment != lease.period_payment) {
+        log!(&env, "Prepayment must cover whole periods at the current rent");
+        return Err(RentError::InvalidAmount);
+    }
+    
+    let asset = assets::load_asset(&env, lease.asset_id)?;
+    let payment_token = xlm::payment_token(&asset);
+    let amount = num_periods as i128 * lease.period_payment;
+    xlm::check_balance(&env, &payment_token, &lessee, amount)?;
+    
+    prepaid.prepaid_periods += num_periods;
+    prepaid.period_payment = lease.period_payment;
+    let key = PrepaidBook::Prepaid(lease_id);
+    env.storage().persistent().set(&key, &prepaid);
+    admin::bump_entry(&env, &key);
+    accounts::credit(&env, SubAccount::TenantDeposits, &payment_token.address, amount);
+    
+    events::publish(&env, (symbol_short!("pay"), symbol_short!("prepaid"), lessee.clone()), (lease_id, num_periods, amount));
+    xlm::transfer(&env, &payment_token, &lessee, &env.current_contract_address(), amount)?;
+    
+    return Ok(prepaid.prepaid_periods);
+}
+
+// Settle the due payment on a lease out of its prepaid rent. Anyone may call it, and the keeper
+// does each round; it returns false, changing nothing, while the lease is not yet due, holds no
+// prepaid periods, or owes more than one prepaid period covers (the lessee then pays as usual).
+pub fn settle_due_payment(env: Env, lease_id: u64) -> Result<bool, RentError> {
+    admin::require_not_paused(&env)?;
+    let lease = leases::active_lease(&env, lease_id)?;
+    let mut prepaid = match view_prepaid_rent(env.clone(), lease_id) {
+        Some(prepaid) => prepaid,
+        None => return Ok(false),
+    };
+    if env.ledger().timestamp() < lease.next_payment_time {
+        return Ok(false);
+    }
+    
+    let contract = env.current_contract_address();
+    let (asset, _, rent, late_fee) = plan_payment(&env, lease.clone(), &lease.lessee, i128::MAX)?;
+    if rent + late_fee > prepaid.period_payment || payment_blocked(&env, lease_id, &contract, &asset, rent + late_fee) {
+        return Ok(false);
+    }
+    
+    // One period is used up whether it settles a full or a reduced bill
+    prepaid.prepaid_periods -= 1;
+    let key = PrepaidBook::Prepaid(lease_id);
+    if prepaid.prepaid_periods == 0 {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &prepaid);
+        admin::bump_entry(&env, &key);
+    }
+    
+    let amount = record_payment(&env, lease_id, &lease.lessee, prepaid.period_payment)?;
+    
+    // A payment that completes rent-to-own closes the lease, so the rest of the credit comes back too
+    let mut held = prepaid.period_payment;
+    if !leases::load_lease(&env, lease_id)?.is_active {
+        held += take_prepaid(&env, lease_id);
+    }
+    let payment_token = xlm::payment_token(&asset);
+    accounts::debit(&env, SubAccount::TenantDeposits, &payment_token.address, held)?;
+    pay_rent(&env, &asset, &contract, amount)?;
+    xlm::transfer(&env, &payment_token, &contract, &lease.lessee, held - amount)?;
+    
+    return Ok(true);
+}
+
+// Lessee lets keepers collect up to `max_periods` due payments on a lease from their token
+// allowance to the contract, replacing any earlier authorization. The allowance (granted with
+// the token's approve) must already cover the current period's rent.
+pub fn authorize_auto_pay(env: Env, lease_id: u64, lessee: Address, max_periods: u32) -> Result<(), RentError> {
+    let lease = leases::active_lease(&env, lease_id)?;
+    if lessee != lease.lessee {
+        log!(&env, "Only the lessee can authorize automatic payments");
+        return Err(RentError::NotLessee);
+    }
+    lessee.require_auth();
+    
+    if max_periods == 0 {
+        log!(&env, "Automatic payments must cover at least one period");
+        return Err(RentError::InvalidAmount);
+    }
+    let asset = assets::load_asset(&env, lease.asset_id)?;
+    xlm::check_allowance(&env, &xlm::payment_token(&asset), &lessee, lease.period_payment)?;
+    
+    let auto_pay = AutoPay {
+        periods_left: max_periods,
+        authorized_at: env.ledger().timestamp(),
+    };
+    let key = AutoPayBook::AutoPay(lease_id);
+    env.storage().persistent().set(&key, &auto_pay);
+    admin::bump_entry(&env, &key);
+    
+    events::publish(&env, (symbol_short!("pay"), symbol_short!("autoauth"), lessee), (lease_id, max_periods));
+    
+    Ok(())
+}
+
+// Lessee withdraws their automatic payment authorization on a lease
+pub fn revoke_auto_pay(env: Env, lease_id: u64, lessee: Address) -> Result<(), RentError> {
+    let lease = leases::load_lease(&env, lease_id)?;
+    if lessee != lease.lessee {
+        log!(&env, "Only the lessee can revoke automatic payments");
+        return Err(RentError::NotLessee);
+    }
+    lessee.require_auth();
+    
+    env.storage().persistent().remove(&AutoPayBook::AutoPay(lease_id));
+    
+    Ok(())
+}
+
+// View the automatic payment authorization on a lease, if any
+pub fn view_auto_pay(env: Env, lease_id: u64) -> Option<AutoPay> {
+    env.storage().persistent().get(&AutoPayBook::AutoPay(lease_id))
+}
+
+// Collect the due payment on a lease from the lessee's allowance under their automatic payment
+// authorization. Anyone may call it, and the keeper does each round; it returns false, changing
+// nothing, while the lease is not yet due, has no authorization left, or the lessee's
+// allowance or balance does not cover what is owed. A collection earns `keeper` the keeper reward.
+pub fn collect_due_payment(env: Env, lease_id: u64, keeper: Address) -> Result<bool, RentError> {
+    admin::require_not_paused(&env)?;
+    let lease = leases::active_lease(&env, lease_id)?;
+    match collect_auto_pay(&env, &lease)? {
+        Some((payment_token, amount)) => {
+            rewards::reward_keeper(&env, &keeper, lease_id, &payment_token, amount)?;
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+// Collect the due payment on an active lease from the lessee's allowance under their auto-pay
+// authorization. Returns the token and amount collected, or None when there is no
+// authorization, nothing is due yet, or the allowance or balance does not cover it. A lease
+// shared by co-tenants is paid share by share, so auto-pay never collects on it.
+fn collect_auto_pay(env: &Env, lease: &LeaseStatus) -> Result<Option<(xlm::Token, i128)>, RentError> {
+    let lease_id = lease.lease_id;
+    let Some(mut auto_pay) = view_auto_pay(env.clone(), lease_id) else {
+        return Ok(None);
+    };
+    if env.ledger().timestamp() < lease.next_payment_time || !lease.co_tenants.is_empty() {
+        return Ok(None);
+    }
+    
+    let (asset, _, rent, late_fee) = plan_payment(env, lease.clone(), &lease.lessee, i128::MAX)?;
+    let payment_token = xlm::payment_token(&asset);
+    let amount = rent + late_fee;
+    if xlm::allowance(env, &payment_token, &lease.lessee) < amount
+        || xlm::balance(env, &payment_token, &lease.lessee) < amount
+        || payment_blocked(env, lease_id, &lease.lessee, &asset, amount)
+    {
+        return Ok(None);
+    }
+    
+    auto_pay.periods_left -= 1;
+    let key = AutoPayBook::AutoPay(lease_id);
+    if auto_pay.periods_left == 0 {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &auto_pay);
+        admin::bump_entry(env, &key);
+    }
+    
+    let amount = record_payment(env, lease_id, &lease.lessee, amount)?;
+    hold_rent(env, &asset, amount);
+    xlm::transfer_from(env, &payment_token, &lease.lessee, &env.current_contract_address(), amount)?;
+    
+    return Ok(Some((payment_token, amount)));
+}
+
+// Collect a missed payment from the lease's guarantor once it is past due by the asset's claim
+// threshold, out of the guarantor's token allowance to the contract. Anyone may call it; the
+// rent is held for the owner as if the lessee had paid. Returns the amount collected.
+pub fn claim_from_guarantor(env: Env, lease_id: u64) -> Result<i128, RentError> {
+    admin::require_not_paused(&env)?;
+    let lease = leases::active_lease(&env, lease_id)?;
+    let guarantor = match leases::view_guarantor(env.clone(), lease_id) {
+        Some(guarantor) => guarantor,
+        None => {
+            log!(&env, "Lease has no guarantor");
+            return Err(RentError::InvalidClaim);
+        }
+    };
+    
+    let now = env.ledger().timestamp();
+    if now < due_date(&env, &lease) + leases::view_guarantor_threshold(env.clone(), lease.asset_id) {
+        log!(&env, "Rent is not past due by the guarantor claim threshold");
+        return Err(RentError::DeadlineNotReached);
+    }
+    
+    let (asset, _, rent, late_fee) = plan_payment(&env, lease.clone(), &lease.lessee, i128::MAX)?;
+    let payment_token = xlm::payment_token(&asset);
+    let amount = rent + late_fee;
+    xlm::check_allowance(&env, &payment_token, &guarantor, amount)?;
+    check_payable(&env, &asset, &guarantor, amount)?;
+    
+    let amount = record_payment(&env, lease_id, &lease.lessee, amount)?;
+    hold_rent(&env, &asset, amount);
+    xlm::transfer_from(&env, &payment_token, &guarantor, &env.current_contract_address(), amount)?;
+    
+    events::publish(&env, (symbol_short!("lease"), symbol_short!("guarclaim"), guarantor), (lease_id, amount));
+    
+    return Ok(amount);
+}
+
+// View the rent a lessee has paid ahead on a lease
+pub fn view_prepaid_rent(env: Env, lease_id: u64) -> Option<PrepaidRent> {
+    env.storage().persistent().get(&PrepaidBook::Prepaid(lease_id))
+}
+
+// Clear a closing lease's prepaid rent; returns the base units to refund to the lessee
+pub(crate) fn take_prepaid(env: &Env, lease_id: u64) -> i128 {
+    let prepaid = match view_prepaid_rent(env.clone(), lease_id) {
+        Some(prepaid) => prepaid,
+        None => return 0,
+    };
+    env.storage().persistent().remove(&PrepaidBook::Prepaid(lease_id));
+    
+    return prepaid.prepaid_periods as i128 * prepaid.period_payment;
+}
+
+// Return prepaid rent taken off a closed lease to its lessee
+pub(crate) fn refund_prepaid(env: &Env, lease: &LeaseStatus, asset: &Asset, amount: i128) -> Result<(), RentError> {
+    if amount == 0 {
+        return Ok(());
+    }
+    
+    let payment_token = xlm::payment_token(asset);
+    accounts::debit(env, SubAccount::TenantDeposits, &payment_token.address, amount)?;
+    xlm::transfer(env, &payment_token, &env.current_contract_address(), &lease.lessee, amount)?;
+    log!(env, "Prepaid rent of {} refunded for lease ID: {}", amount, lease.lease_id);
+    
+    Ok(())
+}
+
+// Process a payment submitted by a relayer or fee sponsor on the lessee's behalf.
+// Only the lessee's signed auth entry is needed; `max_amount` is part of what they sign,
+// so the relayer cannot make them pay more than they agreed to. On a shared lease `lessee`
+// is the co-tenant paying, and only their share is charged.
+pub fn process_sponsored_payment(env: Env, lease_id: u64, lessee: Address, max_amount: i128) -> Result<i128, RentError> {
+    let lease = leases::load_lease(&env, lease_id)?;
+    if lease.co_tenants.is_empty() {
+        return settle_payment(&env, lease_id, &lessee, max_amount);
+    }
+    
+    return pay_share(&env, &lease, &lessee, max_amount);
+}
+
+// Record usage on a metered lease, billed at the next payment
+pub fn record_usage(env: Env, lease_id: u64, owner: Address, units: u64) -> Result<(), RentError> {
+    let mut lease = leases::load_lease(&env, lease_id)?;
+    
+    if !lease.is_active {
+        log!(&env, "Lease is not active");
+        return Err(RentError::LeaseNotActive);
+    }
+    
+    let asset = assets::load_asset(&env, lease.asset_id)?;
+    if !assets::acts_for_owner(&env, &asset.owner, &owner) {
+        log!(&env, "Only the asset owner can record usage");
+        return Err(RentError::NotAssetOwner);
+    }
+    owner.require_auth();
+    
+    if let PaymentPolicy::Metered(_) = lease.payment_policy {
+        lease.metered_units += units;
+    } else {
+        log!(&env, "Lease is not metered");
+        return Err(RentError::NotMetered);
+    }
+    leases::save_lease(&env, &lease);
+    
+    log!(&env, "Recorded {} units for lease ID: {}", units, lease_id);
+    
+    Ok(())
+}
+
+// View the amount due if the lessee paid now, including any late penalty
+pub fn view_amount_due(env: Env, lease_id: u64) -> Result<i128, RentError> {
+    let lease = leases::load_lease(&env, lease_id)?;
+    let asset = assets::load_asset(&env, lease.asset_id)?;
+    amount_due_now(&env, &asset, &lease, env.ledger().timestamp())
+}
+
+// Month-end close for an owner over a batch of up to `limit` (capped at MAX_PAGE) of their
+// assets, after the first `cursor` in their asset list (0 to start): rolls each asset's active
+// lease through renewal or holdover, collects what auto-pay covers, flags leases past their
+// due date overdue, advances late notices, and reports what is owed. Resume from next_cursor.
+pub fn settle_all(env: Env, owner: Address, cursor: u64, limit: u32) -> Result<SettlementReport, RentError> {
+    owner.require_auth();
+    
+    if limit == 0 {
+        log!(&env, "Batch limit must be positive");
+        return Err(RentError::InvalidAmount);
+    }
+    let limit = limit.min(assets::MAX_PAGE);
+    let collecting = !admin::is_paused(env.clone());
+    
+    let now = env.ledger().timestamp();
+    let owned = assets::get_owner_assets(env.clone(), owner.clone());
+    let asset_count = owned.len() as u64;
+    let last_asset = asset_count.min(cursor.saturating_add(limit as u64));
+    let mut report = SettlementReport {
+        owner: owner.clone(),
+        first_asset: cursor + 1,
+        last_asset: last_asset.max(cursor),
+        next_cursor: if last_asset < asset_count { last_asset } else { 0 },
+        leases_settled: 0,
+        newly_overdue: 0,
+        overdue: 0,
+        amount_due: 0,
+    };
+    
+    for position in cursor..last_asset {
+        let asset = assets::load_asset(&env, owned.get_unchecked(position as u32))?;
+        if asset.current_lease_id == 0 {
+            continue;
+        }
+        let lease_id = asset.current_lease_id;
+        let mut lease = leases::load_lease(&env, lease_id)?;
+        if !lease.is_active {
+            continue;
+        }
+        
+        leases::roll_term(&env, &asset, &mut lease, now)?;
+        leases::save_lease(&env, &lease);
+        // Rent auto-pay can cover is collected before anything is judged overdue
+        if collecting
+            && view_auto_pay(env.clone(), lease_id).is_some()
+            && batch_payment_error(&env, lease_id, &lease.lessee)?.is_none()
+            && collect_auto_pay(&env, &lease)?.is_some()
+        {
+            lease = leases::load_lease(&env, lease_id)?;
+        }
+        if !lease.is_overdue && now >= overdue_at(&env, &asset, &lease) {
+            leases::flag_overdue(&env, &mut lease, now);
+            report.newly_overdue += 1;
+        } else {
+            leases::escalate(&env, &lease, now);
+        }
+        
+        if lease.is_overdue {
+            report.overdue += 1;
+        }
+        if now >= lease.next_payment_time {
+            report.amount_due += amount_due_now(&env, &asset, &lease, now)?;
+        }
+        report.leases_settled += 1;
+    }
+    
+    events::publish(
+        &env,
+        (symbol_short!("settle"), symbol_short!("report"), owner),
+        (report.first_asset, report.last_asset, report.leases_settled, report.newly_overdue, report.overdue, report.amount_due),
+    );
+    admin::bump_instance(&env);
+    
+    return Ok(report);
+}
+
+// Lessee opts in to (or out of) paying all their leases with an owner as one bill
+pub fn set_consolidated_billing(env: Env, lessee: Address, owner: Address, enabled: bool) {
+    lessee.require_auth();
+    
+    let key = BillingBook::Consolidated(lessee.clone(), owner.clone());
+    env.storage().persistent().set(&key, &enabled);
+    admin::bump_entry(&env, &key);
+    
+    log!(&env, "Consolidated billing set to {}", enabled);
+}
+
+// Whether a lessee's leases with an owner are billed together
+pub fn view_consolidated_billing(env: Env, lessee: Address, owner: Address) -> bool {
+    env.storage().persistent().get(&BillingBook::Consolidated(lessee, owner)).unwrap_or(false)
+}
+
+// View the net amount a lessee owes an owner right now across all their due leases, as it
+// would be allocated if paid
+pub fn get_consolidated_bill(env: Env, lessee: Address, owner: Address) -> Result<ConsolidatedSettlement, RentError> {
+    let now = env.ledger().timestamp();
+    let mut bill = ConsolidatedSettlement {
+        settlement_id: 0,
+        lessee: lessee.clone(),
+        owner: owner.clone(),
+        paid_at: now,
+        token: None,
+        total: 0,
+        allocations: Vec::new(&env),
+    };
+    
+    for lease_id in billed_leases(&env, &lessee, &owner)?.iter() {
+        let (asset, _, rent, late_fee) = plan_payment(&env, leases::load_lease(&env, lease_id)?, &lessee, i128::MAX)?;
+        
+        // A single transfer can only settle leases priced in the same token
+        match &bill.token {
+            Some(token) if *token != asset.payment_token => {
+                log!(&env, "Billed leases are priced in different tokens");
+                return Err(RentError::MixedPaymentTokens);
+            }
+            Some(_) => {}
+            None => bill.token = Some(asset.payment_token),
+        }
+        bill.total += rent + late_fee;
+        bill.allocations.push_back(LeaseAllocation { lease_id: lease_id, rent: rent, late_fee: late_fee });
+    }
+    
+    return Ok(bill);
+}
+
+// Settle every due lease a lessee holds with an owner in one token transfer, recording how the
+// total was allocated; `max_amount` caps the net total the lessee signs for
+pub fn pay_consolidated_bill(env: Env, lessee: Address, owner: Address, max_amount: i128) -> Result<u64, RentError> {
+    admin::require_not_paused(&env)?;
+    if !view_consolidated_billing(env.clone(), lessee.clone(), owner.clone()) {
+        log!(&env, "Consolidated billing is not enabled");
+        return Err(RentError::ConsolidatedBillingOff);
+    }
+    lessee.require_auth();
+    
+    let mut settlement = get_consolidated_bill(env.clone(), lessee.clone(), owner.clone())?;
+    if settlement.total > max_amount {
+        log!(&env, "Amount due exceeds the authorized maximum");
+        return Err(RentError::AmountExceedsMax);
+    }
+    
+    // A refused token blocks every lease on the bill and nothing is settled
+    let mut blocked = false;
+    for allocation in settlement.allocations.iter() {
+        let asset = assets::load_asset(&env, leases::load_lease(&env, allocation.lease_id)?.asset_id)?;
+        blocked |= payment_blocked(&env, allocation.lease_id, &lessee, &asset, allocation.rent + allocation.late_fee);
+    }
+    if blocked {
+        return Ok(0);
+    }
+    if let Some(first) = settlement.allocations.first() {
+        let asset = assets::load_asset(&env, leases::load_lease(&env, first.lease_id)?.asset_id)?;
+        check_payable(&env, &asset, &lessee, settlement.total)?;
+    }
+    
+    let settlement_id: u64 = env.storage().instance().get(&DataKey::SettlementCount).unwrap_or(0) + 1;
+    settlement.settlement_id = settlement_id;
+    env.storage().instance().set(&DataKey::SettlementCount, &settlement_id);
+    let key = BillingBook::Settlement(settlement_id);
+    env.storage().persistent().set(&key, &settlement);
+    admin::bump_entry(&env, &key);
+    
+    for allocation in settlement.allocations.iter() {
+        record_payment(&env, allocation.lease_id, &lessee, i128::MAX)?;
+    }
+    if let Some(first) = settlement.allocations.first() {
+        let asset = assets::load_asset(&env, leases::load_lease(&env, first.lease_id)?.asset_id)?;
+        pay_rent(&env, &asset, &lessee, settlement.total)?;
+    }
+    
+    log!(&env, "Consolidated payment of {} settled {} leases", settlement.total, settlement.allocations.len());
+    
+    return Ok(settlement_id);
+}
+
+// View a recorded consolidated settlement
+pub fn view_consolidated_settlement(env: Env, settlement_id: u64) -> Option<ConsolidatedSettlement> {
+    env.storage().persistent().get(&BillingBook::Settlement(settlement_id))
+}
+
+// Owner forgives part or all of the late fee accrued so far on a lease; the waived amount is
+// taken off the next payment and recorded with its reason code
+pub fn waive_late_fee(env: Env, lease_id: u64, owner: Address, amount: i128, reason: Symbol) -> Result<(), RentError> {
+    let lease = leases::active_lease(&env, lease_id)?;
+    let asset = assets::owned_asset(&env, lease.asset_id, &owner)?;
+    
+    let now = env.ledger().timestamp();
+    let rent = policy::amount_due(&lease, now);
+    let accrued = penalty::late_fee(&asset.penalty_policy, &lease, rent, due_date(&env, &lease), asset.grace_period, now);
+    let credit = view_waiver_credit(&env, lease_id);
+    if amount <= 0 || credit + amount > accrued {
+        log!(&env, "Waiver exceeds the late fee accrued");
+        return Err(RentError::InvalidAmount);
+    }
+    
+    let mut waivers = view_late_fee_waivers(env.clone(), lease_id);
+    waivers.push_back(LateFeeWaiver { amount: amount, reason: reason.clone(), waived_at: now, paid_due: 0 });
+    let key = WaiverBook::Waivers(lease_id);
+    env.storage().persistent().set(&key, &waivers);
+    admin::bump_entry(&env, &key);
+    let key = WaiverBook::WaiverCredit(lease_id);
+    env.storage().persistent().set(&key, &(credit + amount));
+    admin::bump_entry(&env, &key);
+    
+    events::publish(&env, (symbol_short!("late"), symbol_short!("waived")), (lease_id, amount, reason));
+    log!(&env, "Waived {} base units of late fees for lease ID: {}", amount, lease_id);
+    
+    Ok(())
+}
+
+// View every late-fee waiver granted on a lease, oldest first
+pub fn view_late_fee_waivers(env: Env, lease_id: u64) -> Vec<LateFeeWaiver> {
+    env.storage().persistent().get(&WaiverBook::Waivers(lease_id)).unwrap_or(Vec::new(&env))
+}
+
+// Lessee asks, before a due date, for it to be pushed back by `extra_days`; less than one
+// period, and only while the lease has extensions left this year. A new request replaces a
+// pending one.
+pub fn request_extension(env: Env, lease_id: u64, lessee: Address, extra_days: u32, reason_hash: BytesN<32>) -> Result<(), RentError> {
+    let lease = leases::active_lease(&env, lease_id)?;
+    
+    if lessee != lease.lessee {
+        log!(&env, "Only the lessee can request an extension");
+        return Err(RentError::NotLessee);
+    }
+    lessee.require_auth();
+    
+    if extra_days == 0 || extra_days as u64 * calendar::DAY >= lease.payment_frequency {
+        log!(&env, "Extension must be at least a day and shorter than a period");
+        return Err(RentError::InvalidAmount);
+    }
+    let now = env.ledger().timestamp();
+    if now >= due_date(&env, &lease) {
+        log!(&env, "Extensions must be requested before the due date");
+        return Err(RentError::ExtensionRefused);
+    }
+    check_extension_allowance(&env, lease_id, now)?;
+    
+    let extension = DueDateExtension {
+        due_at: lease.next_payment_time,
+        extra_days: extra_days,
+        reason_hash: reason_hash,
+        requested_at: now,
+        approved_at: 0,
+    };
+    let key = ExtensionBook::Extension(lease_id);
+    env.storage().persistent().set(&key, &extension);
+    admin::bump_entry(&env, &key);
+    
+    events::publish(&env, (symbol_short!("extension"), symbol_short!("requested"), lessee), (lease_id, extension.due_at, extra_days));
+    log!(&env, "Extension of {} days requested for lease ID: {}", extra_days, lease_id);
+    
+    Ok(())
+}
+
+// Owner grants the pending extension, moving that one due date without late fees; uses up
+// one of the lease's extensions for the year
+pub fn approve_extension(env: Env, lease_id: u64, owner: Address) -> Result<(), RentError> {
+    let lease = leases::active_lease(&env, lease_id)?;
+    assets::owned_asset(&env, lease.asset_id, &owner)?;
+    
+    let mut extension = match view_extension(env.clone(), lease_id) {
+        Some(extension) if extension.approved_at == 0 && extension.due_at == lease.next_payment_time => extension,
+        _ => {
+            log!(&env, "No extension pending for the current due date");
+            return Err(RentError::NoPendingExtension)
+        }
+    };
+    let now = env.ledger().timestamp();
+    let mut used = check_extension_allowance(&env, lease_id, now)?;
+    
+    extension.approved_at = now;
+    used.push_back(now);
+    let key = ExtensionBook::Extension(lease_id);
+    env.storage().persistent().set(&key, &extension);
+    admin::bump_entry(&env, &key);
+    let used_key = ExtensionBook::ExtensionsUsed(lease_id);
+    env.storage().persistent().set(&used_key, &used);
+    admin::bump_entry(&env, &used_key);
+    
+    events::publish(&env, (symbol_short!("extension"), symbol_short!("approved"), owner), (lease_id, extension.due_at, extension.extra_days));
+    log!(&env, "Extension of {} days approved for lease ID: {}", extension.extra_days, lease_id);
+    
+    Ok(())
+}
+
+// View a lease's latest extension request, pending or granted
+pub fn view_extension(env: Env, lease_id: u64) -> Option<DueDateExtension> {
+    env.storage().persistent().get(&ExtensionBook::Extension(lease_id))
+}
+
+// Extensions a lease may still be granted in the 365 days up to now
+pub fn view_extensions_left(env: Env, lease_id: u64) -> u32 {
+    let used = recent_extensions(&env, lease_id, env.ledger().timestamp()).len();
+    MAX_EXTENSIONS_PER_YEAR.saturating_sub(used)
+}
+
+// When each extension granted on a lease in the 365 days up to `now` was approved
+fn recent_extensions(env: &Env, lease_id: u64, now: u64) -> Vec<u64> {
+    let granted: Vec<u64> = env.storage().persistent().get(&ExtensionBook::ExtensionsUsed(lease_id)).unwrap_or(Vec::new(env));
+    
+    let mut recent = Vec::new(env);
+    for approved_at in granted.iter() {
+        if approved_at + 365 * calendar::DAY > now {
+            recent.push_back(approved_at);
+        }
+    }
+    
+    return recent;
+}
+
+// Fail with ExtensionRefused once the lease has used up its extensions for the year;
+// returns the approvals still counted against it
+fn check_extension_allowance(env: &Env, lease_id: u64, now: u64) -> Result<Vec<u64>, RentError> {
+    let recent = recent_extensions(env, lease_id, now);
+    if recent.len() >= MAX_EXTENSIONS_PER_YEAR {
+        log!(env, "Lease has used its extensions for the year");
+        return Err(RentError::ExtensionRefused);
+    }
+    
+    return Ok(recent);
+}
+
+// Late fee, net of waivers, a payment on the lease would carry if made now
+pub fn calculate_late_fee(env: Env, lease_id: u64) -> Result<i128, RentError> {
+    let mut lease = leases::active_lease(&env, lease_id)?;
+    let asset = assets::load_asset(&env, lease.asset_id)?;
+    
+    // Quote against the term the payment would land in
+    let now = env.ledger().timestamp();
+    leases::roll_term(&env, &asset, &mut lease, now)?;
+    let (_, late_fee) = quote_payment(&env, &asset, &lease, now);
+    
+    return Ok(late_fee);
+}
+
+// Project the payments still due on a lease through its end_time, assuming each is made on
+// its due date, with the escalation clause applied as it would be. Late fees, renewals and
+// holdover are not projected, and metered leases only show usage recorded so far.
+pub fn view_payment_schedule(env: Env, lease_id: u64) -> Result<Vec<ScheduledPayment>, RentError> {
+    let mut lease = leases::active_lease(&env, lease_id)?;
+    let mut schedule = Vec::new(&env);
+    
+    let mut due_at = lease.next_payment_time;
+    while due_at < lease.end_time && schedule.len() < MAX_SCHEDULE_PERIODS && !policy::ownership_earned(&lease) {
+        let amount = policy::amount_due(&lease, due_at);
+        schedule.push_back(ScheduledPayment { due_at: due_at, amount: amount });
+        policy::apply_payment(&mut lease, amount, due_at);
+        due_at = lease.next_payment_time;
+    }
+    
+    return Ok(schedule);
+}
+
+// Simulate process_payment: run every check and return what the payment would charge and
+// how the schedule would advance, without writing anything
+pub fn dry_run_process_payment(env: Env, lease_id: u64, caller: Address) -> Result<PaymentQuote, RentError> {
+    let (asset, lease, rent, late_fee) = plan_payment(&env, leases::load_lease(&env, lease_id)?, &caller, i128::MAX)?;
+    check_payable(&env, &asset, &caller, rent + late_fee)?;
+    Ok(PaymentQuote {
+        rent: rent,
+        late_fee: late_fee,
+        amount: rent + late_fee,
+        next_payment_time: lease.next_payment_time,
+        transfers_ownership: policy::ownership_earned(&lease),
+    })
+}
+
+// Shared payment path; authorization comes from the payer's signature, never the
+// transaction source, so any account may submit and pay the fees
+pub(crate) fn settle_payment(env: &Env, lease_id: u64, caller: &Address, max_amount: i128) -> Result<i128, RentError> {
+    admin::require_not_paused(env)?;
+    caller.require_auth();
+    
+    // Rent goes to whoever owns the asset before this payment; a completed rent-to-own
+    // schedule only hands the asset over once it is paid for
+    let (asset, _, rent, late_fee) = plan_payment(env, leases::load_lease(env, lease_id)?, caller, max_amount)?;
+    if payment_blocked(env, lease_id, caller, &asset, rent + late_fee) {
+        return Ok(0);
+    }
+    check_payable(env, &asset, caller, rent + late_fee)?;
+    let amount = record_payment(env, lease_id, caller, max_amount)?;
+    pay_rent(env, &asset, caller, amount)?;
+    
+    return Ok(amount);
+}
+
+// Take a recorded rent payment into the contract: the platform fee, if any, is booked for the
+// platform and the rest is held for the asset owner to withdraw
+pub(crate) fn pay_rent(env: &Env, asset: &Asset, payer: &Address, amount: i128) -> Result<(), RentError> {
+    hold_rent(env, asset, amount);
+    xlm::transfer(env, &xlm::payment_token(asset), payer, &env.current_contract_address(), amount)?;
+    
+    Ok(())
+}
+
+// Book rent the contract is taking in: the platform fee, if any, for the platform and the rest
+// for the asset owner to withdraw
+fn hold_rent(env: &Env, asset: &Asset, amount: i128) {
+    let payment_token = xlm::payment_token(asset);
+    let fee = admin::charge_platform_fee(env, &payment_token, amount);
+    accounts::credit(env, SubAccount::OwnerEarnings, &payment_token.address, amount - fee);
+    add_withdrawable(env, asset, amount - fee);
+}
+
+// Book deposit the owner keeps, once released from the escrow contract into this one, as
+// what the owner can withdraw; no platform fee is taken
+pub(crate) fn credit_owner(env: &Env, asset: &Asset, amount: i128) -> Result<(), RentError> {
+    if amount == 0 {
+        return Ok(());
+    }
+    
+    let payment_token = xlm::payment_token(asset);
+    accounts::credit(env, SubAccount::OwnerEarnings, &payment_token.address, amount);
+    add_withdrawable(env, asset, amount);
+    
+    Ok(())
+}
+
+// Raise what the asset owner can withdraw in the asset's payment token
+fn add_withdrawable(env: &Env, asset: &Asset, amount: i128) {
+    let key = EarningsBook::Withdrawable(asset.owner.clone(), asset.payment_token.clone());
+    let withdrawable = view_withdrawable(env.clone(), asset.owner.clone(), asset.payment_token.clone());
+    env.storage().persistent().set(&key, &(withdrawable + amount));
+    admin::bump_entry(env, &key);
+}
+
+// Take the first rent on a lease opened with a trial into the tenant deposits rather than the
+// owner's earnings, so a trial exit can hand the unused part back whatever the owner withdraws
+pub(crate) fn hold_trial_rent(env: &Env, asset: &Asset, lease: &LeaseStatus, amount: i128) -> Result<(), RentError> {
+    let payment_token = xlm::payment_token(asset);
+    let key = TrialBook::HeldRent(lease.lease_id);
+    env.storage().persistent().set(&key, &amount);
+    admin::bump_entry(env, &key);
+    accounts::credit(env, SubAccount::TenantDeposits, &payment_token.address, amount);
+    xlm::transfer(env, &payment_token, &lease.lessee, &env.current_contract_address(), amount)?;
+    
+    Ok(())
+}
+
+// Book the rent held through a lease's trial for the owner once the trial is over
+pub(crate) fn release_trial_rent(env: &Env, lease: &LeaseStatus, asset: &Asset) -> Result<(), RentError> {
+    if env.ledger().timestamp() >= lease.trial_end {
+        settle_trial_rent(env, lease, asset, 0)?;
+    }
+    
+    Ok(())
+}
+
+// Settle the rent held through a lease's trial: `refund` of it goes back to the lessee and the
+// rest is booked for the owner, platform fee and all. A refund beyond what is held, for rent
+// paid ahead during the trial, comes out of rent the owner has yet to withdraw.
+pub(crate) fn settle_trial_rent(env: &Env, lease: &LeaseStatus, asset: &Asset, refund: i128) -> Result<(), RentError> {
+    let key = TrialBook::HeldRent(lease.lease_id);
+    let held: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+    env.storage().persistent().remove(&key);
+    let from_held = refund.min(held);
+    
+    let payment_token = xlm::payment_token(asset);
+    accounts::debit(env, SubAccount::TenantDeposits, &payment_token.address, held)?;
+    if held > from_held {
+        hold_rent(env, asset, held - from_held);
+    }
+    if from_held > 0 {
+        xlm::transfer(env, &payment_token, &env.current_contract_address(), &lease.lessee, from_held)?;
+    }
+    refund_rent(env, asset, &lease.lessee, refund - from_held)?;
+    
+    Ok(())
+}
+
+// Hand `amount` of rent held for the asset owner back to `payee`, out of what the owner has yet
+// to withdraw
+fn refund_rent(env: &Env, asset: &Asset, payee: &Address, amount: i128) -> Result<(), RentError> {
+    if amount == 0 {
+        return Ok(());
+    }
+    
+    let withdrawable = view_withdrawable(env.clone(), asset.owner.clone(), asset.payment_token.clone());
+    if amount > withdrawable {
+        log!(env, "Owner holds {} tokens, less than the {} refunded", withdrawable, amount);
+        return Err(RentError::AccountOverdrawn);
+    }
+    
+    let key = EarningsBook::Withdrawable(asset.owner.clone(), asset.payment_token.clone());
+    if amount == withdrawable {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &(withdrawable - amount));
+        admin::bump_entry(env, &key);
+    }
+    let payment_token = xlm::payment_token(asset);
+    accounts::debit(env, SubAccount::OwnerEarnings, &payment_token.address, amount)?;
+    xlm::transfer(env, &payment_token, &env.current_contract_address(), payee, amount)?;
+    
+    Ok(())
+}
+
+// Owner withdraws `amount` base units of the rent held for them in `token`. Rent collects in
+// the contract as it is paid, so a payment never fails on the owner's account; only the
+// withdrawal needs the owner able to receive the token. Returns what is left to withdraw.
+pub fn withdraw_rent(env: Env, owner: Address, token: Address, amount: i128) -> Result<i128, RentError> {
+    owner.require_auth();
+    
+    let withdrawable = view_withdrawable(env.clone(), owner.clone(), token.clone());
+    if amount <= 0 || amount > withdrawable {
+        log!(&env, "Withdrawal must be positive and at most the {} tokens held", withdrawable);
+        return Err(RentError::AccountOverdrawn);
+    }
+    
+    let key = EarningsBook::Withdrawable(owner.clone(), token.clone());
+    if amount == withdrawable {
+        env.storage().persistent().remove(&key);
+    } else {
+        env.storage().persistent().set(&key, &(withdrawable - amount));
+        admin::bump_entry(&env, &key);
+    }
+    let payment_token = xlm::token(&token);
+    accounts::debit(&env, SubAccount::OwnerEarnings, &token, amount)?;
+    xlm::transfer(&env, &payment_token, &env.current_contract_address(), &owner, amount)?;
+    
+    events::publish(&env, (symbol_short!("rent"), symbol_short!("withdrawn"), owner), (token, amount));
+    
+    return Ok(withdrawable - amount);
+}
+
+// View the rent held for an owner in one token, in base units
+pub fn view_withdrawable(env: Env, owner: Address, token: Address) -> i128 {
+    env.storage().persistent().get(&EarningsBook::Withdrawable(owner, token)).unwrap_or(0)
+}
+
+// Apply a payment and its bookkeeping once the payer's authorization and funds have been checked
+pub(crate) fn record_payment(env: &Env, lease_id: u64, caller: &Address, max_amount: i128) -> Result<i128, RentError> {
+    let now = env.ledger().timestamp();
+    let current = leases::load_lease(env, lease_id)?;
+    let was_overdue = current.is_overdue;
+    let cured = leases::in_cure_window(env, &current, now);
+    let due = due_date(env, &current);
+    let (asset, mut lease, rent, late_fee) = plan_payment(env, current, caller, max_amount)?;
+    let amount = rent + late_fee;
+    release_trial_rent(env, &lease, &asset)?;
+    
+    // Partial payments toward the period are used up by the payment that settles it
+    env.storage().persistent().remove(&PartialBook::PartialCredit(lease_id));
+    env.storage().persistent().remove(&PartialBook::SharesPaid(lease_id));
+    lease.outstanding_balance = 0;
+    
+    // Waivers are used up by the payment that settles the fees they forgave
+    if env.storage().persistent().has(&WaiverBook::WaiverCredit(lease_id)) {
+        env.storage().persistent().remove(&WaiverBook::WaiverCredit(lease_id));
+        let mut waivers = Vec::new(env);
+        for mut waiver in view_late_fee_waivers(env.clone(), lease_id).iter() {
+            if waiver.paid_due == 0 {
+                waiver.paid_due = due;
+            }
+            waivers.push_back(waiver);
+        }
+        let key = WaiverBook::Waivers(lease_id);
+        env.storage().persistent().set(&key, &waivers);
+        admin::bump_entry(env, &key);
+    }
+    
+    // Only paying all arrears closes out the late notice (and any pending default)
+    if lease.next_payment_time > now {
+        env.storage().persistent().remove(&EscalationBook::Notice(lease_id));
+    }
+    env.storage().persistent().remove(&CureBook::MarkedAt(lease_id));
+    
+    stats::update_financials(env, lease.asset_id, |f| {
+        f.rent_collected += rent;
+        f.late_fees += late_fee;
+    });
+    
+    lease.late_fees_paid += late_fee;
+    
+    // A payment on a lease already flagged overdue was counted late when it was flagged
+    if !was_overdue {
+        stats::update_reputation(env, &lease.lessee, |r| if now > due { r.late_payments += 1 } else { r.on_time_payments += 1 });
+    }
+    
+    // Update global stats
+    let mut stats = stats::view_asset_stats(env.clone());
+    stats::record_token_flow(env, &mut stats, &asset.payment_token, amount, late_fee);
+    if was_overdue {
+        stats.overdue_leases -= 1;
+    }
+    
+    // A completed rent-to-own schedule hands the asset to the lessee and closes the lease; the
+    // seller's held listing fee is refunded once everything is recorded
+    let mut listing_refund = None;
+    if policy::ownership_earned(&lease) {
+        let mut asset = asset;
+        listing_refund = Some((asset.asset_id, asset.owner.clone(), assets::release_listing_fee(&mut asset)));
+        leases::count_tenancies(env, &lease, &asset.owner, false);
+        assets::unindex_owner_asset(env, &asset.owner, asset.asset_id);
+        assets::index_owner_asset(env, &lease.lessee, asset.asset_id);
+        assets::unindex_listing(env, &asset);
+        stats::update_reputation(env, &asset.owner, |r| r.completed_leases += 1);
+        asset.owner = lease.lessee.clone();
+        asset.is_available = false;
+        asset.current_lease_id = 0;
+        assets::save_asset(env, &asset);
+        
+        lease.is_active = false;
+        stats::count_storage(env, |m| m.archived_leases += 1);
+        stats.active_leases -= 1;
+        stats.completed_leases += 1;
+        stats::update_reputation(env, &lease.lessee, |r| r.completed_leases += 1);
+        
+        events::publish(env, (symbol_short!("lease"), symbol_short!("ended"), lease.lessee.clone()), (lease_id, lease.asset_id, lease.total_paid));
+        log!(env, "Ownership of asset ID {} transferred to lessee", lease.asset_id);
+    }
+    
+    // Store updated lease data
+    leases::save_lease(env, &lease);
+    env.storage().instance().set(&DataKey::Stats, &stats);
+    
+    admin::bump_instance(env);
+    
+    events::publish(env, (symbol_short!("pay"), symbol_short!("made"), caller.clone()), (lease_id, rent, late_fee, lease.next_payment_time));
+    // Paying off every arrear within the cure period is recorded apart from a late payment
+    if cured && lease.next_payment_time > now {
+        events::publish(env, (symbol_short!("lease"), symbol_short!("cured"), lease.lessee.clone()), (lease_id, now));
+    }
+    if let Some((asset_id, seller, listing_fee)) = listing_refund {
+        assets::refund_listing_fee(env, asset_id, &seller, listing_fee)?;
+    }
+    log!(env, "Payment of {} processed for lease ID: {}", amount, lease_id);
+    
+    return Ok(amount);
+}
+
+// Validate a payment and apply it to the loaded `lease`, without writing anything; shared by
+// settle_payment and the dry-run view. Returns (asset, updated lease, rent, late fee).
+pub(crate) fn plan_payment(env: &Env, mut lease: LeaseStatus, caller: &Address, max_amount: i128) -> Result<(Asset, LeaseStatus, i128, i128), RentError> {
+    // Check if lease exists and is active
+    if !lease.is_active {
+        log!(env, "Lease is not active");
+        return Err(RentError::LeaseNotActive);
+    }
+    
+    // Verify caller is the lessee or a co-tenant
+    if !leases::is_tenant(&lease, caller) {
+        log!(env, "Only a tenant on the lease can make payments");
+        return Err(RentError::NotLessee);
+    }
+    
+    // Get current time
+    let now = env.ledger().timestamp();
+    
+    // Past end_time the lease renews, rolls into holdover, or can no longer take payments
+    let asset = assets::load_asset(env, lease.asset_id)?;
+    if !leases::roll_term(env, &asset, &mut lease, now)? {
+        log!(env, "Lease term has ended");
+        return Err(RentError::LeaseExpired);
+    }
+    
+    // Late behavior is governed by the asset's penalty policy, except that any lease may be
+    // cured within its cure period
+    if !penalty::accepts_payment(&asset.penalty_policy, &lease) && !leases::in_cure_window(env, &lease, now) {
+        log!(env, "Payment refused on overdue lease");
+        return Err(RentError::LatePaymentRefused);
+    }
+    
+    // Update lease payment info according to its payment policy
+    let (rent, late_fee) = quote_payment(env, &asset, &lease, now);
+    let amount = rent + late_fee;
+    if amount > max_amount {
+        log!(env, "Amount due exceeds the authorized maximum");
+        return Err(RentError::AmountExceedsMax);
+    }
+    policy::apply_payment(&mut lease, amount, now);
+    lease.is_overdue = false;
+    
+    return Ok((asset, lease, rent, late_fee));
+}
+
+// Preflight the transfer a payment makes, so a failure names its fix rather than trapping
+// inside the token
+fn check_payable(env: &Env, asset: &Asset, payer: &Address, amount: i128) -> Result<(), RentError> {
+    if amount > 0 {
+        xlm::check_balance(env, &xlm::payment_token(asset), payer, amount)?;
+    }
+    
+    Ok(())
+}
+
+// Total a lessee would pay at `now`, after any renewal or holdover transition
+pub(crate) fn amount_due_now(env: &Env, asset: &Asset, lease: &LeaseStatus, now: u64) -> Result<i128, RentError> {
+    let mut lease = lease.clone();
+    leases::roll_term(env, asset, &mut lease, now)?;
+    let (rent, late_fee) = quote_payment(env, asset, &lease, now);
+    Ok(rent + late_fee)
+}
+
+// Amount due at `now` as (rent per the payment policy, late fee per the penalty policy less
+// any fees the owner has waived). Partial payments already made toward the period are taken
+// off the rent first, then the late fee.
+pub(crate) fn quote_payment(env: &Env, asset: &Asset, lease: &LeaseStatus, now: u64) -> (i128, i128) {
+    let rent = policy::amount_due(lease, now);
+    let late_fee = match leases::in_cure_window(env, lease, now) {
+        true => 0,
+        false => penalty::late_fee(&asset.penalty_policy, lease, rent, due_date(env, lease), asset.grace_period, now),
+    };
+    let late_fee = (late_fee - view_waiver_credit(env, lease.lease_id)).max(0);
+    
+    let paid = view_partial_credit(env, lease.lease_id);
+    ((rent - paid).max(0), (late_fee - (paid - rent).max(0)).max(0))
+}
+
+// Partial payments made toward the current period and not yet settled
+fn view_partial_credit(env: &Env, lease_id: u64) -> i128 {
+    env.storage().persistent().get(&PartialBook::PartialCredit(lease_id)).unwrap_or(0)
+}
+
+// Co-tenants who have paid their share of the current period
+fn view_shares_paid(env: &Env, lease_id: u64) -> Vec<Address> {
+    env.storage().persistent().get(&PartialBook::SharesPaid(lease_id)).unwrap_or(Vec::new(env))
+}
+
+// Waived late fees not yet offset against a payment
+fn view_waiver_credit(env: &Env, lease_id: u64) -> i128 {
+    env.storage().persistent().get(&WaiverBook::WaiverCredit(lease_id)).unwrap_or(0)
+}
+
+// When the next payment of a lease is actually due: its scheduled time, rolled past any
+// holiday or (if enabled) weekend. Every lateness check goes through this.
+pub(crate) fn due_date(env: &Env, lease: &LeaseStatus) -> u64 {
+    // A granted extension moves only the due date it was requested for
+    let mut due = lease.next_payment_time;
+    if let Some(extension) = view_extension(env.clone(), lease.lease_id) {
+        if extension.approved_at != 0 && extension.due_at == lease.next_payment_time {
+            due += extension.extra_days as u64 * calendar::DAY;
+        }
+    }
+    
+    let holidays = admin::view_holidays(env.clone());
+    let skip_weekends = admin::view_weekend_rollover(env.clone());
+    if holidays.is_empty() && !skip_weekends {
+        return due;
+    }
+    calendar::next_business_day(due, &holidays, skip_weekends)
+}
+
+// When a lease with a missed payment turns overdue: its due date plus the asset's grace period
+pub(crate) fn overdue_at(env: &Env, asset: &Asset, lease: &LeaseStatus) -> u64 {
+    due_date(env, lease) + asset.grace_period
+}
+
+// Record funds that could not be settled on a lease and alert off-chain monitors
+pub(crate) fn record_shortfall(env: &Env, lease_id: u64, shortfall: i128) -> Result<(), RentError> {
+    let mut lease = leases::load_lease(env, lease_id)?;
+    lease.shortfall += shortfall;
+    leases::save_lease(env, &lease);
+    
+    events::publish(env, (symbol_short!("alert"), symbol_short!("shortfall")), (lease_id, shortfall));
+    log!(env, "Shortfall of {} recorded for lease ID: {}", shortfall, lease_id);
+    
+    Ok(())
+}
+
+// Active leases of `lessee` on assets of `owner` that have a payment due now. Leases shared
+// with co-tenants are left off: each tenant pays their own share of those.
+fn billed_leases(env: &Env, lessee: &Address, owner: &Address) -> Result<Vec<u64>, RentError> {
+    let now = env.ledger().timestamp();
+    let mut billed = Vec::new(env);
+    
+    for lease_id in leases::get_lessee_leases(env.clone(), lessee.clone())?.iter() {
+        let lease = leases::load_lease(env, lease_id)?;
+        if now >= lease.next_payment_time && lease.co_tenants.is_empty() && assets::load_asset(env, lease.asset_id)?.owner == *owner {
+            billed.push_back(lease_id);
+        }
+    }
+    
+    return Ok(billed);
+}
+
+// Build, store and publish the closing statement of a lease being closed; `refunded` is the
+// escrowed deposit about to be returned and `held` what stays escrowed for a dispute ruling.
+// Returns the statement hash: sha256 of its XDR.
+pub(crate) fn record_closing(env: &Env, lease: &LeaseStatus, asset: &Asset, refunded: i128, held: i128) -> BytesN<32> {
+    let deductions = match escrow::view_deposit_lock(env.clone(), lease.lease_id) {
+        Some(lock) => lock.claimed,
+        None => (lease.security_deposit - refunded - held).max(0),
+    };
+    let statement = ClosingStatement {
+        lease_id: lease.lease_id,
+        asset_id: lease.asset_id,
+        lessee: lease.lessee.clone(),
+        owner: asset.owner.clone(),
+        payment_token: asset.payment_token.clone(),
+        closed_at: env.ledger().timestamp(),
+        total_paid: lease.total_paid,
+        payments_made: lease.payments_made,
+        late_fees: lease.late_fees_paid,
+        waived_fees: view_late_fee_waivers(env.clone(), lease.lease_id).iter().map(|waiver| waiver.amount).sum(),
+        deposit: lease.security_deposit,
+        deductions: deductions,
+        refunded: refunded,
+        held: held,
+        shortfall: lease.shortfall,
+    };
+    let hash: BytesN<32> = env.crypto().sha256(&statement.clone().to_xdr(env)).to_bytes();
+    
+    let key = ClosingBook::Closing(lease.lease_id);
+    env.storage().persistent().set(&key, &(statement, hash.clone()));
+    admin::bump_entry(env, &key);
+    
+    events::publish(env, (symbol_short!("lease"), symbol_short!("settled"), lease.lessee.clone()), (lease.lease_id, hash.clone()));
+    
+    return hash;
+}
+
+// Check that the asset's payment token will move rent from `payer` into the contract. If the
+// payer is frozen, the refusal is recorded on the lease and raised as a ("alert", "blocked")
+// event instead of aborting inside the token; a later payment that can go through clears it.
+// Returns whether the payment is blocked.
+pub(crate) fn payment_blocked(env: &Env, lease_id: u64, payer: &Address, asset: &Asset, amount: i128) -> bool {
+    let key = BlockBook::PaymentBlocked(lease_id);
+    if xlm::authorized(env, &xlm::payment_token(asset), payer) {
+        env.storage().persistent().remove(&key);
+        return false;
+    }
+    
+    let block = PaymentBlock {
+        blocked_at: env.ledger().timestamp(),
+        payer: payer.clone(),
+        token: asset.payment_token.clone(),
+        amount: amount,
+    };
+    env.storage().persistent().set(&key, &block);
+    admin::bump_entry(env, &key);
+    
+    events::publish(env, (symbol_short!("alert"), symbol_short!("blocked"), payer.clone()), (lease_id, amount));
+    log!(env, "Payment blocked by the token for lease ID: {}", lease_id);
+    
+    return true;
+}
+
+// View the payment a token last refused on a lease, if it has not been cleared since
+pub fn view_payment_block(env: Env, lease_id: u64) -> Option<PaymentBlock> {
+    env.storage().persistent().get(&BlockBook::PaymentBlocked(lease_id))
+}
+
+// View the closing statement of an ended lease and its hash, if it has been closed
+pub fn view_closing_statement(env: Env, lease_id: u64) -> Option<(ClosingStatement, BytesN<32>)> {
+    env.storage().persistent().get(&ClosingBook::Closing(lease_id))
+}
@@ -0,0 +1,91 @@
+use soroban_sdk::contracttype;
+
+pub use common::{InterestPenalty, PenaltyPolicy};
+
+use crate::LeaseStatus;
+
+// Number of started payment periods elapsed since the payment fell due at `due`
+pub fn periods_late(lease: &LeaseStatus, due: u64, now: u64) -> u64 {
+    if now <= due {
+        return 0;
+    }
+    if lease.payment_frequency == 0 {
+        return 1;
+    }
+    (now - due - 1) / lease.payment_frequency + 1
+}
+
+// Penalty, in base units of the payment token, on top of `amount_due` when paying at `now` for
+// a payment due at `due`; nothing is charged within `grace` seconds of the due date
+pub fn late_fee(policy: &PenaltyPolicy, lease: &LeaseStatus, amount_due: i128, due: u64, grace: u64, now: u64) -> i128 {
+    let periods = periods_late(lease, due, now);
+    if periods == 0 || now <= due.saturating_add(grace) {
+        return 0;
+    }
+    
+    match policy {
+        PenaltyPolicy::None | PenaltyPolicy::Strict => 0,
+        PenaltyPolicy::Flat(fee) => *fee,
+        PenaltyPolicy::Interest(interest) => {
+            let fee = amount_due * interest.rate_bps as i128 * periods as i128 / 10_000;
+            fee.min(interest.cap)
+        }
+    }
+}
+
+// Whether the lessee may still cure a late lease by paying
+pub fn accepts_payment(policy: &PenaltyPolicy, lease: &LeaseStatus) -> bool {
+    !(lease.is_overdue && *policy == PenaltyPolicy::Strict)
+}
+
+// How far a late-payment notification has escalated, in order of severity
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord)]
+pub enum EscalationLevel {
+    None,           // Lease is not behind on payments
+    Reminder,       // Friendly reminder that a payment is late
+    FormalNotice,   // Formal notice of non-payment
+    DefaultWarning, // Final warning before the lease is treated as in default
+}
+
+// Seconds past the due date at which each escalation level is reached
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct EscalationSchedule {
+    pub reminder_after: u64, // Seconds late before a reminder
+    pub notice_after: u64,   // Seconds late before a formal notice
+    pub warning_after: u64,  // Seconds late before a default warning
+}
+
+// Escalation state of an overdue lease and when each level was reached (0 if not yet)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LateNotice {
+    pub level: EscalationLevel, // Highest level reached for the current late payment
+    pub reminder_at: u64,       // When the reminder was issued
+    pub notice_at: u64,         // When the formal notice was issued
+    pub warning_at: u64,        // When the default warning was issued
+}
+
+// Whether the thresholds only ever move forward
+pub fn is_valid_schedule(schedule: &EscalationSchedule) -> bool {
+    schedule.reminder_after <= schedule.notice_after && schedule.notice_after <= schedule.warning_after
+}
+
+// Level a payment due at `due` should be at when checked at `now`
+pub fn escalation_level(schedule: &EscalationSchedule, due: u64, now: u64) -> EscalationLevel {
+    if now <= due {
+        return EscalationLevel::None;
+    }
+    
+    let late_by = now - due;
+    if late_by > schedule.warning_after {
+        EscalationLevel::DefaultWarning
+    } else if late_by > schedule.notice_after {
+        EscalationLevel::FormalNotice
+    } else if late_by > schedule.reminder_after {
+        EscalationLevel::Reminder
+    } else {
+        EscalationLevel::None
+    }
+}
@@ -0,0 +1,50 @@
+pub use common::PaymentPolicy;
+
+use crate::LeaseStatus;
+
+// Amount, in base units of the payment token, the lessee owes if paying at `now`
+pub fn amount_due(lease: &LeaseStatus, now: u64) -> i128 {
+    match lease.payment_policy {
+        PaymentPolicy::FixedPeriod | PaymentPolicy::RentToOwn(_) => lease.period_payment,
+        PaymentPolicy::Streaming => {
+            if lease.payment_frequency == 0 {
+                return 0;
+            }
+            let elapsed = now.saturating_sub(lease.last_payment_time);
+            lease.period_payment * elapsed as i128 / lease.payment_frequency as i128
+        }
+        PaymentPolicy::Metered(rate) => lease.metered_units as i128 * rate,
+    }
+}
+
+// Apply a payment of `amount_due` at `now` to the lease schedule and totals
+pub fn apply_payment(lease: &mut LeaseStatus, amount: i128, now: u64) {
+    lease.last_payment_time = now;
+    lease.next_payment_time = now + lease.payment_frequency;
+    lease.total_paid += amount;
+    lease.payments_made += 1;
+    
+    if let PaymentPolicy::Metered(_) = lease.payment_policy {
+        lease.metered_units = 0;
+    }
+    escalate_rent(lease);
+}
+
+// Raise period_payment by the lease's escalation clause once each escalation_interval periods
+// have been paid; the period paid at signing counts towards the first interval
+fn escalate_rent(lease: &mut LeaseStatus) {
+    if lease.escalation_bps == 0 || lease.escalation_interval == 0 {
+        return;
+    }
+    if (lease.payments_made + 1).is_multiple_of(lease.escalation_interval as u64) {
+        lease.period_payment += lease.period_payment * lease.escalation_bps as i128 / 10_000;
+    }
+}
+
+// Whether the lessee has earned ownership of the asset under a rent-to-own policy
+pub fn ownership_earned(lease: &LeaseStatus) -> bool {
+    match lease.payment_policy {
+        PaymentPolicy::RentToOwn(payments) => lease.payments_made >= payments,
+        _ => false,
+    }
+}
@@ -0,0 +1,91 @@
+use soroban_sdk::{contracttype, log, symbol_short, Address, Env};
+
+use crate::storage_keys::{DataKey, RewardBook};
+use crate::accounts::{self, SubAccount};
+use crate::{admin, events, xlm, RentError};
+
+// What a keeper earns for a maintenance call that did its job: `flat` base units plus `bps`
+// of any payment it moved, at most `cap` per call (0 for no cap). Rewards are taken from the
+// platform fees held in the lease's payment token, and shrink to what those fees can cover.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct KeeperReward {
+    pub flat: i128, // Base units per successful call
+    pub bps: u32,  // Share of the payment collected, in basis points
+    pub cap: i128,  // Most base units one call can earn (0 for no cap)
+}
+
+// Set the reward paid to keepers for collecting due payments and settling expired leases
+pub fn set_keeper_reward(env: Env, admin: Address, flat: i128, bps: u32, cap: i128) -> Result<(), RentError> {
+    admin::require_admin(&env, &admin)?;
+    xlm::require_amount(&env, flat)?;
+    xlm::require_amount(&env, cap)?;
+    
+    if bps > 10_000 {
+        log!(&env, "Keeper reward exceeds 100% of the payment");
+        return Err(RentError::InvalidAmount);
+    }
+    env.storage().instance().set(&DataKey::KeeperReward, &KeeperReward { flat: flat, bps: bps, cap: cap });
+    
+    log!(&env, "Keeper reward set to {} plus {} bps, capped at {}", flat, bps, cap);
+    
+    Ok(())
+}
+
+// View the keeper reward; none is paid until the admin sets one
+pub fn view_keeper_reward(env: Env) -> KeeperReward {
+    env.storage().instance().get(&DataKey::KeeperReward).unwrap_or(KeeperReward { flat: 0, bps: 0, cap: 0 })
+}
+
+// View the rewards a keeper has earned in one token and not yet claimed, in base units
+pub fn view_keeper_rewards(env: Env, keeper: Address, token: Address) -> i128 {
+    env.storage().persistent().get(&RewardBook::KeeperPending(keeper, token)).unwrap_or(0)
+}
+
+// Keeper claims every reward they have earned in `token`; returns the amount paid
+pub fn claim_keeper_rewards(env: Env, keeper: Address, token: Address) -> Result<i128, RentError> {
+    keeper.require_auth();
+    
+    let amount = view_keeper_rewards(env.clone(), keeper.clone(), token.clone());
+    if amount == 0 {
+        log!(&env, "No keeper rewards to claim");
+        return Err(RentError::InvalidAmount);
+    }
+    
+    env.storage().persistent().remove(&RewardBook::KeeperPending(keeper.clone(), token.clone()));
+    let reward_token = xlm::token(&token);
+    accounts::debit(&env, SubAccount::RewardsPool, &token, amount)?;
+    xlm::transfer(&env, &reward_token, &env.current_contract_address(), &keeper, amount)?;
+    
+    events::publish(&env, (symbol_short!("keeper"), symbol_short!("claimed"), keeper), (token, amount));
+    
+    return Ok(amount);
+}
+
+// Reward `keeper` for a maintenance call on a lease that moved `payment` base units of
+// `token`, moving the reward out of the platform fees into the rewards pool until claimed.
+// Returns the reward.
+pub(crate) fn reward_keeper(env: &Env, keeper: &Address, lease_id: u64, token: &xlm::Token, payment: i128) -> Result<i128, RentError> {
+    let config = view_keeper_reward(env.clone());
+    let mut reward = config.flat + payment * config.bps as i128 / 10_000;
+    if config.cap > 0 {
+        reward = reward.min(config.cap);
+    }
+    let fees = accounts::view_sub_account(env.clone(), SubAccount::PlatformFees, token.address.clone());
+    reward = reward.min(fees);
+    if reward == 0 {
+        return Ok(0);
+    }
+    
+    let units = reward;
+    accounts::debit(env, SubAccount::PlatformFees, &token.address, units)?;
+    accounts::credit(env, SubAccount::RewardsPool, &token.address, units);
+    let key = RewardBook::KeeperPending(keeper.clone(), token.address.clone());
+    let pending = view_keeper_rewards(env.clone(), keeper.clone(), token.address.clone());
+    env.storage().persistent().set(&key, &(pending + reward));
+    admin::bump_entry(env, &key);
+    
+    events::publish(env, (symbol_short!("keeper"), symbol_short!("reward"), keeper.clone()), (lease_id, token.address.clone(), reward));
+    
+    return Ok(reward);
+}
@@ -0,0 +1,560 @@
+use soroban_sdk::{contracttype, log, symbol_short, Address, BytesN, Env, Vec};
+
+use crate::calendar::ReportingPeriod;
+use crate::penalty::EscalationLevel;
+use crate::merkle::{StateCommitment, StateRootBuild};
+use crate::storage_keys::{DataKey, FinancialsBook, ReportBook, TokenStatsBook, ReputationBook};
+use crate::{calendar, events, merkle, admin, assets, leases, payments, escrow, AssetStats, RentError, DepositPosition};
+
+// Money that has moved through the contract in one payment token, in base units
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TokenStats {
+    pub processed: i128, // Rent, late fees and deposit claims paid in this token
+    pub late_fees: i128, // Late fees paid in this token
+}
+
+// Lifetime money flows of a single asset, updated as they happen
+#[contracttype]
+#[derive(Clone)]
+pub struct AssetFinancials {
+    pub rent_collected: i128,    // Rent paid by lessees in the asset's payment token, excluding late fees
+    pub late_fees: i128,         // Late fees paid by lessees in the asset's payment token
+    pub deposits_retained: i128, // Deposit kept by the owner through claims, in the asset's payment token
+    pub vacancies: u64,         // Number of times a lease ended and the asset became vacant
+    pub platform_fees: i128,     // Listing and boost fees in stroops paid by the owner
+}
+
+// Track record of an address across every lease it has been party to, as lessee or owner
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Reputation {
+    pub on_time_payments: u64,      // Rent payments made by their due date (as lessee)
+    pub late_payments: u64,         // Payments flagged overdue or made after their due date (as lessee)
+    pub completed_leases: u64,      // Leases closed in good standing (as lessee or owner)
+    pub defaults: u64,              // Leases terminated in default (as lessee)
+    pub applications_answered: u64, // Applications approved or rejected (as owner)
+    pub applications_expired: u64,  // Applications left unanswered past their response deadline (as owner)
+    pub total_response_time: u64,   // Seconds taken to answer those applications, summed (as owner)
+}
+
+// Everything a landlord page needs, assembled in a single read
+#[contracttype]
+#[derive(Clone)]
+pub struct OwnerDashboard {
+    pub owner: Address,                // Owner the dashboard is for
+    pub asset_count: u64,              // Assets registered by the owner
+    pub active_leases: u64,            // Owner's assets currently leased
+    pub rent_collected: i128,           // Lifetime rent in stroops across the owner's assets
+    pub overdue_leases: Vec<u64>,      // Active leases marked overdue
+    pub upcoming_expirations: Vec<u64>, // Active leases ending within DASHBOARD_HORIZON
+    pub pending_earnings: i128,         // Stroops currently due from lessees and not yet paid
+}
+
+// One active lease as shown on a tenant's dashboard
+#[contracttype]
+#[derive(Clone)]
+pub struct LesseeLeaseSummary {
+    pub lease_id: u64,          // Lease identifier
+    pub asset_id: u64,          // Leased asset
+    pub amount_due: i128,        // Stroops due if paid now, including late fees
+    pub next_payment_time: u64, // When the next payment is due
+    pub end_time: u64,          // Lease end timestamp
+    pub is_overdue: bool,       // Whether the lease is marked overdue
+}
+
+// Everything a wallet "my rent" view needs, assembled in a single read
+#[contracttype]
+#[derive(Clone)]
+pub struct LesseeDashboard {
+    pub lessee: Address,                  // Lessee the dashboard is for
+    pub leases: Vec<LesseeLeaseSummary>,  // Active leases with what is due on each
+    pub total_due: i128,                   // Stroops due across all active leases
+    pub xlm_deposits: i128,                // Stroops of deposits securing active leases (collected or allowance-locked)
+    pub split_deposits: Vec<DepositPosition>, // Token positions held as split deposits
+}
+
+// One lease behind on rent, as listed on an owner's delinquency report
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DelinquencyEntry {
+    pub lease_id: u64,               // Lease identifier
+    pub asset_id: u64,               // Leased asset
+    pub lessee: Address,             // Lessee behind on rent
+    pub days_past_due: u64,          // Whole days since the missed due date
+    pub amount_outstanding: i128,     // Rent plus late fees due if paid now
+    pub escalation: EscalationLevel, // Highest late notice issued so far
+    pub has_guarantor: bool,         // Whether a guarantor backs the lease
+}
+
+// Where a lease ending soon stands in renewal negotiations
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RenewalStatus {
+    Open,            // Neither auto-renewal nor non-renewal notice; still to be negotiated
+    AutoRenewing,    // Lessee opted in to auto-renewal and no notice has been filed
+    NonRenewalFiled, // Either party gave notice; the asset will come vacant
+    Holdover,        // Term has ended and the lessee is holding over
+}
+
+// One lease in an owner's renewal pipeline
+#[contracttype]
+#[derive(Clone)]
+pub struct RenewalPipelineEntry {
+    pub lease_id: u64,         // Lease identifier
+    pub asset_id: u64,         // Leased asset
+    pub lessee: Address,       // Current lessee
+    pub end_time: u64,         // End of the current term
+    pub status: RenewalStatus, // Renewal negotiation status
+    pub vacates_at: u64,       // When the asset is projected to come vacant (0 if not expected to)
+}
+
+// Record and index sizes held in contract storage, for monitoring growth and TTL budgets
+#[contracttype]
+#[derive(Clone)]
+pub struct StorageMetrics {
+    pub asset_count: u64,       // Asset records
+    pub lease_count: u64,       // Lease records, active or ended
+    pub archived_leases: u64,   // Ended lease records still held in storage
+    pub settlements: u64,       // Consolidated settlement records
+    pub featured_listings: u32, // Entries in the featured-listing index
+    pub holidays: u32,          // Entries in the holiday calendar
+    pub blackout_windows: u64,  // Blackout windows across all assets
+    pub external_ids: u64,      // Asset and lease external IDs (each also holds a reverse-lookup entry)
+    pub owner_index_entries: u64,    // Asset IDs across every owner's index
+    pub lessee_index_entries: u64,   // Lease IDs across every lessee's index
+    pub category_index_entries: u64, // Asset IDs across every category index
+    pub tag_index_entries: u64,      // Asset IDs across every tag index
+}
+
+// Platform-wide money flows over one reporting period
+#[contracttype]
+#[derive(Clone)]
+pub struct ReportingBucket {
+    pub period_start: u64,           // First second of the period
+    pub period_end: u64,             // First second after the period
+    pub financials: AssetFinancials, // Flows across all assets during the period
+    pub closed: bool,                // Frozen for the compliance archive
+}
+
+// How far ahead dashboards look for expiring leases (30 days)
+const DASHBOARD_HORIZON: u64 = 30 * 24 * 60 * 60;
+
+// Choose how reporting periods are bounded; applies to buckets opened from now on
+pub fn set_reporting_period(env: Env, admin: Address, period: ReportingPeriod) -> Result<(), RentError> {
+    admin::require_admin(&env, &admin)?;
+    
+    if let ReportingPeriod::FiscalQuarter(first_month) = period {
+        if first_month == 0 || first_month > 12 {
+            log!(&env, "Fiscal year must start in months 1-12");
+            return Err(RentError::InvalidWindow);
+        }
+    }
+    env.storage().instance().set(&DataKey::ReportingPeriod, &period);
+    
+    log!(&env, "Reporting period updated");
+    
+    Ok(())
+}
+
+// View how reporting periods are bounded (default: calendar months)
+pub fn view_reporting_period(env: Env) -> ReportingPeriod {
+    env.storage().instance().get(&DataKey::ReportingPeriod).unwrap_or(ReportingPeriod::CalendarMonth)
+}
+
+// View the bucket of the reporting period starting at `period_start`, if it saw any activity
+pub fn view_reporting_bucket(env: Env, period_start: u64) -> Option<ReportingBucket> {
+    env.storage().persistent().get(&ReportBook::Bucket(period_start))
+}
+
+// Freeze the bucket of the last completed reporting period and emit its summary for
+// compliance archives (callable by anyone, e.g. a keeper)
+pub fn close_reporting_period(env: Env) -> Result<ReportingBucket, RentError> {
+    let period = view_reporting_period(env.clone());
+    let (current_start, _) = calendar::period_bounds(period, env.ledger().timestamp());
+    if current_start == 0 {
+        log!(&env, "No reporting period has completed yet");
+        return Err(RentError::InvalidWindow);
+    }
+    let (start, end) = calendar::period_bounds(period, current_start - 1);
+    
+    let mut bucket = open_bucket(&env, start, end);
+    if bucket.closed {
+        log!(&env, "Reporting period already closed");
+        return Err(RentError::PeriodClosed);
+    }
+    bucket.closed = true;
+    let key = ReportBook::Bucket(start);
+    env.storage().persistent().set(&key, &bucket);
+    admin::bump_entry(&env, &key);
+    
+    let f = &bucket.financials;
+    events::publish(&env, 
+        (symbol_short!("report"), symbol_short!("closed")),
+        (start, end, f.rent_collected, f.late_fees, f.deposits_retained, f.platform_fees, f.vacancies),
+    );
+    
+    return Ok(bucket);
+}
+
+// View asset stats
+pub fn view_asset_stats(env: Env) -> AssetStats {
+    env.storage().instance().get(&DataKey::Stats).unwrap_or(AssetStats {
+        active_leases: 0,
+        completed_leases: 0,
+        overdue_leases: 0,
+        total_leases: 0,
+        total_xlm_processed: 0,
+        total_late_fees: 0,
+    })
+}
+
+// View the totals paid in one payment token
+pub fn view_token_stats(env: Env, token: Address) -> TokenStats {
+    env.storage().persistent().get(&TokenStatsBook::TokenStats(token)).unwrap_or(TokenStats {
+        processed: 0,
+        late_fees: 0,
+    })
+}
+
+// Count money moved in `token`: always in its TokenStats, and in the caller's copy of the
+// global XLM counters when it is the native token
+pub(crate) fn record_token_flow(env: &Env, stats: &mut AssetStats, token: &Address, processed: i128, late_fees: i128) {
+    if admin::view_native_token(env.clone()).as_ref() == Some(token) {
+        stats.total_xlm_processed += processed;
+        stats.total_late_fees += late_fees;
+    }
+    
+    let key = TokenStatsBook::TokenStats(token.clone());
+    let mut totals = view_token_stats(env.clone(), token.clone());
+    totals.processed += processed;
+    totals.late_fees += late_fees;
+    env.storage().persistent().set(&key, &totals);
+    admin::bump_entry(env, &key);
+}
+
+// View an asset's lifetime rent, fees, retained deposits and vacancies
+pub fn view_asset_financials(env: Env, asset_id: u64) -> AssetFinancials {
+    env.storage().persistent().get(&FinancialsBook::Financials(asset_id)).unwrap_or(AssetFinancials {
+        rent_collected: 0,
+        late_fees: 0,
+        deposits_retained: 0,
+        vacancies: 0,
+        platform_fees: 0,
+    })
+}
+
+// View an address's reputation, e.g. an applicant's before approving them or an owner's before
+// applying; all zero for an address that has never been party to a lease or application
+pub fn view_reputation(env: Env, address: Address) -> Reputation {
+    env.storage().persistent().get(&ReputationBook::Reputation(address)).unwrap_or_default()
+}
+
+// Apply an incremental change to an address's reputation
+pub(crate) fn update_reputation(env: &Env, address: &Address, update: impl Fn(&mut Reputation)) {
+    let mut reputation = view_reputation(env.clone(), address.clone());
+    update(&mut reputation);
+    
+    let key = ReputationBook::Reputation(address.clone());
+    env.storage().persistent().set(&key, &reputation);
+    admin::bump_entry(env, &key);
+}
+
+// Owner dashboard: per-owner stats, overdue leases, upcoming expirations and pending earnings
+pub fn get_owner_dashboard(env: Env, owner: Address) -> Result<OwnerDashboard, RentError> {
+    let now = env.ledger().timestamp();
+    let assets = assets::get_owner_assets(env.clone(), owner.clone());
+    let mut dashboard = OwnerDashboard {
+        owner: owner,
+        asset_count: assets.len() as u64,
+        active_leases: 0,
+        rent_collected: 0,
+        overdue_leases: Vec::new(&env),
+        upcoming_expirations: Vec::new(&env),
+        pending_earnings: 0,
+    };
+    
+    for asset_id in assets.iter() {
+        dashboard.rent_collected += view_asset_financials(env.clone(), asset_id).rent_collected;
+        
+        let asset = assets::load_asset(&env, asset_id)?;
+        if asset.current_lease_id == 0 {
+            continue;
+        }
+        let lease = leases::load_lease(&env, asset.current_lease_id)?;
+        if !lease.is_active {
+            continue;
+        }
+        
+        dashboard.active_leases += 1;
+        if lease.is_overdue {
+            dashboard.overdue_leases.push_back(lease.lease_id);
+        }
+        if lease.end_time <= now + DASHBOARD_HORIZON {
+            dashboard.upcoming_expirations.push_back(lease.lease_id);
+        }
+        if now >= lease.next_payment_time {
+            dashboard.pending_earnings += payments::amount_due_now(&env, &asset, &lease, now)?;
+        }
+    }
+    
+    return Ok(dashboard);
+}
+
+// Owner's active leases past their due date, most severe first: by escalation level issued,
+// then days past due, then amount outstanding
+pub fn get_delinquency_report(env: Env, owner: Address) -> Result<Vec<DelinquencyEntry>, RentError> {
+    let now = env.ledger().timestamp();
+    let mut report: Vec<DelinquencyEntry> = Vec::new(&env);
+    
+    for asset_id in assets::get_owner_assets(env.clone(), owner).iter() {
+        let asset = assets::load_asset(&env, asset_id)?;
+        if asset.current_lease_id == 0 {
+            continue;
+        }
+        let lease = leases::load_lease(&env, asset.current_lease_id)?;
+        let due = payments::due_date(&env, &lease);
+        if !lease.is_active || now < due {
+            continue;
+        }
+        
+        let entry = DelinquencyEntry {
+            lease_id: lease.lease_id,
+            asset_id: asset_id,
+            lessee: lease.lessee.clone(),
+            days_past_due: (now - due) / calendar::DAY,
+            amount_outstanding: payments::amount_due_now(&env, &asset, &lease, now)?,
+            escalation: leases::view_late_notice(env.clone(), lease.lease_id).level,
+            has_guarantor: leases::view_guarantor(env.clone(), lease.lease_id).is_some(),
+        };
+        
+        // Insert ahead of the first less severe entry, keeping the report sorted
+        let severity = |e: &DelinquencyEntry| (e.escalation, e.days_past_due, e.amount_outstanding);
+        let position = report.iter().position(|other| severity(&other) < severity(&entry)).unwrap_or(report.len() as usize);
+        report.insert(position as u32, entry);
+    }
+    
+    return Ok(report);
+}
+
+// Owner's active leases ending within `horizon` seconds (plus any in holdover), with their
+// renewal status and projected vacancy, so the renewal funnel can be worked from chain state
+pub fn get_renewal_pipeline(env: Env, owner: Address, horizon: u64) -> Result<Vec<RenewalPipelineEntry>, RentError> {
+    let now = env.ledger().timestamp();
+    let mut pipeline = Vec::new(&env);
+    
+    for asset_id in assets::get_owner_assets(env.clone(), owner).iter() {
+        let asset = assets::load_asset(&env, asset_id)?;
+        if asset.current_lease_id == 0 {
+            continue;
+        }
+        let lease = leases::load_lease(&env, asset.current_lease_id)?;
+        if !lease.is_active || (!lease.in_holdover && lease.end_time > now + horizon) {
+            continue;
+        }
+        
+        let (status, vacates_at) = if lease.in_holdover {
+            (RenewalStatus::Holdover, lease.holdover_end)
+        } else if lease.non_renewal_filed {
+            (RenewalStatus::NonRenewalFiled, lease.end_time)
+        } else if lease.auto_renew {
+            (RenewalStatus::AutoRenewing, 0)
+        } else if asset.holdover_bps == 0 {
+            (RenewalStatus::Open, lease.end_time)
+        } else {
+            (RenewalStatus::Open, 0)
+        };
+        
+        pipeline.push_back(RenewalPipelineEntry {
+            lease_id: lease.lease_id,
+            asset_id: asset_id,
+            lessee: lease.lessee,
+            end_time: lease.end_time,
+            status: status,
+            vacates_at: vacates_at,
+        });
+    }
+    
+    return Ok(pipeline);
+}
+
+// Tenant dashboard: active leases with amounts due and the deposits securing them
+pub fn get_lessee_dashboard(env: Env, lessee: Address) -> Result<LesseeDashboard, RentError> {
+    let now = env.ledger().timestamp();
+    let mut dashboard = LesseeDashboard {
+        lessee: lessee.clone(),
+        leases: Vec::new(&env),
+        total_due: 0,
+        xlm_deposits: 0,
+        split_deposits: Vec::new(&env),
+    };
+    
+    for lease_id in leases::get_lessee_leases(env.clone(), lessee)?.iter() {
+        let lease = leases::load_lease(&env, lease_id)?;
+        let asset = assets::load_asset(&env, lease.asset_id)?;
+        
+        let amount_due = if now >= lease.next_payment_time {
+            payments::amount_due_now(&env, &asset, &lease, now)?
+        } else {
+            0
+        };
+        dashboard.total_due += amount_due;
+        dashboard.leases.push_back(LesseeLeaseSummary {
+            lease_id: lease_id,
+            asset_id: lease.asset_id,
+            amount_due: amount_due,
+            next_payment_time: lease.next_payment_time,
+            end_time: lease.end_time,
+            is_overdue: lease.is_overdue,
+        });
+        
+        dashboard.xlm_deposits += match escrow::view_deposit_lock(env.clone(), lease_id) {
+            Some(lock) => lock.amount - lock.claimed,
+            None => lease.security_deposit,
+        };
+        for position in escrow::view_split_deposit(env.clone(), lease_id).iter() {
+            dashboard.split_deposits.push_back(position);
+        }
+    }
+    
+    return Ok(dashboard);
+}
+
+// Build a Merkle root over all asset and lease records (callable by the admin or any
+// keeper) so individual records can later be proven against it. Each call folds in up to
+// `limit` (capped at MAX_PAGE) more records; the records to cover are fixed by the first
+// call, and each is hashed as it stands when its batch runs. Returns how many records are
+// still to fold in, storing the commitment once that reaches 0.
+pub fn commit_state_root(env: Env, limit: u32) -> Result<u64, RentError> {
+    if limit == 0 {
+        log!(&env, "Batch limit must be positive");
+        return Err(RentError::InvalidAmount);
+    }
+    
+    let mut build = env.storage().instance().get(&DataKey::StateRootBuild).unwrap_or_else(|| StateRootBuild {
+        asset_count: assets::get_asset_count(env.clone()),
+        lease_count: leases::get_lease_count(env.clone()),
+        next_leaf: 0,
+        frontier: Vec::new(&env),
+    });
+    let leaf_count = build.asset_count + build.lease_count;
+    let last_leaf = leaf_count.min(build.next_leaf + limit.min(assets::MAX_PAGE) as u64);
+    
+    for i in build.next_leaf..last_leaf {
+        let leaf = if i < build.asset_count {
+            merkle::leaf_hash(&env, &assets::load_asset(&env, i + 1)?)
+        } else {
+            merkle::leaf_hash(&env, &leases::load_lease(&env, i - build.asset_count + 1)?)
+        };
+        merkle::push_leaf(&env, &mut build.frontier, leaf);
+    }
+    build.next_leaf = last_leaf;
+    
+    if last_leaf < leaf_count {
+        env.storage().instance().set(&DataKey::StateRootBuild, &build);
+        admin::bump_instance(&env);
+        return Ok(leaf_count - last_leaf);
+    }
+    
+    let commitment = StateCommitment {
+        root: merkle::frontier_root(&env, &build.frontier),
+        asset_count: build.asset_count,
+        lease_count: build.lease_count,
+        timestamp: env.ledger().timestamp(),
+        ledger: env.ledger().sequence(),
+    };
+    env.storage().instance().remove(&DataKey::StateRootBuild);
+    env.storage().instance().set(&DataKey::StateRoot, &commitment);
+    
+    events::publish(&env, (symbol_short!("state"), symbol_short!("root")), commitment.root.clone());
+    log!(&env, "State root committed over {} assets and {} leases", commitment.asset_count, commitment.lease_count);
+    admin::bump_instance(&env);
+    
+    return Ok(0);
+}
+
+// View the latest state commitment, if any
+pub fn view_state_root(env: Env) -> Option<StateCommitment> {
+    env.storage().instance().get(&DataKey::StateRoot)
+}
+
+// Verify a record's leaf hash at `index` against the latest committed root
+pub fn verify_state_proof(env: Env, leaf: BytesN<32>, index: u64, proof: Vec<BytesN<32>>) -> bool {
+    match view_state_root(env.clone()) {
+        Some(commitment) => merkle::verify(&env, &commitment.root, &leaf, index, commitment.asset_count + commitment.lease_count, &proof),
+        None => false,
+    }
+}
+
+// View how many records and index entries the contract is holding. Everything not counted
+// by an ID counter or a single list comes from running counts, so no record is read.
+pub fn get_storage_metrics(env: Env) -> Result<StorageMetrics, RentError> {
+    let featured: Vec<(u64, u64)> = env.storage().instance().get(&DataKey::Featured).unwrap_or(Vec::new(&env));
+    let mut metrics = storage_counts(&env);
+    metrics.asset_count = assets::get_asset_count(env.clone());
+    metrics.lease_count = leases::get_lease_count(env.clone());
+    metrics.settlements = env.storage().instance().get(&DataKey::SettlementCount).unwrap_or(0);
+    metrics.featured_listings = featured.len();
+    metrics.holidays = admin::view_holidays(env.clone()).len();
+    
+    return Ok(metrics);
+}
+
+// Running counts of archived leases, blackouts, external IDs and index entries
+fn storage_counts(env: &Env) -> StorageMetrics {
+    env.storage().instance().get(&DataKey::StorageCounts).unwrap_or(StorageMetrics {
+        asset_count: 0,
+        lease_count: 0,
+        archived_leases: 0,
+        settlements: 0,
+        featured_listings: 0,
+        holidays: 0,
+        blackout_windows: 0,
+        external_ids: 0,
+        owner_index_entries: 0,
+        lessee_index_entries: 0,
+        category_index_entries: 0,
+        tag_index_entries: 0,
+    })
+}
+
+// Adjust the running counts as records and index entries come and go
+pub(crate) fn count_storage(env: &Env, update: impl Fn(&mut StorageMetrics)) {
+    let mut counts = storage_counts(env);
+    update(&mut counts);
+    env.storage().instance().set(&DataKey::StorageCounts, &counts);
+}
+
+// Apply an incremental change to an asset's financials and to the current reporting bucket
+pub(crate) fn update_financials(env: &Env, asset_id: u64, update: impl Fn(&mut AssetFinancials)) {
+    let mut financials = view_asset_financials(env.clone(), asset_id);
+    update(&mut financials);
+    let key = FinancialsBook::Financials(asset_id);
+    env.storage().persistent().set(&key, &financials);
+    admin::bump_entry(env, &key);
+    
+    let (start, end) = calendar::period_bounds(view_reporting_period(env.clone()), env.ledger().timestamp());
+    let mut bucket = open_bucket(env, start, end);
+    if !bucket.closed {
+        update(&mut bucket.financials);
+        let key = ReportBook::Bucket(start);
+        env.storage().persistent().set(&key, &bucket);
+        admin::bump_entry(env, &key);
+    }
+}
+
+// Load a reporting bucket, or an empty one for a period with no activity yet
+fn open_bucket(env: &Env, start: u64, end: u64) -> ReportingBucket {
+    view_reporting_bucket(env.clone(), start).unwrap_or(ReportingBucket {
+        period_start: start,
+        period_end: end,
+        financials: AssetFinancials {
+            rent_collected: 0,
+            late_fees: 0,
+            deposits_retained: 0,
+            vacancies: 0,
+            platform_fees: 0,
+        },
+        closed: false,
+    })
+}
@@ -0,0 +1,312 @@
+use soroban_sdk::{contracttype, Address, BytesN, Symbol};
+
+use crate::accounts::SubAccount;
+
+// Only DataKey, the jurisdiction rules and the internal ledger live in instance storage; every
+// per-record book is persistent, each entry bumped on write. All key enums still share one key
+// space per storage type, and a variant is stored as its bare name plus payload, so variant
+// names must stay unique across all of them.
+
+// Contract-wide singleton entries
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Stats,              // Global AssetStats
+    AssetCount,         // Last asset ID issued
+    LeaseCount,         // Last lease ID issued
+    Admin,              // Contract administrator allowed to change configuration
+    LandlordRegistry,   // Optional landlord-verification contract consulted by register_asset
+    NativeToken,        // Address of the wrapped-XLM Stellar Asset Contract used for all XLM transfers
+    EscrowContract,     // Escrow contract holding standard deposits on this contract's behalf
+    ListingFee,         // Listing fee charged by register_asset
+    Platform,           // Platform account receiving marketplace fees
+    BoostFee,           // Stroops charged per day of featured-listing boost
+    Featured,           // Featured listings as (asset_id, expires_at) pairs
+    TtlConfig,          // Minimum instance TTL policy kept by every state-changing call
+    EscalationSchedule, // Late-payment escalation thresholds applied to every lease
+    StateRoot,          // Latest Merkle commitment over asset and lease records
+    StateRootBuild,     // Merkle commitment being built across commit_state_root batches
+    StorageCounts,      // Running counts behind get_storage_metrics
+    Holidays,           // UTC day numbers on which payments never fall due
+    WeekendRollover,    // Whether weekend due dates roll to the next business day
+    SettlementCount,    // Last consolidated settlement ID issued
+    ApplicationCount,   // Last lease application ID issued
+    ReportingPeriod,    // How reporting periods are bounded for the aggregation buckets
+    EventSequence,      // Sequence number of the latest event emitted
+    Network,            // Network profile chosen at initialize, if any
+    Paused,             // Circuit breaker: lease creation and payments are refused while set
+    CreationFrozen,     // Kill switch: new leases and listings are refused while set
+    PlatformFeeBps,     // Share of each rent payment kept as a platform fee, in basis points
+    MaxConcurrentLeases, // Most active leases one lessee may hold across the platform (0 for no limit)
+    Arbiter,            // Account besides the admin allowed to rule on disputes
+    DisputeCount,       // Last dispute ID issued
+    ImportCursor,       // MigrationProgress of the records imported so far
+    ExportCursor,       // MigrationProgress of the records exported so far
+    KeeperReward,       // Reward paid to keepers for maintenance calls
+}
+
+// Mapping unique_id of asset to its LeaseStatus
+#[contracttype] 
+pub enum LeaseStatusBook { 
+    LeaseStatus(u64)
+}
+
+// Mapping asset_id to Asset, and to the ledger its latest lease was created in
+#[contracttype] 
+pub enum AssetBook { 
+    Asset(u64),
+    LeasedInLedger(u64),
+}
+
+// Mapping lease_id to its allowance-locked deposit (persistent)
+#[contracttype]
+pub enum DepositLockBook {
+    Lock(u64)
+}
+
+// Mapping lease_id to the deposit held in contract escrow, in the lease's payment token (persistent)
+#[contracttype]
+pub enum EscrowBook {
+    Escrowed(u64)
+}
+
+// Split deposit composition required by an asset, and positions held for a lease (persistent)
+#[contracttype]
+pub enum SplitDepositBook {
+    Required(u64), // asset_id -> Vec<DepositPosition> the lessee must post
+    Held(u64),     // lease_id -> Vec<DepositPosition> held by the contract
+}
+
+// Mutual sign-off terms and in-flight deposit releases (persistent)
+#[contracttype]
+pub enum ReleaseBook {
+    Window(u64),    // asset_id -> seconds the parties have to agree once a lease ends
+    Lease(u64),     // lease_id -> window captured when the lease opened
+    Pending(u64),   // lease_id -> DepositRelease awaiting sign-off
+    Unsettled(u64), // asset_id -> ended leases whose deposit awaits a release or ruling
+}
+
+// Mapping asset_id to its pending relist plan (persistent)
+#[contracttype]
+pub enum RelistBook {
+    Plan(u64)
+}
+
+// Lease applications, the assets that require one and the terms they are made under (persistent)
+#[contracttype]
+pub enum ApplicationBook {
+    Application(u64),       // application_id -> LeaseApplication
+    ScreeningRequired(u64), // asset_id -> set while the asset only leases through approved applications
+    Terms(u64),             // asset_id -> ApplicationTerms
+}
+
+// Mapping asset_id to its financials (persistent)
+#[contracttype]
+pub enum FinancialsBook {
+    Financials(u64)
+}
+
+// Mapping asset_id to its blackout windows (persistent)
+#[contracttype]
+pub enum BlackoutBook {
+    Blackouts(u64)
+}
+
+// Mapping lease_id to the escalation state of its current late payment (persistent)
+#[contracttype]
+pub enum EscalationBook {
+    Notice(u64)
+}
+
+// Tenant-protection rules per jurisdiction and the jurisdiction each asset falls under
+#[contracttype]
+pub enum JurisdictionBook {
+    CoolingOff(Symbol), // jurisdiction code -> seconds after a default warning before default may execute
+    Jurisdiction(u64),  // asset_id -> jurisdiction code (persistent)
+    DepositInterest(Symbol), // jurisdiction code -> yearly interest owed on held deposits, in basis points
+}
+
+// Owner-supplied external references and their reverse index, scoped per owner (persistent)
+#[contracttype]
+pub enum ExternalIdBook {
+    OfAsset(u64),                        // asset_id -> external ID
+    OfLease(u64),                        // lease_id -> external ID
+    AssetLookup(Address, BytesN<32>),    // (owner, external ID) -> asset_id
+    LeaseLookup(Address, BytesN<32>),    // (owner, external ID) -> lease_id
+}
+
+// Consolidated billing opt-ins and the settlements made under them (persistent)
+#[contracttype]
+pub enum BillingBook {
+    Consolidated(Address, Address), // (lessee, owner) -> whether their leases are billed together
+    Settlement(u64),                // settlement_id -> ConsolidatedSettlement
+}
+
+// Upcoming-payment reminder settings per asset and the last reminder sent per lease (persistent)
+#[contracttype]
+pub enum ReminderBook {
+    Cadence(u64),  // asset_id -> ReminderPolicy
+    LastSent(u64), // lease_id -> (due time reminded about, when the reminder was sent)
+}
+
+// Trial periods offered by assets, and the first rent held through a lease's trial (persistent)
+#[contracttype]
+pub enum TrialBook {
+    Trial(u64),    // asset_id -> trial period offered on new leases, in days
+    HeldRent(u64), // lease_id -> first period's rent held until the trial is over
+}
+
+// Mapping lease_id to the TerminationNotice given on it (persistent)
+#[contracttype]
+pub enum TerminationBook {
+    Termination(u64)
+}
+
+// Mapping asset_id to whether its owner must co-sign any transfer of a lease on it (persistent)
+#[contracttype]
+pub enum AssignmentBook {
+    ApprovalRequired(u64)
+}
+
+// Mapping lease_id to the guarantor backing it, and asset_id to how long rent must be past due
+// before the guarantor can be claimed from (persistent)
+#[contracttype]
+pub enum GuarantorBook {
+    Guarantor(u64),
+    ClaimThreshold(u64)
+}
+
+// Cure windows offered on overdue leases (persistent)
+#[contracttype]
+pub enum CureBook {
+    Period(u64),   // asset_id -> seconds after an overdue marking the lessee may cure it penalty-free
+    MarkedAt(u64), // lease_id -> when the lease was marked overdue within a cure period
+}
+
+// Mapping lease_id to its escrowed access-code handover (persistent)
+#[contracttype]
+pub enum HandoverBook {
+    Handover(u64)
+}
+
+// Late fees an owner has waived on a lease (persistent)
+#[contracttype]
+pub enum WaiverBook {
+    Waivers(u64),       // lease_id -> Vec<LateFeeWaiver>, oldest first
+    WaiverCredit(u64),  // lease_id -> waived amount not yet offset against a payment
+}
+
+// Mapping reporting period start to its aggregation bucket (persistent)
+#[contracttype]
+pub enum ReportBook {
+    Bucket(u64)
+}
+
+// Mapping a payment token to its TokenStats (persistent)
+#[contracttype]
+pub enum TokenStatsBook {
+    TokenStats(Address)
+}
+
+// Mapping an owner to the emergency contact acting for them while away (persistent)
+#[contracttype]
+pub enum VacationBook {
+    Delegate(Address)
+}
+
+// Concurrent lease caps and the running counts they are checked against (persistent)
+#[contracttype]
+pub enum LeaseLimitBook {
+    OwnerLimit(Address),       // owner -> most active leases one lessee may hold on their assets
+    Held(Address),             // tenant -> active leases they are on
+    HeldWith(Address, Address) // (tenant, owner) -> active leases they are on of that owner's assets
+}
+
+// Mapping lease_id to its closing statement and statement hash (persistent)
+#[contracttype]
+pub enum ClosingBook {
+    Closing(u64)
+}
+
+// Mapping lease_id to the payment a token last refused to move (persistent)
+#[contracttype]
+pub enum BlockBook {
+    PaymentBlocked(u64)
+}
+
+// Owner-defined custom terms, keyed by term name (persistent)
+#[contracttype]
+pub enum TermsBook {
+    AssetTerms(u64), // asset_id -> terms offered on new leases
+    LeaseTerms(u64), // lease_id -> terms frozen at lease creation
+    Canonical(u64),  // lease_id -> (CanonicalTerms, hash) currently binding the lease
+    Amendments(u64), // lease_id -> Vec<LeaseAmendment>, oldest first
+}
+
+// Per-party secondary indexes kept up to date on write (persistent)
+#[contracttype]
+pub enum IndexBook {
+    OwnerAssets(Address),  // owner -> IDs of the assets they own
+    LesseeLeases(Address), // lessee -> IDs of every lease they have held
+    CategoryIndex(Symbol), // category -> IDs of the assets filed under it (the default category has none)
+    TagIndex(Symbol),      // tag -> IDs of the assets carrying it
+}
+
+// Internal ledger of the funds the contract holds
+#[contracttype]
+pub enum AccountBook {
+    SubBalance(SubAccount, Address), // (sub-account, token) -> base units held
+    HeldTokens,                      // Every token the contract has taken into custody
+}
+
+// Due-date extensions requested and granted per lease (persistent)
+#[contracttype]
+pub enum ExtensionBook {
+    Extension(u64),      // lease_id -> latest DueDateExtension
+    ExtensionsUsed(u64), // lease_id -> when each granted extension was approved
+}
+
+// Disputes and the lease each one freezes (persistent)
+#[contracttype]
+pub enum DisputeBook {
+    Dispute(u64),     // dispute_id -> Dispute
+    OpenDispute(u64), // lease_id -> ID of the dispute open on it
+}
+
+// Mapping an address to its Reputation (persistent)
+#[contracttype]
+pub enum ReputationBook {
+    Reputation(Address)
+}
+
+// Mapping lease_id to what partial payments have covered of the current period, and to the
+// co-tenants who have paid their share of it (persistent)
+#[contracttype]
+pub enum PartialBook {
+    PartialCredit(u64),
+    SharesPaid(u64)
+}
+
+// Mapping lease_id to the PrepaidRent its lessee has paid ahead (persistent)
+#[contracttype]
+pub enum PrepaidBook {
+    Prepaid(u64)
+}
+
+// Mapping lease_id to the lessee's automatic payment authorization (persistent)
+#[contracttype]
+pub enum AutoPayBook {
+    AutoPay(u64)
+}
+
+// Mapping (keeper, token) to the rewards a keeper has earned and not yet claimed
+#[contracttype]
+pub enum RewardBook {
+    KeeperPending(Address, Address)
+}
+
+// Mapping (owner, token) to the rent held for the owner until they withdraw it
+#[contracttype]
+pub enum EarningsBook {
+    Withdrawable(Address, Address)
+}
@@ -0,0 +1,4302 @@
+#![cfg(test)]
+extern crate std;
+
+use super::*;
+use soroban_sdk::testutils::{Address as _, AuthorizedFunction, AuthorizedInvocation, Events as _, IssuerFlags, Ledger};
+use soroban_sdk::testutils::storage::{Instance as _, Persistent as _};
+use soroban_sdk::{symbol_short, vec, xdr::ToXdr, IntoVal, InvokeError, TryFromVal};
+use soroban_sdk::token::{StellarAssetClient, TokenClient};
+
+fn setup(env: &Env) -> (Address, RentPaymentContractClient<'_>, Address) {
+    env.mock_all_auths();
+    
+    let contract_id = env.register(RentPaymentContract, ());
+    let client = RentPaymentContractClient::new(env, &contract_id);
+    let admin = Address::generate(env);
+    client.initialize(&admin);
+    
+    let sac = env.register_stellar_asset_contract_v2(admin.clone());
+    client.set_native_token(&admin, &sac.address());
+    
+    let escrow_id = env.register(::escrow::EscrowContract, ());
+    ::escrow::EscrowContractClient::new(env, &escrow_id).initialize(&contract_id);
+    client.set_escrow_contract(&admin, &escrow_id);
+    
+    (contract_id, client, admin)
+}
+
+// Mint `amount` stroops of the native token to `to`
+fn fund(env: &Env, client: &RentPaymentContractClient<'_>, to: &Address, amount: i128) {
+    StellarAssetClient::new(env, &client.view_native_token().unwrap()).mint(to, &amount);
+}
+
+// Populate `count` assets of one owner, each with an active lease, writing records straight to
+// storage so bulk paths can be exercised at realistic volumes. One lease is opened through
+// the entrypoints and cloned, so the fixtures track every field the contract sets; a single
+// lessee holds them all. Leases with even IDs are already a period behind. Returns the owner.
+fn seed_leases(env: &Env, contract_id: &Address, client: &RentPaymentContractClient<'_>, count: u64) -> Address {
+    let owner = Address::generate(env);
+    let lessee = Address::generate(env);
+    fund(env, client, &lessee, 1_000);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(env, "Unit"),
+        &String::from_str(env, "Fixture"),
+        &10, &100, &100, &100_000, &0,
+    );
+    let lease_id = client.create_lease(&asset_id, &lessee, &10_000);
+    
+    env.cost_estimate().budget().reset_unlimited();
+    env.as_contract(contract_id, || {
+        let template_asset = assets::load_asset(env, asset_id).unwrap();
+        let template_lease = leases::load_lease(env, lease_id).unwrap();
+        let mut ids = soroban_sdk::vec![env, asset_id];
+        for id in asset_id + 1..asset_id + count {
+            ids.push_back(id);
+            let mut asset = template_asset.clone();
+            asset.asset_id = id;
+            asset.current_lease_id = id;
+            assets::save_asset(env, &asset);
+            
+            let mut lease = template_lease.clone();
+            lease.lease_id = id;
+            lease.asset_id = id;
+            if id % 2 == 0 {
+                lease.next_payment_time -= lease.payment_frequency;
+            }
+            leases::save_lease(env, &lease);
+        }
+        
+        let mut stats = stats::view_asset_stats(env.clone());
+        stats.active_leases += count - 1;
+        stats.total_leases += count - 1;
+        env.storage().instance().set(&storage_keys::DataKey::Stats, &stats);
+        env.storage().instance().set(&storage_keys::DataKey::AssetCount, &(asset_id + count - 1));
+        env.storage().instance().set(&storage_keys::DataKey::LeaseCount, &(lease_id + count - 1));
+        env.storage().persistent().set(&storage_keys::IndexBook::OwnerAssets(owner.clone()), &ids);
+        env.storage().persistent().set(&storage_keys::IndexBook::LesseeLeases(lessee.clone()), &ids);
+        env.storage().persistent().set(&storage_keys::LeaseLimitBook::Held(lessee.clone()), &(count as u32));
+        env.storage().persistent().set(&storage_keys::LeaseLimitBook::HeldWith(lessee.clone(), owner.clone()), &(count as u32));
+    });
+    env.cost_estimate().budget().reset_default();
+    
+    return owner;
+}
+
+// Carry the ledger over to a fresh host, so each call after a long run of calls is costed like a
+// transaction of its own rather than against every entry the previous host has touched. Each
+// reload needs its own `seed`, or mocked auths would replay nonces already on the ledger.
+fn reload(client: &RentPaymentContractClient<'_>, seed: u8) -> RentPaymentContractClient<'static> {
+    let escrow_id = client.view_escrow_contract().unwrap();
+    let env = Env::from_ledger_snapshot(client.env.to_ledger_snapshot());
+    env.host().set_base_prng_seed([seed; 32]).unwrap();
+    env.mock_all_auths();
+    let contract_id = env.register_at(&carry(&env, &client.address), RentPaymentContract, ());
+    env.register_at(&carry(&env, &escrow_id), ::escrow::EscrowContract, ());
+    RentPaymentContractClient::new(&env, &contract_id)
+}
+
+// The same address as an object of `env`'s host
+fn carry(env: &Env, address: &Address) -> Address {
+    Address::try_from_val(env, &soroban_sdk::xdr::ScAddress::from(address)).unwrap()
+}
+
+// What a try_* call returns when it fails with a code from RentErrorExt
+fn ext_error<T>(error: RentErrorExt) -> Result<T, Result<RentError, InvokeError>> {
+    Err(Err(InvokeError::Contract(error as u32)))
+}
+
+// Initialize a fresh contract for `network` on a ledger whose network ID is `ledger_on`'s
+fn init_for_network(ledger_on: Network, network: Network) -> (Env, RentPaymentContractClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_network_id(network::network_id(&env, &network::profile(ledger_on)).to_array());
+    
+    let client = RentPaymentContractClient::new(&env, &env.register(RentPaymentContract, ()));
+    client.initialize_for_network(&Address::generate(&env), &network);
+    
+    (env, client)
+}
+
+#[test]
+fn test_error_codes_are_listed_in_order_past_fifty() {
+    let env = Env::default();
+    let (_, client, _) = setup(&env);
+    
+    let codes = client.list_error_codes();
+    assert_eq!(codes.len() as usize, ERROR_CODES.len() + ERROR_CODES_EXT.len());
+    for (index, (code, _)) in codes.iter().enumerate() {
+        assert_eq!(code, index as u32 + 1);
+    }
+    assert_eq!(codes.get(50), Some((51, Symbol::new(&env, "NoOpenDispute"))));
+}
+
+#[test]
+fn test_interface_client_drives_the_contract() {
+    let env = Env::default();
+    let (contract_id, client, _) = setup(&env);
+    let integrator = interface::RentPaymentInterfaceClient::new(&env, &contract_id);
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    fund(&env, &client, &lessee, 1_000);
+    let asset_id = integrator.register_asset(
+        &owner,
+        &String::from_str(&env, "Kayak"),
+        &String::from_str(&env, "Two-seater"),
+        &10, &100, &100, &1000, &0,
+    );
+    let lease_id = integrator.create_lease(&asset_id, &lessee, &500);
+    assert_eq!(integrator.get_owner_assets(&owner), vec![&env, asset_id]);
+    assert_eq!(integrator.get_lessee_leases(&lessee), vec![&env, lease_id]);
+    
+    env.ledger().set_timestamp(100);
+    integrator.process_payment(&lease_id, &lessee);
+    assert_eq!(integrator.view_lease(&lease_id).unwrap().total_paid, client.view_lease(&lease_id).unwrap().total_paid);
+    
+    integrator.end_lease(&lease_id, &lessee);
+    assert!(integrator.view_asset(&asset_id).unwrap().is_available);
+    assert_eq!(
+        integrator.try_end_lease(&lease_id, &lessee),
+        Err(Ok(RentError::LeaseNotActive))
+    );
+    assert_eq!(integrator.view_asset_stats().completed_leases, 1);
+    assert_eq!(integrator.list_error_codes(), client.list_error_codes());
+}
+
+#[test]
+fn test_testnet_profile() {
+    let (env, client) = init_for_network(Network::Testnet, Network::Testnet);
+    assert_eq!(client.view_network(), Some(Network::Testnet));
+    assert_eq!(client.view_native_token(), Some(Address::from_str(&env, network::TESTNET.native_token)));
+    let fee = client.view_listing_fee();
+    assert_eq!((fee.amount, fee.refundable), (0, true));
+    assert_eq!(client.view_boost_fee(), 0);
+}
+
+#[test]
+fn test_futurenet_profile() {
+    let (env, client) = init_for_network(Network::Futurenet, Network::Futurenet);
+    assert_eq!(client.view_network(), Some(Network::Futurenet));
+    assert_eq!(client.view_native_token(), Some(Address::from_str(&env, network::FUTURENET.native_token)));
+    let fee = client.view_listing_fee();
+    assert_eq!((fee.amount, fee.refundable), (0, true));
+    assert_eq!(client.view_boost_fee(), 0);
+}
+
+#[test]
+fn test_mainnet_profile() {
+    let (env, client) = init_for_network(Network::Mainnet, Network::Mainnet);
+    assert_eq!(client.view_network(), Some(Network::Mainnet));
+    assert_eq!(client.view_native_token(), Some(Address::from_str(&env, network::MAINNET.native_token)));
+    let fee = client.view_listing_fee();
+    assert_eq!((fee.amount, fee.refundable), (10, true));
+    assert_eq!(client.view_boost_fee(), 1);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #42)")]
+fn test_network_profile_must_match_ledger() {
+    init_for_network(Network::Testnet, Network::Mainnet);
+}
+
+#[test]
+fn test_only_verified_landlords_list_or_buy_assets() {
+    let env = Env::default();
+    let (_, client, admin) = setup(&env);
+    let registry_id = env.register(mock::MockLandlordRegistry, ());
+    let registry = mock::MockLandlordRegistryClient::new(&env, &registry_id);
+    
+    let owner = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let register = |owner: &Address| client.try_register_asset(
+        owner,
+        &String::from_str(&env, "Loft"),
+        &String::from_str(&env, "Open-plan loft"),
+        &10, &100, &100, &1000, &0,
+    );
+    
+    // Without a registry anyone may list
+    assert_eq!(register(&owner), Ok(Ok(1)));
+    
+    client.set_landlord_registry(&admin, &Some(registry_id.clone()));
+    assert_eq!(client.view_landlord_registry(), Some(registry_id));
+    assert_eq!(
+        register(&owner).err(),
+        Some(Ok(RentError::LandlordNotVerified))
+    );
+    registry.set_verified(&owner, &true);
+    assert_eq!(register(&owner), Ok(Ok(2)));
+    
+    // The buyer of an asset must be verified too
+    assert_eq!(
+        client.try_transfer_asset(&2, &owner, &buyer),
+        Err(Ok(RentError::LandlordNotVerified))
+    );
+    registry.set_verified(&buyer, &true);
+    client.transfer_asset(&2, &owner, &buyer);
+    assert_eq!(client.view_asset(&2).unwrap().owner, buyer);
+    
+    client.set_landlord_registry(&admin, &None);
+    registry.set_verified(&owner, &false);
+    assert_eq!(register(&owner), Ok(Ok(3)));
+}
+
+#[test]
+fn test_quote_in_stroops() {
+    let env = Env::default();
+    let (_, client, _) = setup(&env);
+    
+    assert_eq!(client.quote_in_stroops(&0), 0);
+    assert_eq!(client.quote_in_stroops(&1), xlm::STROOPS_PER_XLM);
+    assert_eq!(client.quote_in_stroops(&250), 2_500_000_000);
+}
+
+#[test]
+fn test_native_token_config() {
+    let env = Env::default();
+    let (_, client, admin) = setup(&env);
+    
+    let other = env.register_stellar_asset_contract_v2(admin.clone());
+    client.set_native_token(&admin, &other.address());
+    assert_eq!(client.view_native_token(), Some(other.address()));
+}
+
+#[test]
+fn test_asset_records_payment_currency() {
+    let env = Env::default();
+    let (_, client, _) = setup(&env);
+    
+    let asset_id = client.register_asset(
+        &Address::generate(&env),
+        &String::from_str(&env, "Parking"),
+        &String::from_str(&env, "Spot 12"),
+        &10, &100, &100, &1000, &0,
+    );
+    let asset = client.view_asset(&asset_id).unwrap();
+    let token = TokenClient::new(&env, &client.view_native_token().unwrap());
+    assert_eq!(asset.currency_symbol, token.symbol());
+    assert_eq!(asset.currency_decimals, 7);
+}
+
+#[test]
+fn test_asset_priced_in_another_token() {
+    let env = Env::default();
+    let (_, client, admin) = setup(&env);
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    let usdc = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    StellarAssetClient::new(&env, &usdc).mint(&lessee, &1_000);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Parking"),
+        &String::from_str(&env, "Spot 12"),
+        &10, &100, &100, &1000, &50,
+    );
+    client.set_payment_token(&asset_id, &owner, &usdc);
+    assert_eq!(client.view_asset(&asset_id).unwrap().currency_symbol, TokenClient::new(&env, &usdc).symbol());
+    
+    // Deposit and rent move in the asset's token; the lessee holds no XLM at all
+    let lease_id = client.create_lease(&asset_id, &lessee, &500);
+    env.ledger().set_timestamp(100);
+    client.process_payment(&lease_id, &lessee);
+    client.withdraw_rent(&owner, &usdc, &10);
+    assert_eq!(TokenClient::new(&env, &usdc).balance(&owner), 10);
+    assert_eq!(TokenClient::new(&env, &usdc).balance(&lessee), 930);
+    
+    // Lease creation counts the first period and deposit as collected
+    assert_eq!(client.view_token_stats(&usdc), TokenStats { processed: 70, late_fees: 0 });
+    assert_eq!(client.view_asset_stats().total_xlm_processed, 0);
+    assert_eq!(
+        client.try_set_payment_token(&asset_id, &owner, &client.view_native_token().unwrap()),
+        Err(Ok(RentError::AssetUnavailable))
+    );
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #10)")]
+fn test_native_token_requires_admin() {
+    let env = Env::default();
+    let (_, client, _) = setup(&env);
+    
+    let stranger = Address::generate(&env);
+    client.set_native_token(&stranger, &stranger);
+}
+
+#[test]
+fn test_native_transfer_needs_no_trustline() {
+    let env = Env::default();
+    let (contract_id, client, _) = setup(&env);
+    let token = client.view_native_token().unwrap();
+    
+    let lessee = Address::generate(&env);
+    let owner = Address::generate(&env);
+    StellarAssetClient::new(&env, &token).mint(&lessee, &100);
+    
+    // The owner never opted in to the asset, yet can receive native XLM
+    env.mock_all_auths_allowing_non_root_auth();
+    env.as_contract(&contract_id, || xlm::transfer(&env, &xlm::native(&env).unwrap(), &lessee, &owner, 40).unwrap());
+    
+    let token_client = TokenClient::new(&env, &token);
+    assert_eq!(token_client.balance(&owner), 40);
+    assert_eq!(token_client.balance(&lessee), 60);
+}
+
+#[test]
+fn test_payment_preflight_names_the_fix() {
+    let env = Env::default();
+    let (_, client, admin) = setup(&env);
+    let revocable = env.register_stellar_asset_contract_v2(admin.clone());
+    revocable.issuer().set_flag(IssuerFlags::RevocableFlag);
+    client.set_native_token(&admin, &revocable.address());
+    let token = revocable.address();
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    fund(&env, &client, &lessee, 15);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Kayak"),
+        &String::from_str(&env, "Two-seater"),
+        &10, &100, &100, &1000, &0,
+    );
+    let lease_id = client.create_lease(&asset_id, &lessee, &500);
+    env.ledger().set_timestamp(100);
+    
+    // Each failure is reported before any funds move, with the error naming what to fix
+    assert_eq!(
+        client.try_process_payment(&lease_id, &lessee),
+        Err(Ok(RentError::InsufficientBalance))
+    );
+    fund(&env, &client, &lessee, 10);
+    StellarAssetClient::new(&env, &token).set_authorized(&owner, &false);
+    client.process_payment(&lease_id, &lessee);
+    assert_eq!(
+        client.try_withdraw_rent(&owner, &token, &10),
+        Err(Ok(RentError::NoTrustline))
+    );
+    assert_eq!(client.view_withdrawable(&owner, &token), 20);
+}
+
+#[test]
+fn test_frozen_account_blocks_payment_with_alert() {
+    let env = Env::default();
+    let (_, client, admin) = setup(&env);
+    let revocable = env.register_stellar_asset_contract_v2(admin.clone());
+    revocable.issuer().set_flag(IssuerFlags::RevocableFlag);
+    client.set_native_token(&admin, &revocable.address());
+    let token = revocable.address();
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    fund(&env, &client, &lessee, 100);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Kayak"),
+        &String::from_str(&env, "Two-seater"),
+        &10, &100, &100, &1000, &0,
+    );
+    let lease_id = client.create_lease(&asset_id, &lessee, &500);
+    env.ledger().set_timestamp(100);
+    
+    // A frozen payer is recorded on the lease and alerted, and the lease is left as it was
+    StellarAssetClient::new(&env, &token).set_authorized(&lessee, &false);
+    assert_eq!(client.process_sponsored_payment(&lease_id, &lessee, &10), 0);
+    assert_eq!(
+        env.events().all().last().unwrap().1,
+        (symbol_short!("alert"), symbol_short!("blocked"), lessee.clone()).into_val(&env)
+    );
+    let block = client.view_payment_block(&lease_id).unwrap();
+    assert_eq!((block.amount, block.payer), (10, lessee.clone()));
+    assert_eq!(client.view_lease(&lease_id).unwrap().next_payment_time, 100);
+    
+    // In a batch the frozen payer fails only their own item, with its own code
+    let results = client.process_payments_batch(&vec![&env, (lease_id, lessee.clone())]);
+    assert_eq!(results.get(0).unwrap().error_code, RentErrorExt::PayerFrozen as u32);
+    
+    // Rent is held for the owner, so a deauthorized owner no longer blocks payment, and the
+    // next payment that goes through clears the block
+    StellarAssetClient::new(&env, &token).set_authorized(&lessee, &true);
+    StellarAssetClient::new(&env, &token).set_authorized(&owner, &false);
+    client.process_payment(&lease_id, &lessee);
+    assert_eq!(client.view_payment_block(&lease_id), None);
+    assert_eq!(TokenClient::new(&env, &token).balance(&lessee), 80);
+    
+    StellarAssetClient::new(&env, &token).set_authorized(&owner, &true);
+    assert_eq!(client.withdraw_rent(&owner, &token, &20), 0);
+    assert_eq!(TokenClient::new(&env, &token).balance(&owner), 20);
+}
+
+#[test]
+fn test_allowance_locked_deposit_claim() {
+    let env = Env::default();
+    let (contract_id, client, _) = setup(&env);
+    let token = client.view_native_token().unwrap();
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Van"),
+        &String::from_str(&env, "Cargo van"),
+        &10, &100, &100, &1000, &50,
+    );
+    client.set_deposit_mode(&asset_id, &owner, &DepositMode::AllowanceLock);
+    
+    StellarAssetClient::new(&env, &token).mint(&lessee, &100);
+    TokenClient::new(&env, &token).approve(&lessee, &contract_id, &50, &1000);
+    
+    let lease_id = client.create_lease(&asset_id, &lessee, &500);
+    assert_eq!(client.view_lease(&lease_id).unwrap().total_paid, 10);
+    
+    client.claim_locked_deposit(&lease_id, &owner, &20);
+    client.approve_deposit_claim(&lease_id, &lessee);
+    
+    let lock = client.view_deposit_lock(&lease_id).unwrap();
+    assert_eq!(lock.claimed, 20);
+    assert_eq!(lock.pending_claim, 0);
+    assert_eq!(TokenClient::new(&env, &token).balance(&owner), 20);
+    assert_eq!(TokenClient::new(&env, &token).balance(&lessee), 70);
+}
+
+#[test]
+fn test_deposit_claim_records_shortfall_after_clawback() {
+    let env = Env::default();
+    let (contract_id, client, admin) = setup(&env);
+    
+    // Balances created after the issuer enables clawback can be reclaimed
+    let clawbackable = env.register_stellar_asset_contract_v2(admin.clone());
+    clawbackable.issuer().set_flag(IssuerFlags::RevocableFlag);
+    clawbackable.issuer().set_flag(IssuerFlags::ClawbackEnabledFlag);
+    client.set_native_token(&admin, &clawbackable.address());
+    let token = clawbackable.address();
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Van"),
+        &String::from_str(&env, "Cargo van"),
+        &10, &100, &100, &1000, &50,
+    );
+    client.set_deposit_mode(&asset_id, &owner, &DepositMode::AllowanceLock);
+    
+    let sac = StellarAssetClient::new(&env, &token);
+    sac.mint(&lessee, &60);
+    TokenClient::new(&env, &token).approve(&lessee, &contract_id, &50, &1000);
+    let lease_id = client.create_lease(&asset_id, &lessee, &500);
+    
+    // Simulate the issuer reclaiming most of the funds behind the allowance
+    sac.clawback(&lessee, &35);
+    
+    client.claim_locked_deposit(&lease_id, &owner, &40);
+    client.approve_deposit_claim(&lease_id, &lessee);
+    
+    assert_eq!(client.view_deposit_lock(&lease_id).unwrap().claimed, 15);
+    assert_eq!(client.view_lease(&lease_id).unwrap().shortfall, 25);
+    assert_eq!(TokenClient::new(&env, &token).balance(&owner), 15);
+}
+
+#[test]
+fn test_sponsored_payment_needs_only_lessee_auth() {
+    let env = Env::default();
+    let (contract_id, client, _) = setup(&env);
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    fund(&env, &client, &lessee, 1_000);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Parking"),
+        &String::from_str(&env, "Spot 12"),
+        &10, &100, &100, &1000, &0,
+    );
+    let lease_id = client.create_lease(&asset_id, &lessee, &500);
+    
+    let token = client.view_native_token().unwrap();
+    env.ledger().set_timestamp(100);
+    assert_eq!(client.process_sponsored_payment(&lease_id, &lessee, &10), 10);
+    
+    // The only signature required is the lessee's, bound to the maximum they agreed to pay
+    // and covering the rent transfer into the contract
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            lessee.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    contract_id.clone(),
+                    Symbol::new(&env, "process_sponsored_payment"),
+                    (lease_id, lessee.clone(), 10_i128).into_val(&env),
+                )),
+                sub_invocations: std::vec![AuthorizedInvocation {
+                    function: AuthorizedFunction::Contract((
+                        token.clone(),
+                        symbol_short!("transfer"),
+                        (lessee.clone(), contract_id.clone(), 10_i128).into_val(&env),
+                    )),
+                    sub_invocations: std::vec![],
+                }],
+            }
+        )]
+    );
+    assert_eq!(client.view_withdrawable(&owner, &token), 20);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #18)")]
+fn test_sponsored_payment_rejects_amount_above_max() {
+    let env = Env::default();
+    let (_, client, _) = setup(&env);
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    fund(&env, &client, &lessee, 1_000);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Parking"),
+        &String::from_str(&env, "Spot 12"),
+        &10, &100, &100, &1000, &0,
+    );
+    let lease_id = client.create_lease(&asset_id, &lessee, &500);
+    
+    client.process_sponsored_payment(&lease_id, &lessee, &9);
+}
+
+#[test]
+fn test_holdover_rolls_at_multiplier_until_notice() {
+    let env = Env::default();
+    let (_, client, _) = setup(&env);
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    fund(&env, &client, &lessee, 1_000);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Flat"),
+        &String::from_str(&env, "Two rooms"),
+        &100, &100, &100, &1000, &0,
+    );
+    client.set_holdover_rate(&asset_id, &owner, &12500);
+    let lease_id = client.create_lease(&asset_id, &lessee, &200);
+    
+    // Past end_time the tenant keeps paying at 125% per period
+    env.ledger().set_timestamp(250);
+    assert_eq!(client.view_amount_due(&lease_id), 125);
+    client.process_payment(&lease_id, &lessee);
+    assert!(client.view_lease(&lease_id).unwrap().in_holdover);
+    
+    let holdover_end = client.give_holdover_notice(&lease_id, &owner);
+    assert_eq!(holdover_end, 350);
+    
+    env.ledger().set_timestamp(350);
+    assert!(client.try_process_payment(&lease_id, &lessee).is_err());
+}
+
+#[test]
+fn test_holdover_rent_past_the_amount_range_is_refused() {
+    let env = Env::default();
+    let (_, client, _) = setup(&env);
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    let price = 1_000_000_000_000_000_000_000_000_000_000;
+    fund(&env, &client, &lessee, price);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Flat"),
+        &String::from_str(&env, "Two rooms"),
+        &price, &100, &100, &1000, &0,
+    );
+    client.set_holdover_rate(&asset_id, &owner, &u32::MAX);
+    let lease_id = client.create_lease(&asset_id, &lessee, &200);
+    
+    env.ledger().set_timestamp(250);
+    assert_eq!(client.try_view_amount_due(&lease_id), Err(Ok(RentError::InvalidAmount)));
+    assert_eq!(client.try_process_payment(&lease_id, &lessee), Err(Ok(RentError::InvalidAmount)));
+    assert!(!client.view_lease(&lease_id).unwrap().in_holdover);
+}
+
+#[test]
+fn test_renewal_pipeline_shows_where_ending_leases_stand() {
+    let env = Env::default();
+    let (_, client, _) = setup(&env);
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    fund(&env, &client, &lessee, 1_000);
+    let lease = |duration: u64, holdover_bps: u32| {
+        let asset_id = client.register_asset(
+            &owner,
+            &String::from_str(&env, "Flat"),
+            &String::from_str(&env, "Two rooms"),
+            &100, &100, &100, &10_000, &0,
+        );
+        if holdover_bps > 0 {
+            client.set_holdover_rate(&asset_id, &owner, &holdover_bps);
+        }
+        client.create_lease(&asset_id, &lessee, &duration)
+    };
+    let open = lease(1_000, 0);
+    let auto_renewing = lease(1_000, 0);
+    let noticed = lease(1_000, 0);
+    let holding_over = lease(200, 12_500);
+    lease(5_000, 0);
+    let open_with_holdover = lease(1_000, 12_500);
+    client.set_auto_renew(&auto_renewing, &lessee, &true);
+    client.file_non_renewal(&noticed, &owner);
+    
+    env.ledger().set_timestamp(250);
+    client.process_payment(&holding_over, &lessee);
+    client.give_holdover_notice(&holding_over, &owner);
+    
+    // Leases ending past the horizon are left out; holdovers are always in
+    let pipeline = client.get_renewal_pipeline(&owner, &1_000);
+    let statuses: std::vec::Vec<(u64, RenewalStatus, u64)> = pipeline.iter()
+        .map(|entry| (entry.lease_id, entry.status, entry.vacates_at))
+        .collect();
+    assert_eq!(statuses, [
+        (open, RenewalStatus::Open, 1_000),
+        (auto_renewing, RenewalStatus::AutoRenewing, 0),
+        (noticed, RenewalStatus::NonRenewalFiled, 1_000),
+        (holding_over, RenewalStatus::Holdover, 350),
+        (open_with_holdover, RenewalStatus::Open, 0),
+    ]);
+    let entry = pipeline.get(0).unwrap();
+    assert_eq!((entry.asset_id, entry.lessee, entry.end_time), (1, lessee.clone(), 1_000));
+    
+    let pipeline = client.get_renewal_pipeline(&owner, &0);
+    assert_eq!(pipeline.len(), 1);
+    assert_eq!(pipeline.get(0).unwrap().lease_id, holding_over);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #19)")]
+fn test_payment_after_term_without_holdover_fails() {
+    let env = Env::default();
+    let (_, client, _) = setup(&env);
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    fund(&env, &client, &lessee, 1_000);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Flat"),
+        &String::from_str(&env, "Two rooms"),
+        &100, &100, &100, &1000, &0,
+    );
+    let lease_id = client.create_lease(&asset_id, &lessee, &200);
+    
+    env.ledger().set_timestamp(200);
+    client.process_payment(&lease_id, &lessee);
+}
+
+#[test]
+fn test_recurring_blackout_overlap() {
+    // Weekly window covering the first day of each week
+    let weekly = calendar::BlackoutWindow { start: 0, end: 86_400, repeat_every: 604_800 };
+    
+    assert!(calendar::overlaps(&weekly, 0, 10));
+    assert!(!calendar::overlaps(&weekly, 86_400, 604_800));
+    assert!(calendar::overlaps(&weekly, 86_400, 604_801));
+    assert!(calendar::overlaps(&weekly, 3 * 604_800 + 100, 3 * 604_800 + 200));
+    assert!(!calendar::overlaps(&weekly, 3 * 604_800 + 86_400, 4 * 604_800));
+    
+    let one_off = calendar::BlackoutWindow { start: 1_000, end: 2_000, repeat_every: 0 };
+    assert!(!calendar::overlaps(&one_off, 2_000, 5_000));
+    assert!(calendar::overlaps(&one_off, 500, 1_001));
+}
+
+#[test]
+fn test_split_deposit_claims_proportionally_and_refunds_rest() {
+    let env = Env::default();
+    let (contract_id, client, admin) = setup(&env);
+    
+    let usdc = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    let xlm_token = client.view_native_token().unwrap();
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Excavator"),
+        &String::from_str(&env, "Mini excavator"),
+        &10, &100, &100, &1000, &0,
+    );
+    client.set_split_deposit(&asset_id, &owner, &soroban_sdk::vec![
+        &env,
+        DepositPosition { token: usdc.clone(), amount: 1_000 },
+        DepositPosition { token: xlm_token.clone(), amount: 400 },
+    ]);
+    
+    StellarAssetClient::new(&env, &usdc).mint(&lessee, &1_000);
+    StellarAssetClient::new(&env, &xlm_token).mint(&lessee, &410);
+    let lease_id = client.create_lease(&asset_id, &lessee, &500);
+    assert_eq!(TokenClient::new(&env, &usdc).balance(&contract_id), 1_000);
+    
+    client.claim_split_deposit(&lease_id, &owner, &2_500);
+    assert_eq!(TokenClient::new(&env, &usdc).balance(&owner), 250);
+    assert_eq!(TokenClient::new(&env, &xlm_token).balance(&owner), 100);
+    
+    client.end_lease(&lease_id, &lessee);
+    assert_eq!(TokenClient::new(&env, &usdc).balance(&lessee), 750);
+    assert_eq!(TokenClient::new(&env, &xlm_token).balance(&lessee), 300);
+    assert!(client.view_split_deposit(&lease_id).is_empty());
+}
+
+#[test]
+fn test_mutual_release_needs_both_signatures_and_defaults_to_refund() {
+    let env = Env::default();
+    let (contract_id, client, admin) = setup(&env);
+    
+    let usdc = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    let usdc_token = TokenClient::new(&env, &usdc);
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    fund(&env, &client, &lessee, 1_000);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Crane"),
+        &String::from_str(&env, "Tower crane"),
+        &10, &100, &100, &1000, &0,
+    );
+    client.set_split_deposit(&asset_id, &owner, &soroban_sdk::vec![&env, DepositPosition { token: usdc.clone(), amount: 1_000 }]);
+    client.set_mutual_release(&asset_id, &owner, &50);
+    StellarAssetClient::new(&env, &usdc).mint(&lessee, &2_000);
+    
+    // Agreed release: owner proposes, lessee counter-signs
+    let lease_id = client.create_lease(&asset_id, &lessee, &500);
+    assert!(client.try_claim_split_deposit(&lease_id, &owner, &5_000).is_err());
+    client.end_lease(&lease_id, &lessee);
+    assert_eq!(usdc_token.balance(&contract_id), 1_000);
+    client.propose_deposit_release(&lease_id, &owner, &3_000);
+    client.sign_deposit_release(&lease_id, &lessee);
+    assert_eq!(usdc_token.balance(&owner), 300);
+    assert_eq!(usdc_token.balance(&lessee), 1_700);
+    assert!(client.view_deposit_release(&lease_id).is_none());
+    
+    // Owner never signs: after the deadline anyone can refund the lessee in full
+    let lease_id = client.create_lease(&asset_id, &lessee, &500);
+    client.end_lease(&lease_id, &lessee);
+    client.propose_deposit_release(&lease_id, &lessee, &0);
+    assert!(client.try_finalize_deposit_release(&lease_id).is_err());
+    
+    // The asset cannot change hands while the release is pending
+    let buyer = Address::generate(&env);
+    assert_eq!(client.try_transfer_asset(&asset_id, &owner, &buyer), ext_error(RentErrorExt::DepositUnsettled));
+    
+    env.ledger().with_mut(|l| l.timestamp += 51);
+    client.finalize_deposit_release(&lease_id);
+    assert_eq!(usdc_token.balance(&owner), 300);
+    assert_eq!(usdc_token.balance(&lessee), 1_700);
+    assert_eq!(usdc_token.balance(&contract_id), 0);
+    client.transfer_asset(&asset_id, &owner, &buyer);
+}
+
+#[test]
+fn test_late_notice_escalates_on_poke_and_clears_on_payment() {
+    let env = Env::default();
+    let (_, client, admin) = setup(&env);
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    fund(&env, &client, &lessee, 1_000);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Van"),
+        &String::from_str(&env, "Cargo van"),
+        &10, &100, &100, &10_000, &0,
+    );
+    let lease_id = client.create_lease(&asset_id, &lessee, &5_000);
+    client.set_escalation_schedule(&admin, &penalty::EscalationSchedule { reminder_after: 0, notice_after: 50, warning_after: 200 });
+    
+    client.poke_lease(&lease_id);
+    assert_eq!(client.view_late_notice(&lease_id).level, penalty::EscalationLevel::None);
+    
+    env.ledger().with_mut(|l| l.timestamp = 101);
+    client.poke_lease(&lease_id);
+    let notice = client.view_late_notice(&lease_id);
+    assert_eq!(notice.level, penalty::EscalationLevel::Reminder);
+    assert_eq!(notice.reminder_at, 101);
+    
+    // Skipping past both later thresholds issues each level once
+    env.ledger().with_mut(|l| l.timestamp = 400);
+    client.poke_lease(&lease_id);
+    let notice = client.view_late_notice(&lease_id);
+    assert_eq!(notice.level, penalty::EscalationLevel::DefaultWarning);
+    assert_eq!((notice.notice_at, notice.warning_at), (400, 400));
+    
+    client.process_payment(&lease_id, &lessee);
+    assert_eq!(client.view_late_notice(&lease_id).level, penalty::EscalationLevel::None);
+}
+
+#[test]
+fn test_default_waits_for_jurisdiction_cooling_off() {
+    let env = Env::default();
+    let (_, client, admin) = setup(&env);
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    fund(&env, &client, &lessee, 1_000);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Flat"),
+        &String::from_str(&env, "Studio flat"),
+        &10, &100, &100, &10_000, &0,
+    );
+    client.set_escalation_schedule(&admin, &penalty::EscalationSchedule { reminder_after: 0, notice_after: 0, warning_after: 0 });
+    client.set_jurisdiction(&admin, &symbol_short!("US_CA"), &300);
+    client.set_asset_jurisdiction(&asset_id, &owner, &Some(symbol_short!("US_CA")));
+    let lease_id = client.create_lease(&asset_id, &lessee, &5_000);
+    
+    env.ledger().with_mut(|l| l.timestamp = 101);
+    assert!(client.try_default_lease(&lease_id, &owner).is_err());
+    client.poke_lease(&lease_id);
+    
+    // Within the cooling-off period, paying the arrears cancels the default
+    env.ledger().with_mut(|l| l.timestamp = 150);
+    assert!(client.try_default_lease(&lease_id, &owner).is_err());
+    client.process_payment(&lease_id, &lessee);
+    assert_eq!(client.view_late_notice(&lease_id).level, penalty::EscalationLevel::None);
+    
+    env.ledger().with_mut(|l| l.timestamp = 251);
+    client.poke_lease(&lease_id);
+    env.ledger().with_mut(|l| l.timestamp = 550);
+    assert!(client.try_default_lease(&lease_id, &owner).is_err());
+    env.ledger().with_mut(|l| l.timestamp = 551);
+    client.default_lease(&lease_id, &owner);
+    assert!(!client.view_lease(&lease_id).unwrap().is_active);
+    assert!(client.view_asset(&asset_id).unwrap().is_available);
+}
+
+#[test]
+fn test_reputation_tracks_payments_completions_and_defaults() {
+    let env = Env::default();
+    let (_, client, admin) = setup(&env);
+    
+    let owner = Address::generate(&env);
+    let tenant = Address::generate(&env);
+    let defaulter = Address::generate(&env);
+    fund(&env, &client, &tenant, 1_000);
+    fund(&env, &client, &defaulter, 1_000);
+    let mut asset_ids = Vec::new(&env);
+    for _ in 0..2 {
+        asset_ids.push_back(client.register_asset(
+            &owner,
+            &String::from_str(&env, "Flat"),
+            &String::from_str(&env, "Studio flat"),
+            &10, &100, &100, &10_000, &0,
+        ));
+    }
+    client.set_escalation_schedule(&admin, &penalty::EscalationSchedule { reminder_after: 0, notice_after: 0, warning_after: 0 });
+    let lease_id = client.create_lease(&asset_ids.get(0).unwrap(), &tenant, &5_000);
+    let defaulted = client.create_lease(&asset_ids.get(1).unwrap(), &defaulter, &5_000);
+    
+    // Paid on time, then flagged overdue; paying those arrears is not counted twice
+    env.ledger().set_timestamp(100);
+    client.process_payment(&lease_id, &tenant);
+    env.ledger().set_timestamp(201);
+    client.mark_lease_overdue(&lease_id, &owner);
+    client.mark_lease_overdue(&defaulted, &owner);
+    client.process_payment(&lease_id, &tenant);
+    client.end_lease(&lease_id, &tenant);
+    
+    client.default_lease(&defaulted, &owner);
+    
+    assert_eq!(client.view_reputation(&tenant), Reputation { on_time_payments: 1, late_payments: 1, completed_leases: 1, defaults: 0, ..Reputation::default() });
+    assert_eq!(client.view_reputation(&defaulter), Reputation { on_time_payments: 0, late_payments: 1, completed_leases: 0, defaults: 1, ..Reputation::default() });
+    assert_eq!(client.view_reputation(&owner).completed_leases, 1);
+    assert_eq!(client.view_reputation(&Address::generate(&env)), Reputation::default());
+}
+
+#[test]
+fn test_deposit_statement_lists_held_deposits_with_interest() {
+    let env = Env::default();
+    let (_, client, admin) = setup(&env);
+    
+    let usdc = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Loft"),
+        &String::from_str(&env, "City loft"),
+        &10, &100, &100, &100_000_000, &50_000_000,
+    );
+    client.set_split_deposit(&asset_id, &owner, &soroban_sdk::vec![&env, DepositPosition { token: usdc.clone(), amount: 10_000 }]);
+    client.set_deposit_interest(&admin, &symbol_short!("NY"), &500);
+    client.set_asset_jurisdiction(&asset_id, &owner, &Some(symbol_short!("NY")));
+    StellarAssetClient::new(&env, &usdc).mint(&lessee, &10_000);
+    fund(&env, &client, &lessee, 50_000_010);
+    let lease_id = client.create_lease(&asset_id, &lessee, &50_000_000);
+    
+    // Half a year at 5% accrues 2.5%
+    env.ledger().with_mut(|l| l.timestamp = 365 * 24 * 60 * 60 / 2);
+    let statement = client.get_deposit_statement(&lessee);
+    assert_eq!(statement.len(), 2);
+    
+    let collected = statement.get(0).unwrap();
+    assert_eq!(collected.kind, DepositKind::Collected);
+    assert_eq!(collected.amount, 50_000_000);
+    assert_eq!(collected.accrued_interest, 1_250_000);
+    assert_eq!(collected.eligible_at, 50_000_000);
+    
+    let split = statement.get(1).unwrap();
+    assert_eq!((split.kind, split.token, split.amount, split.accrued_interest), (DepositKind::Split, usdc, 10_000, 250));
+    assert_eq!(split.lease_id, lease_id);
+}
+
+#[test]
+fn test_due_date_rolls_past_holiday_and_weekend() {
+    let env = Env::default();
+    let (_, client, admin) = setup(&env);
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    fund(&env, &client, &lessee, 1_000);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Bike"),
+        &String::from_str(&env, "Cargo bike"),
+        &10, &calendar::DAY, &calendar::DAY, &(10 * calendar::DAY), &0,
+    );
+    client.set_penalty_policy(&asset_id, &owner, &PenaltyPolicy::Flat(5));
+    let lease_id = client.create_lease(&asset_id, &lessee, &(10 * calendar::DAY));
+    
+    // Due on day 1 (a Friday); without a calendar, paying on Sunday is late
+    env.ledger().with_mut(|l| l.timestamp = 3 * calendar::DAY);
+    assert_eq!(client.view_amount_due(&lease_id), 15);
+    
+    // A Friday holiday plus weekend rollover moves the due date to Monday
+    client.add_holiday(&admin, &1);
+    client.set_weekend_rollover(&admin, &true);
+    assert_eq!(client.view_amount_due(&lease_id), 10);
+    assert!(client.try_mark_lease_overdue(&lease_id, &owner).is_err());
+    
+    // Friday itself is a business day once the holiday is removed
+    client.remove_holiday(&admin, &1);
+    assert!(client.view_holidays().is_empty());
+    assert_eq!(client.view_amount_due(&lease_id), 15);
+}
+
+#[test]
+fn test_external_ids_resolve_per_owner() {
+    let env = Env::default();
+    let (_, client, _) = setup(&env);
+    
+    let owner = Address::generate(&env);
+    let other_owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    fund(&env, &client, &lessee, 1_000);
+    let register = |owner: &Address| client.register_asset(
+        owner,
+        &String::from_str(&env, "Unit"),
+        &String::from_str(&env, "Apartment"),
+        &10, &100, &100, &1000, &0,
+    );
+    let asset_id = register(&owner);
+    let second_id = register(&owner);
+    let other_id = register(&other_owner);
+    let code = BytesN::from_array(&env, &[7; 32]);
+    
+    client.set_asset_external_id(&asset_id, &owner, &Some(code.clone()));
+    assert!(client.try_set_asset_external_id(&second_id, &owner, &Some(code.clone())).is_err());
+    client.set_asset_external_id(&other_id, &other_owner, &Some(code.clone()));
+    assert_eq!(client.find_asset_by_external_id(&owner, &code), Some(asset_id));
+    assert_eq!(client.find_asset_by_external_id(&other_owner, &code), Some(other_id));
+    
+    // Re-tagging frees the old reference
+    let new_code = BytesN::from_array(&env, &[8; 32]);
+    client.set_asset_external_id(&asset_id, &owner, &Some(new_code.clone()));
+    assert_eq!(client.find_asset_by_external_id(&owner, &code), None);
+    assert_eq!(client.view_asset_external_id(&asset_id), Some(new_code));
+    
+    let lease_id = client.create_lease(&asset_id, &lessee, &500);
+    client.set_lease_external_id(&lease_id, &owner, &Some(code.clone()));
+    assert_eq!(client.find_lease_by_external_id(&owner, &code), Some(lease_id));
+    client.set_lease_external_id(&lease_id, &owner, &None);
+    assert_eq!(client.find_lease_by_external_id(&owner, &code), None);
+}
+
+#[test]
+fn test_state_root_proofs() {
+    let env = Env::default();
+    let (_, client, _) = setup(&env);
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    fund(&env, &client, &lessee, 1_000);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Bike"),
+        &String::from_str(&env, "City bike"),
+        &5, &100, &100, &1000, &0,
+    );
+    client.register_asset(
+        &owner,
+        &String::from_str(&env, "Scooter"),
+        &String::from_str(&env, "E-scooter"),
+        &5, &100, &100, &1000, &0,
+    );
+    let lease_id = client.create_lease(&asset_id, &lessee, &500);
+    
+    assert_eq!(client.commit_state_root(&10), 0);
+    let commitment = client.view_state_root().unwrap();
+    assert_eq!(commitment.asset_count, 2);
+    assert_eq!(commitment.lease_count, 1);
+    
+    let leaves = soroban_sdk::vec![
+        &env,
+        merkle::leaf_hash(&env, &client.view_asset(&asset_id).unwrap()),
+        merkle::leaf_hash(&env, &client.view_asset(&2).unwrap()),
+        merkle::leaf_hash(&env, &client.view_lease(&lease_id).unwrap()),
+    ];
+    assert_eq!(commitment.root, merkle::root(&env, leaves.clone()));
+    
+    // The lease leaf is carried up one level, so its proof is just the hash of the asset pair
+    let pair = merkle::node_hash(&env, &leaves.get(0).unwrap(), &leaves.get(1).unwrap());
+    assert!(client.verify_state_proof(&leaves.get(2).unwrap(), &2, &soroban_sdk::vec![&env, pair]));
+    assert!(client.verify_state_proof(&leaves.get(0).unwrap(), &0, &soroban_sdk::vec![&env, leaves.get(1).unwrap(), leaves.get(2).unwrap()]));
+    assert!(!client.verify_state_proof(&leaves.get(1).unwrap(), &0, &soroban_sdk::vec![&env, leaves.get(1).unwrap(), leaves.get(2).unwrap()]));
+}
+
+#[test]
+fn test_dry_runs_match_real_calls_without_writing() {
+    let env = Env::default();
+    let (_, client, _) = setup(&env);
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    fund(&env, &client, &lessee, 1_000);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Parking"),
+        &String::from_str(&env, "Spot 12"),
+        &10, &100, &100, &1000, &0,
+    );
+    
+    let preview = client.dry_run_create_lease(&asset_id, &lessee, &500);
+    assert!(client.view_asset(&asset_id).unwrap().is_available);
+    assert_eq!(client.view_asset_stats().total_leases, 0);
+    
+    let lease_id = client.create_lease(&asset_id, &lessee, &500);
+    let lease = client.view_lease(&lease_id).unwrap();
+    assert_eq!(preview.lease_id, lease_id);
+    assert_eq!((preview.end_time, preview.total_paid), (lease.end_time, lease.total_paid));
+    
+    env.ledger().set_timestamp(100);
+    let quote = client.dry_run_process_payment(&lease_id, &lessee);
+    assert_eq!(quote.amount, 10);
+    assert_eq!(quote.next_payment_time, 200);
+    assert_eq!(client.view_lease(&lease_id).unwrap().payments_made, 0);
+    
+    client.process_payment(&lease_id, &lessee);
+    assert_eq!(client.view_lease(&lease_id).unwrap().next_payment_time, quote.next_payment_time);
+}
+
+#[test]
+fn test_tagged_amounts_normalize_to_stroops() {
+    let env = Env::default();
+    let (_, client, _) = setup(&env);
+    
+    let owner = Address::generate(&env);
+    let asset_id = client.register_asset_with_amounts(
+        &owner,
+        &String::from_str(&env, "Parking"),
+        &String::from_str(&env, "Spot 12"),
+        &Amount::Stroops(xlm::STROOPS_PER_XLM / 2), &100, &100, &1000, &Amount::Whole(30),
+    );
+    let asset = client.view_asset(&asset_id).unwrap();
+    assert_eq!((asset.price_per_period, asset.deposit_required), (5_000_000, 300_000_000));
+    assert_eq!(xlm::to_base_units(3, 6), 3_000_000);
+    
+    // Base units can't be negative
+    let negative = client.try_register_asset_with_amounts(
+        &owner,
+        &String::from_str(&env, "Parking"),
+        &String::from_str(&env, "Spot 13"),
+        &Amount::Stroops(-1), &100, &100, &1000, &Amount::Whole(0),
+    );
+    assert_eq!(negative, Err(Ok(RentError::InvalidAmount)));
+}
+
+#[test]
+fn test_consolidated_bill_settles_due_leases_in_one_transfer() {
+    let env = Env::default();
+    let (_, client, _) = setup(&env);
+    let token = client.view_native_token().unwrap();
+    
+    let owner = Address::generate(&env);
+    let other_owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    StellarAssetClient::new(&env, &token).mint(&lessee, &100);
+    
+    let mut lease_ids = std::vec::Vec::new();
+    for (asset_owner, price) in [(&owner, 10_i128), (&owner, 15), (&other_owner, 20)] {
+        let asset_id = client.register_asset(
+            asset_owner,
+            &String::from_str(&env, "Unit"),
+            &String::from_str(&env, "Storage unit"),
+            &price, &100, &100, &1000, &0,
+        );
+        lease_ids.push(client.create_lease(&asset_id, &lessee, &500));
+    }
+    
+    env.ledger().set_timestamp(100);
+    assert_eq!(
+        client.try_pay_consolidated_bill(&lessee, &owner, &25),
+        Err(Ok(RentError::ConsolidatedBillingOff))
+    );
+    client.set_consolidated_billing(&lessee, &owner, &true);
+    
+    let bill = client.get_consolidated_bill(&lessee, &owner);
+    assert_eq!(bill.total, 25);
+    assert_eq!(bill.allocations.len(), 2);
+    
+    let settlement_id = client.pay_consolidated_bill(&lessee, &owner, &25);
+    let settlement = client.view_consolidated_settlement(&settlement_id).unwrap();
+    assert_eq!(settlement.allocations, bill.allocations);
+    assert_eq!(client.withdraw_rent(&owner, &token, &50), 0);
+    assert_eq!(TokenClient::new(&env, &token).balance(&owner), 50);
+    
+    // Leases with other owners are untouched and nothing is left to bill
+    assert_eq!(client.view_lease(&lease_ids[0]).unwrap().next_payment_time, 200);
+    assert_eq!(client.view_lease(&lease_ids[2]).unwrap().next_payment_time, 100);
+    assert_eq!(client.get_consolidated_bill(&lessee, &owner).total, 0);
+}
+
+#[test]
+fn test_storage_metrics_count_records_and_indexes() {
+    let env = Env::default();
+    let (_, client, admin) = setup(&env);
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    fund(&env, &client, &lessee, 1_000);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Parking"),
+        &String::from_str(&env, "Spot 12"),
+        &10, &100, &100, &1000, &0,
+    );
+    let lease_id = client.create_lease(&asset_id, &lessee, &500);
+    client.end_lease(&lease_id, &lessee);
+    client.create_lease(&asset_id, &lessee, &500);
+    client.add_holiday(&admin, &20);
+    client.set_asset_external_id(&asset_id, &owner, &Some(BytesN::from_array(&env, &[7; 32])));
+    
+    client.set_asset_category(&asset_id, &owner, &symbol_short!("parking"), &vec![&env, symbol_short!("covered"), symbol_short!("ev")]);
+    
+    let metrics = client.get_storage_metrics();
+    assert_eq!((metrics.asset_count, metrics.lease_count, metrics.archived_leases), (1, 2, 1));
+    assert_eq!((metrics.holidays, metrics.external_ids, metrics.blackout_windows), (1, 1, 0));
+    assert_eq!((metrics.owner_index_entries, metrics.lessee_index_entries), (1, 2));
+    assert_eq!((metrics.category_index_entries, metrics.tag_index_entries), (1, 2));
+    
+    // The counts follow records as they are removed again
+    client.add_blackout(&asset_id, &owner, &calendar::BlackoutWindow { start: 5_000, end: 6_000, repeat_every: 0 });
+    client.set_asset_external_id(&asset_id, &owner, &Some(BytesN::from_array(&env, &[8; 32])));
+    client.set_asset_category(&asset_id, &owner, &symbol_short!("general"), &vec![&env, symbol_short!("ev")]);
+    let metrics = client.get_storage_metrics();
+    assert_eq!((metrics.external_ids, metrics.blackout_windows), (1, 1));
+    assert_eq!((metrics.category_index_entries, metrics.tag_index_entries), (0, 1));
+    client.remove_blackout(&asset_id, &owner, &0);
+    client.set_asset_external_id(&asset_id, &owner, &None);
+    let metrics = client.get_storage_metrics();
+    assert_eq!((metrics.external_ids, metrics.blackout_windows), (0, 0));
+}
+
+#[test]
+fn test_payment_reminders_follow_asset_window_and_cadence() {
+    let env = Env::default();
+    let (_, client, _) = setup(&env);
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    fund(&env, &client, &lessee, 1_000);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Excavator"),
+        &String::from_str(&env, "Weekly hire"),
+        &10, &100, &100, &1000, &0,
+    );
+    client.set_reminder_policy(&asset_id, &owner, &ReminderPolicy { lead_time: 20, cadence: 15 });
+    let lease_id = client.create_lease(&asset_id, &lessee, &500);
+    
+    env.ledger().set_timestamp(79);
+    assert!(!client.send_payment_reminder(&lease_id));
+    env.ledger().set_timestamp(80);
+    assert!(client.send_payment_reminder(&lease_id));
+    
+    // Every event carries the contract-wide sequence number ahead of its data; registering
+    // the asset and creating the lease took the first two
+    let (contract, _, data) = env.events().all().last().unwrap();
+    let (sequence, (reminded, due)) = <(u64, (u64, u64))>::try_from_val(&env, &data).unwrap();
+    assert_eq!(contract, client.address);
+    assert_eq!((sequence, reminded, due), (3, lease_id, 100));
+    assert_eq!(client.get_event_sequence(), 3);
+    env.ledger().set_timestamp(94);
+    assert!(!client.send_payment_reminder(&lease_id));
+    
+    // Still unpaid past the due date, the reminder repeats on the asset's cadence
+    env.ledger().set_timestamp(95);
+    assert!(client.send_payment_reminder(&lease_id));
+    
+    // Paying starts a fresh cycle for the next due date
+    client.process_payment(&lease_id, &lessee);
+    assert!(!client.send_payment_reminder(&lease_id));
+    env.ledger().set_timestamp(180);
+    assert!(client.send_payment_reminder(&lease_id));
+    assert_eq!(client.get_event_sequence(), 6);
+}
+
+#[test]
+fn test_trial_exit_prorates_rent_and_returns_deposit() {
+    let env = Env::default();
+    let (contract_id, client, admin) = setup(&env);
+    let native = client.view_native_token().unwrap();
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Apartment"),
+        &String::from_str(&env, "Studio"),
+        &10, &100, &100, &1000, &30,
+    );
+    client.set_trial_period(&asset_id, &owner, &1);
+    fund(&env, &client, &lessee, 80);
+    let lease_id = client.create_lease(&asset_id, &lessee, &500);
+    assert_eq!(client.view_escrow_balance(&lease_id), 30);
+    assert_eq!(client.view_lease(&lease_id).unwrap().trial_end, calendar::DAY);
+    assert_eq!(client.view_withdrawable(&owner, &native), 0);
+    
+    // Forty seconds into a 100-second period, only 4 of the 10 XLM paid is rent owed; the
+    // credit and the deposit go back to the lessee
+    env.ledger().set_timestamp(40);
+    assert_eq!(client.exit_trial(&lease_id, &owner), 6);
+    let lease = client.view_lease(&lease_id).unwrap();
+    assert!(!lease.is_active);
+    assert_eq!(lease.total_paid, 34);
+    assert!(client.view_asset(&asset_id).unwrap().is_available);
+    assert_eq!(client.view_escrow_balance(&lease_id), 0);
+    assert_eq!(TokenClient::new(&env, &native).balance(&lessee), 76);
+    assert_eq!(client.view_withdrawable(&owner, &native), 4);
+    assert_sub_accounts_cover_balances(&env, &contract_id, &client);
+    
+    // Once the trial has run out, leases end the normal way and the rent held through it
+    // goes to the owner
+    let lease_id = client.create_lease(&asset_id, &lessee, &500);
+    env.ledger().set_timestamp(40 + calendar::DAY);
+    assert_eq!(
+        client.try_exit_trial(&lease_id, &lessee),
+        Err(Ok(RentError::NotInTrial))
+    );
+    client.poke_lease(&lease_id);
+    assert_eq!(client.view_withdrawable(&owner, &native), 14);
+    assert_sub_accounts_cover_balances(&env, &contract_id, &client);
+    
+    // An owner who has withdrawn everything can still be exited from a trial, and the
+    // platform fee is only charged on the rent the owner keeps
+    client.withdraw_rent(&owner, &native, &14);
+    client.set_platform_fee_bps(&admin, &2_000);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Apartment"),
+        &String::from_str(&env, "Loft"),
+        &10, &100, &100, &1000, &30,
+    );
+    client.set_trial_period(&asset_id, &owner, &1);
+    fund(&env, &client, &lessee, 40);
+    let lease_id = client.create_lease(&asset_id, &lessee, &500);
+    assert_eq!(client.view_withdrawable(&owner, &native), 0);
+    env.ledger().set_timestamp(40 + calendar::DAY + 50);
+    assert_eq!(client.exit_trial(&lease_id, &lessee), 5);
+    assert_eq!(client.view_withdrawable(&owner, &native), 4);
+    assert_eq!(client.view_platform_fees().get(native.clone()), Some(1));
+    assert_sub_accounts_cover_balances(&env, &contract_id, &client);
+    
+    // A lease without periods owes no prorated rent on a trial exit
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Apartment"),
+        &String::from_str(&env, "Annex"),
+        &10, &0, &100, &1000, &30,
+    );
+    client.set_trial_period(&asset_id, &owner, &1);
+    let lease_id = client.create_lease(&asset_id, &lessee, &500);
+    assert_eq!(client.exit_trial(&lease_id, &lessee), 10);
+    assert_sub_accounts_cover_balances(&env, &contract_id, &client);
+}
+
+#[test]
+fn test_access_code_revealed_after_first_payment() {
+    let env = Env::default();
+    let (_, client, _) = setup(&env);
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    fund(&env, &client, &lessee, 1_000);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Storage"),
+        &String::from_str(&env, "Unit 4B"),
+        &10, &100, &100, &1000, &0,
+    );
+    let lease_id = client.create_lease(&asset_id, &lessee, &500);
+    
+    let code = Bytes::from_slice(&env, b"4821#");
+    client.commit_access_code(&lease_id, &owner, &env.crypto().sha256(&code).into());
+    assert_eq!(
+        client.try_reveal_access_code(&lease_id, &owner, &code),
+        Err(Ok(RentError::PaymentNotDue))
+    );
+    
+    env.ledger().set_timestamp(100);
+    client.process_payment(&lease_id, &lessee);
+    assert_eq!(
+        client.try_reveal_access_code(&lease_id, &owner, &Bytes::from_slice(&env, b"0000#")),
+        Err(Ok(RentError::AccessCodeMismatch))
+    );
+    client.reveal_access_code(&lease_id, &owner, &code);
+    
+    let handover = client.view_access_handover(&lease_id).unwrap();
+    assert_eq!(handover.code, Some(code));
+    assert_eq!(handover.revealed_at, 100);
+}
+
+#[test]
+fn test_min_ttl_policy_keeps_the_instance_alive() {
+    let env = Env::default();
+    let (contract_id, client, admin) = setup(&env);
+    let instance_ttl = || env.as_contract(&contract_id, || env.storage().instance().get_ttl());
+    
+    // Only the admin may set a policy, and only one that fits under the network maximum
+    assert!(client.try_set_min_ttl(&Address::generate(&env), &20_000, &30_000).is_err());
+    for (threshold, extend_to) in [(30_000, 20_000), (20_000, u32::MAX)] {
+        assert_eq!(
+            client.try_set_min_ttl(&admin, &threshold, &extend_to),
+            Err(Ok(RentError::InvalidAmount))
+        );
+    }
+    
+    client.set_min_ttl(&admin, &20_000, &30_000);
+    let ttl = client.view_min_ttl();
+    assert_eq!((ttl.threshold, ttl.extend_to), (20_000, 30_000));
+    assert_eq!(instance_ttl(), 30_000);
+    
+    // A bump above the threshold leaves the TTL alone, one below it tops it back up
+    env.ledger().with_mut(|l| l.sequence_number += 5_000);
+    client.bump_contract_ttl();
+    assert_eq!(instance_ttl(), 25_000);
+    env.ledger().with_mut(|l| l.sequence_number += 10_000);
+    client.bump_contract_ttl();
+    assert_eq!(instance_ttl(), 30_000);
+}
+
+#[test]
+fn test_bump_entries_extends_persistent_records() {
+    let env = Env::default();
+    let (contract_id, client, _) = setup(&env);
+    
+    let key = storage_keys::LeaseStatusBook::LeaseStatus(7);
+    env.as_contract(&contract_id, || env.storage().persistent().set(&key, &true));
+    
+    assert_eq!(client.bump_entries(&EntryKind::Lease, &soroban_sdk::vec![&env, 7, 8], &50_000), 1);
+    assert_eq!(client.bump_entries(&EntryKind::Asset, &soroban_sdk::vec![&env, 7], &50_000), 0);
+    env.as_contract(&contract_id, || assert_eq!(env.storage().persistent().get_ttl(&key), 50_000));
+}
+
+#[test]
+fn test_records_live_in_persistent_storage() {
+    let env = Env::default();
+    let (contract_id, client, _) = setup(&env);
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    fund(&env, &client, &lessee, 1_000);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Parking"),
+        &String::from_str(&env, "Spot 12"),
+        &10, &100, &100, &1000, &0,
+    );
+    let lease_id = client.create_lease(&asset_id, &lessee, &500);
+    
+    let asset_key = storage_keys::AssetBook::Asset(asset_id);
+    let lease_key = storage_keys::LeaseStatusBook::LeaseStatus(lease_id);
+    let extend_to = client.view_min_ttl().extend_to;
+    env.as_contract(&contract_id, || {
+        assert!(!env.storage().instance().has(&asset_key));
+        assert!(!env.storage().instance().has(&lease_key));
+        // Each write keeps the record at least `extend_to` ledgers from expiry
+        assert!(env.storage().persistent().get_ttl(&asset_key) >= extend_to);
+        assert!(env.storage().persistent().get_ttl(&lease_key) >= extend_to);
+    });
+    
+    assert_eq!(client.bump_entries(&EntryKind::Asset, &soroban_sdk::vec![&env, asset_id], &50_000), 1);
+    assert_eq!(client.bump_entries(&EntryKind::Lease, &soroban_sdk::vec![&env, lease_id], &50_000), 1);
+}
+
+#[test]
+fn test_late_fee_waits_out_grace_period_then_accrues_from_due_date() {
+    let env = Env::default();
+    let (_, client, _) = setup(&env);
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    fund(&env, &client, &lessee, 1_000);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Parking"),
+        &String::from_str(&env, "Spot 12"),
+        &10, &100, &100, &1000, &0,
+    );
+    client.set_penalty_policy(&asset_id, &owner, &PenaltyPolicy::Interest(penalty::InterestPenalty { rate_bps: 1_000, cap: 50 }));
+    client.set_grace_period(&asset_id, &owner, &30);
+    let lease_id = client.create_lease(&asset_id, &lessee, &500);
+    
+    // Due at 100; nothing accrues until the grace period lapses at 130
+    env.ledger().set_timestamp(130);
+    assert_eq!(client.calculate_late_fee(&lease_id), 0);
+    env.ledger().set_timestamp(131);
+    assert_eq!(client.calculate_late_fee(&lease_id), 1);
+    
+    client.process_payment(&lease_id, &lessee);
+    assert_eq!(client.view_lease(&lease_id).unwrap().late_fees_paid, 1);
+    assert_eq!(client.view_asset_stats().total_late_fees, 1);
+    assert_eq!(client.view_asset_financials(&asset_id).late_fees, 1);
+}
+
+#[test]
+fn test_approved_extension_moves_one_due_date_without_late_fees() {
+    let env = Env::default();
+    let (_, client, _) = setup(&env);
+    let day = calendar::DAY;
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    fund(&env, &client, &lessee, 1_000);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Parking"),
+        &String::from_str(&env, "Spot 12"),
+        &10, &(10 * day), &(10 * day), &(100 * day), &0,
+    );
+    client.set_penalty_policy(&asset_id, &owner, &PenaltyPolicy::Interest(penalty::InterestPenalty { rate_bps: 1_000, cap: 50 }));
+    let lease_id = client.create_lease(&asset_id, &lessee, &(60 * day));
+    let reason = BytesN::from_array(&env, &[7; 32]);
+    
+    // Due on day 10; an extension must be shorter than the 10-day period
+    env.ledger().set_timestamp(day);
+    assert_eq!(
+        client.try_request_extension(&lease_id, &lessee, &10, &reason),
+        Err(Ok(RentError::InvalidAmount))
+    );
+    client.request_extension(&lease_id, &lessee, &3, &reason);
+    assert_eq!(client.view_extension(&lease_id).unwrap().approved_at, 0);
+    client.approve_extension(&lease_id, &owner);
+    assert_eq!(client.view_extensions_left(&lease_id), 1);
+    
+    env.ledger().set_timestamp(13 * day);
+    assert_eq!(client.calculate_late_fee(&lease_id), 0);
+    env.ledger().set_timestamp(13 * day + 1);
+    assert!(client.calculate_late_fee(&lease_id) > 0);
+    env.ledger().set_timestamp(12 * day);
+    client.process_payment(&lease_id, &lessee);
+    assert_eq!(client.view_lease(&lease_id).unwrap().late_fees_paid, 0);
+    
+    // The grant covered only that due date; the next one, a period after paying, is not moved
+    env.ledger().set_timestamp(22 * day + 1);
+    assert!(client.calculate_late_fee(&lease_id) > 0);
+    assert_eq!(
+        client.try_approve_extension(&lease_id, &owner),
+        Err(Ok(RentError::NoPendingExtension))
+    );
+    assert_eq!(
+        client.try_request_extension(&lease_id, &lessee, &3, &reason),
+        Err(Ok(RentError::ExtensionRefused))
+    );
+    client.process_payment(&lease_id, &lessee);
+    
+    // A second grant uses up the year's allowance
+    client.request_extension(&lease_id, &lessee, &2, &reason);
+    client.approve_extension(&lease_id, &owner);
+    assert_eq!(client.view_extensions_left(&lease_id), 0);
+    client.process_payment(&lease_id, &lessee);
+    assert_eq!(
+        client.try_request_extension(&lease_id, &lessee, &2, &reason),
+        Err(Ok(RentError::ExtensionRefused))
+    );
+}
+
+#[test]
+fn test_screened_asset_leases_only_through_approved_application() {
+    let env = Env::default();
+    let (_, client, _) = setup(&env);
+    
+    let owner = Address::generate(&env);
+    let applicant = Address::generate(&env);
+    let other = Address::generate(&env);
+    fund(&env, &client, &applicant, 1_000);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Flat"),
+        &String::from_str(&env, "One bedroom"),
+        &10, &100, &100, &1000, &0,
+    );
+    client.set_screening(&asset_id, &owner, &true);
+    assert_eq!(
+        client.try_create_lease(&asset_id, &applicant, &500),
+        Err(Ok(RentError::AssetUnavailable))
+    );
+    
+    // A rejected application is closed for good
+    let rejected = client.apply_for_lease(&asset_id, &other, &500);
+    client.reject_application(&rejected, &owner);
+    assert_eq!(client.view_application(&rejected).unwrap().status, ApplicationStatus::Rejected);
+    assert_eq!(
+        client.try_approve_application(&rejected, &owner),
+        Err(Ok(RentError::InvalidApplication))
+    );
+    
+    let application_id = client.apply_for_lease(&asset_id, &applicant, &500);
+    assert_eq!(
+        client.try_accept_application(&application_id, &applicant),
+        Err(Ok(RentError::InvalidApplication))
+    );
+    client.approve_application(&application_id, &owner);
+    let lease_id = client.accept_application(&application_id, &applicant);
+    
+    let application = client.view_application(&application_id).unwrap();
+    assert_eq!((application.status, application.lease_id), (ApplicationStatus::Leased, lease_id));
+    assert_eq!(client.view_lease(&lease_id).unwrap().lessee, applicant);
+    assert_eq!(client.get_application_count(), 2);
+    assert_eq!(
+        client.try_accept_application(&application_id, &applicant),
+        Err(Ok(RentError::InvalidApplication))
+    );
+}
+
+#[test]
+fn test_renew_lease_extends_term_within_max_duration() {
+    let env = Env::default();
+    let (contract_id, client, _) = setup(&env);
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    fund(&env, &client, &lessee, 1_000);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Parking"),
+        &String::from_str(&env, "Spot 12"),
+        &10, &100, &100, &1000, &0,
+    );
+    let lease_id = client.create_lease(&asset_id, &lessee, &500);
+    
+    // Keeping the old price, then taking the asset's new price
+    env.as_contract(&contract_id, || {
+        let mut asset = assets::load_asset(&env, asset_id).unwrap();
+        asset.price_per_period = 12;
+        assets::save_asset(&env, &asset);
+    });
+    assert_eq!(client.renew_lease(&lease_id, &lessee, &200, &false), 700);
+    assert_eq!(client.view_lease(&lease_id).unwrap().period_payment, 10);
+    assert_eq!(client.renew_lease(&lease_id, &lessee, &300, &true), 1000);
+    assert_eq!(client.view_lease(&lease_id).unwrap().period_payment, 12);
+    
+    assert_eq!(
+        client.try_renew_lease(&lease_id, &lessee, &1, &false),
+        Err(Ok(RentError::InvalidLeaseDuration))
+    );
+    client.file_non_renewal(&lease_id, &owner);
+    assert_eq!(
+        client.try_renew_lease(&lease_id, &lessee, &0, &false),
+        Err(Ok(RentError::RenewalRefused))
+    );
+}
+
+#[test]
+fn test_vacation_delegate_acts_for_owner_until_expiry() {
+    let env = Env::default();
+    let (_, client, _) = setup(&env);
+    
+    let owner = Address::generate(&env);
+    let contact = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    fund(&env, &client, &lessee, 1_000);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Parking"),
+        &String::from_str(&env, "Spot 12"),
+        &10, &100, &100, &1000, &40,
+    );
+    let lease_id = client.create_lease(&asset_id, &lessee, &500);
+    
+    assert_eq!(
+        client.try_set_vacation_delegate(&owner, &contact, &(assets::MAX_VACATION + 1)),
+        Err(Ok(RentError::InvalidWindow))
+    );
+    client.set_vacation_delegate(&owner, &contact, &200);
+    
+    // The contact can act on the owner's assets, but payouts still go to the owner
+    client.set_grace_period(&asset_id, &contact, &30);
+    client.claim_deposit_deduction(&lease_id, &contact, &15);
+    let token = TokenClient::new(&env, &client.view_native_token().unwrap());
+    assert_eq!(client.view_withdrawable(&owner, &token.address), 25);
+    assert_eq!(client.view_withdrawable(&contact, &token.address), 0);
+    
+    // Delegation lapses on its own
+    env.ledger().set_timestamp(200);
+    assert_eq!(client.view_vacation_delegate(&owner), None);
+    assert_eq!(
+        client.try_set_grace_period(&asset_id, &contact, &0),
+        Err(Ok(RentError::NotAssetOwner))
+    );
+}
+
+#[test]
+fn test_waived_late_fee_reduces_next_payment() {
+    let env = Env::default();
+    let (_, client, _) = setup(&env);
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    fund(&env, &client, &lessee, 1_000);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Parking"),
+        &String::from_str(&env, "Spot 12"),
+        &10, &100, &100, &1000, &0,
+    );
+    client.set_penalty_policy(&asset_id, &owner, &PenaltyPolicy::Flat(5));
+    let lease_id = client.create_lease(&asset_id, &lessee, &500);
+    
+    env.ledger().set_timestamp(150);
+    assert_eq!(client.view_amount_due(&lease_id), 15);
+    client.waive_late_fee(&lease_id, &owner, &3, &symbol_short!("goodwill"));
+    assert_eq!(client.view_amount_due(&lease_id), 12);
+    assert_eq!(
+        client.try_waive_late_fee(&lease_id, &owner, &3, &symbol_short!("goodwill")),
+        Err(Ok(RentError::InvalidAmount))
+    );
+    
+    client.process_payment(&lease_id, &lessee);
+    assert_eq!(client.view_asset_financials(&asset_id).late_fees, 2);
+    let waiver = client.view_late_fee_waivers(&lease_id).get(0).unwrap();
+    assert_eq!((waiver.amount, waiver.reason, waiver.paid_due), (3, symbol_short!("goodwill"), 100));
+    
+    // The waiver covered that payment only; the next late payment pays the full fee
+    env.ledger().set_timestamp(300);
+    assert_eq!(client.view_amount_due(&lease_id), 15);
+}
+
+#[test]
+fn test_reporting_period_bounds() {
+    let day = |y, m, d| calendar::days_from_civil(y, m, d) * calendar::DAY;
+    assert_eq!(calendar::civil_from_days(calendar::days_from_civil(2024, 2, 29)), (2024, 2, 29));
+    assert_eq!(
+        calendar::period_bounds(ReportingPeriod::CalendarMonth, day(2024, 12, 31) + 5),
+        (day(2024, 12, 1), day(2025, 1, 1))
+    );
+    
+    // A fiscal year starting in April puts February in the quarter that began in January
+    assert_eq!(
+        calendar::period_bounds(ReportingPeriod::FiscalQuarter(4), day(2025, 2, 15)),
+        (day(2025, 1, 1), day(2025, 4, 1))
+    );
+    assert_eq!(
+        calendar::period_bounds(ReportingPeriod::FiscalQuarter(11), day(2025, 1, 10)),
+        (day(2024, 11, 1), day(2025, 2, 1))
+    );
+}
+
+#[test]
+fn test_close_reporting_period_freezes_bucket() {
+    let env = Env::default();
+    let (_, client, _) = setup(&env);
+    let january = calendar::days_from_civil(2025, 1, 1) * calendar::DAY;
+    let february = calendar::days_from_civil(2025, 2, 1) * calendar::DAY;
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    fund(&env, &client, &lessee, 1_000);
+    env.ledger().set_timestamp(january + 10);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Parking"),
+        &String::from_str(&env, "Spot 12"),
+        &10, &100, &100, &1000, &0,
+    );
+    client.create_lease(&asset_id, &lessee, &500);
+    
+    env.ledger().set_timestamp(february + 10);
+    let bucket = client.close_reporting_period();
+    assert_eq!((bucket.period_start, bucket.period_end), (january, february));
+    assert_eq!(bucket.financials.rent_collected, 10);
+    assert!(client.view_reporting_bucket(&january).unwrap().closed);
+    assert_eq!(
+        client.try_close_reporting_period().err(),
+        Some(Ok(RentError::PeriodClosed))
+    );
+}
+
+#[test]
+fn test_simulated_year_keeps_stats_schedules_and_totals_consistent() {
+    let env = Env::default();
+    let (_, client, admin) = setup(&env);
+    let month = 30 * calendar::DAY;
+    client.set_escalation_schedule(&admin, &penalty::EscalationSchedule {
+        reminder_after: 0,
+        notice_after: 5 * calendar::DAY,
+        warning_after: 20 * calendar::DAY,
+    });
+    
+    let owner = Address::generate(&env);
+    let tenant = Address::generate(&env);
+    let seasonal = Address::generate(&env);
+    fund(&env, &client, &tenant, 2_000);
+    fund(&env, &client, &seasonal, 200);
+    let home = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Apartment"),
+        &String::from_str(&env, "Two-bed"),
+        &100, &month, &month, &(12 * month), &200,
+    );
+    let garage = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Garage"),
+        &String::from_str(&env, "Lockup"),
+        &20, &month, &month, &(12 * month), &0,
+    );
+    client.set_penalty_policy(&home, &owner, &PenaltyPolicy::Flat(10));
+    
+    // A six-month apartment lease that auto-renews, and a six-month garage lease that doesn't
+    let lease_id = client.create_lease(&home, &tenant, &(6 * month));
+    client.set_auto_renew(&lease_id, &tenant, &true);
+    let garage_lease = client.create_lease(&garage, &seasonal, &(6 * month));
+    
+    let mut processed = 300 + 20;
+    let mut late_fees = 0;
+    let mut last_paid_at = 0;
+    for m in 1..=12_u64 {
+        // The garage is paid on its due date until the owner takes it back after six months
+        if m < 6 {
+            env.ledger().set_timestamp(m * month);
+            processed += client.view_amount_due(&garage_lease);
+            client.process_payment(&garage_lease, &seasonal);
+        } else if m == 6 {
+            env.ledger().set_timestamp(m * month);
+            client.end_lease(&garage_lease, &owner);
+        }
+        
+        // Every fourth month the rent is a week late: the notice escalates, the owner marks
+        // the lease overdue and the tenant cures it with the late fee
+        let due = client.view_lease(&lease_id).unwrap().next_payment_time;
+        if m % 4 == 0 {
+            env.ledger().set_timestamp(due + 7 * calendar::DAY);
+            client.poke_lease(&lease_id);
+            assert_eq!(client.view_late_notice(&lease_id).level, penalty::EscalationLevel::FormalNotice);
+            client.mark_lease_overdue(&lease_id, &owner);
+            assert_eq!(client.view_asset_stats().overdue_leases, 1);
+            assert_eq!(client.view_amount_due(&lease_id), 110);
+            late_fees += 10;
+        } else {
+            env.ledger().set_timestamp(due);
+            assert_eq!(client.view_amount_due(&lease_id), 100);
+        }
+        processed += client.view_amount_due(&lease_id);
+        client.process_payment(&lease_id, &tenant);
+        last_paid_at = env.ledger().timestamp();
+        
+        assert_eq!(client.view_asset_stats().overdue_leases, 0);
+        assert_eq!(client.view_late_notice(&lease_id).level, penalty::EscalationLevel::None);
+    }
+    
+    // Each late payment pushed the schedule back a week, and the term renewed once at six months
+    let lease = client.view_lease(&lease_id).unwrap();
+    assert!(lease.is_active);
+    assert_eq!(lease.payments_made, 12);
+    assert_eq!(last_paid_at, 12 * month + 21 * calendar::DAY);
+    assert_eq!(lease.next_payment_time, last_paid_at + month);
+    assert_eq!(lease.end_time, 18 * month);
+    assert_eq!(lease.total_paid, 300 + 12 * 100 + late_fees);
+    
+    let stats = client.view_asset_stats();
+    assert_eq!(stats.total_xlm_processed, processed);
+    assert_eq!((stats.total_leases, stats.active_leases, stats.completed_leases), (2, 1, 1));
+    
+    let financials = client.view_asset_financials(&home);
+    assert_eq!((financials.rent_collected, financials.late_fees, financials.vacancies), (1300, 30, 0));
+    let financials = client.view_asset_financials(&garage);
+    assert_eq!((financials.rent_collected, financials.vacancies), (120, 1));
+    assert!(client.view_asset(&garage).unwrap().is_available);
+}
+
+#[test]
+fn test_monthly_reporting_buckets_add_up_over_a_year() {
+    let env = Env::default();
+    let (_, client, _) = setup(&env);
+    let month = |m: u32| calendar::days_from_civil(2025, m, 1) * calendar::DAY;
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    fund(&env, &client, &lessee, 1_000);
+    env.ledger().set_timestamp(month(1));
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Office"),
+        &String::from_str(&env, "Desk 3"),
+        &50, &(28 * calendar::DAY), &(28 * calendar::DAY), &(400 * calendar::DAY), &0,
+    );
+    let lease_id = client.create_lease(&asset_id, &lessee, &(400 * calendar::DAY));
+    
+    // Four-week billing drifts against calendar months, so some months take two payments
+    let mut bucket_rent = 0;
+    for m in 2..=12 {
+        while client.view_lease(&lease_id).unwrap().next_payment_time < month(m) {
+            env.ledger().set_timestamp(client.view_lease(&lease_id).unwrap().next_payment_time);
+            client.process_payment(&lease_id, &lessee);
+        }
+        env.ledger().set_timestamp(month(m));
+        let bucket = client.close_reporting_period();
+        assert_eq!(bucket.period_start, month(m - 1));
+        bucket_rent += bucket.financials.rent_collected;
+    }
+    
+    let lease = client.view_lease(&lease_id).unwrap();
+    assert_eq!(bucket_rent, client.view_asset_financials(&asset_id).rent_collected);
+    assert_eq!(bucket_rent, 50 * (lease.payments_made as i128 + 1));
+}
+
+#[test]
+fn test_unanswered_application_expires_and_refunds_its_holding_deposit() {
+    let env = Env::default();
+    let (contract_id, client, _) = setup(&env);
+    let token = TokenClient::new(&env, &client.view_native_token().unwrap());
+    
+    let owner = Address::generate(&env);
+    let applicant = Address::generate(&env);
+    let other = Address::generate(&env);
+    fund(&env, &client, &applicant, 1_000);
+    fund(&env, &client, &other, 1_000);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Flat"),
+        &String::from_str(&env, "One bedroom"),
+        &10, &100, &100, &1000, &0,
+    );
+    let terms = ApplicationTerms { response_window: 100, holding_deposit: 25 };
+    client.set_application_terms(&asset_id, &owner, &terms);
+    assert_eq!(client.view_application_terms(&asset_id), terms);
+    
+    // Applying escrows the holding deposit and starts the owner's clock
+    env.ledger().set_timestamp(1_000);
+    let answered = client.apply_for_lease(&asset_id, &other, &500);
+    let ignored = client.apply_for_lease(&asset_id, &applicant, &500);
+    let application = client.view_application(&ignored).unwrap();
+    assert_eq!((application.respond_by, application.holding_deposit), (1_100, 25));
+    assert_eq!(token.balance(&applicant), 975);
+    
+    // An answer in time releases the deposit and counts toward the owner's responsiveness
+    env.ledger().set_timestamp(1_040);
+    client.reject_application(&answered, &owner);
+    assert_eq!(token.balance(&other), 1_000);
+    
+    // Nobody can expire an application before its deadline, nor answer it after
+    env.ledger().set_timestamp(1_100);
+    assert_eq!(
+        client.try_expire_application(&ignored),
+        Err(Ok(RentError::DeadlineNotReached))
+    );
+    env.ledger().set_timestamp(1_101);
+    assert_eq!(
+        client.try_approve_application(&ignored, &owner),
+        Err(Ok(RentError::InvalidApplication))
+    );
+    
+    // Past it anyone can expire it, refunding the applicant once
+    client.expire_application(&ignored);
+    assert_eq!(client.view_application(&ignored).unwrap().status, ApplicationStatus::Expired);
+    assert_eq!(token.balance(&applicant), 1_000);
+    assert_eq!(
+        client.try_expire_application(&ignored),
+        Err(Ok(RentError::InvalidApplication))
+    );
+    assert_eq!(
+        client.try_accept_application(&ignored, &applicant),
+        Err(Ok(RentError::InvalidApplication))
+    );
+    
+    // Applicants can see how the owner answers before applying
+    let reputation = client.view_reputation(&owner);
+    assert_eq!((reputation.applications_answered, reputation.applications_expired, reputation.total_response_time), (1, 1, 40));
+    assert_sub_accounts_cover_balances(&env, &contract_id, &client);
+    
+    // Applications made without a deadline never expire
+    client.set_application_terms(&asset_id, &owner, &ApplicationTerms::default());
+    let open = client.apply_for_lease(&asset_id, &applicant, &500);
+    env.ledger().set_timestamp(1_000_000);
+    assert_eq!(
+        client.try_expire_application(&open),
+        Err(Ok(RentError::DeadlineNotReached))
+    );
+}
+
+#[test]
+fn test_one_lease_per_asset_per_ledger() {
+    let env = Env::default();
+    let (_, client, _) = setup(&env);
+    
+    let owner = Address::generate(&env);
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    fund(&env, &client, &first, 100);
+    fund(&env, &client, &second, 100);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Parking"),
+        &String::from_str(&env, "Spot 12"),
+        &10, &100, &100, &1000, &0,
+    );
+    env.ledger().set_sequence_number(10);
+    let lease_id = client.create_lease(&asset_id, &first, &500);
+    
+    // The loser in the same ledger is told it lost the race; later it is just unavailable
+    assert_eq!(
+        client.try_create_lease(&asset_id, &second, &500),
+        Err(Ok(RentError::LeaseRaceLost))
+    );
+    env.ledger().set_sequence_number(11);
+    assert_eq!(
+        client.try_create_lease(&asset_id, &second, &500),
+        Err(Ok(RentError::AssetUnavailable))
+    );
+    
+    client.end_lease(&lease_id, &first);
+    client.create_lease(&asset_id, &second, &500);
+}
+
+#[test]
+fn test_existence_and_count_views() {
+    let env = Env::default();
+    let (_, client, _) = setup(&env);
+    
+    assert_eq!((client.get_asset_count(), client.get_lease_count()), (0, 0));
+    assert!(!client.asset_exists(&1));
+    
+    let owner = Address::generate(&env);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Parking"),
+        &String::from_str(&env, "Spot 12"),
+        &10, &100, &100, &1000, &0,
+    );
+    let lessee = Address::generate(&env);
+    fund(&env, &client, &lessee, 100);
+    let lease_id = client.create_lease(&asset_id, &lessee, &500);
+    
+    assert!(client.asset_exists(&asset_id));
+    assert!(client.lease_exists(&lease_id));
+    assert!(!client.lease_exists(&(lease_id + 1)));
+    assert_eq!((client.get_asset_count(), client.get_lease_count()), (1, 1));
+}
+
+#[test]
+fn test_views_return_none_for_missing_records() {
+    let env = Env::default();
+    let (_, client, _) = setup(&env);
+    
+    assert!(client.view_asset(&1).is_none());
+    assert!(client.view_lease(&1).is_none());
+    
+    let owner = Address::generate(&env);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Parking"),
+        &String::from_str(&env, "Spot 12"),
+        &10, &100, &100, &1000, &0,
+    );
+    assert_eq!(client.view_asset(&asset_id).unwrap().owner, owner);
+    
+    // Entrypoints that need a record still fail with the usual not-found errors
+    assert_eq!(
+        client.try_create_lease(&(asset_id + 1), &Address::generate(&env), &500),
+        Err(Ok(RentError::AssetNotFound))
+    );
+    assert_eq!(
+        client.try_end_lease(&1, &owner),
+        Err(Ok(RentError::LeaseNotActive))
+    );
+}
+
+#[test]
+fn test_state_changes_require_the_named_party_to_sign() {
+    let env = Env::default();
+    let (_, client, _) = setup(&env);
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    fund(&env, &client, &lessee, 1_000);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Bike"),
+        &String::from_str(&env, "City bike"),
+        &10, &100, &100, &1000, &0,
+    );
+    assert_eq!(env.auths()[0].0, owner);
+    let lease_id = client.create_lease(&asset_id, &lessee, &500);
+    assert_eq!(env.auths()[0].0, lessee);
+    env.ledger().set_timestamp(100);
+    client.mark_lease_overdue(&lease_id, &owner);
+    assert_eq!(env.auths()[0].0, owner);
+    
+    // Without signatures, naming someone else's address gets nowhere
+    env.set_auths(&[]);
+    assert!(client.try_end_lease(&lease_id, &lessee).is_err());
+    assert!(client.try_register_asset(
+        &owner,
+        &String::from_str(&env, "Bike"),
+        &String::from_str(&env, "Spoofed listing"),
+        &10, &100, &100, &1000, &0,
+    ).is_err());
+    assert!(client.view_lease(&lease_id).unwrap().is_active);
+    assert_eq!(client.get_asset_count(), 1);
+}
+
+#[test]
+fn test_deposits_need_the_escrow_contract_configured_once() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(RentPaymentContract, ());
+    let client = RentPaymentContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    client.set_native_token(&admin, &env.register_stellar_asset_contract_v2(admin.clone()).address());
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    fund(&env, &client, &lessee, 1_000);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Camper"),
+        &String::from_str(&env, "Four berth"),
+        &10, &100, &100, &1000, &50,
+    );
+    assert_eq!(client.try_create_lease(&asset_id, &lessee, &500), ext_error(RentErrorExt::EscrowNotSet));
+    
+    let escrow_id = env.register(::escrow::EscrowContract, ());
+    ::escrow::EscrowContractClient::new(&env, &escrow_id).initialize(&contract_id);
+    assert_eq!(
+        client.try_set_escrow_contract(&owner, &escrow_id),
+        Err(Ok(RentError::NotAdmin))
+    );
+    client.set_escrow_contract(&admin, &escrow_id);
+    assert_eq!(
+        client.try_set_escrow_contract(&admin, &Address::generate(&env)),
+        Err(Ok(RentError::AlreadyInitialized))
+    );
+    client.create_lease(&asset_id, &lessee, &500);
+    assert_eq!(::escrow::EscrowContractClient::new(&env, &escrow_id).view_balance(&1, &client.view_native_token().unwrap()), 50);
+}
+
+#[test]
+fn test_escrowed_deposit_refunded_minus_deductions() {
+    let env = Env::default();
+    let (contract_id, client, _) = setup(&env);
+    let token = TokenClient::new(&env, &client.view_native_token().unwrap());
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    fund(&env, &client, &lessee, 60);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Camper"),
+        &String::from_str(&env, "Four berth"),
+        &10, &100, &100, &1000, &50,
+    );
+    let lease_id = client.create_lease(&asset_id, &lessee, &500);
+    
+    // The deposit sits with the escrow contract; only the first rent reaches this one
+    let custodian = client.view_escrow_contract().unwrap();
+    assert_eq!(token.balance(&contract_id), 10);
+    assert_eq!(token.balance(&custodian), 50);
+    assert_eq!(client.view_escrow_balance(&lease_id), 50);
+    
+    assert_eq!(client.claim_deposit_deduction(&lease_id, &owner, &20), 30);
+    assert_eq!(
+        client.try_claim_deposit_deduction(&lease_id, &owner, &31),
+        Err(Ok(RentError::InvalidClaim))
+    );
+    assert_eq!(client.view_asset_financials(&asset_id).deposits_retained, 20);
+    
+    client.end_lease(&lease_id, &lessee);
+    assert_eq!(client.view_escrow_balance(&lease_id), 0);
+    assert_eq!(client.view_withdrawable(&owner, &token.address), 30);
+    assert_eq!(token.balance(&lessee), 30);
+    assert_eq!(token.balance(&contract_id), 30);
+    assert_eq!(token.balance(&custodian), 0);
+    
+    // The closing statement records that outcome and its stored hash can be recomputed
+    let (statement, hash) = client.view_closing_statement(&lease_id).unwrap();
+    assert_eq!((statement.deposit, statement.deductions, statement.refunded, statement.held), (50, 20, 30, 0));
+    assert_eq!(hash, env.crypto().sha256(&statement.to_xdr(&env)).to_bytes());
+}
+
+#[test]
+fn test_lifecycle_events_name_parties_and_amounts() {
+    let env = Env::default();
+    let (_, client, _) = setup(&env);
+    // This contract's events from the last call, skipping the token contract's transfer events
+    let ours = |env: &Env| env.events().all().iter().filter(|(contract, _, _)| *contract == client.address).collect::<std::vec::Vec<_>>();
+    let topics = |env: &Env| ours(env).last().unwrap().1.clone();
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    fund(&env, &client, &lessee, 1_000);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Studio"),
+        &String::from_str(&env, "Recording studio"),
+        &10, &100, &100, &1000, &40,
+    );
+    assert_eq!(topics(&env), (symbol_short!("asset"), symbol_short!("register"), owner.clone()).into_val(&env));
+    
+    let lease_id = client.create_lease(&asset_id, &lessee, &500);
+    let (_, created, data) = ours(&env).last().unwrap().clone();
+    assert_eq!(created, (symbol_short!("lease"), symbol_short!("created"), lessee.clone()).into_val(&env));
+    let (_, (id, asset, lease_owner, start, end, collected)) = <(u64, (u64, u64, Address, u64, u64, i128))>::try_from_val(&env, &data).unwrap();
+    assert_eq!((id, asset, lease_owner, start, end, collected), (lease_id, asset_id, owner.clone(), 0, 500, 50));
+    
+    env.ledger().set_timestamp(100);
+    client.process_payment(&lease_id, &lessee);
+    assert_eq!(topics(&env), (symbol_short!("pay"), symbol_short!("made"), lessee.clone()).into_val(&env));
+    
+    env.ledger().set_timestamp(250);
+    client.mark_lease_overdue(&lease_id, &owner);
+    assert_eq!(topics(&env), (symbol_short!("lease"), symbol_short!("overdue"), lessee.clone()).into_val(&env));
+    
+    // Ending the lease refunds the escrowed deposit, then records the end
+    client.end_lease(&lease_id, &lessee);
+    let events = ours(&env);
+    let (_, refunded, data) = events[events.len() - 2].clone();
+    assert_eq!(refunded, (symbol_short!("deposit"), symbol_short!("refunded"), lessee.clone()).into_val(&env));
+    assert_eq!(<(u64, (u64, i128))>::try_from_val(&env, &data).unwrap().1, (lease_id, 40));
+    assert_eq!(topics(&env), (symbol_short!("lease"), symbol_short!("ended"), lessee.clone()).into_val(&env));
+}
+
+#[test]
+fn test_settle_all_closes_the_month_in_batches() {
+    let env = Env::default();
+    let (contract_id, client, _) = setup(&env);
+    let token = TokenClient::new(&env, &client.view_native_token().unwrap());
+    
+    let owner = Address::generate(&env);
+    let other_owner = Address::generate(&env);
+    let mut lease_ids = std::vec::Vec::new();
+    for listing_owner in [&owner, &other_owner, &owner, &owner] {
+        let asset_id = client.register_asset(
+            listing_owner,
+            &String::from_str(&env, "Unit"),
+            &String::from_str(&env, "Storage unit"),
+            &10, &100, &100, &1000, &0,
+        );
+        let lessee = Address::generate(&env);
+        fund(&env, &client, &lessee, 100);
+        lease_ids.push(client.create_lease(&asset_id, &lessee, &500));
+        
+        // The last lessee pays by auto-pay
+        if lease_ids.len() == 4 {
+            token.approve(&lessee, &contract_id, &50, &1000);
+            client.authorize_auto_pay(&lease_ids[3], &lessee, &5);
+        }
+    }
+    
+    // Past the first due date, the first batch covers the owner's first two assets and never
+    // sees the other owner's
+    env.ledger().set_timestamp(150);
+    let report = client.settle_all(&owner, &0, &2);
+    assert_eq!((report.first_asset, report.last_asset, report.next_cursor), (1, 2, 2));
+    assert_eq!((report.leases_settled, report.newly_overdue, report.overdue, report.amount_due), (2, 2, 2, 20));
+    
+    // Rent auto-pay covers is collected rather than flagged overdue
+    let report = client.settle_all(&owner, &report.next_cursor, &2);
+    assert_eq!((report.first_asset, report.last_asset, report.next_cursor), (3, 3, 0));
+    assert_eq!((report.leases_settled, report.newly_overdue, report.amount_due), (1, 0, 0));
+    assert!(client.view_lease(&lease_ids[2]).unwrap().is_overdue);
+    assert!(!client.view_lease(&lease_ids[1]).unwrap().is_overdue);
+    let collected = client.view_lease(&lease_ids[3]).unwrap();
+    assert_eq!((collected.is_overdue, collected.total_paid), (false, 20));
+    
+    // Settling again flags nothing new but still reports everything owed
+    let report = client.settle_all(&owner, &0, &10);
+    assert_eq!((report.leases_settled, report.newly_overdue, report.overdue, report.amount_due), (3, 0, 2, 20));
+    assert_eq!(client.view_asset_stats().overdue_leases, 2);
+}
+
+#[test]
+fn test_settle_all_pages_through_seeded_leases_within_budget() {
+    let env = Env::default();
+    let (contract_id, client, _) = setup(&env);
+    let owner = seed_leases(&env, &contract_id, &client, 2_000);
+    assert_eq!(client.get_lease_count(), 2_000);
+    assert_eq!(client.view_asset(&2_000).unwrap().current_lease_id, 2_000);
+    
+    // Lease 1 falls due at 100; the even-numbered fixtures fell due a period earlier
+    env.ledger().set_timestamp(50);
+    let (mut client, mut owner) = (client, owner);
+    let mut cursor = 0;
+    let mut settled = 0;
+    let mut newly_overdue = 0;
+    loop {
+        if cursor % 500 == 0 {
+            client = reload(&client, (cursor / 500) as u8 + 1);
+            owner = carry(&client.env, &owner);
+        }
+        // Each page must fit a single transaction's budget on its own
+        client.env.cost_estimate().budget().reset_default();
+        let report = client.settle_all(&owner, &cursor, &10);
+        settled += report.leases_settled;
+        newly_overdue += report.newly_overdue;
+        if report.next_cursor == 0 {
+            break;
+        }
+        cursor = report.next_cursor;
+    }
+    assert_eq!(settled, 2_000);
+    assert_eq!(newly_overdue, 1_000);
+    assert_eq!(client.view_asset_stats().overdue_leases, 1_000);
+    
+    // A page never covers more than MAX_PAGE leases
+    client.env.cost_estimate().budget().reset_unlimited();
+    let report = client.settle_all(&owner, &0, &2_000);
+    assert_eq!((report.last_asset, report.next_cursor), (assets::MAX_PAGE as u64, assets::MAX_PAGE as u64));
+}
+
+#[test]
+fn test_state_root_builds_over_seeded_records_in_batches() {
+    let env = Env::default();
+    let (contract_id, client, _) = setup(&env);
+    seed_leases(&env, &contract_id, &client, 500);
+    assert_eq!(client.try_commit_state_root(&0), Err(Ok(RentError::InvalidAmount)));
+    
+    // 1,000 leaves in pages of at most MAX_PAGE, each within a single transaction's budget
+    let mut batches = 0;
+    loop {
+        env.cost_estimate().budget().reset_default();
+        let remaining = client.commit_state_root(&1_000);
+        batches += 1;
+        if remaining == 0 {
+            break;
+        }
+        assert_eq!(remaining, 1_000 - batches * assets::MAX_PAGE as u64);
+        assert!(client.view_state_root().is_none());
+    }
+    assert_eq!(batches, 10);
+    
+    // Same root as folding every leaf at once
+    env.cost_estimate().budget().reset_unlimited();
+    let commitment = client.view_state_root().unwrap();
+    assert_eq!((commitment.asset_count, commitment.lease_count), (500, 500));
+    let leaves = env.as_contract(&contract_id, || {
+        let mut leaves = Vec::new(&env);
+        for id in 1..=500 {
+            leaves.push_back(merkle::leaf_hash(&env, &assets::load_asset(&env, id).unwrap()));
+        }
+        for id in 1..=500 {
+            leaves.push_back(merkle::leaf_hash(&env, &leases::load_lease(&env, id).unwrap()));
+        }
+        leaves
+    });
+    assert_eq!(commitment.root, merkle::root(&env, leaves.clone()));
+    
+    // Records added mid-build wait for the next one
+    client.commit_state_root(&7);
+    seed_leases(&env, &contract_id, &client, 1);
+    while client.commit_state_root(&100) > 0 {}
+    assert_eq!(client.view_state_root().unwrap().root, merkle::root(&env, leaves));
+}
+
+#[test]
+fn test_listing_views_page_through_seeded_records() {
+    let env = Env::default();
+    let (contract_id, client, _) = setup(&env);
+    let owner = seed_leases(&env, &contract_id, &client, 2_000);
+    let client = reload(&client, 1);
+    let env = client.env.clone();
+    let owner = carry(&env, &owner);
+    
+    // Index reads stay within one call's budget however many records exist
+    env.cost_estimate().budget().reset_default();
+    assert_eq!(client.get_owner_assets(&owner).len(), 2_000);
+    
+    env.cost_estimate().budget().reset_default();
+    let page = client.list_assets(&1_951, &500);
+    assert_eq!(page.len(), 50);
+    assert_eq!(page.get_unchecked(0).asset_id, 1_951);
+    env.cost_estimate().budget().reset_default();
+    assert_eq!(client.list_leases(&1_001, &500).len(), assets::MAX_PAGE);
+    assert_eq!(client.list_leases(&2_001, &10).len(), 0);
+    env.cost_estimate().budget().reset_default();
+    assert_eq!(client.search_assets_by_category(&assets::DEFAULT_CATEGORY, &1_991, &50).len(), 10);
+    
+    // Ownership handed over by rent-to-own moves the asset between owners' indexes
+    let seller = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    fund(&env, &client, &buyer, 1_000);
+    let asset_id = client.register_asset(
+        &seller,
+        &String::from_str(&env, "Scooter"),
+        &String::from_str(&env, "Electric"),
+        &10, &100, &100, &1000, &0,
+    );
+    client.set_payment_policy(&asset_id, &seller, &PaymentPolicy::RentToOwn(1));
+    let lease_id = client.create_lease(&asset_id, &buyer, &500);
+    
+    // A lessee's deposit statement reads only their own leases
+    env.cost_estimate().budget().reset_default();
+    let statement = client.get_deposit_statement(&buyer);
+    assert!(statement.is_empty());
+    assert!(env.cost_estimate().budget().cpu_instruction_cost() < 2_000_000);
+    
+    env.ledger().set_timestamp(100);
+    client.process_payment(&lease_id, &buyer);
+    assert_eq!(client.get_owner_assets(&seller).len(), 0);
+    assert_eq!(client.get_owner_assets(&buyer), soroban_sdk::vec![&env, asset_id]);
+    assert_eq!(client.get_lessee_leases(&buyer).len(), 0);
+}
+
+#[test]
+fn test_pause_blocks_lease_creation_and_payments() {
+    let env = Env::default();
+    let (_, client, admin) = setup(&env);
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    fund(&env, &client, &lessee, 1_000);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Parking"),
+        &String::from_str(&env, "Spot 12"),
+        &10, &100, &100, &1000, &0,
+    );
+    let lease_id = client.create_lease(&asset_id, &lessee, &500);
+    let other_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Parking"),
+        &String::from_str(&env, "Spot 13"),
+        &10, &100, &100, &1000, &0,
+    );
+    
+    assert_eq!(
+        client.try_set_paused(&owner, &true),
+        Err(Ok(RentError::NotAdmin))
+    );
+    client.set_paused(&admin, &true);
+    assert!(client.is_paused());
+    
+    env.ledger().with_mut(|li| li.timestamp += 100);
+    assert_eq!(
+        client.try_create_lease(&other_id, &lessee, &500),
+        Err(Ok(RentError::ContractPaused))
+    );
+    assert_eq!(
+        client.try_process_payment(&lease_id, &lessee),
+        Err(Ok(RentError::ContractPaused))
+    );
+    
+    // Views keep answering while paused
+    assert_eq!(client.view_lease(&lease_id).unwrap().total_paid, 10);
+    assert_eq!(client.dry_run_process_payment(&lease_id, &lessee).rent, 10);
+    
+    client.set_paused(&admin, &false);
+    client.process_payment(&lease_id, &lessee);
+    client.create_lease(&other_id, &lessee, &500);
+}
+
+#[test]
+fn test_creation_freeze_blocks_only_new_leases_and_listings() {
+    let env = Env::default();
+    let (_, client, admin) = setup(&env);
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    fund(&env, &client, &lessee, 1_000);
+    let register = || client.try_register_asset(
+        &owner,
+        &String::from_str(&env, "Locker"),
+        &String::from_str(&env, "Storage locker"),
+        &10, &100, &100, &1000, &5,
+    );
+    let asset_id = register().unwrap().unwrap();
+    let other_id = register().unwrap().unwrap();
+    let lease_id = client.create_lease(&asset_id, &lessee, &500);
+    
+    assert_eq!(
+        client.try_set_creation_frozen(&owner, &true),
+        Err(Ok(RentError::NotAdmin))
+    );
+    client.set_creation_frozen(&admin, &true);
+    assert!(client.is_creation_frozen() && !client.is_paused());
+    
+    let frozen = ext_error(RentErrorExt::CreationFrozen);
+    assert_eq!(register(), frozen);
+    assert_eq!(client.try_create_lease(&other_id, &lessee, &500), frozen);
+    assert_eq!(client.try_apply_for_lease(&other_id, &lessee, &500), frozen);
+    
+    // Running leases still pay and settle, and deposits still come back
+    env.ledger().set_timestamp(100);
+    client.process_payment(&lease_id, &lessee);
+    client.end_lease(&lease_id, &lessee);
+    assert_eq!(client.view_escrow_balance(&lease_id), 0);
+    
+    client.set_creation_frozen(&admin, &false);
+    client.create_lease(&other_id, &lessee, &500);
+}
+
+// Token that calls back into the rent contract from inside every transfer, standing in for a
+// malicious payment token. With `swallow` set it hides the failed re-entry and lets the
+// transfer succeed, counting each refusal.
+#[contract]
+struct ReentrantToken;
+
+#[soroban_sdk::contracttype]
+enum ReentrantKey {
+    Target,
+    Refusals,
+}
+
+#[contractimpl]
+impl ReentrantToken {
+    pub fn arm(env: Env, target: Address, lease_id: u64, lessee: Address, swallow: bool) {
+        env.storage().instance().set(&ReentrantKey::Target, &(target, lease_id, lessee, swallow));
+    }
+    
+    pub fn refusals(env: Env) -> u32 {
+        env.storage().instance().get(&ReentrantKey::Refusals).unwrap_or(0)
+    }
+    
+    pub fn decimals(_env: Env) -> u32 {
+        7
+    }
+    
+    pub fn symbol(env: Env) -> String {
+        String::from_str(&env, "EVIL")
+    }
+    
+    pub fn balance(_env: Env, _id: Address) -> i128 {
+        1_000_000
+    }
+    
+    pub fn transfer(env: Env, _from: Address, _to: Address, _amount: i128) {
+        let armed: Option<(Address, u64, Address, bool)> = env.storage().instance().get(&ReentrantKey::Target);
+        if let Some((target, lease_id, lessee, swallow)) = armed {
+            let rent = RentPaymentContractClient::new(&env, &target);
+            if !swallow {
+                rent.process_payment(&lease_id, &lessee);
+            } else if rent.try_process_payment(&lease_id, &lessee).is_err() {
+                env.storage().instance().set(&ReentrantKey::Refusals, &(Self::refusals(env.clone()) + 1));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_reentrant_token_cannot_pay_twice() {
+    let env = Env::default();
+    let (contract_id, client, _) = setup(&env);
+    let evil = env.register(ReentrantToken, ());
+    let evil_client = ReentrantTokenClient::new(&env, &evil);
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Parking"),
+        &String::from_str(&env, "Spot 12"),
+        &10, &100, &100, &1000, &50,
+    );
+    client.set_payment_token(&asset_id, &owner, &evil);
+    evil_client.arm(&contract_id, &1, &lessee, &true);
+    
+    // The deposit pull and each rent transfer try to re-enter; all are refused and the
+    // lease records exactly the payments that were made
+    let lease_id = client.create_lease(&asset_id, &lessee, &500);
+    env.ledger().set_timestamp(100);
+    client.process_payment(&lease_id, &lessee);
+    assert_eq!(evil_client.refusals(), 3);
+    let lease = client.view_lease(&lease_id).unwrap();
+    assert_eq!((lease.payments_made, lease.next_payment_time), (1, 200));
+    
+    // A token that lets the re-entry abort its transfer aborts the whole payment with it
+    evil_client.arm(&contract_id, &lease_id, &lessee, &false);
+    env.ledger().set_timestamp(200);
+    assert!(client.try_process_payment(&lease_id, &lessee).is_err());
+    let unchanged = client.view_lease(&lease_id).unwrap();
+    assert_eq!((unchanged.payments_made, unchanged.next_payment_time, unchanged.total_paid), (1, 200, lease.total_paid));
+    
+    // Closing pays the deposit back only after the lease and asset are final
+    evil_client.arm(&contract_id, &lease_id, &lessee, &true);
+    env.ledger().set_timestamp(500);
+    client.end_lease(&lease_id, &owner);
+    assert_eq!(evil_client.refusals(), 4);
+    assert!(!client.view_lease(&lease_id).unwrap().is_active);
+    assert_eq!(client.view_escrow_balance(&lease_id), 0);
+}
+
+// Token reporting whatever decimal places and symbol it was deployed with; in its own module
+// since its entrypoints share names with ReentrantToken's
+mod decimals_token {
+    use soroban_sdk::{contract, contractimpl, symbol_short, Env, String};
+    
+    #[contract]
+    pub struct DecimalsToken;
+    
+    #[contractimpl]
+    impl DecimalsToken {
+        pub fn __constructor(env: Env, decimals: u32, symbol: String) {
+            env.storage().instance().set(&symbol_short!("decimals"), &decimals);
+            env.storage().instance().set(&symbol_short!("symbol"), &symbol);
+        }
+        
+        pub fn decimals(env: Env) -> u32 {
+            env.storage().instance().get(&symbol_short!("decimals")).unwrap()
+        }
+        
+        pub fn symbol(env: Env) -> String {
+            env.storage().instance().get(&symbol_short!("symbol")).unwrap()
+        }
+    }
+}
+
+#[test]
+fn test_payment_token_with_too_many_decimals_is_refused() {
+    let env = Env::default();
+    let (_, client, _) = setup(&env);
+    let wide = env.register(decimals_token::DecimalsToken, (xlm::MAX_DECIMALS + 1, String::from_str(&env, "WIDE")));
+    
+    let owner = Address::generate(&env);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Parking"),
+        &String::from_str(&env, "Spot 12"),
+        &10, &100, &100, &1000, &50,
+    );
+    assert_eq!(
+        client.try_set_payment_token(&asset_id, &owner, &wide),
+        Err(Ok(RentError::UnsupportedDecimals))
+    );
+    assert_eq!(client.view_asset(&asset_id).unwrap().currency_decimals, xlm::XLM_DECIMALS);
+}
+
+#[test]
+fn test_fractional_price_in_a_six_decimal_token() {
+    let env = Env::default();
+    let (_, client, _) = setup(&env);
+    let usdc = env.register(decimals_token::DecimalsToken, (6_u32, String::from_str(&env, "USDC")));
+    
+    let owner = Address::generate(&env);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Studio"),
+        &String::from_str(&env, "Studio flat"),
+        &10, &100, &100, &1000, &0,
+    );
+    client.set_payment_token(&asset_id, &owner, &usdc);
+    
+    // 120.50 USDC a period is 120_500_000 base units at 6 decimals
+    client.update_asset(&asset_id, &owner, &120_500_000, &0, &100, &1000, &String::from_str(&env, "Studio flat"));
+    let asset = client.view_asset(&asset_id).unwrap();
+    assert_eq!((asset.price_per_period, asset.currency_decimals), (120_500_000, 6));
+    assert_eq!(asset.currency_symbol, String::from_str(&env, "USDC"));
+}
+
+#[test]
+fn test_anyone_settles_a_lease_past_its_term() {
+    let env = Env::default();
+    let (contract_id, client, _) = setup(&env);
+    let token = TokenClient::new(&env, &client.view_native_token().unwrap());
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    fund(&env, &client, &lessee, 1_000);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Parking"),
+        &String::from_str(&env, "Spot 12"),
+        &10, &100, &100, &1000, &40,
+    );
+    let lease_id = client.create_lease(&asset_id, &lessee, &500);
+    let keeper = Address::generate(&env);
+    
+    env.ledger().set_timestamp(499);
+    assert_eq!(
+        client.try_settle_expired_lease(&lease_id, &keeper),
+        Err(Ok(RentError::DeadlineNotReached))
+    );
+    
+    // Nobody signs: the call needs no auth once end_time has passed
+    env.ledger().set_timestamp(500);
+    env.set_auths(&[]);
+    client.settle_expired_lease(&lease_id, &keeper);
+    assert!(!client.view_lease(&lease_id).unwrap().is_active);
+    assert!(client.view_asset(&asset_id).unwrap().is_available);
+    assert_eq!(token.balance(&lessee), 990);
+    assert_eq!(token.balance(&contract_id), 10);
+    assert_eq!(client.view_asset_stats().active_leases, 0);
+}
+
+#[test]
+fn test_custom_terms_are_frozen_into_leases() {
+    let env = Env::default();
+    let (_, client, _) = setup(&env);
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    fund(&env, &client, &lessee, 1_000);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Flat"),
+        &String::from_str(&env, "One bedroom"),
+        &10, &100, &100, &1000, &0,
+    );
+    client.set_asset_term(&asset_id, &owner, &symbol_short!("pets"), &Some(String::from_str(&env, "no")));
+    client.set_asset_term(&asset_id, &owner, &symbol_short!("smoking"), &Some(String::from_str(&env, "no")));
+    let lease_id = client.create_lease(&asset_id, &lessee, &500);
+    
+    // Later changes only apply to new leases
+    client.set_asset_term(&asset_id, &owner, &symbol_short!("pets"), &Some(String::from_str(&env, "cats")));
+    client.set_asset_term(&asset_id, &owner, &symbol_short!("smoking"), &None);
+    let asset_terms = client.view_asset_terms(&asset_id);
+    assert_eq!(asset_terms.len(), 1);
+    assert_eq!(asset_terms.get(symbol_short!("pets")), Some(String::from_str(&env, "cats")));
+    let lease_terms = client.view_lease_terms(&lease_id);
+    assert_eq!(lease_terms.len(), 2);
+    assert_eq!(lease_terms.get(symbol_short!("pets")), Some(String::from_str(&env, "no")));
+    
+    let long = String::from_bytes(&env, &[b'x'; assets::MAX_TERM_LEN as usize + 1]);
+    assert_eq!(
+        client.try_set_asset_term(&asset_id, &owner, &symbol_short!("rules"), &Some(long)),
+        Err(Ok(RentError::TermLimitExceeded))
+    );
+}
+
+#[test]
+fn test_canonical_terms_hash_reproduces_off_chain() {
+    let env = Env::default();
+    let (_, client, _) = setup(&env);
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    fund(&env, &client, &lessee, 1_000);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Flat"),
+        &String::from_str(&env, "One bedroom"),
+        &10, &100, &100, &1000, &25,
+    );
+    client.set_asset_term(&asset_id, &owner, &symbol_short!("pets"), &Some(String::from_str(&env, "no")));
+    env.ledger().set_timestamp(40);
+    let lease_id = client.create_lease(&asset_id, &lessee, &500);
+    
+    // Later listing changes don't touch the terms the lease was bound by
+    client.update_asset(&asset_id, &owner, &20, &25, &100, &1000, &String::from_str(&env, "Refurbished"));
+    let (terms, hash) = client.get_canonical_terms(&lease_id).unwrap();
+    assert_eq!((terms.lessee.clone(), terms.owner.clone()), (lessee.clone(), owner));
+    assert_eq!((terms.start_time, terms.end_time, terms.period_payment, terms.security_deposit), (40, 540, 10, 25));
+    assert_eq!(terms.custom_terms.get(symbol_short!("pets")), Some(String::from_str(&env, "no")));
+    assert_eq!((terms.bound_at, terms.previous_hash.clone()), (40, BytesN::from_array(&env, &[0; 32])));
+    
+    // Rebuilding the struct from its parts gives the same hash
+    let rebuilt = CanonicalTerms { custom_terms: client.view_lease_terms(&lease_id), ..terms };
+    assert_eq!(hash, env.crypto().sha256(&rebuilt.to_xdr(&env)).to_bytes());
+    assert!(client.get_canonical_terms(&99).is_none());
+}
+
+#[test]
+fn test_amendments_chain_terms_hashes_and_apply_to_the_lease() {
+    let env = Env::default();
+    let (_, client, _) = setup(&env);
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    let occupant = Address::generate(&env);
+    fund(&env, &client, &lessee, 1_000);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Flat"),
+        &String::from_str(&env, "One bedroom"),
+        &10, &100, &100, &1000, &0,
+    );
+    let lease_id = client.create_lease(&asset_id, &lessee, &500);
+    let (_, signed_hash) = client.get_canonical_terms(&lease_id).unwrap();
+    
+    // Both parties sign each amendment
+    env.ledger().set_timestamp(50);
+    assert_eq!(client.amend_lease(&lease_id, &owner, &lessee, &AmendmentChange::Rent(12)), 1);
+    let signers: std::vec::Vec<Address> = env.auths().into_iter().map(|(address, _)| address).collect();
+    assert_eq!(signers, [owner.clone(), lessee.clone()]);
+    assert_eq!(client.amend_lease(&lease_id, &owner, &lessee, &AmendmentChange::EndDate(800)), 2);
+    assert_eq!(client.amend_lease(&lease_id, &owner, &lessee, &AmendmentChange::AddOccupant(occupant.clone())), 3);
+    assert_eq!(
+        client.try_amend_lease(&lease_id, &owner, &lessee, &AmendmentChange::AddOccupant(occupant.clone())),
+        Err(Ok(RentError::InvalidAmount))
+    );
+    assert_eq!(
+        client.try_amend_lease(&lease_id, &owner, &lessee, &AmendmentChange::EndDate(50)),
+        Err(Ok(RentError::InvalidLeaseDuration))
+    );
+    
+    let lease = client.view_lease(&lease_id).unwrap();
+    assert_eq!((lease.period_payment, lease.end_time), (12, 800));
+    
+    // Each entry references the hash of the terms it replaced
+    let history = client.view_amendments(&lease_id);
+    let (terms, hash) = client.get_canonical_terms(&lease_id).unwrap();
+    assert_eq!(history.len(), 3);
+    assert_eq!(history.get(0).unwrap().previous_hash, signed_hash);
+    assert_eq!(history.get(1).unwrap().previous_hash, history.get(0).unwrap().terms_hash);
+    assert_eq!(history.get(2).unwrap().terms_hash, hash);
+    assert_eq!(terms.previous_hash, history.get(1).unwrap().terms_hash);
+    assert_eq!((terms.period_payment, terms.end_time, terms.occupants), (12, 800, soroban_sdk::vec![&env, occupant]));
+}
+
+#[test]
+fn test_platform_fee_taken_from_rent_and_withdrawn_by_admin() {
+    let env = Env::default();
+    let (contract_id, client, admin) = setup(&env);
+    let native = client.view_native_token().unwrap();
+    let token = TokenClient::new(&env, &native);
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    fund(&env, &client, &lessee, 1_000);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Studio"),
+        &String::from_str(&env, "Recording studio"),
+        &100, &100, &100, &1000, &0,
+    );
+    let lease_id = client.create_lease(&asset_id, &lessee, &500);
+    
+    assert_eq!(
+        client.try_set_platform_fee_bps(&admin, &10_001),
+        Err(Ok(RentError::InvalidAmount))
+    );
+    client.set_platform_fee_bps(&admin, &250);
+    
+    // The lessee pays the full rent; 2.5% of it is kept as the platform fee
+    env.ledger().set_timestamp(100);
+    client.process_payment(&lease_id, &lessee);
+    assert_eq!(token.balance(&lessee), 800);
+    assert_eq!(client.view_withdrawable(&owner, &native), 198);
+    assert_eq!(client.view_platform_fees().get(native.clone()), Some(2));
+    
+    assert_eq!(
+        client.try_withdraw_platform_fees(&owner, &owner),
+        Err(Ok(RentError::NotAdmin))
+    );
+    assert_eq!(client.withdraw_platform_fees(&admin, &treasury).get(native.clone()), Some(2));
+    assert_eq!(token.balance(&treasury), 2);
+    assert_eq!(token.balance(&contract_id), 198);
+    assert!(client.view_platform_fees().is_empty());
+}
+
+#[test]
+fn test_find_available_assets_filters_by_terms() {
+    let env = Env::default();
+    let (_, client, _) = setup(&env);
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    fund(&env, &client, &lessee, 1_000);
+    for pets in ["no", "yes", "no"] {
+        let asset_id = client.register_asset(
+            &owner,
+            &String::from_str(&env, "Flat"),
+            &String::from_str(&env, "One bedroom"),
+            &10, &100, &100, &1000, &0,
+        );
+        client.set_asset_term(&asset_id, &owner, &symbol_short!("pets"), &Some(String::from_str(&env, pets)));
+    }
+    client.create_lease(&3, &lessee, &500);
+    
+    let no_pets = soroban_sdk::vec![&env, (symbol_short!("pets"), String::from_str(&env, "no"))];
+    assert_eq!(client.find_available_assets(&0, &10, &no_pets), soroban_sdk::vec![&env, 1]);
+    assert_eq!(client.find_available_assets(&2, &10, &no_pets), soroban_sdk::vec![&env]);
+    assert_eq!(client.find_available_assets(&0, &10, &soroban_sdk::vec![&env]), soroban_sdk::vec![&env, 1, 2]);
+}
+
+#[test]
+fn test_owner_updates_and_delists_asset() {
+    let env = Env::default();
+    let (_, client, admin) = setup(&env);
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    fund(&env, &client, &lessee, 1_000);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Flat"),
+        &String::from_str(&env, "One bedroom"),
+        &10, &100, &100, &1000, &0,
+    );
+    let lease_id = client.create_lease(&asset_id, &lessee, &500);
+    
+    // While leased only the price and description change, and the lease keeps its rent
+    assert_eq!(
+        client.try_update_asset(&asset_id, &owner, &12, &20, &100, &1000, &String::from_str(&env, "Furnished")),
+        Err(Ok(RentError::AssetUnavailable))
+    );
+    client.update_asset(&asset_id, &owner, &12, &0, &100, &1000, &String::from_str(&env, "Furnished"));
+    assert_eq!(client.view_asset(&asset_id).unwrap().price_per_period, 12);
+    assert_eq!(client.view_lease(&lease_id).unwrap().period_payment, 10);
+    assert_eq!(
+        client.try_delist_asset(&asset_id, &owner),
+        Err(Ok(RentError::AssetUnavailable))
+    );
+    
+    client.end_lease(&lease_id, &lessee);
+    client.update_asset(&asset_id, &owner, &12, &20, &200, &2000, &String::from_str(&env, "Furnished"));
+    assert_eq!(client.view_asset(&asset_id).unwrap().deposit_required, 20);
+    
+    let flat = symbol_short!("apartment");
+    let pets = symbol_short!("pets");
+    client.set_asset_category(&asset_id, &owner, &flat, &vec![&env, pets.clone()]);
+    client.set_platform_account(&admin, &Address::generate(&env));
+    client.boost_listing(&asset_id, &owner, &3_600);
+    
+    // Delisting takes the asset out of every discovery list for good
+    client.delist_asset(&asset_id, &owner);
+    assert!(client.get_available_assets().is_empty());
+    assert!(client.search_assets_by_category(&flat, &0, &10).is_empty());
+    assert!(client.search_assets_by_tag(&pets, &0, &10).is_empty());
+    assert!(client.get_featured_assets().is_empty());
+    assert_eq!(
+        client.try_set_asset_category(&asset_id, &owner, &flat, &vec![&env, pets.clone()]),
+        Err(Ok(RentError::AssetUnavailable))
+    );
+    assert_eq!(
+        client.try_create_lease(&asset_id, &lessee, &500),
+        Err(Ok(RentError::AssetUnavailable))
+    );
+}
+
+// Every token the contract holds must be fully accounted for by its internal sub-accounts
+fn assert_sub_accounts_cover_balances(env: &Env, contract_id: &Address, client: &RentPaymentContractClient<'_>) {
+    for token in client.view_held_tokens().iter() {
+        let booked: i128 = client.view_sub_accounts(&token).values().iter().sum();
+        assert_eq!(booked, TokenClient::new(env, &token).balance(contract_id));
+    }
+}
+
+#[test]
+fn test_relist_plan_reprices_the_asset_when_its_lease_ends() {
+    let env = Env::default();
+    let (_, client, _) = setup(&env);
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    fund(&env, &client, &lessee, 1_000);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Studio"),
+        &String::from_str(&env, "Garden studio"),
+        &100, &100, &100, &1000, &0,
+    );
+    
+    // Only the owner may plan a relist
+    assert_eq!(
+        client.try_set_relist_plan(&asset_id, &lessee, &Some(RelistPlan::Price(150))),
+        Err(Ok(RentError::NotAssetOwner))
+    );
+    
+    // A raise applies to the price the lease ran at, and the plan is used up once applied
+    let lease_id = client.create_lease(&asset_id, &lessee, &100);
+    client.set_relist_plan(&asset_id, &owner, &Some(RelistPlan::IncreaseBps(1_000)));
+    assert_eq!(client.view_relist_plan(&asset_id), Some(RelistPlan::IncreaseBps(1_000)));
+    env.ledger().set_timestamp(100);
+    client.end_lease(&lease_id, &lessee);
+    assert_eq!(client.view_asset(&asset_id).unwrap().price_per_period, 110);
+    assert_eq!(client.view_relist_plan(&asset_id), None);
+    
+    // A fixed price also puts a lapsed listing back on the market
+    let lease_id = client.create_lease(&asset_id, &lessee, &100);
+    client.refresh_listing(&asset_id, &owner, &150);
+    client.set_relist_plan(&asset_id, &owner, &Some(RelistPlan::Price(90)));
+    env.ledger().set_timestamp(200);
+    client.end_lease(&lease_id, &lessee);
+    let asset = client.view_asset(&asset_id).unwrap();
+    assert_eq!((asset.price_per_period, asset.listing_expires_at), (90, 0));
+    
+    // A cancelled plan leaves the price alone
+    let lease_id = client.create_lease(&asset_id, &lessee, &100);
+    client.set_relist_plan(&asset_id, &owner, &Some(RelistPlan::Price(500)));
+    client.set_relist_plan(&asset_id, &owner, &None);
+    env.ledger().set_timestamp(300);
+    client.end_lease(&lease_id, &lessee);
+    assert_eq!(client.view_asset(&asset_id).unwrap().price_per_period, 90);
+    
+    // A raise past the amount range is refused rather than wrapping the price
+    let price = 1_000_000_000_000_000_000_000_000_000_000;
+    fund(&env, &client, &lessee, price);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Penthouse"),
+        &String::from_str(&env, "Top floor"),
+        &price, &100, &100, &1000, &0,
+    );
+    let lease_id = client.create_lease(&asset_id, &lessee, &100);
+    client.set_relist_plan(&asset_id, &owner, &Some(RelistPlan::IncreaseBps(u32::MAX)));
+    env.ledger().set_timestamp(400);
+    assert_eq!(client.try_end_lease(&lease_id, &lessee), Err(Ok(RentError::InvalidAmount)));
+    assert_eq!(client.view_asset(&asset_id).unwrap().price_per_period, price);
+}
+
+#[test]
+fn test_owner_dashboard_summarizes_assets_and_leases() {
+    let env = Env::default();
+    let (_, client, _) = setup(&env);
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    let keeper = Address::generate(&env);
+    fund(&env, &client, &lessee, 1_000);
+    let register = |price: i128, max_duration: u64| client.register_asset(
+        &owner,
+        &String::from_str(&env, "Bay"),
+        &String::from_str(&env, "Parking bay"),
+        &price, &100, &100, &max_duration, &0,
+    );
+    let long_term = register(10, 5_000_000);
+    let short_term = register(20, 1_000);
+    register(30, 1_000);
+    
+    let dashboard = client.get_owner_dashboard(&Address::generate(&env));
+    assert_eq!((dashboard.asset_count, dashboard.active_leases, dashboard.rent_collected), (0, 0, 0));
+    
+    let long_lease = client.create_lease(&long_term, &lessee, &5_000_000);
+    let short_lease = client.create_lease(&short_term, &lessee, &1_000);
+    env.ledger().set_timestamp(150);
+    assert!(client.check_and_mark_overdue(&short_lease, &keeper));
+    
+    // Both leases are due, only the short one ends within the horizon
+    let dashboard = client.get_owner_dashboard(&owner);
+    assert_eq!(dashboard.owner, owner);
+    assert_eq!((dashboard.asset_count, dashboard.active_leases, dashboard.rent_collected), (3, 2, 30));
+    assert_eq!(dashboard.overdue_leases, vec![&env, short_lease]);
+    assert_eq!(dashboard.upcoming_expirations, vec![&env, short_lease]);
+    assert_eq!(dashboard.pending_earnings, 30);
+    
+    // Paid rent moves from pending to collected
+    client.process_payment(&long_lease, &lessee);
+    let dashboard = client.get_owner_dashboard(&owner);
+    assert_eq!((dashboard.rent_collected, dashboard.pending_earnings), (40, 20));
+}
+
+#[test]
+fn test_lessee_dashboard_lists_what_is_due_on_active_leases() {
+    let env = Env::default();
+    let (_, client, admin) = setup(&env);
+    let usdc = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    fund(&env, &client, &lessee, 1_000);
+    StellarAssetClient::new(&env, &usdc).mint(&lessee, &1_000);
+    let register = |price: i128, deposit: i128| client.register_asset(
+        &owner,
+        &String::from_str(&env, "Locker"),
+        &String::from_str(&env, "Storage locker"),
+        &price, &100, &100, &1000, &deposit,
+    );
+    let escrowed = register(10, 50);
+    let split = register(20, 0);
+    let ended = register(5, 0);
+    client.set_split_deposit(&split, &owner, &vec![&env, DepositPosition { token: usdc.clone(), amount: 1_000 }]);
+    
+    let escrowed_lease = client.create_lease(&escrowed, &lessee, &1000);
+    let split_lease = client.create_lease(&split, &lessee, &1000);
+    let ended_lease = client.create_lease(&ended, &lessee, &100);
+    env.ledger().set_timestamp(100);
+    client.end_lease(&ended_lease, &lessee);
+    
+    // Ended leases drop off; the rest show what is due now and the deposits securing them
+    env.ledger().set_timestamp(150);
+    let dashboard = client.get_lessee_dashboard(&lessee);
+    assert_eq!(dashboard.lessee, lessee);
+    assert_eq!(dashboard.leases.len(), 2);
+    let first = dashboard.leases.get(0).unwrap();
+    assert_eq!((first.lease_id, first.asset_id, first.amount_due, first.next_payment_time), (escrowed_lease, escrowed, 10, 100));
+    let second = dashboard.leases.get(1).unwrap();
+    assert_eq!((second.lease_id, second.asset_id, second.amount_due, second.end_time), (split_lease, split, 20, 1000));
+    assert_eq!((dashboard.total_due, dashboard.xlm_deposits), (30, 50));
+    assert_eq!(dashboard.split_deposits, vec![&env, DepositPosition { token: usdc.clone(), amount: 1_000 }]);
+    
+    // Nothing is due on a lease paid up to its next period
+    client.process_payment(&split_lease, &lessee);
+    let dashboard = client.get_lessee_dashboard(&lessee);
+    assert_eq!(dashboard.leases.get(1).unwrap().amount_due, 0);
+    assert_eq!(dashboard.total_due, 10);
+    
+    assert!(client.get_lessee_dashboard(&owner).leases.is_empty());
+}
+
+#[test]
+fn test_boosted_listings_are_featured_until_they_expire() {
+    let env = Env::default();
+    let (_, client, admin) = setup(&env);
+    
+    let owner = Address::generate(&env);
+    let platform = Address::generate(&env);
+    fund(&env, &client, &owner, 10);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Kayak"),
+        &String::from_str(&env, "Two-seat kayak"),
+        &10, &100, &100, &1000, &0,
+    );
+    
+    // Boosting needs a platform account and a positive duration
+    assert_eq!(
+        client.try_boost_listing(&asset_id, &owner, &86_400),
+        Err(Ok(RentError::PlatformNotSet))
+    );
+    client.set_platform_account(&admin, &platform);
+    client.set_boost_fee(&admin, &2);
+    assert_eq!(
+        client.try_boost_listing(&asset_id, &owner, &0),
+        Err(Ok(RentError::InvalidAmount))
+    );
+    
+    // The daily fee is prorated by the second and rounded up; a repeat boost extends the current one
+    assert_eq!(client.boost_listing(&asset_id, &owner, &86_400), 86_400);
+    assert_eq!(client.boost_listing(&asset_id, &owner, &3_600), 90_000);
+    let native = TokenClient::new(&env, &client.view_native_token().unwrap());
+    assert_eq!(native.balance(&platform), 3);
+    assert_eq!(client.view_asset_financials(&asset_id).platform_fees, 3);
+    assert_eq!(client.get_featured_assets(), soroban_sdk::vec![&env, asset_id]);
+    
+    // A fee that does not fit is refused rather than wrapping
+    client.set_boost_fee(&admin, &i128::MAX);
+    assert_eq!(
+        client.try_boost_listing(&asset_id, &owner, &u64::MAX),
+        Err(Ok(RentError::InvalidAmount))
+    );
+    
+    env.ledger().set_timestamp(90_000);
+    assert!(client.get_featured_assets().is_empty());
+}
+
+#[test]
+fn test_sub_accounts_always_sum_to_contract_balances() {
+    let env = Env::default();
+    let (contract_id, client, admin) = setup(&env);
+    let native = client.view_native_token().unwrap();
+    let usdc = env.register_stellar_asset_contract_v2(admin.clone()).address();
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    fund(&env, &client, &owner, 5);
+    fund(&env, &client, &lessee, 1_000);
+    StellarAssetClient::new(&env, &usdc).mint(&lessee, &1_000);
+    client.set_listing_fee(&admin, &5, &true);
+    client.set_platform_fee_bps(&admin, &250);
+    
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Camper"),
+        &String::from_str(&env, "Four berth"),
+        &100, &100, &100, &1000, &50,
+    );
+    assert_eq!(client.view_sub_account(&SubAccount::OwnerEarnings, &native), 5);
+    assert_sub_accounts_cover_balances(&env, &contract_id, &client);
+    
+    client.set_split_deposit(&asset_id, &owner, &soroban_sdk::vec![&env, DepositPosition { token: usdc.clone(), amount: 1_000 }]);
+    let lease_id = client.create_lease(&asset_id, &lessee, &500);
+    assert_eq!(client.view_sub_account(&SubAccount::TenantDeposits, &native), 0);
+    assert_eq!(client.view_sub_account(&SubAccount::TenantDeposits, &usdc), 1_000);
+    assert_sub_accounts_cover_balances(&env, &contract_id, &client);
+    
+    env.ledger().set_timestamp(100);
+    client.process_payment(&lease_id, &lessee);
+    assert_eq!(client.view_sub_account(&SubAccount::PlatformFees, &native), 4);
+    assert_sub_accounts_cover_balances(&env, &contract_id, &client);
+    
+    client.claim_deposit_deduction(&lease_id, &owner, &20);
+    client.claim_split_deposit(&lease_id, &owner, &2_500);
+    assert_eq!(client.view_sub_account(&SubAccount::TenantDeposits, &native), 0);
+    assert_eq!(client.view_sub_account(&SubAccount::TenantDeposits, &usdc), 750);
+    assert_sub_accounts_cover_balances(&env, &contract_id, &client);
+    
+    // Closing refunds the deposits and the listing fee; only the platform fee and the owner's
+    // rent are left, until the owner withdraws it
+    client.end_lease(&lease_id, &lessee);
+    assert_eq!(client.view_sub_accounts(&native).get(SubAccount::PlatformFees), Some(4));
+    assert_eq!(client.view_sub_account(&SubAccount::TenantDeposits, &native), 0);
+    assert_eq!(client.view_sub_account(&SubAccount::OwnerEarnings, &native), 216);
+    assert_sub_accounts_cover_balances(&env, &contract_id, &client);
+    client.withdraw_rent(&owner, &native, &216);
+    assert_eq!(client.view_sub_account(&SubAccount::OwnerEarnings, &native), 0);
+    assert_sub_accounts_cover_balances(&env, &contract_id, &client);
+    
+    client.withdraw_platform_fees(&admin, &treasury);
+    assert_sub_accounts_cover_balances(&env, &contract_id, &client);
+    assert_eq!(TokenClient::new(&env, &native).balance(&contract_id), 0);
+    assert_eq!(client.view_held_tokens(), soroban_sdk::vec![&env, native, usdc]);
+}
+
+#[test]
+fn test_delinquency_report_lists_late_leases_most_severe_first() {
+    let env = Env::default();
+    let (_, client, admin) = setup(&env);
+    let day = calendar::DAY;
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    fund(&env, &client, &lessee, 1_000);
+    let mut lease_ids = Vec::new(&env);
+    for _ in 0..4 {
+        let asset_id = client.register_asset(
+            &owner,
+            &String::from_str(&env, "Locker"),
+            &String::from_str(&env, "Storage locker"),
+            &10, &day, &day, &(100 * day), &0,
+        );
+        lease_ids.push_back(client.create_lease(&asset_id, &lessee, &(10 * day)));
+    }
+    client.set_escalation_schedule(&admin, &penalty::EscalationSchedule { reminder_after: 0, notice_after: 10 * day, warning_after: 20 * day });
+    
+    // Lease 1 misses its first due date, 2 its second, 3 its third, and 4 is kept up to date
+    env.ledger().set_timestamp(day);
+    for lease_id in [2, 3, 4] {
+        client.process_payment(&lease_id, &lessee);
+    }
+    env.ledger().set_timestamp(2 * day);
+    for lease_id in [3, 4] {
+        client.process_payment(&lease_id, &lessee);
+    }
+    env.ledger().set_timestamp(3 * day + 60);
+    client.process_payment(&4, &lessee);
+    
+    // Lease 2 has had a reminder, which ranks it above the longer-overdue lease 1
+    client.mark_lease_overdue(&2, &owner);
+    let report = client.get_delinquency_report(&owner);
+    let order: std::vec::Vec<u64> = report.iter().map(|entry| entry.lease_id).collect();
+    assert_eq!(order, [2, 1, 3]);
+    
+    let first = report.get(0).unwrap();
+    assert_eq!((first.days_past_due, first.escalation, first.has_guarantor), (1, penalty::EscalationLevel::Reminder, false));
+    assert_eq!(report.get(1).unwrap().days_past_due, 2);
+    assert_eq!(report.get(2).unwrap().amount_outstanding, client.dry_run_process_payment(&3, &lessee).amount);
+}
+
+#[test]
+fn test_partial_payments_accumulate_until_the_period_is_covered() {
+    let env = Env::default();
+    let (_, client, _) = setup(&env);
+    let token = TokenClient::new(&env, &client.view_native_token().unwrap());
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    fund(&env, &client, &lessee, 1_000);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Studio"),
+        &String::from_str(&env, "Recording studio"),
+        &100, &100, &100, &1000, &0,
+    );
+    let lease_id = client.create_lease(&asset_id, &lessee, &500);
+    let paid_upfront = client.view_lease(&lease_id).unwrap().total_paid;
+    
+    // Two partial payments are held for the owner but leave the schedule where it is
+    env.ledger().set_timestamp(100);
+    assert_eq!(client.process_partial_payment(&lease_id, &lessee, &30), 70);
+    assert_eq!(client.process_partial_payment(&lease_id, &lessee, &50), 20);
+    let lease = client.view_lease(&lease_id).unwrap();
+    assert_eq!((lease.next_payment_time, lease.outstanding_balance, lease.payments_made), (100, 20, 0));
+    assert_eq!(client.view_withdrawable(&owner, &token.address), 180);
+    assert_eq!(client.dry_run_process_payment(&lease_id, &lessee).amount, 20);
+    
+    // A full payment only charges the remainder
+    client.process_payment(&lease_id, &lessee);
+    let lease = client.view_lease(&lease_id).unwrap();
+    assert_eq!((lease.next_payment_time, lease.outstanding_balance, lease.payments_made), (200, 0, 1));
+    assert_eq!(client.view_withdrawable(&owner, &token.address), 200);
+    
+    // A partial payment that covers everything settles the period
+    env.ledger().set_timestamp(200);
+    assert_eq!(client.process_partial_payment(&lease_id, &lessee, &150), 0);
+    let lease = client.view_lease(&lease_id).unwrap();
+    assert_eq!((lease.next_payment_time, lease.payments_made, lease.total_paid), (300, 2, paid_upfront + 200));
+    assert_eq!(client.view_withdrawable(&owner, &token.address), 300);
+    assert_eq!(client.view_asset_financials(&asset_id).rent_collected, 300);
+    assert_eq!(
+        client.try_process_partial_payment(&lease_id, &lessee, &0),
+        Err(Ok(RentError::InvalidAmount))
+    );
+}
+
+#[test]
+fn test_prepaid_rent_settles_each_due_date_and_refunds_the_rest() {
+    let env = Env::default();
+    let (contract_id, client, _) = setup(&env);
+    let token = TokenClient::new(&env, &client.view_native_token().unwrap());
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    fund(&env, &client, &lessee, 1_000);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Loft"),
+        &String::from_str(&env, "Loft apartment"),
+        &100, &100, &100, &1000, &0,
+    );
+    let lease_id = client.create_lease(&asset_id, &lessee, &500);
+    let lessee_before = token.balance(&lessee);
+    
+    // Three periods are held by the contract up front
+    env.ledger().set_timestamp(50);
+    assert_eq!(client.prepay(&lease_id, &lessee, &3), 3);
+    assert_eq!(token.balance(&lessee), lessee_before - 300);
+    assert_eq!(client.view_sub_account(&SubAccount::TenantDeposits, &token.address), 300);
+    assert_sub_accounts_cover_balances(&env, &contract_id, &client);
+    
+    // Nothing is drawn before the due date
+    assert!(!client.settle_due_payment(&lease_id));
+    assert_eq!(client.view_withdrawable(&owner, &token.address), 100);
+    
+    // Each due date settles one period without the lessee signing anything
+    env.ledger().set_timestamp(100);
+    assert!(client.settle_due_payment(&lease_id));
+    assert!(!client.settle_due_payment(&lease_id));
+    env.ledger().set_timestamp(200);
+    assert!(client.settle_due_payment(&lease_id));
+    let lease = client.view_lease(&lease_id).unwrap();
+    assert_eq!((lease.next_payment_time, lease.payments_made), (300, 2));
+    assert_eq!(client.view_withdrawable(&owner, &token.address), 300);
+    assert_eq!(client.view_prepaid_rent(&lease_id).unwrap().prepaid_periods, 1);
+    assert_eq!(client.view_reputation(&lessee).on_time_payments, 2);
+    assert_sub_accounts_cover_balances(&env, &contract_id, &client);
+    
+    // Credit priced at the old rent can't be topped up, and zero periods are refused
+    client.amend_lease(&lease_id, &owner, &lessee, &AmendmentChange::Rent(120));
+    assert_eq!(
+        client.try_prepay(&lease_id, &lessee, &1),
+        Err(Ok(RentError::InvalidAmount))
+    );
+    assert_eq!(
+        client.try_prepay(&lease_id, &lessee, &0),
+        Err(Ok(RentError::InvalidAmount))
+    );
+    
+    // A bill the held period no longer covers is left to the lessee
+    env.ledger().set_timestamp(300);
+    assert!(!client.settle_due_payment(&lease_id));
+    
+    // Closing the lease returns the unused period
+    client.end_lease(&lease_id, &lessee);
+    assert_eq!(client.view_prepaid_rent(&lease_id), None);
+    assert_eq!(token.balance(&lessee), lessee_before - 200);
+    assert_eq!(client.view_sub_account(&SubAccount::TenantDeposits, &token.address), 0);
+    assert_sub_accounts_cover_balances(&env, &contract_id, &client);
+}
+
+#[test]
+fn test_rent_escalates_every_interval_and_schedule_projects_it() {
+    let env = Env::default();
+    let (_, client, _) = setup(&env);
+    let token = TokenClient::new(&env, &client.view_native_token().unwrap());
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    fund(&env, &client, &lessee, 1_000);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Shop"),
+        &String::from_str(&env, "Corner shop"),
+        &100, &100, &100, &1000, &0,
+    );
+    assert_eq!(
+        client.try_set_rent_escalation(&asset_id, &owner, &10_001, &2),
+        Err(Ok(RentError::InvalidAmount))
+    );
+    
+    // 10% more every two periods, the one paid at signing included
+    client.set_rent_escalation(&asset_id, &owner, &1_000, &2);
+    let lease_id = client.create_lease(&asset_id, &lessee, &600);
+    let (terms, _) = client.get_canonical_terms(&lease_id).unwrap();
+    assert_eq!((terms.escalation_bps, terms.escalation_interval), (1_000, 2));
+    
+    let projected = |pairs: &[(u64, i128)]| {
+        let mut schedule = soroban_sdk::Vec::new(&env);
+        for (due_at, amount) in pairs {
+            schedule.push_back(ScheduledPayment { due_at: *due_at, amount: *amount });
+        }
+        schedule
+    };
+    assert_eq!(
+        client.view_payment_schedule(&lease_id),
+        projected(&[(100, 100), (200, 110), (300, 110), (400, 121), (500, 121)])
+    );
+    
+    // Payments follow the projection and the schedule shrinks as they are made
+    env.ledger().set_timestamp(100);
+    client.process_payment(&lease_id, &lessee);
+    env.ledger().set_timestamp(200);
+    assert_eq!(client.dry_run_process_payment(&lease_id, &lessee).amount, 110);
+    client.process_payment(&lease_id, &lessee);
+    assert_eq!(client.view_withdrawable(&owner, &token.address), 310);
+    assert_eq!(client.view_lease(&lease_id).unwrap().period_payment, 110);
+    assert_eq!(client.view_payment_schedule(&lease_id), projected(&[(300, 110), (400, 121), (500, 121)]));
+    
+    // Leases signed without a clause keep a flat rent
+    client.set_rent_escalation(&asset_id, &owner, &0, &0);
+    client.end_lease(&lease_id, &lessee);
+    let flat_id = client.create_lease(&asset_id, &lessee, &300);
+    assert_eq!(client.view_payment_schedule(&flat_id), projected(&[(300, 100), (400, 100)]));
+}
+
+#[test]
+fn test_concurrent_lease_limits_stop_hoarding() {
+    let env = Env::default();
+    let (_, client, admin) = setup(&env);
+    
+    let owner = Address::generate(&env);
+    let other_owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    fund(&env, &client, &lessee, 1_000);
+    let register = |owner: &Address| client.register_asset(
+        owner,
+        &String::from_str(&env, "Parking spot"),
+        &String::from_str(&env, "Covered parking"),
+        &10, &100, &100, &1000, &0,
+    );
+    let spots = [register(&owner), register(&owner), register(&other_owner), register(&other_owner)];
+    let limit_reached = Err(Ok(RentError::TermLimitExceeded));
+    
+    // An owner's cap only counts leases of that owner's assets
+    client.set_owner_lease_limit(&owner, &1);
+    assert_eq!(client.view_owner_lease_limit(&owner), 1);
+    let first = client.create_lease(&spots[0], &lessee, &500);
+    assert_eq!(client.try_create_lease(&spots[1], &lessee, &500), limit_reached);
+    client.create_lease(&spots[2], &lessee, &500);
+    
+    // The platform cap counts every active lease
+    client.set_max_concurrent_leases(&admin, &2);
+    assert_eq!(client.view_max_concurrent_leases(), 2);
+    assert_eq!(client.try_create_lease(&spots[3], &lessee, &500), limit_reached);
+    
+    // Ending a lease frees a slot
+    client.end_lease(&first, &lessee);
+    client.create_lease(&spots[1], &lessee, &500);
+    assert_eq!(client.get_lessee_leases(&lessee).len(), 2);
+    
+    // A sold asset's running lease counts towards the buyer's cap instead of the seller's
+    client.set_max_concurrent_leases(&admin, &0);
+    client.transfer_asset(&spots[1], &owner, &other_owner);
+    client.create_lease(&spots[0], &lessee, &500);
+    client.set_owner_lease_limit(&other_owner, &2);
+    assert_eq!(client.try_create_lease(&spots[3], &lessee, &500), limit_reached);
+    
+    // Lifting every cap lets the lessee lease again
+    client.set_owner_lease_limit(&owner, &0);
+    client.set_owner_lease_limit(&other_owner, &0);
+    client.create_lease(&spots[3], &lessee, &500);
+    assert_eq!(
+        client.try_set_max_concurrent_leases(&lessee, &1),
+        Err(Ok(RentError::NotAdmin))
+    );
+}
+
+#[test]
+fn test_concurrent_lease_limits_read_running_counts() {
+    let env = Env::default();
+    let (contract_id, client, admin) = setup(&env);
+    let owner = seed_leases(&env, &contract_id, &client, 2_000);
+    let lessee = client.view_lease(&1).unwrap().lessee;
+    let client = reload(&client, 1);
+    let env = client.env.clone();
+    let (owner, lessee, admin) = (carry(&env, &owner), carry(&env, &lessee), carry(&env, &admin));
+    let register = || client.register_asset(
+        &owner,
+        &String::from_str(&env, "Parking spot"),
+        &String::from_str(&env, "Covered parking"),
+        &10, &100, &100, &1000, &0,
+    );
+    let spots = [register(), register()];
+    
+    // The caps are checked without reading any of the leases the lessee already holds
+    let limit_reached = Err(Ok(RentError::TermLimitExceeded));
+    client.set_max_concurrent_leases(&admin, &2_001);
+    env.cost_estimate().budget().reset_default();
+    client.create_lease(&spots[0], &lessee, &500);
+    assert!(env.cost_estimate().budget().cpu_instruction_cost() < 10_000_000);
+    assert_eq!(client.try_create_lease(&spots[1], &lessee, &500), limit_reached);
+    
+    client.set_max_concurrent_leases(&admin, &0);
+    client.set_owner_lease_limit(&owner, &2_001);
+    assert_eq!(client.try_create_lease(&spots[1], &lessee, &500), limit_reached);
+    client.set_owner_lease_limit(&owner, &2_002);
+    client.create_lease(&spots[1], &lessee, &500);
+}
+
+#[test]
+fn test_open_dispute_freezes_deposit_until_arbiter_splits_it() {
+    let env = Env::default();
+    let (contract_id, client, admin) = setup(&env);
+    let token = TokenClient::new(&env, &client.view_native_token().unwrap());
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    let arbiter = Address::generate(&env);
+    fund(&env, &client, &lessee, 1_000);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Van"),
+        &String::from_str(&env, "Cargo van"),
+        &100, &100, &100, &1000, &200,
+    );
+    let lease_id = client.create_lease(&asset_id, &lessee, &500);
+    let lessee_after_signing = token.balance(&lessee);
+    
+    // Either party may open one dispute at a time
+    let dispute_id = client.open_dispute(&lease_id, &lessee, &symbol_short!("damage"));
+    assert_eq!(client.view_open_dispute(&lease_id), Some(dispute_id));
+    assert_eq!(
+        client.try_open_dispute(&lease_id, &owner, &symbol_short!("damage")),
+        Err(Ok(RentError::InvalidClaim))
+    );
+    
+    // The deposit is frozen: no owner deductions, and ending the lease refunds nothing
+    assert_eq!(
+        client.try_claim_deposit_deduction(&lease_id, &owner, &50),
+        ext_error(RentErrorExt::DepositFrozen)
+    );
+    client.end_lease(&lease_id, &lessee);
+    assert_eq!(client.view_escrow_balance(&lease_id), 200);
+    assert_eq!(token.balance(&lessee), lessee_after_signing);
+    let (statement, _) = client.view_closing_statement(&lease_id).unwrap();
+    assert_eq!((statement.deductions, statement.refunded, statement.held), (0, 0, 200));
+    
+    // Only the admin or the appointed arbiter rules
+    client.set_arbiter(&admin, &Some(arbiter.clone()));
+    assert_eq!(
+        client.try_resolve_dispute(&owner, &dispute_id, &2_500),
+        Err(Ok(RentError::NotAdmin))
+    );
+    client.resolve_dispute(&arbiter, &dispute_id, &2_500);
+    
+    let dispute = client.view_dispute(&dispute_id).unwrap();
+    assert_eq!((dispute.status, dispute.deposit_split_bps, dispute.opener), (DisputeStatus::Resolved, 2_500, lessee.clone()));
+    assert_eq!(client.view_open_dispute(&lease_id), None);
+    assert_eq!(client.view_escrow_balance(&lease_id), 0);
+    assert_eq!(client.view_withdrawable(&owner, &token.address), 150);
+    assert_eq!(token.balance(&lessee), lessee_after_signing + 150);
+    assert_sub_accounts_cover_balances(&env, &contract_id, &client);
+    assert_eq!(
+        client.try_resolve_dispute(&arbiter, &dispute_id, &0),
+        ext_error(RentErrorExt::NoOpenDispute)
+    );
+    
+    // A ruling on a running lease pays the owner's share and keeps the rest in escrow
+    let lease_id = client.create_lease(&asset_id, &lessee, &500);
+    let dispute_id = client.open_dispute(&lease_id, &owner, &symbol_short!("noise"));
+    client.resolve_dispute(&admin, &dispute_id, &5_000);
+    assert_eq!(client.view_escrow_balance(&lease_id), 100);
+    assert_eq!(client.get_dispute_count(), 2);
+    assert_sub_accounts_cover_balances(&env, &contract_id, &client);
+}
+
+#[test]
+fn test_cure_period_waives_late_fee_and_escalation() {
+    let env = Env::default();
+    let (_, client, _) = setup(&env);
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    fund(&env, &client, &lessee, 1_000);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Kiosk"),
+        &String::from_str(&env, "Market kiosk"),
+        &100, &100, &100, &1000, &0,
+    );
+    client.set_penalty_policy(&asset_id, &owner, &PenaltyPolicy::Flat(25));
+    client.set_cure_period(&asset_id, &owner, &50);
+    assert_eq!(client.view_cure_period(&asset_id), 50);
+    let lease_id = client.create_lease(&asset_id, &lessee, &500);
+    let topics = |env: &Env| env.events().all().iter().filter(|(contract, _, _)| *contract == client.address).last().unwrap().1;
+    
+    // Within the cure window nothing escalates and no late fee is due
+    env.ledger().set_timestamp(150);
+    client.mark_lease_overdue(&lease_id, &owner);
+    env.ledger().set_timestamp(190);
+    client.poke_lease(&lease_id);
+    assert_eq!(client.view_late_notice(&lease_id).level, penalty::EscalationLevel::None);
+    assert_eq!(client.dry_run_process_payment(&lease_id, &lessee).amount, 100);
+    
+    // Paying it all clears the overdue flag and is recorded as a cure
+    client.process_payment(&lease_id, &lessee);
+    assert_eq!(topics(&env), (symbol_short!("lease"), symbol_short!("cured"), lessee.clone()).into_val(&env));
+    let lease = client.view_lease(&lease_id).unwrap();
+    assert_eq!((lease.is_overdue, lease.late_fees_paid), (false, 0));
+    
+    // Once the window lapses the lease escalates and pays the late fee as usual
+    env.ledger().set_timestamp(300);
+    client.mark_lease_overdue(&lease_id, &owner);
+    env.ledger().set_timestamp(351);
+    client.poke_lease(&lease_id);
+    assert!(client.view_late_notice(&lease_id).level > penalty::EscalationLevel::None);
+    client.process_payment(&lease_id, &lessee);
+    assert_eq!(topics(&env), (symbol_short!("pay"), symbol_short!("made"), lessee.clone()).into_val(&env));
+    assert_eq!(client.view_lease(&lease_id).unwrap().late_fees_paid, 25);
+}
+
+#[test]
+fn test_early_end_needs_served_notice_or_termination_fee() {
+    let env = Env::default();
+    let (contract_id, client, _) = setup(&env);
+    let token = TokenClient::new(&env, &client.view_native_token().unwrap());
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    fund(&env, &client, &lessee, 1_000);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Flat"),
+        &String::from_str(&env, "Two-bed flat"),
+        &100, &100, &100, &1000, &200,
+    );
+    client.set_termination_terms(&asset_id, &owner, &300, &80);
+    let (terms, _) = client.get_canonical_terms(&client.create_lease(&asset_id, &lessee, &1000)).unwrap();
+    assert_eq!((terms.notice_period, terms.early_termination_fee), (300, 80));
+    let lease_id = terms.lease_id;
+    
+    // The owner can't end the lease before notice has run
+    env.ledger().set_timestamp(50);
+    assert_eq!(
+        client.try_end_lease(&lease_id, &owner),
+        Err(Ok(RentError::DeadlineNotReached))
+    );
+    assert_eq!(client.give_termination_notice(&lease_id, &owner), 350);
+    assert_eq!(client.give_termination_notice(&lease_id, &lessee), 350);
+    assert_eq!(client.view_termination_notice(&lease_id).unwrap().given_by, owner);
+    env.ledger().set_timestamp(349);
+    assert!(client.try_end_lease(&lease_id, &owner).is_err());
+    env.ledger().set_timestamp(350);
+    let lessee_before = token.balance(&lessee);
+    client.end_lease(&lease_id, &owner);
+    assert_eq!(token.balance(&lessee), lessee_before + 200);
+    assert_eq!(client.view_termination_notice(&lease_id), None);
+    
+    // A lessee leaving without notice pays the fee out of the deposit, at the terms signed
+    let lease_id = client.create_lease(&asset_id, &lessee, &1000);
+    client.set_termination_terms(&asset_id, &owner, &600, &150);
+    env.ledger().set_timestamp(400);
+    let lessee_before = token.balance(&lessee);
+    let owner_before = client.view_withdrawable(&owner, &token.address);
+    client.end_lease(&lease_id, &lessee);
+    assert_eq!(client.view_withdrawable(&owner, &token.address), owner_before + 80);
+    assert_eq!(token.balance(&lessee), lessee_before + 120);
+    assert_sub_accounts_cover_balances(&env, &contract_id, &client);
+    
+    // An open dispute freezes the deposit the fee would come out of
+    let lease_id = client.create_lease(&asset_id, &lessee, &1000);
+    client.open_dispute(&lease_id, &owner, &symbol_short!("damage"));
+    assert_eq!(
+        client.try_end_lease(&lease_id, &lessee),
+        ext_error(RentErrorExt::DepositFrozen)
+    );
+    assert_eq!(client.view_escrow_balance(&lease_id), 200);
+    
+    // A deposit too small for the fee leaves the lessee to serve notice instead
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Studio"),
+        &String::from_str(&env, "Studio flat"),
+        &100, &100, &100, &1000, &200,
+    );
+    client.set_termination_terms(&asset_id, &owner, &300, &500);
+    let lease_id = client.create_lease(&asset_id, &lessee, &1000);
+    assert_eq!(
+        client.try_end_lease(&lease_id, &lessee),
+        Err(Ok(RentError::InsufficientBalance))
+    );
+    
+    // Without a fee the lessee has no early exit at all
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Loft"),
+        &String::from_str(&env, "Loft flat"),
+        &100, &100, &100, &1000, &200,
+    );
+    client.set_termination_terms(&asset_id, &owner, &300, &0);
+    let other_lessee = Address::generate(&env);
+    fund(&env, &client, &other_lessee, 1_000);
+    env.ledger().set_timestamp(500);
+    let lease_id = client.create_lease(&asset_id, &other_lessee, &1000);
+    assert_eq!(
+        client.try_end_lease(&lease_id, &other_lessee),
+        Err(Ok(RentError::DeadlineNotReached))
+    );
+    assert_eq!(client.view_escrow_balance(&lease_id), 200);
+}
+
+#[test]
+fn test_assets_are_searchable_by_category_and_tag() {
+    let env = Env::default();
+    let (contract_id, client, _) = setup(&env);
+    
+    let owner = Address::generate(&env);
+    let mut ids = Vec::new(&env);
+    for _ in 0..3 {
+        ids.push_back(client.register_asset(
+            &owner,
+            &String::from_str(&env, "Flat"),
+            &String::from_str(&env, "Two-bed flat"),
+            &100, &100, &100, &1000, &200,
+        ));
+    }
+    let general = symbol_short!("general");
+    let flat = symbol_short!("apartment");
+    let pets = symbol_short!("pets");
+    assert_eq!(client.search_assets_by_category(&general, &0, &10).len(), 3);
+    
+    // Every listing starts in the default category, so it is never written to an index
+    env.as_contract(&contract_id, || {
+        assert!(!env.storage().persistent().has(&storage_keys::IndexBook::CategoryIndex(general.clone())));
+    });
+    
+    // Refiling moves an asset between categories, and repeated tags are indexed once
+    client.set_asset_category(&ids.get(0).unwrap(), &owner, &flat, &vec![&env, pets.clone(), pets.clone()]);
+    client.set_asset_category(&ids.get(2).unwrap(), &owner, &flat, &vec![&env, pets.clone()]);
+    let found = client.search_assets_by_category(&flat, &0, &10);
+    assert_eq!((found.len(), found.get(1).unwrap().asset_id), (2, ids.get(2).unwrap()));
+    assert_eq!(found.get(0).unwrap().tags.len(), 1);
+    assert_eq!(client.search_assets_by_category(&flat, &1, &10).len(), 1);
+    assert_eq!(client.search_assets_by_category(&general, &0, &10).get(0).unwrap().asset_id, ids.get(1).unwrap());
+    assert_eq!(client.search_assets_by_tag(&pets, &0, &10).len(), 2);
+    
+    // Dropping a tag takes the asset out of that tag's index
+    client.set_asset_category(&ids.get(2).unwrap(), &owner, &flat, &Vec::new(&env));
+    assert_eq!(client.search_assets_by_tag(&pets, &0, &10).len(), 1);
+    
+    let too_many = vec![&env, symbol_short!("a"), symbol_short!("b"), symbol_short!("c"), symbol_short!("d"),
+        symbol_short!("e"), symbol_short!("f"), symbol_short!("g"), symbol_short!("h"), symbol_short!("i")];
+    assert_eq!(
+        client.try_set_asset_category(&ids.get(1).unwrap(), &owner, &flat, &too_many),
+        Err(Ok(RentError::TermLimitExceeded))
+    );
+}
+
+#[test]
+fn test_records_migrate_to_a_redeployed_contract_in_verified_batches() {
+    let env = Env::default();
+    let (_, old, old_admin) = setup(&env);
+    let (_, new, new_admin) = setup(&env);
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    fund(&env, &old, &lessee, 1_000);
+    for _ in 0..2 {
+        old.register_asset(
+            &owner,
+            &String::from_str(&env, "Flat"),
+            &String::from_str(&env, "Two-bed flat"),
+            &100, &100, &100, &1000, &200,
+        );
+    }
+    old.set_asset_category(&2, &owner, &symbol_short!("house"), &vec![&env, symbol_short!("garden")]);
+    old.set_asset_term(&2, &owner, &symbol_short!("pets"), &Some(String::from_str(&env, "cats only")));
+    let external_id = BytesN::from_array(&env, &[7; 32]);
+    old.set_asset_external_id(&2, &owner, &Some(external_id.clone()));
+    let lease_id = old.create_lease(&2, &lessee, &1000);
+    old.set_lease_external_id(&lease_id, &owner, &Some(external_id.clone()));
+    let dispute_id = old.open_dispute(&lease_id, &lessee, &symbol_short!("repairs"));
+    old.resolve_dispute(&old_admin, &dispute_id, &2_500);
+    
+    // Only the admin may export, only from a paused contract, and only once every lease has ended
+    assert_eq!(
+        old.try_export_batch(&old_admin, &0, &2).err(),
+        ext_error::<()>(RentErrorExt::MigrationNotPaused).err()
+    );
+    old.set_paused(&old_admin, &true);
+    assert!(old.try_export_batch(&new_admin, &0, &2).is_err());
+    assert_eq!(
+        old.try_export_batch(&old_admin, &0, &3).err(),
+        ext_error::<()>(RentErrorExt::LeaseNotEnded).err()
+    );
+    old.set_paused(&old_admin, &false);
+    env.ledger().with_mut(|l| l.timestamp = 1_000);
+    old.end_lease(&lease_id, &lessee);
+    old.set_paused(&old_admin, &true);
+    
+    let first = old.export_batch(&old_admin, &0, &2);
+    assert_eq!((first.assets.len(), first.leases.len(), first.next_cursor), (2, 0, 2));
+    assert_eq!(
+        new.try_import_batch(&new_admin, &first),
+        ext_error(RentErrorExt::MigrationNotPaused)
+    );
+    new.set_paused(&new_admin, &true);
+    
+    // A batch altered in transit is refused
+    let mut tampered = first.clone();
+    let mut asset = tampered.assets.get(0).unwrap();
+    asset.owner = new_admin.clone();
+    tampered.assets.set(0, asset);
+    assert_eq!(
+        new.try_import_batch(&new_admin, &tampered),
+        Err(Ok(RentError::InvalidWindow))
+    );
+    
+    // Batches land in cursor order, once each
+    assert_eq!(new.import_batch(&new_admin, &first), 2);
+    assert_eq!(
+        new.try_import_batch(&new_admin, &first),
+        Err(Ok(RentError::InvalidWindow))
+    );
+    
+    // A batch exported after the export restarted is not chained to the batch imported
+    old.export_batch(&old_admin, &0, &1);
+    old.export_batch(&old_admin, &1, &1);
+    let stray = old.export_batch(&old_admin, &2, &2);
+    assert_eq!(stray.cursor, 2);
+    assert_eq!(
+        new.try_import_batch(&new_admin, &stray),
+        Err(Ok(RentError::InvalidWindow))
+    );
+    
+    old.export_batch(&old_admin, &0, &2);
+    let last = old.export_batch(&old_admin, &new.view_import_cursor(), &2);
+    assert_eq!((last.assets.len(), last.leases.len(), last.disputes.len(), last.next_cursor), (0, 1, 1, 4));
+    assert_eq!(new.import_batch(&new_admin, &last), 4);
+    
+    // The new contract holds the same records, with their indexes rebuilt
+    new.commit_state_root(&10);
+    old.commit_state_root(&10);
+    assert_eq!(new.view_state_root().unwrap().root, old.view_state_root().unwrap().root);
+    assert_eq!(new.get_owner_assets(&owner), old.get_owner_assets(&owner));
+    assert_eq!(new.search_assets_by_tag(&symbol_short!("garden"), &0, &10).get(0).unwrap().asset_id, 2);
+    assert_eq!(new.get_lease_count(), 1);
+    
+    // and the books kept for each record: terms, external IDs, statements, rulings,
+    // reputations, earnings and per-token totals
+    let token = old.view_native_token().unwrap();
+    assert_eq!(new.view_asset_terms(&2), old.view_asset_terms(&2));
+    assert_eq!(new.view_lease_terms(&lease_id), old.view_asset_terms(&2));
+    assert_eq!(new.get_canonical_terms(&lease_id), old.get_canonical_terms(&lease_id));
+    assert_eq!(new.find_asset_by_external_id(&owner, &external_id), Some(2));
+    assert_eq!(new.find_lease_by_external_id(&owner, &external_id), Some(lease_id));
+    assert_eq!(new.view_closing_statement(&lease_id), old.view_closing_statement(&lease_id));
+    assert_eq!(new.get_dispute_count(), 1);
+    assert_eq!(new.view_dispute(&dispute_id), old.view_dispute(&dispute_id));
+    assert_eq!(new.view_reputation(&lessee), old.view_reputation(&lessee));
+    assert_eq!(new.view_reputation(&owner), old.view_reputation(&owner));
+    assert_eq!(new.view_asset_financials(&2).rent_collected, old.view_asset_financials(&2).rent_collected);
+    assert!(old.view_withdrawable(&owner, &token) > 0);
+    assert_eq!(new.view_withdrawable(&owner, &token), old.view_withdrawable(&owner, &token));
+    assert_eq!(new.view_token_stats(&token), old.view_token_stats(&token));
+    assert_eq!(new.view_sub_accounts(&token), old.view_sub_accounts(&token));
+    
+    // An export cannot continue once records were added after it started
+    old.export_batch(&old_admin, &0, &2);
+    old.register_asset(
+        &owner,
+        &String::from_str(&env, "Flat"),
+        &String::from_str(&env, "Two-bed flat"),
+        &100, &100, &100, &1000, &200,
+    );
+    assert_eq!(
+        old.try_export_batch(&old_admin, &2, &2).err(),
+        Some(Ok(RentError::InvalidWindow))
+    );
+}
+
+#[test]
+fn test_payment_batch_charges_each_lease_and_reports_failures() {
+    let env = Env::default();
+    let (_, client, _) = setup(&env);
+    
+    let owner = Address::generate(&env);
+    let tenant = Address::generate(&env);
+    let broke = Address::generate(&env);
+    fund(&env, &client, &tenant, 1_000);
+    fund(&env, &client, &broke, 300);
+    let mut leases = Vec::new(&env);
+    for lessee in [&tenant, &tenant, &broke] {
+        let asset_id = client.register_asset(
+            &owner,
+            &String::from_str(&env, "Flat"),
+            &String::from_str(&env, "Two-bed flat"),
+            &100, &100, &100, &1000, &200,
+        );
+        leases.push_back(client.create_lease(&asset_id, lessee, &1000));
+    }
+    
+    env.ledger().set_timestamp(100);
+    let results = client.process_payments_batch(&vec![
+        &env,
+        (leases.get(0).unwrap(), tenant.clone()),
+        (leases.get(1).unwrap(), tenant.clone()),
+        (leases.get(2).unwrap(), broke.clone()),
+        (leases.get(2).unwrap(), tenant.clone()),
+        (99, tenant.clone()),
+    ]);
+    let outcomes: std::vec::Vec<(i128, u32)> = results.iter().map(|r| (r.amount, r.error_code)).collect();
+    assert_eq!(outcomes, [
+        (100, 0),
+        (100, 0),
+        (0, RentError::InsufficientBalance as u32),
+        (0, RentError::NotLessee as u32),
+        (0, RentError::LeaseNotActive as u32),
+    ]);
+    assert_eq!(client.view_lease(&leases.get(0).unwrap()).unwrap().next_payment_time, 200);
+    assert_eq!(client.view_lease(&leases.get(2).unwrap()).unwrap().next_payment_time, 100);
+    
+    assert_eq!(
+        client.try_process_payments_batch(&Vec::new(&env)),
+        Err(Ok(RentError::InvalidAmount))
+    );
+}
+
+#[test]
+fn test_owner_withdraws_rent_held_by_the_contract() {
+    let env = Env::default();
+    let (contract_id, client, _) = setup(&env);
+    let native = client.view_native_token().unwrap();
+    let token = TokenClient::new(&env, &native);
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    fund(&env, &client, &lessee, 1_000);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Flat"),
+        &String::from_str(&env, "Two-bed flat"),
+        &100, &100, &100, &1000, &200,
+    );
+    let lease_id = client.create_lease(&asset_id, &lessee, &1000);
+    for due in [100, 200] {
+        env.ledger().set_timestamp(due);
+        client.process_payment(&lease_id, &lessee);
+    }
+    assert_eq!((client.view_withdrawable(&owner, &native), token.balance(&owner)), (300, 0));
+    
+    // Owners pull what they are owed, in as many withdrawals as they like
+    assert_eq!(client.withdraw_rent(&owner, &native, &250), 50);
+    assert_eq!(
+        client.try_withdraw_rent(&owner, &native, &60),
+        Err(Ok(RentError::AccountOverdrawn))
+    );
+    assert_eq!(client.withdraw_rent(&owner, &native, &50), 0);
+    assert_eq!(
+        env.events().all().last().unwrap().1,
+        (symbol_short!("rent"), symbol_short!("withdrawn"), owner.clone()).into_val(&env)
+    );
+    assert_eq!(token.balance(&owner), 300);
+    assert_sub_accounts_cover_balances(&env, &contract_id, &client);
+}
+
+#[test]
+fn test_keeper_collects_authorized_rent_from_allowance() {
+    let env = Env::default();
+    let (contract_id, client, _) = setup(&env);
+    let native = client.view_native_token().unwrap();
+    let token = TokenClient::new(&env, &native);
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    fund(&env, &client, &lessee, 1_000);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Flat"),
+        &String::from_str(&env, "Two-bed flat"),
+        &100, &100, &100, &1000, &200,
+    );
+    let lease_id = client.create_lease(&asset_id, &lessee, &1000);
+    let keeper = Address::generate(&env);
+    
+    // The allowance must be in place before the lessee signs up
+    assert_eq!(
+        client.try_authorize_auto_pay(&lease_id, &lessee, &2),
+        Err(Ok(RentError::AllowanceTooLow))
+    );
+    token.approve(&lessee, &contract_id, &250, &1000);
+    client.authorize_auto_pay(&lease_id, &lessee, &2);
+    
+    // Nothing is collected before the due date, then one period per due date
+    assert!(!client.collect_due_payment(&lease_id, &keeper));
+    env.ledger().set_timestamp(100);
+    assert!(client.collect_due_payment(&lease_id, &keeper));
+    assert!(!client.collect_due_payment(&lease_id, &keeper));
+    assert_eq!(client.view_auto_pay(&lease_id).unwrap().periods_left, 1);
+    assert_eq!(client.view_withdrawable(&owner, &native), 200);
+    assert_eq!(token.allowance(&lessee, &contract_id), 150);
+    
+    // The authorization runs out after the periods the lessee agreed to
+    env.ledger().set_timestamp(200);
+    assert!(client.collect_due_payment(&lease_id, &keeper));
+    assert_eq!(client.view_auto_pay(&lease_id), None);
+    env.ledger().set_timestamp(300);
+    assert!(!client.collect_due_payment(&lease_id, &keeper));
+    assert_eq!(client.view_lease(&lease_id).unwrap().next_payment_time, 300);
+    
+    // Collection stops short of what the allowance can't cover
+    token.approve(&lessee, &contract_id, &100, &1000);
+    client.authorize_auto_pay(&lease_id, &lessee, &5);
+    token.approve(&lessee, &contract_id, &60, &1000);
+    assert!(!client.collect_due_payment(&lease_id, &keeper));
+    client.revoke_auto_pay(&lease_id, &lessee);
+    assert_eq!(client.view_auto_pay(&lease_id), None);
+    assert_sub_accounts_cover_balances(&env, &contract_id, &client);
+}
+
+#[test]
+fn test_keepers_earn_capped_rewards_from_platform_fees() {
+    let env = Env::default();
+    let (contract_id, client, admin) = setup(&env);
+    let native = client.view_native_token().unwrap();
+    let token = TokenClient::new(&env, &native);
+    client.set_platform_fee_bps(&admin, &1_000);
+    client.set_keeper_reward(&admin, &1, &500, &5);
+    assert_eq!(
+        client.try_set_keeper_reward(&admin, &1, &10_001, &5),
+        Err(Ok(RentError::InvalidAmount))
+    );
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    let keeper = Address::generate(&env);
+    fund(&env, &client, &lessee, 1_000);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Flat"),
+        &String::from_str(&env, "Two-bed flat"),
+        &100, &100, &100, &300, &200,
+    );
+    let lease_id = client.create_lease(&asset_id, &lessee, &300);
+    token.approve(&lessee, &contract_id, &500, &1000);
+    client.authorize_auto_pay(&lease_id, &lessee, &2);
+    
+    // 1 flat plus 5% of the 100 collected is capped at 5, out of the 10 platform fee
+    env.ledger().set_timestamp(100);
+    assert!(client.collect_due_payment(&lease_id, &keeper));
+    assert_eq!(client.view_keeper_rewards(&keeper, &native), 5);
+    assert_eq!(client.view_platform_fees().get(native.clone()), Some(15));
+    
+    // Flagging an overdue lease and settling the expired lease each earn the flat reward
+    env.ledger().set_timestamp(200);
+    assert!(client.check_and_mark_overdue(&lease_id, &keeper));
+    assert_eq!(client.view_keeper_rewards(&keeper, &native), 6);
+    assert!(!client.check_and_mark_overdue(&lease_id, &keeper));
+    assert_eq!(client.view_keeper_rewards(&keeper, &native), 6);
+    env.ledger().set_timestamp(300);
+    client.settle_expired_lease(&lease_id, &keeper);
+    assert_eq!(client.view_keeper_rewards(&keeper, &native), 7);
+    assert_eq!(client.view_sub_account(&SubAccount::RewardsPool, &native), 7);
+    
+    assert_eq!(client.claim_keeper_rewards(&keeper, &native), 7);
+    assert_eq!(token.balance(&keeper), 7);
+    assert_eq!(client.view_keeper_rewards(&keeper, &native), 0);
+    assert_eq!(
+        client.try_claim_keeper_rewards(&keeper, &native),
+        Err(Ok(RentError::InvalidAmount))
+    );
+    assert_sub_accounts_cover_balances(&env, &contract_id, &client);
+}
+
+#[test]
+fn test_asset_transfer_keeps_the_lease_and_redirects_rent() {
+    let env = Env::default();
+    let (contract_id, client, admin) = setup(&env);
+    let native = client.view_native_token().unwrap();
+    
+    let seller = Address::generate(&env);
+    let buyer = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    fund(&env, &client, &lessee, 1_000);
+    fund(&env, &client, &seller, 5);
+    client.set_listing_fee(&admin, &5, &true);
+    let asset_id = client.register_asset(
+        &seller,
+        &String::from_str(&env, "Flat"),
+        &String::from_str(&env, "Two-bed flat"),
+        &100, &100, &100, &1000, &200,
+    );
+    let external_id = BytesN::from_array(&env, &[7; 32]);
+    client.set_asset_external_id(&asset_id, &seller, &Some(external_id.clone()));
+    client.set_deposit_mode(&asset_id, &seller, &DepositMode::AllowanceLock);
+    TokenClient::new(&env, &native).approve(&lessee, &contract_id, &200, &1000);
+    let lease_id = client.create_lease(&asset_id, &lessee, &1000);
+    client.set_lease_external_id(&lease_id, &seller, &Some(external_id.clone()));
+    env.ledger().set_timestamp(100);
+    client.process_payment(&lease_id, &lessee);
+    
+    assert_eq!(
+        client.try_transfer_asset(&asset_id, &buyer, &buyer),
+        Err(Ok(RentError::NotAssetOwner))
+    );
+    let lease_before = client.view_lease(&lease_id).unwrap().to_xdr(&env);
+    
+    // A dispute on the lease must be ruled on before the sale
+    let dispute_id = client.open_dispute(&lease_id, &lessee, &symbol_short!("noise"));
+    assert_eq!(client.try_transfer_asset(&asset_id, &seller, &buyer), ext_error(RentErrorExt::DepositUnsettled));
+    client.resolve_dispute(&admin, &dispute_id, &0);
+    
+    // The seller gets back the listing fee they paid
+    client.transfer_asset(&asset_id, &seller, &buyer);
+    assert_eq!(client.view_asset(&asset_id).unwrap().owner, buyer);
+    assert_eq!(client.view_asset(&asset_id).unwrap().listing_fee_held, 0);
+    assert_eq!(TokenClient::new(&env, &native).balance(&seller), 5);
+    assert_eq!((client.get_owner_assets(&seller).len(), client.get_owner_assets(&buyer)), (0, vec![&env, asset_id]));
+    assert_eq!(client.find_asset_by_external_id(&seller, &external_id), None);
+    assert_eq!(client.view_lease(&lease_id).unwrap().to_xdr(&env), lease_before);
+    
+    // The buyer takes over the seller's reference for the lease and the deposit claim
+    assert_eq!(client.find_lease_by_external_id(&seller, &external_id), None);
+    assert_eq!(client.find_lease_by_external_id(&buyer, &external_id), Some(lease_id));
+    assert_eq!(client.view_deposit_lock(&lease_id).unwrap().owner, buyer);
+    assert!(client.try_claim_locked_deposit(&lease_id, &seller, &20).is_err());
+    client.claim_locked_deposit(&lease_id, &buyer, &20);
+    client.approve_deposit_claim(&lease_id, &lessee);
+    assert_eq!(TokenClient::new(&env, &native).balance(&buyer), 20);
+    
+    // Rent paid before the sale stays with the seller; the next payment is held for the buyer
+    env.ledger().set_timestamp(200);
+    client.process_payment(&lease_id, &lessee);
+    assert_eq!(client.view_withdrawable(&seller, &native), 200);
+    assert_eq!(client.view_withdrawable(&buyer, &native), 100);
+    assert!(client.try_update_asset(
+        &asset_id, &seller, &120, &200, &100, &1000, &String::from_str(&env, "Two-bed flat")
+    ).is_err());
+}
+
+#[test]
+fn test_lease_transfer_moves_tenancy_and_deposit_lock() {
+    let env = Env::default();
+    let (contract_id, client, _) = setup(&env);
+    let token = client.view_native_token().unwrap();
+    
+    let owner = Address::generate(&env);
+    let outgoing = Address::generate(&env);
+    let incoming = Address::generate(&env);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Van"),
+        &String::from_str(&env, "Cargo van"),
+        &10, &100, &100, &1000, &50,
+    );
+    client.set_deposit_mode(&asset_id, &owner, &DepositMode::AllowanceLock);
+    client.set_assignment_approval(&asset_id, &owner, &true);
+    for lessee in [&outgoing, &incoming] {
+        StellarAssetClient::new(&env, &token).mint(lessee, &100);
+    }
+    TokenClient::new(&env, &token).approve(&outgoing, &contract_id, &50, &1000);
+    let lease_id = client.create_lease(&asset_id, &outgoing, &500);
+    
+    // The incoming lessee must take over the deposit allowance first
+    assert_eq!(
+        client.try_transfer_lease(&lease_id, &outgoing, &incoming),
+        Err(Ok(RentError::AllowanceTooLow))
+    );
+    TokenClient::new(&env, &token).approve(&incoming, &contract_id, &50, &1000);
+    client.transfer_lease(&lease_id, &outgoing, &incoming);
+    let signers: std::vec::Vec<Address> = env.auths().into_iter().map(|(address, _)| address).collect();
+    assert_eq!(signers, [outgoing.clone(), incoming.clone(), owner.clone()]);
+    assert_eq!(
+        env.events().all().last().unwrap().1,
+        (symbol_short!("lease"), symbol_short!("transfer"), incoming.clone()).into_val(&env)
+    );
+    
+    assert_eq!(client.view_lease(&lease_id).unwrap().lessee, incoming);
+    assert_eq!(client.view_deposit_lock(&lease_id).unwrap().lessee, incoming);
+    assert!(client.get_lessee_leases(&outgoing).is_empty());
+    assert_eq!(client.get_lessee_leases(&incoming), vec![&env, lease_id]);
+    
+    // Only the new lessee pays from here on
+    env.ledger().set_timestamp(100);
+    assert_eq!(
+        client.try_process_payment(&lease_id, &outgoing),
+        Err(Ok(RentError::NotLessee))
+    );
+    client.process_payment(&lease_id, &incoming);
+    assert_eq!(TokenClient::new(&env, &token).balance(&incoming), 90);
+}
+
+#[test]
+fn test_co_tenants_pay_their_shares_and_all_sign_early_termination() {
+    let env = Env::default();
+    let (contract_id, client, _) = setup(&env);
+    let token = TokenClient::new(&env, &client.view_native_token().unwrap());
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    let roommate = Address::generate(&env);
+    fund(&env, &client, &lessee, 1_000);
+    fund(&env, &client, &roommate, 1_000);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Flat"),
+        &String::from_str(&env, "Two-bed flat"),
+        &100, &100, &100, &1000, &200,
+    );
+    client.set_termination_terms(&asset_id, &owner, &300, &80);
+    let lease_id = client.create_lease(&asset_id, &lessee, &1000);
+    
+    // Shares must include the lessee and add up to the whole rent
+    let share = |tenant: &Address, share_bps: u32| CoTenant { tenant: tenant.clone(), share_bps: share_bps };
+    assert_eq!(
+        client.try_set_co_tenants(&lease_id, &lessee, &vec![&env, share(&lessee, 6_000), share(&roommate, 3_000)]),
+        Err(Ok(RentError::InvalidAmount))
+    );
+    assert_eq!(
+        client.try_set_co_tenants(&lease_id, &roommate, &vec![&env, share(&roommate, 10_000)]),
+        Err(Ok(RentError::NotLessee))
+    );
+    client.set_co_tenants(&lease_id, &lessee, &vec![&env, share(&lessee, 6_000), share(&roommate, 4_000)]);
+    assert_eq!(client.get_lessee_leases(&roommate), vec![&env, lease_id]);
+    
+    // Consolidated bills and auto-pay never charge one tenant the whole rent of a shared lease
+    env.ledger().set_timestamp(100);
+    client.set_consolidated_billing(&roommate, &owner, &true);
+    assert_eq!(client.get_consolidated_bill(&roommate, &owner).total, 0);
+    assert_eq!(client.get_consolidated_bill(&lessee, &owner).total, 0);
+    token.approve(&lessee, &contract_id, &100, &1000);
+    client.authorize_auto_pay(&lease_id, &lessee, &2);
+    assert!(!client.collect_due_payment(&lease_id, &Address::generate(&env)));
+    client.revoke_auto_pay(&lease_id, &lessee);
+    
+    // Each co-tenant pays their share once; the last one settles the period
+    let roommate_before = token.balance(&roommate);
+    client.process_payment(&lease_id, &roommate);
+    assert_eq!(token.balance(&roommate), roommate_before - 40);
+    let lease = client.view_lease(&lease_id).unwrap();
+    assert_eq!((lease.next_payment_time, lease.outstanding_balance), (100, 60));
+    assert_eq!(
+        client.try_process_payment(&lease_id, &roommate),
+        Err(Ok(RentError::PaymentNotDue))
+    );
+    let lessee_before = token.balance(&lessee);
+    client.process_payment(&lease_id, &lessee);
+    assert_eq!(token.balance(&lessee), lessee_before - 60);
+    let lease = client.view_lease(&lease_id).unwrap();
+    assert_eq!((lease.next_payment_time, lease.outstanding_balance), (200, 0));
+    
+    // Batched and sponsored payments charge shares the same way
+    env.ledger().set_timestamp(200);
+    let results = client.process_payments_batch(&vec![&env, (lease_id, roommate.clone()), (lease_id, roommate.clone())]);
+    assert_eq!((results.get(0).unwrap().amount, results.get(0).unwrap().error_code), (40, 0));
+    assert_eq!(results.get(1).unwrap().error_code, RentError::PaymentNotDue as u32);
+    assert_eq!(
+        client.try_process_sponsored_payment(&lease_id, &lessee, &59),
+        Err(Ok(RentError::AmountExceedsMax))
+    );
+    assert_eq!(client.process_sponsored_payment(&lease_id, &lessee, &60), 60);
+    assert_eq!(client.view_lease(&lease_id).unwrap().next_payment_time, 300);
+    assert_eq!(client.view_withdrawable(&owner, &token.address), 300);
+    
+    // A co-tenant may end the lease early, but only with every co-tenant signing
+    env.ledger().set_timestamp(250);
+    client.end_lease(&lease_id, &roommate);
+    let signers: std::vec::Vec<Address> = env.auths().into_iter().map(|(address, _)| address).collect();
+    assert_eq!(signers, [roommate.clone(), lessee.clone()]);
+    assert_eq!(client.view_withdrawable(&owner, &token.address), 380);
+    assert!(!client.view_lease(&lease_id).unwrap().is_active);
+}
+
+#[test]
+fn test_guarantor_covers_rent_past_the_claim_threshold() {
+    let env = Env::default();
+    let (contract_id, client, _) = setup(&env);
+    let token = TokenClient::new(&env, &client.view_native_token().unwrap());
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    let guarantor = Address::generate(&env);
+    fund(&env, &client, &lessee, 1_000);
+    fund(&env, &client, &guarantor, 1_000);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Flat"),
+        &String::from_str(&env, "Two-bed flat"),
+        &100, &100, &100, &1000, &200,
+    );
+    client.set_guarantor_threshold(&asset_id, &owner, &50);
+    let lease_id = client.create_guaranteed_lease(&asset_id, &lessee, &1000, &guarantor);
+    assert_eq!(client.view_guarantor(&lease_id), Some(guarantor.clone()));
+    
+    // Only once rent is past due by the threshold, and only as far as the guarantor approved
+    env.ledger().set_timestamp(120);
+    assert_eq!(
+        client.try_claim_from_guarantor(&lease_id),
+        Err(Ok(RentError::DeadlineNotReached))
+    );
+    assert!(client.get_delinquency_report(&owner).get(0).unwrap().has_guarantor);
+    env.ledger().set_timestamp(150);
+    assert_eq!(
+        client.try_claim_from_guarantor(&lease_id),
+        Err(Ok(RentError::AllowanceTooLow))
+    );
+    token.approve(&guarantor, &contract_id, &500, &1000);
+    let amount = client.claim_from_guarantor(&lease_id);
+    assert_eq!(token.balance(&guarantor), 1_000 - amount);
+    assert_eq!(client.view_withdrawable(&owner, &token.address), 100 + amount);
+    assert_eq!(client.view_lease(&lease_id).unwrap().next_payment_time, 250);
+    
+    // A lease without a guarantor has nobody to claim from
+    client.end_lease(&lease_id, &owner);
+    assert_eq!(client.view_guarantor(&lease_id), None);
+    let lease_id = client.create_lease(&asset_id, &lessee, &1000);
+    env.ledger().set_timestamp(400);
+    assert_eq!(
+        client.try_claim_from_guarantor(&lease_id),
+        Err(Ok(RentError::InvalidClaim))
+    );
+}
+
+#[test]
+fn test_overdue_waits_out_grace_period_and_needs_no_owner() {
+    let env = Env::default();
+    let (_, client, _) = setup(&env);
+    
+    let owner = Address::generate(&env);
+    let lessee = Address::generate(&env);
+    fund(&env, &client, &lessee, 1_000);
+    let asset_id = client.register_asset(
+        &owner,
+        &String::from_str(&env, "Flat"),
+        &String::from_str(&env, "Two-bed flat"),
+        &100, &100, &100, &1000, &200,
+    );
+    client.set_grace_period(&asset_id, &owner, &50);
+    let lease_id = client.create_lease(&asset_id, &lessee, &1000);
+    let keeper = Address::generate(&env);
+    
+    // Within the grace period neither the owner nor anyone else can flag the lease
+    env.ledger().set_timestamp(120);
+    assert_eq!(
+        client.try_mark_lease_overdue(&lease_id, &owner),
+        Err(Ok(RentError::PaymentNotDue))
+    );
+    assert!(!client.check_and_mark_overdue(&lease_id, &keeper));
+    
+    env.ledger().set_timestamp(150);
+    assert!(client.check_and_mark_overdue(&lease_id, &keeper));
+    assert_eq!(
+        env.events().all().last().unwrap().1,
+        (symbol_short!("lease"), symbol_short!("overdue"), lessee.clone()).into_val(&env)
+    );
+    assert!(client.view_lease(&lease_id).unwrap().is_overdue);
+    assert!(!client.check_and_mark_overdue(&lease_id, &keeper));
+    assert_eq!(client.view_asset_stats().overdue_leases, 1);
+    
+    // Marking it again counts nothing more against the lessee
+    client.mark_lease_overdue(&lease_id, &owner);
+    client.mark_lease_overdue(&lease_id, &owner);
+    assert_eq!(client.view_asset_stats().overdue_leases, 1);
+    assert_eq!(client.view_reputation(&lessee).late_payments, 1);
+}
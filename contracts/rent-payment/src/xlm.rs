@@ -0,0 +1,209 @@
+use soroban_sdk::{contracttype, log, token, Address, Env, String};
+
+use crate::storage_keys::DataKey;
+use crate::{Asset, RentError};
+
+// Native XLM has 7 decimal places, so one XLM is 10^7 stroops
+pub const XLM_DECIMALS: u32 = 7;
+pub const STROOPS_PER_XLM: i128 = 10_000_000;
+
+// Most decimal places a payment token may report. Every stored amount is i128 base units, and
+// this keeps any u64 count of whole tokens representable in them.
+pub const MAX_DECIMALS: u32 = 18;
+
+// An amount tagged with its unit, so whole tokens and base units can't be mixed up at the API
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Amount {
+    Whole(u64),    // Whole tokens, scaled by the token's decimals before storage
+    Stroops(i128), // Base units of the token (stroops for XLM), the unit stored on assets and leases
+}
+
+// Convert a whole-token amount into base units of a token with at most MAX_DECIMALS places
+pub fn to_base_units(whole: u64, decimals: u32) -> i128 {
+    whole as i128 * 10_i128.pow(decimals)
+}
+
+// Convert a whole-XLM amount into stroops
+pub fn to_stroops(xlm: u64) -> i128 {
+    to_base_units(xlm, XLM_DECIMALS)
+}
+
+// Normalize a tagged amount into base units of a token with `decimals` places for storage;
+// base-unit amounts must not be negative
+pub fn normalize(env: &Env, amount: Amount, decimals: u32) -> Result<i128, RentError> {
+    match amount {
+        Amount::Whole(whole) => Ok(to_base_units(whole, decimals)),
+        Amount::Stroops(units) => {
+            require_amount(env, units)?;
+            Ok(units)
+        }
+    }
+}
+
+// Fail with InvalidAmount if a base-unit amount given to an entrypoint is negative
+pub fn require_amount(env: &Env, units: i128) -> Result<(), RentError> {
+    if units < 0 {
+        log!(env, "Amount is negative");
+        return Err(RentError::InvalidAmount);
+    }
+    
+    Ok(())
+}
+
+// Confirm the configured native token uses the decimals every conversion here assumes
+pub fn check_decimals(env: &Env) -> Result<(), RentError> {
+    let configured: Option<Address> = env.storage().instance().get(&DataKey::NativeToken);
+    if let Some(token) = configured {
+        if token::Client::new(env, &token).decimals() != XLM_DECIMALS {
+            log!(env, "Native token does not use 7 decimals");
+            return Err(RentError::UnsupportedDecimals);
+        }
+    }
+    
+    Ok(())
+}
+
+// Display symbol and decimals of a payment token, read from the token contract so listings
+// can render amounts without calling it themselves. The native SAC reports its symbol as
+// "native", which is shown as XLM. Tokens reporting more than MAX_DECIMALS places are refused
+// with UnsupportedDecimals, so no conversion of their amounts can overflow.
+pub fn currency(env: &Env, token: &Address) -> Result<(String, u32), RentError> {
+    let client = token::Client::new(env, token);
+    let decimals = client.decimals();
+    if decimals > MAX_DECIMALS {
+        log!(env, "Token reports more than {} decimals", MAX_DECIMALS);
+        return Err(RentError::UnsupportedDecimals);
+    }
+    
+    let symbol = client.symbol();
+    if symbol == String::from_str(env, "native") {
+        return Ok((String::from_str(env, "XLM"), decimals));
+    }
+    Ok((symbol, decimals))
+}
+
+// Address of the wrapped-XLM Stellar Asset Contract configured by the admin
+pub fn native_token(env: &Env) -> Result<Address, RentError> {
+    env.storage().instance().get(&DataKey::NativeToken).ok_or_else(|| {
+        log!(env, "Native XLM token is not configured");
+        RentError::NativeTokenNotSet
+    })
+}
+
+// A token contract amounts are moved in. Amounts on assets and leases are base units of the
+// asset's payment token; platform fees are stroops.
+#[derive(Clone)]
+pub struct Token {
+    pub address: Address,
+}
+
+// The native XLM SAC
+pub fn native(env: &Env) -> Result<Token, RentError> {
+    Ok(Token { address: native_token(env)? })
+}
+
+// Any token
+pub fn token(address: &Address) -> Token {
+    Token { address: address.clone() }
+}
+
+// The token an asset's rent and deposits are paid in
+pub fn payment_token(asset: &Asset) -> Token {
+    Token { address: asset.payment_token.clone() }
+}
+
+// Move `amount` base units from one address to another. Native XLM has no trustlines or
+// issuer authorization, so the recipient never needs to opt in first; issued assets
+// are checked up front so a missing trustline fails with NoTrustline.
+pub fn transfer(env: &Env, token: &Token, from: &Address, to: &Address, amount: i128) -> Result<(), RentError> {
+    if amount == 0 || from == to {
+        return Ok(());
+    }
+    
+    check_balance(env, token, from, amount)?;
+    check_receivable(env, token, to)?;
+    token::Client::new(env, &token.address).transfer(from, to, &amount);
+    
+    Ok(())
+}
+
+// Preflight checks run before each transfer, so a failure surfaces as the error naming its
+// fix (top up, raise the allowance, add a trustline) rather than a trap inside the token
+
+// Fail with InsufficientBalance unless `from` holds at least `amount` base units
+pub(crate) fn check_balance(env: &Env, token: &Token, from: &Address, amount: i128) -> Result<(), RentError> {
+    if balance(env, token, from) < amount {
+        log!(env, "Balance does not cover {} base units", amount);
+        return Err(RentError::InsufficientBalance);
+    }
+    
+    Ok(())
+}
+
+// Fail with AllowanceTooLow unless `from` has approved this contract for at least `amount`
+pub(crate) fn check_allowance(env: &Env, token: &Token, from: &Address, amount: i128) -> Result<(), RentError> {
+    if allowance(env, token, from) < amount {
+        log!(env, "Allowance does not cover {} base units", amount);
+        return Err(RentError::AllowanceTooLow);
+    }
+    
+    Ok(())
+}
+
+// Fail with NoTrustline if `to` cannot receive the token. Native XLM always passes; a SAC
+// for an issued asset turns away accounts without a trustline or deauthorized by the issuer.
+// Tokens that are not SACs have no such notion and are left to fail on transfer.
+pub(crate) fn check_receivable(env: &Env, token: &Token, to: &Address) -> Result<(), RentError> {
+    let authorized = token::StellarAssetClient::new(env, &token.address).try_authorized(to);
+    if let Ok(Ok(false)) = authorized {
+        log!(env, "Recipient cannot receive the payment token");
+        return Err(RentError::NoTrustline);
+    }
+    
+    Ok(())
+}
+
+// Whether the token lets `id` move it. A SAC for an issued asset reports frozen or
+// deauthorized accounts; native XLM and tokens that are not SACs report every account as free.
+pub(crate) fn authorized(env: &Env, token: &Token, id: &Address) -> bool {
+    token::StellarAssetClient::new(env, &token.address).try_authorized(id) != Ok(Ok(false))
+}
+
+// Balance of an address in base units
+pub fn balance(env: &Env, token: &Token, id: &Address) -> i128 {
+    token::Client::new(env, &token.address).balance(id)
+}
+
+// Allowance, in base units, that `from` has granted this contract
+pub fn allowance(env: &Env, token: &Token, from: &Address) -> i128 {
+    token::Client::new(env, &token.address).allowance(from, &env.current_contract_address())
+}
+
+// Pull `amount` base units from `from` to `to` using the allowance granted to this contract
+pub fn transfer_from(env: &Env, token: &Token, from: &Address, to: &Address, amount: i128) -> Result<(), RentError> {
+    if amount == 0 {
+        return Ok(());
+    }
+    
+    check_allowance(env, token, from, amount)?;
+    check_balance(env, token, from, amount)?;
+    check_receivable(env, token, to)?;
+    let spender = env.current_contract_address();
+    token::Client::new(env, &token.address).transfer_from(&spender, from, to, &amount);
+    
+    Ok(())
+}
+
+// Permanently remove `stroops` of XLM held by `from` from circulation
+pub fn burn(env: &Env, from: &Address, stroops: i128) -> Result<(), RentError> {
+    if stroops == 0 {
+        return Ok(());
+    }
+    
+    let native = native(env)?;
+    check_balance(env, &native, from, stroops)?;
+    token::Client::new(env, &native.address).burn(from, &stroops);
+    
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use std::env;
+use std::time::Duration;
+
+// Where the keeper runs and how often, read from KEEPER_* environment variables
+#[derive(Clone, Debug, PartialEq)]
+pub struct Config {
+    pub contract_id: String, // KEEPER_CONTRACT_ID: deployed rent-payment contract
+    pub source: String,      // KEEPER_SOURCE: identity that signs, pays fees and earns keeper rewards
+    pub network: String,     // KEEPER_NETWORK: network name known to the CLI (default "testnet")
+    pub cli: String,         // KEEPER_CLI: path to the stellar CLI (default "stellar")
+    pub interval: Duration,  // KEEPER_INTERVAL_SECS: pause between rounds (default one hour)
+    pub once: bool,          // Run a single round and exit (`--once`)
+}
+
+impl Config {
+    // Build the configuration from the process environment and command-line flags
+    pub fn from_env() -> Result<Config, String> {
+        let once = env::args().skip(1).any(|arg| arg == "--once");
+        Config::from_lookup(|name| env::var(name).ok(), once)
+    }
+    
+    // Build the configuration from any variable source, so it can be checked without a process
+    pub fn from_lookup(lookup: impl Fn(&str) -> Option<String>, once: bool) -> Result<Config, String> {
+        let required = |name: &str| lookup(name).ok_or(format!("{} is not set", name));
+        let interval = match lookup("KEEPER_INTERVAL_SECS") {
+            Some(secs) => secs.parse::<u64>().map_err(|_| format!("KEEPER_INTERVAL_SECS is not a number: {}", secs))?,
+            None => 3600,
+        };
+    
+        return Ok(Config {
+            contract_id: required("KEEPER_CONTRACT_ID")?,
+            source: required("KEEPER_SOURCE")?,
+            network: lookup("KEEPER_NETWORK").unwrap_or(String::from("testnet")),
+            cli: lookup("KEEPER_CLI").unwrap_or(String::from("stellar")),
+            interval: Duration::from_secs(interval),
+            once: once,
+        });
+    }
+}
@@ -0,0 +1,69 @@
+use std::process::Command;
+
+use serde_json::Value;
+
+use crate::config::Config;
+
+// A deployed rent-payment contract the keeper can call by entrypoint name
+pub trait Contract {
+    // Invoke `function` with named arguments, returning its result as JSON
+    fn invoke(&self, function: &str, args: &[(&str, String)]) -> Result<Value, String>;
+}
+
+// Calls the contract through the stellar CLI, which builds the client bindings from the
+// on-chain spec, simulates read-only calls and signs and submits the rest
+pub struct StellarCli {
+    config: Config,
+}
+
+impl StellarCli {
+    pub fn new(config: Config) -> StellarCli {
+        StellarCli { config: config }
+    }
+    
+    // Full argument list passed to the CLI for one invocation
+    pub fn command_args(&self, function: &str, args: &[(&str, String)]) -> Vec<String> {
+        let mut command = vec![
+            String::from("contract"),
+            String::from("invoke"),
+            String::from("--id"),
+            self.config.contract_id.clone(),
+            String::from("--source-account"),
+            self.config.source.clone(),
+            String::from("--network"),
+            self.config.network.clone(),
+            String::from("--"),
+            String::from(function),
+        ];
+        for (name, value) in args {
+            command.push(format!("--{}", name));
+            command.push(value.clone());
+        }
+    
+        return command;
+    }
+}
+
+impl Contract for StellarCli {
+    fn invoke(&self, function: &str, args: &[(&str, String)]) -> Result<Value, String> {
+        let output = Command::new(&self.config.cli)
+            .args(self.command_args(function, args))
+            .output()
+            .map_err(|err| format!("failed to run {}: {}", self.config.cli, err))?;
+    
+        if !output.status.success() {
+            return Err(format!("{} failed: {}", function, String::from_utf8_lossy(&output.stderr).trim()));
+        }
+    
+        return Ok(parse_output(&String::from_utf8_lossy(&output.stdout)));
+    }
+}
+
+// The CLI prints results as JSON; anything else (or nothing, for unit results) is kept as-is
+pub fn parse_output(stdout: &str) -> Value {
+    let trimmed = stdout.trim();
+    if trimmed.is_empty() {
+        return Value::Null;
+    }
+    serde_json::from_str(trimmed).unwrap_or(Value::String(String::from(trimmed)))
+}
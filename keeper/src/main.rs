@@ -0,0 +1,45 @@
+#![allow(clippy::needless_return, clippy::redundant_field_names)]
+
+// Keeper bot for the rent-payment contract: on a schedule, it calls the maintenance
+// entrypoints that anyone may invoke (TTL bumps, expired-lease settlement, prepaid rent
+// draw-downs, auto-pay collections, payment reminders, lease pokes and reporting-period closes) so automation does not depend on owners or tenants.
+mod config;
+mod contract;
+mod round;
+mod test;
+
+use std::process;
+use std::thread;
+
+use config::Config;
+use contract::StellarCli;
+
+fn main() {
+    let config = match Config::from_env() {
+        Ok(config) => config,
+        Err(err) => {
+            eprintln!("keeper-bot: {}", err);
+            process::exit(2);
+        }
+    };
+    let interval = config.interval;
+    let once = config.once;
+    let keeper = config.source.clone();
+    let contract = StellarCli::new(config);
+    
+    loop {
+        let report = round::run_round(&contract, &keeper);
+        println!(
+            "keeper-bot: checked {} leases, settled {} expired, drew {} prepaid, collected {} auto-pay, marked {} overdue, sent {} reminders, poked {} leases, closed period: {}",
+            report.leases_checked, report.leases_settled, report.prepaid_drawn, report.autopay_collected, report.marked_overdue, report.reminders_sent, report.leases_poked, report.period_closed
+        );
+        for err in &report.errors {
+            eprintln!("keeper-bot: {}", err);
+        }
+    
+        if once {
+            break;
+        }
+        thread::sleep(interval);
+    }
+}
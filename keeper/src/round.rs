@@ -0,0 +1,107 @@
+use serde_json::Value;
+
+use crate::contract::Contract;
+
+// Contract error codes a keeper round expects and does not report
+const NOT_EXPIRED: &str = "#30";    // Lease term has not ended, or it renews or rolls into holdover
+const INVALID_WINDOW: &str = "#22"; // No reporting period has completed yet
+const PERIOD_CLOSED: &str = "#39";  // Last reporting period was already closed
+
+// What one keeper round did
+#[derive(Debug, Default, PartialEq)]
+pub struct RoundReport {
+    pub leases_checked: u64, // Active leases visited
+    pub reminders_sent: u64, // Payment reminders the contract emitted
+    pub leases_poked: u64,   // Leases whose renewal/holdover/escalation state was refreshed
+    pub leases_settled: u64, // Expired leases closed out
+    pub prepaid_drawn: u64,  // Due payments settled out of prepaid rent
+    pub autopay_collected: u64, // Due payments collected under a lessee's auto-pay authorization
+    pub marked_overdue: u64, // Leases flagged overdue once past their grace period
+    pub period_closed: bool, // Whether a reporting period was closed this round
+    pub errors: Vec<String>, // Calls that failed; the round carries on past them
+}
+
+// Run one maintenance round: keep the contract alive, close out expired leases, settle due
+// payments out of prepaid rent or auto-pay allowances, flag leases still unpaid past their grace
+// period as overdue, send due reminders, apply time-driven lease transitions and close the last reporting period once it
+// has ended. Keeper rewards earned along the way accrue to `keeper`.
+pub fn run_round(contract: &impl Contract, keeper: &str) -> RoundReport {
+    let mut report = RoundReport::default();
+    
+    if let Err(err) = contract.invoke("bump_contract_ttl", &[]) {
+        report.errors.push(err);
+    }
+    
+    let lease_count = match contract.invoke("get_lease_count", &[]) {
+        Ok(count) => as_u64(&count),
+        Err(err) => {
+            report.errors.push(err);
+            0
+        }
+    };
+    
+    for lease_id in 1..=lease_count {
+        let args = [("lease_id", lease_id.to_string())];
+        match contract.invoke("view_lease", &args) {
+            Ok(lease) if lease["is_active"] == Value::Bool(true) => {}
+            Ok(_) => continue,
+            Err(err) => {
+                report.errors.push(err);
+                continue;
+            }
+        }
+        report.leases_checked += 1;
+    
+        // Leases still in term fail simulation and are never submitted, so trying each is cheap
+        let keeper_args = [("lease_id", lease_id.to_string()), ("keeper", String::from(keeper))];
+        match contract.invoke("settle_expired_lease", &keeper_args) {
+            Ok(_) => {
+                report.leases_settled += 1;
+                continue;
+            }
+            Err(err) if err.contains(NOT_EXPIRED) => {}
+            Err(err) => report.errors.push(err),
+        }
+        match contract.invoke("settle_due_payment", &args) {
+            Ok(Value::Bool(true)) => report.prepaid_drawn += 1,
+            Ok(_) => {}
+            Err(err) => report.errors.push(err),
+        }
+        match contract.invoke("collect_due_payment", &keeper_args) {
+            Ok(Value::Bool(true)) => report.autopay_collected += 1,
+            Ok(_) => {}
+            Err(err) => report.errors.push(err),
+        }
+        match contract.invoke("check_and_mark_overdue", &keeper_args) {
+            Ok(Value::Bool(true)) => report.marked_overdue += 1,
+            Ok(_) => {}
+            Err(err) => report.errors.push(err),
+        }
+        match contract.invoke("send_payment_reminder", &args) {
+            Ok(Value::Bool(true)) => report.reminders_sent += 1,
+            Ok(_) => {}
+            Err(err) => report.errors.push(err),
+        }
+        match contract.invoke("poke_lease", &args) {
+            Ok(_) => report.leases_poked += 1,
+            Err(err) => report.errors.push(err),
+        }
+    }
+    
+    match contract.invoke("close_reporting_period", &[]) {
+        Ok(_) => report.period_closed = true,
+        Err(err) if err.contains(PERIOD_CLOSED) || err.contains(INVALID_WINDOW) => {}
+        Err(err) => report.errors.push(err),
+    }
+    
+    return report;
+}
+
+// Contract u64 values come back as JSON numbers, or as strings when they exceed JS precision
+fn as_u64(value: &Value) -> u64 {
+    match value {
+        Value::Number(number) => number.as_u64().unwrap_or(0),
+        Value::String(text) => text.parse().unwrap_or(0),
+        _ => 0,
+    }
+}
@@ -0,0 +1,99 @@
+#![cfg(test)]
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::time::Duration;
+
+use serde_json::{json, Value};
+
+use crate::config::Config;
+use crate::contract::{parse_output, Contract, StellarCli};
+use crate::round::{run_round, RoundReport};
+
+// Answers each entrypoint from a table and records every call made
+struct FakeContract {
+    responses: HashMap<String, Result<Value, String>>,
+    calls: RefCell<Vec<String>>,
+}
+
+impl Contract for FakeContract {
+    fn invoke(&self, function: &str, args: &[(&str, String)]) -> Result<Value, String> {
+        let call = match args.first() {
+            Some((_, value)) => format!("{}({})", function, value),
+            None => String::from(function),
+        };
+        self.calls.borrow_mut().push(call.clone());
+    
+        self.responses.get(&call).or(self.responses.get(function)).cloned().unwrap_or(Ok(Value::Null))
+    }
+}
+
+fn config() -> Config {
+    Config::from_lookup(|name| match name {
+        "KEEPER_CONTRACT_ID" => Some(String::from("CCONTRACT")),
+        "KEEPER_SOURCE" => Some(String::from("keeper")),
+        _ => None,
+    }, false).unwrap()
+}
+
+#[test]
+fn test_config_defaults_and_required_vars() {
+    let config = config();
+    assert_eq!(config.network, "testnet");
+    assert_eq!(config.cli, "stellar");
+    assert_eq!(config.interval, Duration::from_secs(3600));
+    
+    assert!(Config::from_lookup(|_| None, true).is_err());
+}
+
+#[test]
+fn test_cli_invocation_and_output() {
+    let cli = StellarCli::new(config());
+    assert_eq!(
+        cli.command_args("poke_lease", &[("lease_id", String::from("7"))]).join(" "),
+        "contract invoke --id CCONTRACT --source-account keeper --network testnet -- poke_lease --lease_id 7"
+    );
+    
+    assert_eq!(parse_output("true\n"), Value::Bool(true));
+    assert_eq!(parse_output(""), Value::Null);
+    assert_eq!(parse_output("{\"lease_count\":3}")["lease_count"], json!(3));
+}
+
+#[test]
+fn test_round_visits_active_leases_and_tolerates_closed_period() {
+    let contract = FakeContract {
+        responses: HashMap::from([
+            (String::from("get_lease_count"), Ok(json!(4))),
+            (String::from("view_lease(1)"), Ok(json!({ "is_active": true }))),
+            (String::from("view_lease(2)"), Ok(json!({ "is_active": false }))),
+            (String::from("view_lease(3)"), Ok(json!({ "is_active": true }))),
+            (String::from("view_lease(4)"), Ok(json!({ "is_active": true }))),
+            (String::from("settle_expired_lease"), Err(String::from("settle_expired_lease failed: Error(Contract, #30)"))),
+            (String::from("settle_expired_lease(4)"), Ok(Value::Null)),
+            (String::from("settle_due_payment(3)"), Ok(Value::Bool(true))),
+            (String::from("collect_due_payment(1)"), Ok(Value::Bool(true))),
+            (String::from("check_and_mark_overdue(3)"), Ok(Value::Bool(true))),
+            (String::from("send_payment_reminder(1)"), Ok(Value::Bool(true))),
+            (String::from("send_payment_reminder(3)"), Ok(Value::Bool(false))),
+            (String::from("poke_lease(3)"), Err(String::from("poke_lease failed: Error(Contract, #19)"))),
+            (String::from("close_reporting_period"), Err(String::from("close_reporting_period failed: Error(Contract, #39)"))),
+        ]),
+        calls: RefCell::new(Vec::new()),
+    };
+    
+    let report = run_round(&contract, "keeper");
+    assert_eq!(report, RoundReport {
+        leases_checked: 3,
+        reminders_sent: 1,
+        leases_poked: 1,
+        leases_settled: 1,
+        prepaid_drawn: 1,
+        autopay_collected: 1,
+        marked_overdue: 1,
+        period_closed: false,
+        errors: vec![String::from("poke_lease failed: Error(Contract, #19)")],
+    });
+    assert!(!contract.calls.borrow().contains(&String::from("poke_lease(2)")));
+    assert!(!contract.calls.borrow().contains(&String::from("poke_lease(4)")));
+    assert!(!contract.calls.borrow().contains(&String::from("settle_due_payment(4)")));
+}